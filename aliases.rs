@@ -0,0 +1,31 @@
+// Redirects from old ids to current ones. Example and exercise ids end up in
+// progress files, bookmarks, deep links and other people's slides, so
+// renaming or moving one adds an (old, new) pair to its registry's alias
+// table instead of breaking all of those. An old id is never reused.
+
+// The current id for `id`, following renames of renames
+pub fn resolve<'a>(table: &[(&'a str, &'a str)], id: &'a str) -> &'a str {
+    let mut current = id;
+    // A table can't hold a longer chain than it has entries, so a cycle ends too
+    for _ in 0..table.len() {
+        match table.iter().find(|(old, _)| *old == current) {
+            Some((_, new)) => current = new,
+            None => break,
+        }
+    }
+    current
+}
+
+// What's wrong with an alias table, for lint-content: aliases that lead
+// nowhere, and old ids that are in use again
+pub fn problems(table: &[(&str, &str)], exists: impl Fn(&str) -> bool, what: &str) -> Vec<String> {
+    let mut problems = Vec::new();
+    for (old, _) in table {
+        if exists(old) {
+            problems.push(format!("{} alias '{}' is also a current id; old ids can't be reused", what, old));
+        } else if !exists(resolve(table, old)) {
+            problems.push(format!("{} alias '{}' doesn't lead to an existing {}", what, old, what));
+        }
+    }
+    problems
+}
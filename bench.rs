@@ -0,0 +1,137 @@
+// `bench`: runs the criterion benchmarks in benches/lessons.rs and says what
+// they found in the terms the lessons use, e.g. "the borrowing pipeline was
+// 5.3x faster, with 17 allocations instead of 13506". The lessons measure one
+// run on whatever build the demo is; this is the release build, repeated
+// until criterion trusts the numbers.
+//
+// The benchmarks are built from source, so this runs in the demo's source
+// directory and needs cargo and the criterion crate (the bench feature).
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::toolchain;
+
+// A benchmark group and the lesson it checks
+struct Bench {
+    // The example id, which also selects it on the command line
+    example: &'static str,
+    // The criterion group in benches/lessons.rs
+    group: &'static str,
+    // The costly version and the cheap one: (function name, how to say it)
+    costly: (&'static str, &'static str),
+    cheap: (&'static str, &'static str),
+}
+
+const BENCHES: &[Bench] = &[
+    Bench {
+        example: "practical_clone_hotspots",
+        group: "clone_hotspots",
+        costly: ("cloning", "the cloning pipeline"),
+        cheap: ("borrowing", "the borrowing pipeline"),
+    },
+    Bench {
+        example: "api_design_cow_sanitize",
+        group: "cow_sanitize",
+        costly: ("always", "sanitize_always -> String"),
+        cheap: ("cow", "sanitize -> Cow<str>"),
+    },
+    Bench {
+        example: "smart_pointers_make_mut",
+        group: "make_mut",
+        costly: ("shared", "make_mut on shared data"),
+        cheap: ("unique", "make_mut on unique data"),
+    },
+];
+
+pub fn command(args: &[String]) -> Result<(), String> {
+    let chosen: Vec<&Bench> = match args.first() {
+        None => BENCHES.iter().collect(),
+        Some(id) => {
+            let bench = BENCHES.iter().find(|bench| bench.example == id);
+            let known: Vec<&str> = BENCHES.iter().map(|bench| bench.example).collect();
+            vec![bench.ok_or_else(|| format!("there's no benchmark for '{}'; there are {}", id, known.join(", ")))?]
+        }
+    };
+    if !Path::new("Cargo.toml").exists() || !Path::new("benches/lessons.rs").exists() {
+        return Err("bench builds the benchmarks from source; run it in the demo's source directory".to_string());
+    }
+    toolchain::require_cargo("bench")?;
+
+    // Named for criterion and the benchmarks both, rather than leaving
+    // criterion to find the target directory its own way
+    let results = match env::var_os("CRITERION_HOME") {
+        Some(home) => PathBuf::from(home),
+        None => env::var_os("CARGO_TARGET_DIR").map_or_else(|| PathBuf::from("target"), PathBuf::from).join("criterion"),
+    };
+    let results = env::current_dir().map_err(|e| format!("could not find the current directory: {}", e))?.join(results);
+
+    // Criterion takes a regex of the benchmarks to run
+    let filter: Vec<&str> = chosen.iter().map(|bench| bench.group).collect();
+    let status = Command::new("cargo")
+        .args(["bench", "--features", "bench", "--bench", "lessons", "--", "--noplot"])
+        .arg(format!("^({})/", filter.join("|")))
+        .env("CRITERION_HOME", &results)
+        .status()
+        .map_err(|e| format!("could not run cargo: {}", e))?;
+    if !status.success() {
+        return Err("the benchmarks did not build or run (they need the criterion crate)".to_string());
+    }
+
+    println!("\nWhat the benchmarks say about the lessons:");
+    for bench in chosen {
+        let read = |name: &str| -> Result<(f64, usize), String> {
+            let dir = results.join(bench.group).join(name);
+            let estimates = fs::read_to_string(dir.join("new").join("estimates.json"));
+            let allocations = fs::read_to_string(dir.join("allocations"));
+            let time = estimates.ok().as_deref().and_then(mean);
+            let allocations = allocations.ok().and_then(|text| text.trim().parse().ok());
+            time.zip(allocations).ok_or_else(|| format!("no results for {}/{} in {}", bench.group, name, results.display()))
+        };
+        let (costly_time, costly_allocations) = read(bench.costly.0)?;
+        let (cheap_time, cheap_allocations) = read(bench.cheap.0)?;
+        println!("  {}", bench.example);
+        println!(
+            "    {} took {}, {} took {}",
+            bench.costly.1,
+            duration(costly_time),
+            bench.cheap.1,
+            duration(cheap_time)
+        );
+        println!(
+            "    {} was {:.1}x faster, with {} instead of {}",
+            bench.cheap.1,
+            costly_time / cheap_time,
+            allocations(cheap_allocations),
+            costly_allocations
+        );
+    }
+    println!("  (means per run on a release build; criterion's full report is in {})", results.display());
+    Ok(())
+}
+
+// The mean time in nanoseconds from criterion's estimates.json, which looks like
+// {"mean":{"confidence_interval":{..},"point_estimate":1234.5,..},"median":..}
+fn mean(estimates: &str) -> Option<f64> {
+    let (_, after) = estimates.split_once("\"mean\"")?;
+    let (_, after) = after.split_once("\"point_estimate\":")?;
+    let end = after.find([',', '}'])?;
+    after[..end].trim().parse().ok()
+}
+
+fn duration(nanoseconds: f64) -> String {
+    match nanoseconds {
+        n if n >= 1e6 => format!("{:.2} ms", n / 1e6),
+        n if n >= 1e3 => format!("{:.2} µs", n / 1e3),
+        n => format!("{:.0} ns", n),
+    }
+}
+
+fn allocations(count: usize) -> String {
+    match count {
+        1 => "1 allocation".to_string(),
+        n => format!("{} allocations", n),
+    }
+}
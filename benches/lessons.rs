@@ -0,0 +1,186 @@
+// Criterion benchmarks for the lessons that argue about performance, so their
+// claims can be checked on a release build with proper statistics. Run them
+// with `rust_ownership_demo bench`, which builds this with the bench feature
+// and sums the results up in the lessons' terms (see bench.rs), or directly
+// with `cargo bench --features bench`.
+//
+// Each group is one lesson, with the costly version and the cheap one as its
+// two functions, named as bench.rs expects. The code is the lesson's own,
+// copied like the programs in examples/. Besides criterion's timings, every
+// function's allocations for one call are written next to criterion's
+// results as `allocations`, counted the way instrument.rs counts them.
+// Criterion's results go to $CRITERION_HOME when it's set, which is how
+// bench.rs makes sure it reads the same directory these are written to.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::borrow::Cow;
+use std::env;
+use std::fs;
+use std::hint::black_box;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::measurement::WallTime;
+use criterion::{BenchmarkGroup, Criterion, criterion_group, criterion_main};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+// SAFETY: every call is forwarded unchanged to the system allocator
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+// Where criterion keeps its results: $CRITERION_HOME, or criterion under the
+// target directory
+fn results_dir() -> PathBuf {
+    if let Some(home) = env::var_os("CRITERION_HOME") {
+        return PathBuf::from(home);
+    }
+    env::var_os("CARGO_TARGET_DIR").map_or_else(|| PathBuf::from("target"), PathBuf::from).join("criterion")
+}
+
+// Benchmarks `f` as name in `group`, and records how many allocations one call
+// makes next to criterion's results, so bench.rs finds both in one place
+fn bench<T>(group: &mut BenchmarkGroup<'_, WallTime>, group_name: &str, name: &str, mut f: impl FnMut() -> T) {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    drop(black_box(f()));
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+
+    let dir = results_dir().join(group_name).join(name);
+    if fs::create_dir_all(&dir).and_then(|()| fs::write(dir.join("allocations"), allocations.to_string())).is_err() {
+        eprintln!("could not record the allocations in {}", dir.display());
+    }
+
+    group.bench_function(name, |b| b.iter(&mut f));
+}
+
+// practical_clone_hotspots: the report over CSV lines, cloning or borrowing
+
+#[derive(Clone)]
+struct Record {
+    name: String,
+    score: u32,
+    role: String,
+}
+
+fn parse_owned(lines: &[String]) -> Vec<Record> {
+    let mut records = Vec::new();
+    for line in lines {
+        let fields: Vec<String> = line.split(',').map(String::from).collect();
+        records.push(Record {
+            name: fields[0].clone(),
+            score: fields[1].parse().unwrap_or(0),
+            role: fields[2].clone(),
+        });
+    }
+    records
+}
+
+fn admins_cloned(records: &[Record]) -> Vec<Record> {
+    records.iter().filter(|record| record.role == "admin").cloned().collect()
+}
+
+fn names_cloned(records: &[Record]) -> Vec<String> {
+    records.iter().filter(|record| record.score > 0).map(|record| record.name.clone()).collect()
+}
+
+struct RecordRef<'a> {
+    name: &'a str,
+    score: u32,
+    role: &'a str,
+}
+
+fn parse_borrowed(lines: &[String]) -> Vec<RecordRef<'_>> {
+    let mut records = Vec::with_capacity(lines.len());
+    for line in lines {
+        let mut fields = line.split(',');
+        let (name, score, role) = (fields.next().unwrap_or(""), fields.next().unwrap_or(""), fields.next().unwrap_or(""));
+        records.push(RecordRef { name, score: score.parse().unwrap_or(0), role });
+    }
+    records
+}
+
+fn admins_borrowed<'a, 'r>(records: &'r [RecordRef<'a>]) -> Vec<&'r RecordRef<'a>> {
+    records.iter().filter(|record| record.role == "admin").collect()
+}
+
+fn names_borrowed<'a>(records: &[&RecordRef<'a>]) -> Vec<&'a str> {
+    records.iter().filter(|record| record.score > 0).map(|record| record.name).collect()
+}
+
+fn clone_hotspots(c: &mut Criterion) {
+    let input: Vec<String> = (0..2000)
+        .map(|i| format!("user{},{},{}", i, i * 7 % 100, if i % 4 == 0 { "admin" } else { "member" }))
+        .collect();
+    let mut group = c.benchmark_group("clone_hotspots");
+    bench(&mut group, "clone_hotspots", "cloning", || names_cloned(&admins_cloned(&parse_owned(black_box(&input)))));
+    bench(&mut group, "clone_hotspots", "borrowing", || {
+        let records = parse_borrowed(black_box(&input));
+        names_borrowed(&admins_borrowed(&records)).len()
+    });
+    group.finish();
+}
+
+// api_design_cow_sanitize: escaping comments, always copying or only when needed
+
+fn sanitize(input: &str) -> Cow<'_, str> {
+    if !input.contains(['<', '>', '&']) {
+        return Cow::Borrowed(input);
+    }
+    Cow::Owned(sanitize_always(input))
+}
+
+fn sanitize_always(input: &str) -> String {
+    input.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn cow_sanitize(c: &mut Criterion) {
+    let comments: Vec<String> = (0..1000)
+        .map(|i| match i % 50 {
+            0 => format!("comment {} with <b>markup</b>", i),
+            _ => format!("comment {}", i),
+        })
+        .collect();
+    let mut group = c.benchmark_group("cow_sanitize");
+    bench(&mut group, "cow_sanitize", "always", || {
+        black_box(&comments).iter().map(|c| sanitize_always(c)).collect::<Vec<_>>()
+    });
+    bench(&mut group, "cow_sanitize", "cow", || black_box(&comments).iter().map(|c| sanitize(c)).collect::<Vec<_>>());
+    group.finish();
+}
+
+// smart_pointers_make_mut: a write to shared data (a deep copy) or to unique data
+
+fn make_mut(c: &mut Criterion) {
+    let mut group = c.benchmark_group("make_mut");
+    let shared = Arc::new((0..10_000).collect::<Vec<u32>>());
+    bench(&mut group, "make_mut", "shared", || {
+        let mut config = Arc::clone(&shared);
+        Arc::make_mut(&mut config)[0] = 42;
+        config
+    });
+    let mut unique = Arc::new((0..10_000).collect::<Vec<u32>>());
+    bench(&mut group, "make_mut", "unique", || Arc::make_mut(black_box(&mut unique))[0] = 42);
+    group.finish();
+}
+
+criterion_group!(lessons, clone_hotspots, cow_sanitize, make_mut);
+criterion_main!(lessons);
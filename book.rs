@@ -0,0 +1,135 @@
+// `book <topic>`: short excerpts from "The Rust Programming Language" (the
+// Rust Book) for the chapters each section of the demo is based on, bundled
+// so they can be read offline. The Book is dual-licensed under MIT and
+// Apache-2.0, which allows redistributing excerpts with attribution.
+
+use crate::lessons;
+use crate::render;
+
+const WIDTH: usize = 78;
+
+struct Chapter {
+    // Name used on the command line
+    topic: &'static str,
+    title: &'static str,
+    url: &'static str,
+    // Demo sections this chapter covers
+    sections: &'static [&'static str],
+    text: &'static str,
+}
+
+const CHAPTERS: &[Chapter] = &[
+    Chapter {
+        topic: "ownership",
+        title: "4.1 What Is Ownership?",
+        url: "https://doc.rust-lang.org/book/ch04-01-what-is-ownership.html",
+        sections: &["ownership"],
+        text: "Ownership is a set of rules that govern how a Rust program manages memory. \
+               Some languages have garbage collection that regularly looks for no-longer-used \
+               memory as the program runs; in other languages, the programmer must explicitly \
+               allocate and free the memory. Rust uses a third approach: memory is managed \
+               through a system of ownership with a set of rules that the compiler checks.\n\n\
+               Ownership rules: Each value in Rust has an owner. There can only be one owner \
+               at a time. When the owner goes out of scope, the value will be dropped.\n\n\
+               A String is made up of three parts stored on the stack: a pointer to the \
+               memory that holds the contents, a length, and a capacity. The contents \
+               themselves live on the heap. Assigning one String to another copies the \
+               pointer, length and capacity but not the heap data, and the first variable \
+               is considered no longer valid. This is called a move.\n\n\
+               Passing a value to a function moves or copies it just as assignment does, and \
+               returning a value transfers ownership back to the caller. Types such as \
+               integers that are stored entirely on the stack implement the Copy trait, so \
+               they are copied instead of moved and remain valid after assignment.",
+    },
+    Chapter {
+        topic: "references",
+        title: "4.2 References and Borrowing",
+        url: "https://doc.rust-lang.org/book/ch04-02-references-and-borrowing.html",
+        sections: &["borrowing"],
+        text: "A reference is like a pointer in that it's an address we can follow to access \
+               the data stored at that address; that data is owned by some other variable. \
+               Unlike a pointer, a reference is guaranteed to point to a valid value of a \
+               particular type for the life of that reference. We call the action of \
+               creating a reference borrowing.\n\n\
+               Just as variables are immutable by default, so are references. A mutable \
+               reference (&mut) allows modifying a borrowed value, with one big restriction: \
+               if you have a mutable reference to a value, you can have no other references \
+               to that value. This prevents data races at compile time.\n\n\
+               The rules of references: At any given time, you can have either one mutable \
+               reference or any number of immutable references. References must always be \
+               valid.",
+    },
+    Chapter {
+        topic: "slices",
+        title: "4.3 The Slice Type",
+        url: "https://doc.rust-lang.org/book/ch04-03-slices.html",
+        sections: &["slices", "practical"],
+        text: "Slices let you reference a contiguous sequence of elements in a collection \
+               rather than the whole collection. A slice is a kind of reference, so it does \
+               not have ownership.\n\n\
+               A string slice is a reference to part of a String. Internally, the slice data \
+               structure stores the starting position and the length of the slice. String \
+               literals are slices too: their type is &str, a slice pointing to that \
+               specific point of the binary.\n\n\
+               Returning a slice from a function like first_word ties it to the underlying \
+               data. Because the slice borrows the String, the borrow checker won't let the \
+               String be cleared while the slice is still in use, turning what would be a \
+               bug at runtime into a compile-time error. Taking &str parameters instead of \
+               &String makes such functions work with both String values and literals.",
+    },
+    Chapter {
+        topic: "lifetimes",
+        title: "10.3 Validating References with Lifetimes",
+        url: "https://doc.rust-lang.org/book/ch10-03-lifetime-syntax.html",
+        sections: &["lifetimes"],
+        text: "Every reference in Rust has a lifetime, the scope for which that reference is \
+               valid. Most of the time lifetimes are implicit and inferred, just like most \
+               of the time types are inferred. We must annotate lifetimes when the lifetimes \
+               of references could be related in a few different ways.\n\n\
+               The main aim of lifetimes is to prevent dangling references. The borrow \
+               checker compares scopes to determine whether all borrows are valid: a \
+               reference can't outlive the data it refers to.\n\n\
+               Lifetime annotations don't change how long any of the references live. \
+               Rather, they describe the relationships of the lifetimes of multiple \
+               references to each other. In fn longest<'a>(x: &'a str, y: &'a str) -> &'a \
+               str, the returned reference will be valid as long as both parameters are \
+               valid; concretely, 'a is the smaller of the lifetimes of x and y. When \
+               returning a reference from a function, the lifetime parameter for the return \
+               type needs to match the lifetime parameter for one of the parameters.",
+    },
+];
+
+pub fn command(args: &[String]) -> Result<(), String> {
+    let Some(topic) = args.first() else {
+        println!("Available topics:");
+        for chapter in CHAPTERS {
+            println!("  {} {}", render::pad(chapter.topic, 12), chapter.title);
+        }
+        println!("\nA section or example id works too, e.g. `book borrowing_mutable_refs`.");
+        return Ok(());
+    };
+
+    let chapter = find(topic).ok_or_else(|| format!("no Rust Book excerpt for '{}' (run `book` to list topics)", topic))?;
+
+    println!("{}\n", render::heading(chapter.title));
+    for line in render::wrap(chapter.text, WIDTH) {
+        println!("{}", line);
+    }
+    println!("\nExcerpted and adapted from \"The Rust Programming Language\" by Steve Klabnik,");
+    println!("Carol Nichols, Chris Krycho and the Rust community (MIT / Apache-2.0).");
+    println!("Read the full chapter at:");
+    println!("{}", chapter.url);
+    Ok(())
+}
+
+// Accepts a topic, a section name or an example id
+fn find(topic: &str) -> Option<&'static Chapter> {
+    let section = match lessons::find(topic) {
+        Some((section, _)) => section.name,
+        None => topic,
+    };
+
+    CHAPTERS
+        .iter()
+        .find(|chapter| chapter.topic == topic || chapter.sections.contains(&section))
+}
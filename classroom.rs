@@ -0,0 +1,562 @@
+// `classroom`: an instructor hosts a session and students' copies of the demo
+// join it over TCP, so a whole room can follow one walkthrough. The host
+// moves through an example's steps (the same stops as `present`) and every
+// student's screen follows; it asks questions from the quiz pool (see
+// quiz.rs), students answer from their own terminal, and the host sees how
+// many have answered as the answers come in. Nothing on the host's screen
+// gives the answer away (it's often the one on the projector) until `reveal`,
+// which shows the answer and how many got it right.
+//
+// The protocol is one line of text per message, so it can be watched with
+// netcat. Both ends look examples and questions up by name in their own copy
+// of the lessons, which is why a student has to run the host's release:
+//
+//   student -> host   HELLO <version> <name>
+//                     ANSWER <question> <choice>
+//   host -> student   WELCOME <students> <seed>
+//                     STEP <deep link>
+//                     QUESTION <question>
+//                     TALLY <question> <choice>=<count>...
+//                     REVEAL <question> <answer>
+//                     BYE <reason>
+//
+// TALLY is how many chose each answer, with no names attached, so the room
+// sees the spread of opinion clicker-style. The host sends it on `share` and
+// just before every REVEAL.
+//
+// Someone who joins late gets the current step, any open question and, if
+// the tally was already shared, that too, straight after WELCOME. The seed in
+// WELCOME is the host's (see rng.rs); students switch to it, so everyone's
+// choices are in the same order and a letter means the same answer on every
+// screen.
+//
+// Neither end trusts the other to behave: a line longer than MAX_LINE ends the
+// connection instead of being buffered, and once MAX_STUDENTS connections are
+// open the host turns new ones away with a BYE.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::iter;
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::links;
+use crate::present;
+use crate::quiz::{self, Question};
+use crate::rng;
+use crate::xapi;
+
+const DEFAULT_PORT: u16 = 7878;
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+const BAR_WIDTH: usize = 20;
+// How long a write to a student may block before they're dropped, so one
+// stalled connection can't freeze the session for the whole room
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+// The longest message either end accepts, in bytes; real ones are far shorter
+const MAX_LINE: usize = 1024;
+// Connections the host keeps open at once, joined or not
+const MAX_STUDENTS: usize = 200;
+
+const HOST_HELP: &str = "  show <example>  Start walking through an example (or a deep link to one of its steps)
+  next, back      Move to the next or previous step
+  ask [question]  Ask the next question about the current example, or one by name
+  tally           Show how the answers so far are spread, before revealing anything
+  share           Show that spread to the students too
+  reveal          Close the question and show everyone the spread and the answer
+  who             List the students who have joined
+  quit            End the session";
+
+pub fn command(args: &[String]) -> Result<(), String> {
+    match (args.first().map(String::as_str), args.get(1)) {
+        (Some("host"), port) => {
+            let port = match port {
+                Some(port) => port.parse().map_err(|_| format!("'{}' isn't a port number", port))?,
+                None => DEFAULT_PORT,
+            };
+            host(port)
+        }
+        (Some("join"), Some(address)) => {
+            let name = args.get(2).cloned().or_else(|| env::var("USER").ok()).unwrap_or_else(|| "student".to_string());
+            join(address, &name)
+        }
+        _ => Err("usage: classroom host [<port>] | classroom join <host>[:<port>] [<name>]".to_string()),
+    }
+}
+
+// What the host's main loop hears about, from the network and the keyboard
+enum HostEvent {
+    Connected(usize, TcpStream),
+    Received(usize, String),
+    Disconnected(usize),
+    Typed(String),
+    KeyboardClosed,
+}
+
+struct Student {
+    name: String,
+    stream: TcpStream,
+}
+
+// The open question and who has answered what
+struct Poll {
+    question: &'static Question,
+    answers: BTreeMap<usize, String>,
+    // Whether the students have been shown the tally
+    shared: bool,
+}
+
+impl Poll {
+    // How many chose each answer, in the order the choices are shown
+    fn counts(&self) -> Vec<(&'static str, usize)> {
+        let choices = self.question.choices();
+        choices
+            .into_iter()
+            .map(|choice| (choice, self.answers.values().filter(|answer| *answer == choice).count()))
+            .collect()
+    }
+
+    fn tally_message(&self) -> String {
+        let counts: Vec<String> = self.counts().iter().map(|(choice, count)| format!("{}={}", choice, count)).collect();
+        format!("TALLY {} {}", self.question.name, counts.join(" "))
+    }
+}
+
+#[derive(Default)]
+struct Session {
+    // Connections that haven't said HELLO yet
+    pending: BTreeMap<usize, TcpStream>,
+    students: BTreeMap<usize, Student>,
+    // The example being walked through, and the step (from 1)
+    current: Option<(links::Target, usize)>,
+    poll: Option<Poll>,
+    // Questions already asked, so `ask` moves on to the next one
+    asked: Vec<String>,
+}
+
+fn host(port: u16) -> Result<(), String> {
+    let listener = TcpListener::bind(("0.0.0.0", port)).map_err(|e| format!("could not listen on port {}: {}", port, e))?;
+    // Leaked once, so polls can refer to questions for as long as the session runs
+    let questions: &'static [Question] = Vec::leak(quiz::questions());
+
+    println!("Hosting a classroom on port {}. Students join with:", port);
+    println!("  rust_ownership_demo classroom join <this machine's address>:{}\n", port);
+    println!("{}", HOST_HELP);
+
+    let (events, inbox) = mpsc::channel();
+    let accepting = events.clone();
+    thread::spawn(move || {
+        // Connections whose reader thread is still running
+        let open = Arc::new(AtomicUsize::new(0));
+        for (id, stream) in listener.incoming().enumerate() {
+            let Ok(mut stream) = stream else { continue };
+            if open.load(Ordering::SeqCst) >= MAX_STUDENTS {
+                let _ = stream.set_write_timeout(Some(WRITE_TIMEOUT));
+                let _ = writeln!(stream, "BYE the classroom is full ({} students)", MAX_STUDENTS);
+                continue;
+            }
+            let Ok(reader) = stream.try_clone() else { continue };
+            if accepting.send(HostEvent::Connected(id, stream)).is_err() {
+                return;
+            }
+            open.fetch_add(1, Ordering::SeqCst);
+            let (events, open) = (accepting.clone(), Arc::clone(&open));
+            thread::spawn(move || {
+                for line in lines(reader) {
+                    let _ = events.send(HostEvent::Received(id, line));
+                }
+                open.fetch_sub(1, Ordering::SeqCst);
+                let _ = events.send(HostEvent::Disconnected(id));
+            });
+        }
+    });
+    read_keyboard(events, HostEvent::Typed, HostEvent::KeyboardClosed);
+
+    let mut session = Session::default();
+    for event in inbox {
+        match event {
+            HostEvent::Connected(id, stream) => {
+                if stream.set_write_timeout(Some(WRITE_TIMEOUT)).is_ok() {
+                    session.pending.insert(id, stream);
+                }
+            }
+            HostEvent::Received(id, line) => session.received(id, &line),
+            HostEvent::Disconnected(id) => {
+                session.pending.remove(&id);
+                if let Some(student) = session.students.remove(&id) {
+                    println!("  {} left ({} here)", student.name, session.students.len());
+                }
+            }
+            HostEvent::Typed(line) => match session.typed(line.trim(), questions) {
+                Ok(true) => {}
+                Ok(false) => break,
+                Err(e) => println!("  {}", e),
+            },
+            HostEvent::KeyboardClosed => break,
+        }
+    }
+    session.broadcast("BYE the instructor ended the session");
+    Ok(())
+}
+
+impl Session {
+    fn received(&mut self, id: usize, line: &str) {
+        let (kind, rest) = line.split_once(' ').unwrap_or((line, ""));
+        match kind {
+            "HELLO" => {
+                let Some(mut stream) = self.pending.remove(&id) else { return };
+                let (version, name) = rest.split_once(' ').unwrap_or((rest, "student"));
+                if version != VERSION {
+                    let _ = writeln!(stream, "BYE the host runs version {}, and you have {}", VERSION, version);
+                    // Ends their reader thread too, so they stop counting towards MAX_STUDENTS
+                    let _ = stream.shutdown(Shutdown::Both);
+                    return;
+                }
+                // Names end up on the instructor's screen, one per line
+                let name: String = name.chars().filter(|c| !c.is_control()).take(40).collect();
+                println!("  {} joined ({} here)", name, self.students.len() + 1);
+                self.students.insert(id, Student { name, stream });
+                self.send(id, &format!("WELCOME {} {}", self.students.len(), rng::seed()));
+                self.catch_up(id);
+            }
+            "ANSWER" => {
+                // Only students who said HELLO get a say in the tally
+                if !self.students.contains_key(&id) {
+                    return;
+                }
+                let Some((name, choice)) = rest.split_once(' ') else { return };
+                let Some(poll) = &mut self.poll else { return };
+                if poll.question.name != name || !poll.question.choices().contains(&choice) {
+                    return;
+                }
+                poll.answers.insert(id, choice.to_string());
+                println!("  {}/{} answered", poll.answers.len(), self.students.len());
+            }
+            _ => {}
+        }
+    }
+
+    // What a student who joins mid-session needs to see
+    fn catch_up(&mut self, id: usize) {
+        if let Some((target, step)) = &self.current {
+            let link = links::link(target.section, target.example, Some(*step));
+            self.send(id, &format!("STEP {}", link));
+        }
+        if let Some(poll) = &self.poll {
+            let question = format!("QUESTION {}", poll.question.name);
+            let tally = poll.shared.then(|| poll.tally_message());
+            self.send(id, &question);
+            if let Some(tally) = tally {
+                self.send(id, &tally);
+            }
+        }
+    }
+
+    // Handles one of the instructor's commands; false ends the session
+    fn typed(&mut self, line: &str, questions: &'static [Question]) -> Result<bool, String> {
+        let (command, argument) = line.split_once(' ').map_or((line, ""), |(c, a)| (c, a.trim()));
+        match command {
+            "" => {}
+            "show" => {
+                let target = links::resolve(argument)?;
+                let step = target.step.unwrap_or(1);
+                self.current = Some((target, step));
+                self.show_step()?;
+            }
+            "next" | "back" => {
+                let Some((target, step)) = &mut self.current else {
+                    return Err("nothing to move through yet: `show <example>` first".to_string());
+                };
+                let total = present::steps(target.example).len();
+                *step = if command == "next" { (*step + 1).min(total) } else { step.saturating_sub(1).max(1) };
+                self.show_step()?;
+            }
+            "ask" => {
+                let question = self.pick_question(argument, questions)?;
+                println!("  Asked {}: {}", question.name, question.prompt());
+                self.asked.push(question.name.clone());
+                self.poll = Some(Poll { question, answers: BTreeMap::new(), shared: false });
+                self.broadcast(&format!("QUESTION {}", question.name));
+            }
+            "tally" => {
+                let poll = self.poll.as_ref().ok_or("no question is open")?;
+                print_histogram(&poll.counts(), |_| "");
+            }
+            "share" => {
+                let poll = self.poll.as_mut().ok_or("no question is open")?;
+                poll.shared = true;
+                let tally = poll.tally_message();
+                self.broadcast(&tally);
+                println!("  Shared the spread of answers");
+            }
+            "reveal" => {
+                let poll = self.poll.take().ok_or("no question is open")?;
+                self.broadcast(&poll.tally_message());
+                self.broadcast(&format!("REVEAL {} {}", poll.question.name, poll.question.answer));
+                print_histogram(&poll.counts(), |choice| if choice == poll.question.answer { "right" } else { "" });
+                self.show_score(&poll);
+            }
+            "who" => {
+                let names: Vec<&str> = self.students.values().map(|student| student.name.as_str()).collect();
+                println!("  {} here: {}", names.len(), names.join(", "));
+            }
+            "quit" => return Ok(false),
+            _ => println!("{}", HOST_HELP),
+        }
+        Ok(true)
+    }
+
+    fn show_step(&mut self) -> Result<(), String> {
+        let Some((target, step)) = &self.current else { return Ok(()) };
+        let stops = present::steps(target.example);
+        let stop = stops.get(*step - 1).ok_or_else(|| format!("{} has only {} steps", target.example.title, stops.len()))?;
+        let code = target.example.code.lines().nth(stop.line).unwrap_or_default();
+        let link = links::link(target.section, target.example, Some(*step));
+        println!("  [{}/{}] {}  {}", step, stops.len(), link, code.trim());
+        if let Some(say) = stop.say {
+            println!("        {}", say);
+        }
+        self.broadcast(&format!("STEP {}", link));
+        Ok(())
+    }
+
+    // The named question, or the next unasked one about the current example
+    fn pick_question(&self, name: &str, questions: &'static [Question]) -> Result<&'static Question, String> {
+        if !name.is_empty() {
+            return questions
+                .iter()
+                .find(|question| question.name == name)
+                .ok_or_else(|| format!("no question named '{}' (see `export-quiz csv` for the names)", name));
+        }
+        let (target, _) = self.current.as_ref().ok_or("`show <example>` first, or `ask <question>` by name")?;
+        let example = target.example.id(target.section);
+        questions
+            .iter()
+            .filter(|question| question.example == example)
+            .find(|question| !self.asked.contains(&question.name))
+            .ok_or_else(|| "no more questions about this example; `ask <question>` picks any".to_string())
+    }
+
+    // How many of the answers were right, as a bar, once it's revealed
+    fn show_score(&self, poll: &Poll) {
+        let answered = poll.answers.len();
+        let correct = poll.answers.values().filter(|choice| *choice == poll.question.answer).count();
+        let filled = (correct * BAR_WIDTH).checked_div(answered).unwrap_or(0);
+        println!(
+            "  {}/{} answered  correct [{}{}] {}/{}",
+            answered,
+            self.students.len(),
+            "#".repeat(filled),
+            "-".repeat(BAR_WIDTH - filled),
+            correct,
+            answered
+        );
+    }
+
+    fn send(&mut self, id: usize, message: &str) {
+        let Some(student) = self.students.get_mut(&id) else { return };
+        if writeln!(student.stream, "{}", message).is_err() {
+            self.drop_student(id);
+        }
+    }
+
+    fn broadcast(&mut self, message: &str) {
+        let failed: Vec<usize> = self
+            .students
+            .iter_mut()
+            .filter_map(|(id, student)| writeln!(student.stream, "{}", message).is_err().then_some(*id))
+            .collect();
+        for id in failed {
+            self.drop_student(id);
+        }
+    }
+
+    // Drops a student whose connection failed or stalled past WRITE_TIMEOUT.
+    // Shutting the socket down also ends their reader thread
+    fn drop_student(&mut self, id: usize) {
+        if let Some(student) = self.students.remove(&id) {
+            let _ = student.stream.shutdown(Shutdown::Both);
+            println!("  {} stopped responding and was dropped ({} here)", student.name, self.students.len());
+        }
+    }
+}
+
+// What a student's main loop hears about
+enum StudentEvent {
+    Received(String),
+    HostGone,
+    Typed(String),
+    KeyboardClosed,
+}
+
+fn join(address: &str, name: &str) -> Result<(), String> {
+    let address = if address.contains(':') { address.to_string() } else { format!("{}:{}", address, DEFAULT_PORT) };
+    let mut stream = TcpStream::connect(&address).map_err(|e| format!("could not reach a classroom at {}: {}", address, e))?;
+    let reader = stream.try_clone().map_err(|e| format!("could not use the connection: {}", e))?;
+    writeln!(stream, "HELLO {} {}", VERSION, name).map_err(|e| format!("could not reach the host: {}", e))?;
+    // The whole pool: the host may be following a curriculum this student wasn't given
+    let questions = quiz::pool();
+
+    let (events, inbox) = mpsc::channel();
+    let received = events.clone();
+    thread::spawn(move || {
+        for line in lines(reader) {
+            if received.send(StudentEvent::Received(line)).is_err() {
+                return;
+            }
+        }
+        let _ = received.send(StudentEvent::HostGone);
+    });
+    read_keyboard(events, StudentEvent::Typed, StudentEvent::KeyboardClosed);
+
+    // The question on screen, and what this student answered
+    let mut open: Option<(&Question, Option<&str>)> = None;
+    for event in inbox {
+        match event {
+            StudentEvent::Received(line) => {
+                let (kind, rest) = line.split_once(' ').unwrap_or((&line, ""));
+                match kind {
+                    "WELCOME" => {
+                        let (here, seed) = rest.split_once(' ').unwrap_or((rest, ""));
+                        if let Ok(seed) = seed.parse() {
+                            rng::set_seed(seed);
+                        }
+                        println!("Joined the classroom at {} ({} here). Follow along on this screen.", address, here);
+                    }
+                    "STEP" => show_step(rest),
+                    "QUESTION" => {
+                        let Some(question) = questions.iter().find(|question| question.name == rest) else {
+                            println!("The host asked a question this version doesn't have ({})", rest);
+                            continue;
+                        };
+                        println!("\n{}", question.prompt());
+                        for (letter, choice) in ('a'..).zip(question.choices()) {
+                            println!("  {}) {}", letter, choice);
+                        }
+                        print!("Your answer: ");
+                        let _ = io::stdout().flush();
+                        open = Some((question, None));
+                    }
+                    "TALLY" => {
+                        let Some((question, answer)) = &open else { continue };
+                        let Some((name, counts)) = rest.split_once(' ') else { continue };
+                        if name != question.name {
+                            continue;
+                        }
+                        let counts: Vec<(&str, usize)> = counts
+                            .split(' ')
+                            .filter_map(|pair| {
+                                let (choice, count) = pair.split_once('=')?;
+                                let choice = question.choices().into_iter().find(|c| *c == choice)?;
+                                Some((choice, count.parse().ok()?))
+                            })
+                            .collect();
+                        println!("\nHow the room answered:");
+                        print_histogram(&counts, |choice| if Some(choice) == *answer { "yours" } else { "" });
+                    }
+                    "REVEAL" => {
+                        let Some((question, answer)) = open.take() else { continue };
+                        let verdict = match answer {
+                            Some(answer) if answer == question.answer => "you got it",
+                            Some(_) => "not this time",
+                            None => "you didn't answer",
+                        };
+                        println!("\nThe answer is {}: {}.", question.answer, verdict);
+                        if let Some(answer) = answer {
+                            xapi::answered_question(&question.name, &question.prompt(), answer, answer == question.answer);
+                        }
+                        if let Some(feedback) = &question.feedback {
+                            println!("{}", feedback);
+                        }
+                    }
+                    "BYE" => {
+                        println!("\nThe session is over: {}", rest);
+                        return Ok(());
+                    }
+                    _ => {}
+                }
+            }
+            StudentEvent::Typed(text) => {
+                let Some((question, answer)) = &mut open else { continue };
+                let choices = question.choices();
+                let picked = text.trim().chars().next().and_then(|letter| {
+                    let index = (letter.to_ascii_lowercase() as usize).checked_sub('a' as usize)?;
+                    choices.get(index).copied()
+                });
+                match picked {
+                    Some(choice) => {
+                        writeln!(stream, "ANSWER {} {}", question.name, choice)
+                            .map_err(|e| format!("could not reach the host: {}", e))?;
+                        *answer = Some(choice);
+                        println!("Sent {}; you can change it until the answer is revealed.", choice);
+                    }
+                    None => {
+                        print!("Type one of the letters: ");
+                        let _ = io::stdout().flush();
+                    }
+                }
+            }
+            StudentEvent::HostGone => return Err("lost the connection to the host".to_string()),
+            StudentEvent::KeyboardClosed => {}
+        }
+    }
+    Ok(())
+}
+
+// One bar per choice, lettered as the students see them, scaled to the number
+// of answers; `note` labels a choice (the right one, or the student's own)
+fn print_histogram(counts: &[(&str, usize)], note: impl Fn(&str) -> &'static str) {
+    let answered: usize = counts.iter().map(|(_, count)| count).sum();
+    for (letter, (choice, count)) in ('a'..).zip(counts) {
+        let filled = (count * BAR_WIDTH).checked_div(answered).unwrap_or(0);
+        let bar = format!("{}{}", "#".repeat(filled), "-".repeat(BAR_WIDTH - filled));
+        let line = format!("  {}) {:6} [{}] {:>3}  {}", letter, choice, bar, count, note(choice));
+        println!("{}", line.trim_end());
+    }
+}
+
+// Draws the step a STEP message points at, as `present` would
+fn show_step(link: &str) {
+    let Ok(target) = links::resolve(link) else {
+        println!("The host is showing {}, which this version doesn't have", link);
+        return;
+    };
+    let stops = present::steps(target.example);
+    let step = target.step.unwrap_or(1);
+    if step > stops.len() {
+        return;
+    }
+    let lines: Vec<&str> = target.example.code.lines().collect();
+    present::draw(target.example.title, &lines, &stops, step - 1);
+    println!("\n  [{}/{}] {}", step, stops.len(), link);
+}
+
+// The lines that come in over `stream`, until it closes, fails or sends one
+// longer than MAX_LINE: `take` stops reading there, so it's never buffered
+fn lines(stream: TcpStream) -> impl Iterator<Item = String> {
+    let mut reader = BufReader::new(stream);
+    iter::from_fn(move || {
+        let mut line = String::new();
+        match (&mut reader).take(MAX_LINE as u64 + 1).read_line(&mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) if !line.ends_with('\n') && line.len() > MAX_LINE => None,
+            Ok(_) => Some(line.trim_end_matches(['\r', '\n']).to_string()),
+        }
+    })
+}
+
+// Passes each line typed on stdin to the main loop, from a thread of its own
+fn read_keyboard<E: Send + 'static>(events: Sender<E>, typed: fn(String) -> E, closed: E) {
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines().map_while(Result::ok) {
+            if events.send(typed(line)).is_err() {
+                return;
+            }
+        }
+        let _ = events.send(closed);
+    });
+}
@@ -0,0 +1,225 @@
+// Command-line entry points. Running the program with no arguments plays the
+// whole demo; these commands let learners work with a single example.
+
+use crate::bench;
+use crate::book;
+use crate::classroom;
+use crate::clipboard;
+use crate::codegen;
+use crate::curriculum;
+use crate::diagnostics;
+use crate::doctor;
+use crate::lessons;
+use crate::links;
+use crate::lint;
+use crate::packs;
+use crate::playground;
+use crate::practice;
+use crate::present;
+use crate::qr::QrCode;
+use crate::quiz;
+use crate::refactor;
+use crate::render;
+use crate::sandbox;
+use crate::scaffold;
+use crate::update;
+use crate::xapi;
+
+const USAGE: &str = "\
+Usage: rust_ownership_demo [--curriculum <file>] [--seed <n>] [--record <cast>] [--trace-clones] [COMMAND]
+
+With no command, plays the whole demo. An <example> is an id from `list`, like
+ownership_transfer, or a deep link like ownership-demo://ownership/transfer#2
+(#2 is the second step of `present`). --curriculum follows an instructor's
+selection and order of lessons (see curricula/) in the demo, `list`,
+`export-quiz` and `classroom`.
+--seed picks what's random (question choices, property-test inputs), so a run
+can be repeated exactly; a classroom's students follow their host's seed.
+--record saves everything the command prints, with its timing, as an asciinema
+cast for publishing a replayable walkthrough.
+--trace-clones records a backtrace for every clone of the lessons' traced values
+and lists the lines that cloned the most when the command finishes.
+
+Commands:
+  list                    List every example with its id
+  run <example> [--copy] [--qr] [--diagnostics] [--refresh-diagnostics]
+                          Play a single example. --copy also puts its code on the clipboard;
+                          --qr shows a QR code of its Playground link for students to scan;
+                          --diagnostics shows what rustc really says about its broken lines
+                          (cached per rustc version; --refresh-diagnostics compiles them again)
+  present <example>       Step through an example's code for a projector, one line and
+                          narration bullet per Enter
+  sandbox new <example>   Copy an example into sandbox/NNN_<example>/ as a cargo project
+  sandbox run <id>        Build and run a sandbox experiment (by number or directory name)
+  exercise [list]         List the exercise packs and which exercises you've solved
+  exercise start <exercise>
+                          Copy an exercise into practice/<exercise>.rs for you to fix
+  exercise check <exercise> | --all [--force]
+                          Build your fix with the exercise's hidden tests and run them
+                          (--all checks every exercise you've started, several at once;
+                          files that passed and haven't changed are skipped without --force)
+  exercise hint <exercise>
+                          Reveal the next hint; `exercise solution <exercise>` shows a fix
+  pack [list]             List the built-in exercise packs and the installed lesson packs
+  pack install <directory or .zip> [--trust ask|signed|all|none]
+                          Check a third-party lesson pack (its checksums and signature, that
+                          you trust it, then its content and its exercises with rustc) and
+                          install it alongside the built-in exercises
+  pack seal <directory>   Write the SHA256SUMS a pack's author ships with it
+  pack remove <pack>      Uninstall a lesson pack
+  refactor                Take a program that uses &String, clones and owned returns, choose
+                          refactorings for it, and see what each does to its allocations
+  bench [<example>]       Benchmark the lessons that compare costs (clone vs borrow, Cow,
+                          Arc::make_mut) with criterion, and sum up what the numbers say
+  book [<topic>]          Read the Rust Book excerpt for a topic, section or example
+  export-quiz [gift|csv]  Print a question for every broken line (which error does it cause?),
+                          as Moodle GIFT or CSV, for importing into a learning platform
+  classroom host [<port>] Lead a session: students' screens follow your steps and they answer
+                          your questions live (port 7878 unless given)
+  classroom join <host>[:<port>] [<name>]
+                          Follow a session someone is hosting, and answer its questions
+  curriculum diff <old> <new>
+                          Show which lessons, examples and questions were added, removed or
+                          changed between two curricula, snapshots, or `default`
+  curriculum snapshot     Print the current lessons, code fingerprints and questions, to keep
+                          and diff against after the next release
+  doctor                  Show which Rust toolchain is installed and what it supports
+  self-update [--check] [--force] [--from <url>]
+                          Download and install the newest release from the update manifest
+                          (--from, or $RUST_OWNERSHIP_DEMO_UPDATE_URL); --check only reports it
+  bundle-diagnostics [--check]
+                          Capture rustc's output for every broken line into diagnostics.txt,
+                          which is built into the program for machines without rustc
+  gen-examples [--check]  Regenerate examples/ from the lessons (--check only reports drift)
+  new-lesson <section> <name>
+                          Start a new example: write its skeleton into lessons/<section>.rs
+                          (a new file and registry entry for a new section)
+  lint-content            Check the lesson content (narration width, example code, error codes)
+  help                    Show this message";
+
+// Runs the command described by `args` and returns the process exit code
+pub fn run(args: &[String]) -> i32 {
+    let result = match args[0].as_str() {
+        "list" => list(),
+        "run" => run_example(&args[1..]),
+        "present" => present::command(&args[1..]),
+        "sandbox" => sandbox::command(&args[1..]),
+        "exercise" => practice::command(&args[1..]),
+        "pack" => packs::command(&args[1..]),
+        "refactor" => refactor::command(),
+        "bench" => bench::command(&args[1..]),
+        "book" => book::command(&args[1..]),
+        "export-quiz" => quiz::command(&args[1..]),
+        "classroom" => classroom::command(&args[1..]),
+        "curriculum" => curriculum::command(&args[1..]),
+        "doctor" => doctor::command(),
+        "self-update" => update::command(&args[1..]),
+        "bundle-diagnostics" => diagnostics::command(&args[1..]),
+        "gen-examples" => codegen::command(&args[1..]),
+        "new-lesson" => scaffold::command(&args[1..]),
+        "lint-content" => lint::command(),
+        "help" | "--help" | "-h" => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        other => Err(format!("unknown command '{}'\n\n{}", other, USAGE)),
+    };
+
+    match result {
+        Ok(()) => 0,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            1
+        }
+    }
+}
+
+fn list() -> Result<(), String> {
+    if let Some(title) = &curriculum::active().title {
+        println!("{}\n", title);
+    }
+    // Ids are padded to the longest one shown, so every title starts in the same column
+    let id_width = curriculum::shown()
+        .flat_map(|lesson| &lesson.examples)
+        .map(|(section, example)| render::width(&example.id(section)))
+        .max()
+        .unwrap_or(0);
+    for lesson in curriculum::shown() {
+        println!("{}", lesson.title);
+        for (section, example) in &lesson.examples {
+            println!("  {} {}", render::pad(&example.id(section), id_width), example.title);
+        }
+    }
+    Ok(())
+}
+
+fn run_example(args: &[String]) -> Result<(), String> {
+    let copy = args.iter().any(|arg| arg == "--copy");
+    let qr = args.iter().any(|arg| arg == "--qr");
+    let refresh = args.iter().any(|arg| arg == "--refresh-diagnostics");
+    let diagnostics = refresh || args.iter().any(|arg| arg == "--diagnostics");
+    let id = args
+        .iter()
+        .find(|arg| !arg.starts_with("--"))
+        .ok_or("run needs an example id (see `list`)")?;
+    let (section, example) = find_example(id)?;
+
+    println!("Example: {}", example.title);
+    let report = example.run_measured();
+    println!("  {}", report.footer());
+    xapi::completed_example(section, example);
+
+    if diagnostics {
+        print_diagnostics(example, refresh)?;
+    }
+
+    if copy {
+        match clipboard::copy(example.code)? {
+            clipboard::Method::Tool(tool) => println!("\nCopied the example's code to the clipboard with {}", tool),
+            clipboard::Method::Osc52 => println!("\nSent the example's code to your terminal's clipboard (OSC 52)"),
+        }
+    }
+
+    if qr {
+        let link = playground::link(example.code);
+        let code = QrCode::encode(link.as_bytes())?;
+        println!("\nScan to open this example in the Rust Playground (zoom out if it doesn't fit):\n");
+        print!("{}", code.to_terminal_string());
+        println!("\n{}", link);
+    }
+    Ok(())
+}
+
+// Prints rustc's own output for every broken line of the example
+fn print_diagnostics(example: &lessons::Example, refresh: bool) -> Result<(), String> {
+    if example.broken_lines.is_empty() {
+        println!("\nThis example has no lines that don't compile");
+        return Ok(());
+    }
+    for broken in example.broken_lines {
+        // One line without diagnostics shouldn't hide the others
+        let captured = match diagnostics::capture(example, broken, refresh) {
+            Ok(captured) => captured,
+            Err(reason) => {
+                println!("\nUncommenting `{}`: no diagnostics to show, {}", broken.line, reason);
+                continue;
+            }
+        };
+        let source = match captured.source {
+            diagnostics::Source::Compiled => "just compiled",
+            diagnostics::Source::Cache => "cached",
+            diagnostics::Source::Bundle => "captured in advance, since rustc isn't available",
+        };
+        println!("\nUncommenting `{}` ({}, {}):\n", broken.line, captured.rustc, source);
+        for line in captured.text.lines() {
+            println!("    {}", line);
+        }
+    }
+    Ok(())
+}
+
+// Looks up an example by id or deep link (see links.rs), with an error
+// message pointing at `list`
+pub fn find_example(address: &str) -> Result<(&'static lessons::Section, &'static lessons::Example), String> {
+    links::resolve(address).map(|target| (target.section, target.example))
+}
@@ -0,0 +1,92 @@
+// Copying example code to the system clipboard.
+//
+// Locally we hand the text to the platform's clipboard tool (pbcopy, wl-copy,
+// xclip, xsel or clip.exe). Over SSH, or when none of those are installed, we
+// fall back to the OSC 52 escape sequence, which asks the terminal emulator
+// itself to set the clipboard, so it works from a remote machine too.
+
+use std::env;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+// How the text reached the clipboard, for the confirmation message
+pub enum Method {
+    Tool(&'static str),
+    Osc52,
+}
+
+const TOOLS: &[(&str, &[&str])] = &[
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+    ("clip.exe", &[]),
+];
+
+pub fn copy(text: &str) -> Result<Method, String> {
+    let over_ssh = env::var_os("SSH_TTY").is_some() || env::var_os("SSH_CONNECTION").is_some();
+    if !over_ssh {
+        for &(tool, args) in TOOLS {
+            if copy_with_tool(tool, args, text) {
+                return Ok(Method::Tool(tool));
+            }
+        }
+    }
+
+    copy_with_osc52(text)
+        .map(|()| Method::Osc52)
+        .map_err(|e| format!("could not copy to the clipboard: {}", e))
+}
+
+// Pipes the text into a clipboard tool, returning false if it isn't available
+fn copy_with_tool(tool: &str, args: &[&str], text: &str) -> bool {
+    let child = Command::new(tool)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+
+    let Ok(mut child) = child else {
+        return false;
+    };
+    let written = match child.stdin.take() {
+        Some(mut stdin) => stdin.write_all(text.as_bytes()).is_ok(),
+        None => false,
+    }; // stdin is dropped here, closing the pipe so the tool sees end of input
+
+    let succeeded = child.wait().is_ok_and(|status| status.success());
+    written && succeeded
+}
+
+fn copy_with_osc52(text: &str) -> io::Result<()> {
+    let mut sequence = format!("\x1b]52;c;{}\x07", base64(text.as_bytes()));
+
+    // tmux only forwards escape sequences to the outer terminal when wrapped
+    if env::var_os("TMUX").is_some() {
+        sequence = format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"));
+    }
+
+    // Write to the terminal directly so redirected output stays clean
+    match OpenOptions::new().write(true).open("/dev/tty") {
+        Ok(mut tty) => tty.write_all(sequence.as_bytes()),
+        Err(_) => io::stdout().write_all(sequence.as_bytes()),
+    }
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+
+        out.push(ALPHABET[(n >> 18) as usize & 63] as char);
+        out.push(ALPHABET[(n >> 12) as usize & 63] as char);
+        out.push(if chunk.len() > 1 { ALPHABET[(n >> 6) as usize & 63] as char } else { '=' });
+        out.push(if chunk.len() > 2 { ALPHABET[n as usize & 63] as char } else { '=' });
+    }
+    out
+}
@@ -0,0 +1,74 @@
+// `gen-examples`: writes every example's code to examples/<id>.rs so it can
+// be run on its own with `cargo run --example <id>`. The files are generated
+// from the lesson registry and checked in; `gen-examples --check` (also run
+// by `lint-content`) fails when they have drifted from it.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::lessons;
+
+const EXAMPLES_DIR: &str = "examples";
+const HEADER: &str = "// Generated from the lesson registry by `rust_ownership_demo gen-examples`.\n\
+                      // Edit the lesson instead of this file.\n\
+                      // Lesson code sometimes shows non-idiomatic forms on purpose.\n\
+                      #![allow(unused)]\n";
+
+pub fn command(args: &[String]) -> Result<(), String> {
+    let check = args.iter().any(|arg| arg == "--check");
+    let stale = stale_files(Path::new(EXAMPLES_DIR))?;
+
+    if check {
+        if stale.is_empty() {
+            println!("{}/ is up to date", EXAMPLES_DIR);
+            return Ok(());
+        }
+        for (path, _) in &stale {
+            println!("out of date: {}", path.display());
+        }
+        return Err(format!("{}/ is out of date, run `gen-examples` to regenerate it", EXAMPLES_DIR));
+    }
+
+    fs::create_dir_all(EXAMPLES_DIR).map_err(|e| format!("could not create {}/: {}", EXAMPLES_DIR, e))?;
+    for (path, contents) in &stale {
+        match contents {
+            Some(contents) => {
+                fs::write(path, contents).map_err(|e| format!("could not write {}: {}", path.display(), e))?;
+                println!("wrote {}", path.display());
+            }
+            None => {
+                fs::remove_file(path).map_err(|e| format!("could not remove {}: {}", path.display(), e))?;
+                println!("removed {}", path.display());
+            }
+        }
+    }
+    println!("{}/ is up to date", EXAMPLES_DIR);
+    Ok(())
+}
+
+// Generated files that are missing, differ from the registry, or belong to
+// examples that no longer exist (those map to None, meaning "delete")
+pub fn stale_files(dir: &Path) -> Result<Vec<(PathBuf, Option<String>)>, String> {
+    let mut stale = Vec::new();
+    let mut expected = Vec::new();
+
+    for (section, example) in lessons::examples() {
+        let path = dir.join(format!("{}.rs", example.id(section)));
+        let contents = format!("{}// {}\n\n{}", HEADER, example.title, example.code);
+        if fs::read_to_string(&path).ok().as_deref() != Some(contents.as_str()) {
+            stale.push((path.clone(), Some(contents)));
+        }
+        expected.push(path);
+    }
+
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let generated = fs::read_to_string(&path).is_ok_and(|text| text.starts_with(HEADER));
+            if generated && !expected.contains(&path) {
+                stale.push((path, None));
+            }
+        }
+    }
+    Ok(stale)
+}
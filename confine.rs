@@ -0,0 +1,114 @@
+// Runs programs we don't trust to behave, like a learner's half-fixed
+// exercise: each gets its own process and scratch directory, a wall-clock
+// timeout, and a cap on how much output we keep. An accidental `loop {}` or
+// a print inside one is stopped instead of hanging the tool or filling memory.
+//
+// This guards against mistakes, not attacks: the program still runs as the
+// current user and could reach the network or files outside its directory.
+// Nothing we ship expects it to.
+
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+pub struct Limits {
+    // How long the program may run before it's killed
+    pub timeout: Duration,
+    // Bytes kept from stdout and from stderr; the program is killed when
+    // either goes over
+    pub max_output: usize,
+}
+
+// rustc on a kata takes well under a second, but a cold machine can be slow
+pub const COMPILE: Limits = Limits { timeout: Duration::from_secs(60), max_output: 1024 * 1024 };
+
+// The hidden tests are tiny, so anything slower than this is stuck
+pub const TESTS: Limits = Limits { timeout: Duration::from_secs(10), max_output: 64 * 1024 };
+
+// How polling for the program's exit is paced
+const POLL: Duration = Duration::from_millis(10);
+
+pub struct Finished {
+    // Whether the program exited successfully on its own
+    pub success: bool,
+    pub stdout: String,
+    pub stderr: String,
+    // Why the program was killed, if it was
+    pub stopped: Option<String>,
+}
+
+// Runs `command` inside `dir` (its working directory and TMPDIR) with no stdin
+pub fn run(command: &mut Command, dir: &Path, limits: &Limits) -> Result<Finished, String> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let mut child = command
+        .current_dir(dir)
+        .env("TMPDIR", dir)
+        .env("TMP", dir)
+        .env("TEMP", dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("could not run {}: {}", program, e))?;
+
+    let overflowed = Arc::new(AtomicBool::new(false));
+    let stdout = capture(child.stdout.take(), limits.max_output, &overflowed);
+    let stderr = capture(child.stderr.take(), limits.max_output, &overflowed);
+
+    let started = Instant::now();
+    let mut stopped = None;
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(|e| format!("could not wait for {}: {}", program, e))? {
+            break Some(status);
+        }
+        if overflowed.load(Ordering::Relaxed) {
+            stopped = Some(format!("it printed more than {} KB", limits.max_output / 1024));
+        } else if started.elapsed() >= limits.timeout {
+            stopped = Some(format!("it ran for more than {} seconds", limits.timeout.as_secs()));
+        }
+        if stopped.is_some() {
+            let _ = child.kill();
+            let _ = child.wait();
+            break None;
+        }
+        thread::sleep(POLL);
+    };
+
+    Ok(Finished {
+        success: status.is_some_and(|status| status.success()),
+        stdout: collect(stdout),
+        stderr: collect(stderr),
+        stopped,
+    })
+}
+
+// Reads a pipe on its own thread, so a full pipe never blocks the program,
+// keeping at most `max` bytes and flagging when there was more
+fn capture(pipe: Option<impl Read + Send + 'static>, max: usize, overflowed: &Arc<AtomicBool>) -> JoinHandle<Vec<u8>> {
+    let overflowed = Arc::clone(overflowed);
+    thread::spawn(move || {
+        let mut kept = Vec::new();
+        let Some(mut pipe) = pipe else { return kept };
+        let mut chunk = [0; 8192];
+        while let Ok(read) = pipe.read(&mut chunk) {
+            if read == 0 {
+                break;
+            }
+            let room = max - kept.len();
+            kept.extend_from_slice(&chunk[..read.min(room)]);
+            if read > room {
+                overflowed.store(true, Ordering::Relaxed);
+                break;
+            }
+        }
+        kept
+    })
+}
+
+fn collect(reader: JoinHandle<Vec<u8>>) -> String {
+    String::from_utf8_lossy(&reader.join().unwrap_or_default()).into_owned()
+}
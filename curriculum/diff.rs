@@ -0,0 +1,243 @@
+// `curriculum diff`: what changed between two versions of a course, so an
+// instructor can see what slides, handouts and question banks need updating
+// before the next cohort. It only reports; nothing is changed.
+//
+// Each side is a curriculum file, `default` for the lessons as shipped, or a
+// snapshot saved by `curriculum snapshot`. A snapshot records the lessons,
+// each example's code (as a fingerprint) and every quiz question, so keeping
+// one from the release a course was taught with shows later what the new
+// release changed in the content itself, not just in the arrangement.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use super::Curriculum;
+use crate::fingerprint;
+use crate::quiz;
+
+const HEADER: &str = "rust_ownership_demo curriculum snapshot 1";
+
+// What a course looked like, from either a curriculum or a snapshot
+struct Version {
+    title: Option<String>,
+    lessons: Vec<LessonSummary>,
+    // Example id to (title, fingerprint of its code)
+    examples: BTreeMap<String, (String, String)>,
+    // Question name to (answer, broken line)
+    questions: BTreeMap<String, (String, String)>,
+}
+
+struct LessonSummary {
+    title: String,
+    examples: Vec<String>,
+    hidden: bool,
+    quiz: bool,
+}
+
+pub fn command(args: &[String]) -> Result<(), String> {
+    match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["diff", old, new] => {
+            let (old, new) = (load(old)?, load(new)?);
+            diff(&old, &new);
+            Ok(())
+        }
+        ["snapshot"] => {
+            print!("{}", snapshot(&version(super::active())));
+            Ok(())
+        }
+        _ => Err("usage: curriculum diff <old> <new> | curriculum snapshot".to_string()),
+    }
+}
+
+fn load(side: &str) -> Result<Version, String> {
+    if side == "default" {
+        return Ok(version(&super::everything()));
+    }
+    let text = fs::read_to_string(side).map_err(|e| format!("could not read {}: {}", side, e))?;
+    if text.lines().next() == Some(HEADER) {
+        parse_snapshot(&text).map_err(|e| format!("{}: {}", side, e))
+    } else {
+        Ok(version(&super::load(Path::new(side))?))
+    }
+}
+
+fn version(curriculum: &Curriculum) -> Version {
+    let lessons = curriculum
+        .lessons
+        .iter()
+        .map(|lesson| LessonSummary {
+            title: lesson.title.clone(),
+            examples: lesson.examples.iter().map(|(section, example)| example.id(section)).collect(),
+            hidden: lesson.hidden,
+            quiz: lesson.quiz,
+        })
+        .collect();
+    let examples = curriculum
+        .lessons
+        .iter()
+        .flat_map(|lesson| &lesson.examples)
+        .map(|(section, example)| (example.id(section), (example.title.to_string(), fingerprint::of(example.code))))
+        .collect();
+    let questions = quiz::questions_for(curriculum)
+        .into_iter()
+        .map(|question| (question.name, (question.answer.to_string(), question.line.to_string())))
+        .collect();
+    Version { title: curriculum.title.clone(), lessons, examples, questions }
+}
+
+// One tab-separated record per line, under HEADER
+fn snapshot(version: &Version) -> String {
+    let yes_no = |flag: bool| if flag { "yes" } else { "no" };
+    let mut out = format!("{}\n", HEADER);
+    if let Some(title) = &version.title {
+        out.push_str(&format!("title\t{}\n", title));
+    }
+    for lesson in &version.lessons {
+        out.push_str(&format!(
+            "lesson\t{}\t{}\t{}\t{}\n",
+            lesson.title,
+            yes_no(lesson.hidden),
+            yes_no(lesson.quiz),
+            lesson.examples.join(" ")
+        ));
+    }
+    for (id, (title, code)) in &version.examples {
+        out.push_str(&format!("example\t{}\t{}\t{}\n", id, code, title));
+    }
+    for (name, (answer, line)) in &version.questions {
+        out.push_str(&format!("question\t{}\t{}\t{}\n", name, answer, line));
+    }
+    out
+}
+
+fn parse_snapshot(text: &str) -> Result<Version, String> {
+    let mut version = Version { title: None, lessons: Vec::new(), examples: BTreeMap::new(), questions: BTreeMap::new() };
+    for (number, line) in text.lines().enumerate().skip(1) {
+        let fields: Vec<&str> = line.split('\t').collect();
+        match fields[..] {
+            ["title", title] => version.title = Some(title.to_string()),
+            ["lesson", title, hidden, quiz, examples] => version.lessons.push(LessonSummary {
+                title: title.to_string(),
+                examples: examples.split_whitespace().map(str::to_string).collect(),
+                hidden: hidden == "yes",
+                quiz: quiz == "yes",
+            }),
+            ["example", id, code, title] => {
+                version.examples.insert(id.to_string(), (title.to_string(), code.to_string()));
+            }
+            ["question", name, answer, line] => {
+                version.questions.insert(name.to_string(), (answer.to_string(), line.to_string()));
+            }
+            [""] => {}
+            _ => return Err(format!("line {} isn't part of a snapshot", number + 1)),
+        }
+    }
+    Ok(version)
+}
+
+fn diff(old: &Version, new: &Version) {
+    let mut changes = 0;
+
+    let mut lines = Vec::new();
+    if old.title != new.title {
+        let show = |title: &Option<String>| title.clone().unwrap_or_else(|| "(none)".to_string());
+        lines.push(format!("  ~ course title: {} -> {}", show(&old.title), show(&new.title)));
+    }
+    let find = |version: &'_ Version, title: &str| version.lessons.iter().position(|lesson| lesson.title == title);
+    for lesson in &old.lessons {
+        if find(new, &lesson.title).is_none() {
+            lines.push(format!("  - {} ({})", lesson.title, count(&lesson.examples)));
+        }
+    }
+    for lesson in &new.lessons {
+        let Some(index) = find(old, &lesson.title) else {
+            lines.push(format!("  + {} ({})", lesson.title, count(&lesson.examples)));
+            continue;
+        };
+        let before = &old.lessons[index];
+        let mut what = Vec::new();
+        let added: Vec<&str> = lesson.examples.iter().filter(|id| !before.examples.contains(id)).map(String::as_str).collect();
+        let removed: Vec<&str> = before.examples.iter().filter(|id| !lesson.examples.contains(id)).map(String::as_str).collect();
+        if !added.is_empty() {
+            what.push(format!("added {}", added.join(", ")));
+        }
+        if !removed.is_empty() {
+            what.push(format!("removed {}", removed.join(", ")));
+        }
+        let kept = |examples: &[String], other: &[String]| -> Vec<String> {
+            examples.iter().filter(|id| other.contains(id)).cloned().collect()
+        };
+        if kept(&lesson.examples, &before.examples) != kept(&before.examples, &lesson.examples) {
+            what.push("examples in a new order".to_string());
+        }
+        if lesson.hidden != before.hidden {
+            what.push(if lesson.hidden { "now hidden" } else { "no longer hidden" }.to_string());
+        }
+        if lesson.quiz != before.quiz {
+            what.push(if lesson.quiz { "now quizzed" } else { "no longer quizzed" }.to_string());
+        }
+        if !what.is_empty() {
+            lines.push(format!("  ~ {}: {}", lesson.title, what.join("; ")));
+        }
+    }
+    let common_order = |from: &Version, other: &Version| -> Vec<String> {
+        from.lessons.iter().map(|lesson| lesson.title.clone()).filter(|title| find(other, title).is_some()).collect()
+    };
+    if common_order(old, new) != common_order(new, old) {
+        lines.push("  ~ the lessons are in a new order".to_string());
+    }
+    changes += section("Lessons", lines);
+
+    // Examples both versions teach, whose content changed
+    let mut lines = Vec::new();
+    for (id, (title, code)) in &new.examples {
+        let Some((old_title, old_code)) = old.examples.get(id) else { continue };
+        if old_title != title {
+            lines.push(format!("  ~ {}: retitled \"{}\" -> \"{}\"", id, old_title, title));
+        }
+        if old_code != code {
+            lines.push(format!("  ~ {}: its code changed", id));
+        }
+    }
+    changes += section("Examples", lines);
+
+    let mut lines = Vec::new();
+    for (name, (answer, line)) in &old.questions {
+        if !new.questions.contains_key(name) {
+            lines.push(format!("  - {}: {} for `{}`", name, answer, line));
+        }
+    }
+    for (name, (answer, line)) in &new.questions {
+        match old.questions.get(name) {
+            None => lines.push(format!("  + {}: {} for `{}`", name, answer, line)),
+            Some((old_answer, old_line)) if old_answer != answer || old_line != line => {
+                lines.push(format!("  ~ {}: {} for `{}`, was {} for `{}`", name, answer, line, old_answer, old_line))
+            }
+            Some(_) => {}
+        }
+    }
+    changes += section("Questions", lines);
+
+    if changes == 0 {
+        println!("No differences");
+    }
+}
+
+// Prints a heading and its lines, if there are any, and says how many
+fn section(heading: &str, lines: Vec<String>) -> usize {
+    if !lines.is_empty() {
+        println!("{}", heading);
+        for line in &lines {
+            println!("{}", line);
+        }
+    }
+    lines.len()
+}
+
+fn count(examples: &[String]) -> String {
+    match examples.len() {
+        1 => "1 example".to_string(),
+        n => format!("{} examples", n),
+    }
+}
@@ -0,0 +1,270 @@
+// Curricula: an instructor's own selection and order of the lessons. Without
+// one, the lessons are the sections in the order the demo has always played
+// them. With `--curriculum <file>` (before or after the command), the demo,
+// `list`, `export-quiz` and `classroom` follow the file instead.
+//
+// The file is a small subset of TOML (see toml.rs) with [[lesson]] tables, e.g.
+//
+//   title = "Week 1: ownership"
+//
+//   [[lesson]]
+//   section = "ownership"             # a whole section, under its own title
+//
+//   [[lesson]]
+//   title = "Borrowing, briefly"      # a new title
+//   examples = ["borrowing_immutable_refs", "slices_string_slices"]
+//
+//   [[lesson]]
+//   section = "lifetimes"
+//   hidden = true                     # not in the menu, but still quizzed
+//
+//   [[lesson]]
+//   section = "threads"
+//   quiz = false                      # in the menu, but not quizzed
+//
+// `examples` picks and orders examples from any section, by id or deep link
+// (old ids still work). Sections and examples the file doesn't mention are
+// left out, though `run` and deep links still reach them.
+//
+// `curriculum diff` compares two curricula, or two releases of the content
+// (see diff.rs).
+
+mod diff;
+
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use crate::lessons::{self, Example, Section};
+use crate::links;
+use crate::toml::{self, Value};
+use crate::xapi;
+
+pub use diff::command;
+
+// Where the curricula shipped with the demo live; lint-content checks them
+pub const CURRICULA_DIR: &str = "curricula";
+
+pub struct Curriculum {
+    pub title: Option<String>,
+    pub lessons: Vec<Lesson>,
+}
+
+pub struct Lesson {
+    pub title: String,
+    pub examples: Vec<(&'static Section, &'static Example)>,
+    // Left out of the menu and the demo
+    pub hidden: bool,
+    // Whether its broken lines become quiz questions
+    pub quiz: bool,
+}
+
+impl Lesson {
+    // Plays every example in the lesson under a "SECTION" heading
+    pub fn run(&self, number: usize) {
+        println!("SECTION {}: {}", number, self.title);
+        println!("------------------------------------------");
+
+        for (i, (section, example)) in self.examples.iter().enumerate() {
+            if self.examples.len() == 1 {
+                println!("Example: {}", example.title);
+            } else {
+                println!("Example {}: {}", i + 1, example.title);
+            }
+            let report = example.run_measured();
+            println!("  {}", report.footer());
+            xapi::completed_example(section, example);
+            println!();
+        }
+    }
+}
+
+static ACTIVE: OnceLock<Curriculum> = OnceLock::new();
+
+// The curriculum in use: the one given with --curriculum, or every section
+pub fn active() -> &'static Curriculum {
+    ACTIVE.get_or_init(everything)
+}
+
+// Every section, whole and in order: the demo without a curriculum
+pub fn everything() -> Curriculum {
+    Curriculum { title: None, lessons: lessons::SECTIONS.iter().map(whole_section).collect() }
+}
+
+// The lessons that appear in the menu and the demo
+pub fn shown() -> impl Iterator<Item = &'static Lesson> {
+    active().lessons.iter().filter(|lesson| !lesson.hidden)
+}
+
+// Removes `--curriculum <file>` (or `--curriculum=<file>`) from the
+// arguments and makes that file the active curriculum
+pub fn take_flag(args: &mut Vec<String>) -> Result<(), String> {
+    let Some(position) = args.iter().position(|arg| arg == "--curriculum" || arg.starts_with("--curriculum=")) else {
+        return Ok(());
+    };
+    let flag = args.remove(position);
+    let path = match flag.strip_prefix("--curriculum=") {
+        Some(path) => path.to_string(),
+        None if position < args.len() => args.remove(position),
+        None => return Err("--curriculum needs a file".to_string()),
+    };
+    let curriculum = load(Path::new(&path))?;
+    if ACTIVE.set(curriculum).is_err() {
+        return Err("only one --curriculum can be given".to_string());
+    }
+    Ok(())
+}
+
+pub fn load(path: &Path) -> Result<Curriculum, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+    parse(&text).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+// Every curriculum in CURRICULA_DIR that doesn't load, for lint-content
+pub fn problems() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(CURRICULA_DIR) else { return Vec::new() };
+    let mut paths: Vec<_> = entries.filter_map(Result::ok).map(|entry| entry.path()).collect();
+    paths.sort();
+    paths
+        .iter()
+        .filter(|path| path.extension().is_some_and(|extension| extension == "toml"))
+        .filter_map(|path| load(path).err())
+        .collect()
+}
+
+fn whole_section(section: &'static Section) -> Lesson {
+    Lesson {
+        title: section.title.to_string(),
+        examples: section.examples.iter().map(|example| (section, example)).collect(),
+        hidden: false,
+        quiz: true,
+    }
+}
+
+fn parse(text: &str) -> Result<Curriculum, String> {
+    let document = toml::parse(text, "lesson")?;
+    let mut title = None;
+    for entry in document.top {
+        match (entry.key.as_str(), entry.value) {
+            ("title", Value::Text(text)) => title = Some(text),
+            ("title", _) => return Err(format!("line {}: title should be a string", entry.line)),
+            (key, _) => {
+                return Err(format!("line {}: unknown setting '{}' (only title goes above the lessons)", entry.line, key));
+            }
+        }
+    }
+
+    let mut lessons = Vec::new();
+    for (number, table) in document.tables {
+        let table = table.into_iter().map(|entry| (entry.key, entry.value)).collect();
+        lessons.push(lesson(table).map_err(|e| format!("the [[lesson]] on line {}: {}", number, e))?);
+    }
+    if lessons.is_empty() {
+        return Err("no [[lesson]] tables, so there's nothing to teach".to_string());
+    }
+    Ok(Curriculum { title, lessons })
+}
+
+fn lesson(table: Vec<(String, Value)>) -> Result<Lesson, String> {
+    let (mut section, mut title, mut examples, mut hidden, mut quiz) = (None, None, None, false, true);
+    for (key, value) in table {
+        match (key.as_str(), value) {
+            ("section", Value::Text(name)) => {
+                let found = lessons::SECTIONS.iter().find(|section| section.name == name);
+                section = Some(found.ok_or_else(|| format!("there's no section '{}'", name))?);
+            }
+            ("title", Value::Text(text)) => title = Some(text),
+            ("examples", Value::List(ids)) => {
+                let found: Result<Vec<_>, String> = ids
+                    .iter()
+                    .map(|id| links::resolve(id).map(|target| (target.section, target.example)))
+                    .collect();
+                examples = Some(found?);
+            }
+            ("hidden", Value::Flag(flag)) => hidden = flag,
+            ("quiz", Value::Flag(flag)) => quiz = flag,
+            ("section" | "title", _) => return Err(format!("{} should be a string", key)),
+            ("examples", _) => return Err("examples should be an array of example ids".to_string()),
+            ("hidden" | "quiz", _) => return Err(format!("{} should be true or false", key)),
+            _ => return Err(format!("unknown key '{}'", key)),
+        }
+    }
+    let mut lesson = match section {
+        Some(section) => whole_section(section),
+        None if examples.is_some() && title.is_some() => {
+            Lesson { title: String::new(), examples: Vec::new(), hidden: false, quiz: true }
+        }
+        None => return Err("needs a section, or a title and examples".to_string()),
+    };
+    if let Some(title) = title {
+        lesson.title = title;
+    }
+    if let Some(examples) = examples {
+        lesson.examples = examples;
+    }
+    lesson.hidden = hidden;
+    lesson.quiz = quiz;
+    Ok(lesson)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(lesson: &Lesson) -> Vec<String> {
+        lesson.examples.iter().map(|(section, example)| example.id(section)).collect()
+    }
+
+    #[test]
+    fn lessons_follow_the_file() {
+        let text = "title = \"Week 1\"\n\n\
+                    [[lesson]]\nsection = \"ownership\"\n\n\
+                    [[lesson]]\ntitle = \"Borrowing, briefly\"\n\
+                    examples = [\"slices_string_slices\", \"borrowing_immutable_refs\"]\n\n\
+                    [[lesson]]\nsection = \"lifetimes\"\ntitle = \"Lifetimes\"\nhidden = true\nquiz = false\n";
+        let curriculum = parse(text).unwrap();
+        assert_eq!(curriculum.title.as_deref(), Some("Week 1"));
+
+        let [whole, picked, hidden] = &curriculum.lessons[..] else { panic!("expected three lessons") };
+        let ownership = lessons::SECTIONS.iter().find(|section| section.name == "ownership").unwrap();
+        assert_eq!((whole.title.as_str(), whole.examples.len()), (ownership.title, ownership.examples.len()));
+        assert_eq!(picked.title, "Borrowing, briefly");
+        assert_eq!(ids(picked), ["slices_string_slices", "borrowing_immutable_refs"]);
+        assert!(!picked.hidden && picked.quiz);
+        assert_eq!(hidden.title, "Lifetimes");
+        assert!(hidden.hidden && !hidden.quiz);
+    }
+
+    #[test]
+    fn mistakes_name_their_line() {
+        let cases = [
+            ("colour = \"red\"\n", "line 1: unknown setting 'colour' (only title goes above the lessons)"),
+            ("title = 1\n[[lesson]]\nsection = \"ownership\"\n", "line 1: title should be a string"),
+            ("[[lesson]]\nsection = \"ownership\"\n\n[[lesson]]\nsection = \"ownership\"\ncolour = \"red\"\n",
+             "the [[lesson]] on line 4: unknown key 'colour'"),
+            ("[[lesson]]\nsection = \"nowhere\"\n", "the [[lesson]] on line 1: there's no section 'nowhere'"),
+            ("[[lesson]]\nsection = [\"ownership\"]\n", "the [[lesson]] on line 1: section should be a string"),
+            ("[[lesson]]\nsection = \"ownership\"\nhidden = \"yes\"\n", "the [[lesson]] on line 1: hidden should be true or false"),
+            ("[[lesson]]\ntitle = \"t\"\nexamples = \"ownership_transfer\"\n",
+             "the [[lesson]] on line 1: examples should be an array of example ids"),
+            ("[[lesson]]\ntitle = \"Only a title\"\n", "the [[lesson]] on line 1: needs a section, or a title and examples"),
+            ("title = \"Empty\"\n", "no [[lesson]] tables, so there's nothing to teach"),
+            ("[[lesson]]\nsection = \"ownership\n", "line 2: a string is never closed"),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(parse(text).err().unwrap(), expected, "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn unknown_examples_are_an_error() {
+        let error = parse("[[lesson]]\ntitle = \"t\"\nexamples = [\"ownership_nothing_here\"]\n").err().unwrap();
+        assert!(error.starts_with("the [[lesson]] on line 1: "), "{}", error);
+        assert!(error.contains("ownership_nothing_here"), "{}", error);
+    }
+
+    #[test]
+    fn the_shipped_curricula_load() {
+        assert_eq!(problems(), Vec::<String>::new());
+    }
+}
@@ -0,0 +1,198 @@
+// Genuine rustc output for an example's broken lines, so `run --diagnostics`
+// can show learners the real error instead of the lesson's paraphrase.
+// Compiling takes a moment per line, so results are cached on disk, keyed by
+// the code and the rustc version that compiled it: a repeat run prints them
+// instantly, and upgrading rustc naturally captures fresh ones.
+//
+// Without a usable rustc (students running a prebuilt binary), the output
+// comes from diagnostics.txt instead: a bundle captured in advance by
+// `bundle-diagnostics` and compiled into the program, labelled with the rustc
+// version that produced it. `lint-content` fails while it's missing a line.
+
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::fingerprint;
+use crate::harness;
+use crate::lessons::{self, BrokenLine, Example};
+use crate::pool;
+use crate::toolchain;
+
+const BUNDLE_PATH: &str = "diagnostics.txt";
+const BUNDLE: &str = include_str!("diagnostics.txt");
+const BUNDLE_HEADER: &str = "\
+# Genuine rustc output for every broken line in the lessons, shown by
+# `run --diagnostics` when rustc isn't available. Generated by
+# `rust_ownership_demo bundle-diagnostics`; don't edit by hand.
+";
+// Starts each entry, followed by the code's fingerprint and a readable label
+const ENTRY_MARKER: &str = "=== ";
+
+// What rustc said about one broken line
+pub struct Captured {
+    // `rustc --version` of the compiler that produced `text`
+    pub rustc: String,
+    pub text: String,
+    pub source: Source,
+}
+
+// Where a Captured came from
+pub enum Source {
+    Compiled,
+    Cache,
+    // The bundle shipped with the program, because rustc couldn't be used
+    Bundle,
+}
+
+// The diagnostics for an example with `broken` uncommented, compiling only
+// when they aren't cached yet (or `refresh` asks for it)
+pub fn capture(example: &Example, broken: &BrokenLine, refresh: bool) -> Result<Captured, String> {
+    let code = harness::with_line_enabled(example, broken)
+        .ok_or_else(|| format!("no commented-out line '// {}' in the example code", broken.line))?;
+    if let Err(reason) = harness::ready_for(example) {
+        return bundled(&code).ok_or_else(|| format!("{}, and no diagnostics were bundled for this line", reason));
+    }
+    let installed = toolchain::require("compiling examples")?;
+
+    let key = fingerprint::of(&format!("{}\n{}", installed.rustc_description, code));
+    let path = cache_dir().join(format!("{}.txt", key));
+    let rustc = installed.rustc_description.clone();
+    let cached = if refresh { None } else { fs::read_to_string(&path).ok() };
+    if let Some(text) = cached {
+        return Ok(Captured { rustc, text, source: Source::Cache });
+    }
+
+    let text = harness::compile(&code)?.diagnostics;
+    // A cache we can't write only costs time on the next run
+    if fs::create_dir_all(cache_dir()).is_ok() {
+        let _ = fs::write(&path, &text);
+    }
+    Ok(Captured { rustc, text, source: Source::Compiled })
+}
+
+// What rustc said about one of an example's broken lines, from the bundle
+// built into the program, for narration that quotes the compiler. It never
+// compiles anything, so it's instant and works without rustc
+pub fn quote(example: &Example, broken: &BrokenLine) -> Option<Captured> {
+    bundled(&harness::with_line_enabled(example, broken)?)
+}
+
+// $XDG_CACHE_HOME or ~/.cache, falling back to the temp dir
+fn cache_dir() -> PathBuf {
+    let base = env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .unwrap_or_else(env::temp_dir);
+    base.join("rust_ownership_demo").join("diagnostics")
+}
+
+// `bundle-diagnostics`: captures the output for every broken line with the
+// installed rustc and writes diagnostics.txt. Rebuild afterwards to ship it
+pub fn command(args: &[String]) -> Result<(), String> {
+    if args.iter().any(|arg| arg == "--check") {
+        return match stale_bundle()? {
+            None => {
+                println!("{} is up to date", BUNDLE_PATH);
+                Ok(())
+            }
+            Some(reason) => Err(format!("{} {}, run `bundle-diagnostics`", BUNDLE_PATH, reason)),
+        };
+    }
+
+    let installed = toolchain::require("bundling diagnostics")?;
+    let mut snippets = Vec::new();
+    for (section, example) in lessons::examples() {
+        let id = example.id(section);
+        if let Err(reason) = harness::ready_for(example) {
+            println!("skipped {}: {}", id, reason);
+            continue;
+        }
+        for (label, code) in snippets_of(&id, example) {
+            snippets.push((snippets.len(), label, code));
+        }
+    }
+
+    let mut captured = vec![None; snippets.len()];
+    let mut failure = None;
+    pool::run(&snippets, |(_, _, code)| harness::compile(code), |(index, label, _), outcome| match outcome {
+        Ok(outcome) => captured[*index] = Some(outcome.diagnostics),
+        Err(e) => {
+            failure.get_or_insert(format!("could not compile {}: {}", label, e));
+        }
+    });
+    if let Some(e) = failure {
+        return Err(e);
+    }
+
+    let mut text = format!("{}{}\n", BUNDLE_HEADER, installed.rustc_description);
+    for ((_, label, code), diagnostics) in snippets.iter().zip(captured) {
+        let diagnostics = diagnostics.unwrap_or_default();
+        text.push_str(&format!("{}{} {}\n{}", ENTRY_MARKER, fingerprint::of(code), label, diagnostics));
+        if !diagnostics.ends_with('\n') {
+            text.push('\n');
+        }
+    }
+    fs::write(BUNDLE_PATH, text).map_err(|e| format!("could not write {}: {}", BUNDLE_PATH, e))?;
+    println!("wrote {} ({} lines, {})", BUNDLE_PATH, snippets.len(), installed.rustc_description);
+    Ok(())
+}
+
+// Why diagnostics.txt on disk doesn't match the lessons, if it doesn't. Only
+// the set of snippets matters: text from an older rustc is still genuine
+pub fn stale_bundle() -> Result<Option<String>, String> {
+    let text = fs::read_to_string(BUNDLE_PATH).map_err(|e| format!("could not read {}: {}", BUNDLE_PATH, e))?;
+    let bundled: BTreeSet<String> = entries(&text).into_iter().map(|(key, _)| key).collect();
+    let mut expected = BTreeSet::new();
+    for (section, example) in lessons::examples() {
+        for (label, code) in snippets_of(&example.id(section), example) {
+            let key = fingerprint::of(&code);
+            if !bundled.contains(&key) {
+                return Ok(Some(format!("has nothing for {}", label)));
+            }
+            expected.insert(key);
+        }
+    }
+    match bundled.iter().find(|key| !expected.contains(*key)) {
+        Some(key) => Ok(Some(format!("has an entry ({}) no lesson uses any more", key))),
+        None => Ok(None),
+    }
+}
+
+// Each broken line of an example that plain rustc can compile, as a label
+// for people and the code with that line enabled
+fn snippets_of(id: &str, example: &Example) -> Vec<(String, String)> {
+    if !example.crates.is_empty() {
+        return Vec::new();
+    }
+    example
+        .broken_lines
+        .iter()
+        .filter_map(|broken| {
+            let code = harness::with_line_enabled(example, broken)?;
+            Some((format!("{}: {}", id, broken.line), code))
+        })
+        .collect()
+}
+
+fn bundled(code: &str) -> Option<Captured> {
+    let rustc = BUNDLE.lines().find(|line| !line.starts_with('#'))?;
+    let key = fingerprint::of(code);
+    let (_, text) = entries(BUNDLE).into_iter().find(|(entry, _)| *entry == key)?;
+    Some(Captured { rustc: rustc.to_string(), text, source: Source::Bundle })
+}
+
+// The bundle's entries as (fingerprint, diagnostics)
+fn entries(bundle: &str) -> Vec<(String, String)> {
+    let marker = format!("\n{}", ENTRY_MARKER);
+    bundle
+        .split(marker.as_str())
+        .skip(1)
+        .filter_map(|entry| {
+            let (header, text) = entry.split_once('\n').unwrap_or((entry, ""));
+            let key = header.split_whitespace().next()?;
+            Some((key.to_string(), text.to_string()))
+        })
+        .collect()
+}
@@ -0,0 +1,44 @@
+// `doctor`: summarizes which parts of the tool work with what is installed.
+
+use crate::harness;
+use crate::lessons;
+use crate::toolchain;
+
+pub fn command() -> Result<(), String> {
+    println!("Rust toolchain");
+    let Some(installed) = toolchain::installed() else {
+        println!("  rustc: not found");
+        println!("\nThe narrated demo, exercise hints and `run --diagnostics` (from diagnostics");
+        println!("captured in advance) work without Rust installed. Compiling anything (sandbox,");
+        println!("exercise check, refactor, lint-content) needs rustc and cargo: install them from https://rustup.rs");
+        return Ok(());
+    };
+    println!("  rustc: {}", installed.rustc_description);
+    match &installed.cargo_description {
+        Some(cargo) => println!("  cargo: {}", cargo),
+        None => println!("  cargo: not found (needed for `sandbox run`)"),
+    }
+
+    println!("\nLanguage features used by the examples");
+    for feature in toolchain::FEATURES {
+        let status = if installed.supports(feature) { "ok" } else { "missing" };
+        println!("  [{:<7}] {:<16} Rust {}.{}+", status, feature.name, feature.since.major, feature.since.minor);
+    }
+
+    println!("\nExamples");
+    let mut gated = 0;
+    let mut total = 0;
+    for (section, example) in lessons::examples() {
+        total += 1;
+        if let Err(reason) = harness::ready_for(example) {
+            gated += 1;
+            println!("  {}: {}", example.id(section), reason);
+        }
+    }
+    if gated == 0 {
+        println!("  All {} examples can be compiled with this toolchain", total);
+    } else {
+        println!("  {} of {} examples can be compiled with this toolchain", total - gated, total);
+    }
+    Ok(())
+}
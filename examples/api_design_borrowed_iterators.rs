@@ -0,0 +1,37 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Returning iterators that borrow from &self
+
+struct Document {
+    text: String,
+}
+
+impl Document {
+    // Elided form of: fn words<'a>(&'a self) -> impl Iterator<Item = &'a str> + 'a
+    fn words(&self) -> impl Iterator<Item = &str> + '_ {
+        self.text.split_whitespace()
+    }
+
+    // The items are usize, so only + '_ tells the compiler the iterator borrows self
+    fn word_lengths(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words().map(str::len)
+    }
+
+    // Without it, edition 2021 rejects the signature
+    // fn lengths(&self) -> impl Iterator<Item = usize> { self.words().map(str::len) }
+}
+
+fn main() {
+    let mut doc = Document { text: String::from("ownership makes memory safety a compile time property") };
+
+    for word in doc.words() {
+        println!("{}", word);
+    }
+    println!("{:?}", doc.word_lengths().collect::<Vec<_>>());
+
+    // Once the iterators are gone, doc can be changed again
+    doc.text.push_str(" and more");
+    println!("{} words", doc.words().count());
+}
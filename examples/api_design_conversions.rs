@@ -0,0 +1,46 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// From and Into consume their input
+
+struct Username(String);
+
+// Takes ownership of the String and keeps its buffer
+impl From<String> for Username {
+    fn from(name: String) -> Self {
+        Username(name)
+    }
+}
+
+// Only borrows, so it must allocate a copy
+impl From<&str> for Username {
+    fn from(name: &str) -> Self {
+        Username(name.to_string())
+    }
+}
+
+struct Account {
+    owner: Username,
+}
+
+impl From<Username> for Account {
+    fn from(owner: Username) -> Self {
+        Account { owner }
+    }
+}
+
+fn main() {
+    let name = String::from("ferris");
+    let user = Username::from(name); // name moves into user
+
+    // name was consumed by the first conversion
+    // let display = Username::from(name);
+
+    let borrowed = Username::from("corro"); // copies the str
+    let via_into: Username = String::from("bors").into(); // Into consumes too
+
+    // Each step of a chain moves the value on, no copies
+    let account = Account::from(user);
+    println!("{} {} {}", account.owner.0, borrowed.0, via_into.0);
+}
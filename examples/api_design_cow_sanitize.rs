@@ -0,0 +1,34 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Returning Cow<str>: allocating only when something changes
+
+use std::borrow::Cow;
+
+// Borrows the input when it's already clean, allocates only when something must change
+fn sanitize(input: &str) -> Cow<'_, str> {
+    if !input.contains(['<', '>', '&']) {
+        return Cow::Borrowed(input);
+    }
+    let escaped = input.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+    // A borrow of a local String can't leave the function
+    // return Cow::Borrowed(&escaped);
+    Cow::Owned(escaped)
+}
+
+// Always allocates, even when nothing changed
+fn sanitize_always(input: &str) -> String {
+    input.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn main() {
+    let comments = ["nice post", "thanks!", "a <b>bold</b> claim", "agreed"];
+    for comment in comments {
+        match sanitize(comment) {
+            Cow::Borrowed(text) => println!("borrowed: {}", text),
+            Cow::Owned(text) => println!("owned:    {}", text),
+        }
+    }
+    println!("{}", sanitize_always("always a new String"));
+}
@@ -0,0 +1,72 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Your own owned/borrowed pair with Borrow and ToOwned
+
+use std::borrow::{Borrow, Cow};
+use std::collections::HashMap;
+use std::ops::Deref;
+
+// The owned half, like String or PathBuf
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct UserName(String);
+
+// The borrowed half, like str or Path: an unsized view of the same bytes
+#[derive(Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+struct UserNameRef(str);
+
+impl UserNameRef {
+    fn new(name: &str) -> &UserNameRef {
+        // SAFETY: UserNameRef is a repr(transparent) wrapper around str,
+        // so the two references have the same layout
+        unsafe { &*(name as *const str as *const UserNameRef) }
+    }
+
+    fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+// Lets a UserName be looked at as a &UserNameRef, which is what HashMap::get needs
+impl Borrow<UserNameRef> for UserName {
+    fn borrow(&self) -> &UserNameRef {
+        UserNameRef::new(&self.0)
+    }
+}
+
+// The way back: make an owned UserName from a borrowed one
+impl ToOwned for UserNameRef {
+    type Owned = UserName;
+
+    fn to_owned(&self) -> UserName {
+        UserName(self.0.to_string())
+    }
+}
+
+// Methods on UserNameRef become available on UserName, like str methods on String
+impl Deref for UserName {
+    type Target = UserNameRef;
+
+    fn deref(&self) -> &UserNameRef {
+        self.borrow()
+    }
+}
+
+fn main() {
+    let mut scores: HashMap<UserName, u32> = HashMap::new();
+    scores.insert(UserName(String::from("ada")), 10);
+
+    // Looking up by the borrowed form needs no UserName, so nothing is allocated
+    let key = UserNameRef::new("ada");
+    println!("ada: {:?}", scores.get(key));
+
+    // UserName only implements Borrow<UserNameRef>, not Borrow<str>
+    // println!("{:?}", scores.get("ada"));
+
+    // ToOwned makes Cow work with the pair, just like Cow<str>
+    let name: Cow<UserNameRef> = Cow::Borrowed(key);
+    let owned: UserName = name.into_owned();
+    println!("{} has {} letters", owned.as_str(), owned.as_str().len());
+}
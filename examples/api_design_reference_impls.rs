@@ -0,0 +1,55 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Why &T implements the traits T does, and generics that won't auto-ref
+
+use std::fmt::Display;
+
+trait Describe {
+    fn describe(&self) -> String;
+}
+
+impl Describe for String {
+    fn describe(&self) -> String {
+        format!("a String of {} bytes", self.len())
+    }
+}
+
+// Like std's impl<T: Display + ?Sized> Display for &T: a reference
+// describes itself as what it points to. Without it, announce(&title)
+// below is error[E0277]: `&String: Describe` is not satisfied
+impl<T: Describe + ?Sized> Describe for &T {
+    fn describe(&self) -> String {
+        (**self).describe()
+    }
+}
+
+fn show<T: Display>(value: T) -> String {
+    format!("[{}]", value)
+}
+
+fn announce<T: Describe>(item: T) -> String {
+    item.describe()
+}
+
+fn main() {
+    let mut title = String::from("Ferris");
+    title.push_str(" the crab");
+
+    // T = String would take title by value
+    // let shown = show(title);
+    // T = &String borrows it instead
+    println!("{}", show(&title));
+    println!("{}", announce(&title));
+
+    // Generics don't turn &mut String into &String...
+    // announce(&mut title);
+    // ...or &String into &str, and str has no impl
+    // announce(title.as_str());
+
+    // iter() yields &String, which the forwarding impl covers
+    let names = [String::from("ana"), String::from("bo")];
+    let described: Vec<String> = names.iter().map(announce).collect();
+    println!("{:?}, and title is still {}", described, title);
+}
@@ -0,0 +1,60 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Deserializing into borrowed vs owned fields with serde
+
+use std::borrow::Cow;
+
+use serde::Deserialize;
+
+// Owned fields: every string is copied out of the input
+#[derive(Deserialize)]
+struct OwnedEvent {
+    name: String,
+    user: String,
+}
+
+// Borrowed fields point straight into the input, so the struct borrows it
+#[derive(Deserialize)]
+struct BorrowedEvent<'a> {
+    name: &'a str,
+    // &str fields borrow automatically; Cow needs #[serde(borrow)] to do so
+    #[serde(borrow)]
+    user: Cow<'a, str>,
+}
+
+fn main() {
+    let input = String::from(r#"{"name": "login", "user": "ferris"}"#);
+
+    let owned: OwnedEvent = serde_json::from_str(&input).unwrap();
+    let borrowed: BorrowedEvent = serde_json::from_str(&input).unwrap();
+    println!("{} {} / {} {}", owned.name, owned.user, borrowed.name, borrowed.user);
+
+    // Escapes have to be decoded into a new String, which a &str field can't hold
+    let escaped = r#"{"name": "log\"in", "user": "ferris"}"#;
+    let result: Result<BorrowedEvent, _> = serde_json::from_str(escaped);
+    println!("&str with escapes: {}", result.err().unwrap());
+
+    // A Cow can borrow when possible and own when it has to
+    let escaped = r#"{"name": "login", "user": "fer\"ris"}"#;
+    let event: BorrowedEvent = serde_json::from_str(escaped).unwrap();
+    println!("Cow with escapes: {}, owned: {}", event.user, matches!(event.user, Cow::Owned(_)));
+
+    // The borrowed struct can't outlive the buffer it was parsed from
+    let event: BorrowedEvent;
+    {
+        let input = String::from(r#"{"name": "logout", "user": "ferris"}"#);
+        event = serde_json::from_str(&input).unwrap();
+        println!("parsed {} while input is alive", event.name);
+    }
+    // println!("{}", event.name);
+
+    // Owned fields don't depend on the input at all
+    let owned: OwnedEvent;
+    {
+        let input = String::from(r#"{"name": "logout", "user": "ferris"}"#);
+        owned = serde_json::from_str(&input).unwrap();
+    }
+    println!("owned event outlives its input: {} {}", owned.name, owned.user);
+}
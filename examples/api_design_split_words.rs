@@ -0,0 +1,39 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Returning Vec<&str> vs Vec<String>
+
+fn main() {
+    let text = String::from("the quick brown fox");
+    println!("borrowed: {:?}", split_words_borrowed(&text));
+    println!("owned: {:?}", split_words_owned(&text));
+
+    // Owned words don't depend on the text they came from
+    let owned;
+    {
+        let temporary = String::from("jumps over the lazy dog");
+        owned = split_words_owned(&temporary);
+    }
+    println!("owned words outlive their text: {:?}", owned);
+
+    // Borrowed words can't outlive the text they point into
+    let words;
+    {
+        let text = String::from("jumps over the lazy dog");
+        words = split_words_borrowed(&text);
+        println!("borrowed words, used while text is alive: {:?}", words);
+    }
+    // println!("{:?}", words);
+}
+
+// The returned slices borrow from text, so they live at most as long as 'a
+#[allow(clippy::needless_lifetimes)] // written out on purpose, elision would hide 'a
+fn split_words_borrowed<'a>(text: &'a str) -> Vec<&'a str> {
+    text.split_whitespace().collect()
+}
+
+// Every word is copied into a new String owned by the caller
+fn split_words_owned(text: &str) -> Vec<String> {
+    text.split_whitespace().map(String::from).collect()
+}
@@ -0,0 +1,36 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Taking &str (or AsRef<str>) instead of &String
+
+// Only accepts a String that already exists
+#[allow(clippy::ptr_arg)] // the &String parameter is the mistake this example is about
+fn length_of_string(s: &String) -> usize {
+    s.len()
+}
+
+// Accepts literals, slices and &String (which derefs to &str)
+fn length_of_str(s: &str) -> usize {
+    s.len()
+}
+
+// Also accepts owned values: String, Box<str>, Cow<str>
+fn calculate_length(s: impl AsRef<str>) -> usize {
+    s.as_ref().len()
+}
+
+fn main() {
+    let text = String::from("hello world");
+    println!("{}", length_of_string(&text));
+
+    // A literal is a &str, not a &String
+    // let direct = length_of_string("hello");
+    println!("{}", length_of_string(&"hello".to_string())); // allocates just to call
+
+    println!("{} {}", length_of_str("hello"), length_of_str(&text[..5]));
+    println!("{}", length_of_str(&text)); // &String coerces to &str
+
+    println!("{} {}", calculate_length(String::from("owned")), calculate_length(&text));
+    println!("{}", calculate_length(Box::<str>::from("boxed")));
+}
@@ -0,0 +1,83 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Trait methods taking self, &self and &mut self, and an owned or borrowed Output
+
+trait Publish {
+    // Owned for a Draft, borrowed for a Preview
+    type Output;
+
+    fn title(&self) -> &str;
+    fn publish(self) -> Self::Output;
+
+    fn heading(&self) -> String {
+        // publish needs self, and a default method with &self only has a reference
+        // let text = self.publish();
+        format!("# {}", self.title())
+    }
+}
+
+trait Editable: Publish {
+    fn append(&mut self, line: &str);
+}
+
+struct Draft {
+    title: String,
+    body: String,
+}
+
+impl Publish for Draft {
+    type Output = String;
+
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn publish(self) -> String {
+        self.body
+    }
+}
+
+impl Editable for Draft {
+    fn append(&mut self, line: &str) {
+        self.body.push_str(line);
+        self.body.push('\n');
+    }
+}
+
+struct Preview<'a> {
+    draft: &'a Draft,
+}
+
+impl<'a> Publish for Preview<'a> {
+    type Output = &'a str;
+
+    fn title(&self) -> &str {
+        &self.draft.title
+    }
+
+    fn publish(self) -> &'a str {
+        &self.draft.body
+    }
+}
+
+// &mut self on a Preview still only reaches the draft through &
+// impl Editable for Preview<'_> { fn append(&mut self, line: &str) { self.draft.body.push_str(line); } }
+
+fn main() {
+    let mut draft = Draft { title: String::from("Release notes"), body: String::new() };
+    draft.append("Faster builds");
+    println!("{}", draft.heading());
+
+    let preview = Preview { draft: &draft };
+    let shown = preview.publish();
+    // shown borrows from draft, so draft can't change while it's in use
+    // draft.append("Late change");
+    println!("preview: {}", shown);
+
+    let published = draft.publish();
+    // publish took the draft by value
+    // draft.append("Too late");
+    println!("published: {}", published);
+}
@@ -0,0 +1,39 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Vec<u8> and String: conversions that give ownership back
+
+fn main() {
+    // from_utf8 takes the Vec by value: on success the String reuses its buffer
+    let bytes = vec![b'h', b'i'];
+    let text = String::from_utf8(bytes).unwrap();
+    println!("{}", text);
+
+    // On failure nothing is lost: the error owns the bytes and gives them back
+    let invalid = vec![b'h', 0xff, b'i'];
+    let bytes = match String::from_utf8(invalid) {
+        Ok(text) => text.into_bytes(),
+        Err(error) => {
+            println!("invalid at byte {}", error.utf8_error().valid_up_to());
+            error.into_bytes()
+        }
+    };
+    println!("got the bytes back: {:?}", bytes);
+
+    // into_bytes goes the other way, also without copying
+    let mut text = String::from("hello");
+    let borrowed: &[u8] = text.as_bytes(); // read-only view, always safe
+    println!("{:?}", borrowed);
+
+    // Mutable access to the bytes could break UTF-8, so it is unsafe
+    // let bytes: &mut Vec<u8> = text.as_mut_vec();
+    // SAFETY: we only push an ASCII byte, so the String stays valid UTF-8
+    unsafe { text.as_mut_vec().push(b'!') };
+
+    let bytes: Vec<u8> = text.into_bytes();
+    println!("{:?}", bytes);
+
+    // from_utf8_lossy only borrows, and only allocates when it has to replace something
+    println!("{}", String::from_utf8_lossy(&bytes));
+}
@@ -0,0 +1,23 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Dangling references, in rustc's own words
+
+fn main() {
+    let s = no_dangle();
+    println!("{}", s);
+}
+
+// The String itself is returned: ownership moves out, and nothing is dropped
+#[allow(clippy::let_and_return)] // the same body as dangle below, minus the &
+fn no_dangle() -> String {
+    let s = String::from("hello");
+    s
+}
+
+// s is dropped at the end of the function, so a reference to it would dangle
+// fn dangle() -> &String { let s = String::from("hello"); &s }
+
+// A named lifetime doesn't help: there's still nothing for the result to borrow from
+// fn dangle_named<'a>() -> &'a String { let s = String::from("hello"); &s }
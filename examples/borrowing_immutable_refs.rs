@@ -0,0 +1,20 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Immutable references (borrowing)
+
+fn main() {
+    let s1 = String::from("hello");
+
+    // calculate_length borrows s1 but doesn't take ownership
+    let len = calculate_length(&s1);
+
+    // so s1 is still usable here
+    println!("The length of '{}' is {}.", s1, len);
+}
+
+#[allow(clippy::ptr_arg)] // &str would be better; see api_design_string_params
+fn calculate_length(s: &String) -> usize {
+    s.len()
+} // s goes out of scope, but it doesn't own the String, so nothing is dropped
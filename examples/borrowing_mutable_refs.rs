@@ -0,0 +1,26 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Mutable references
+
+fn main() {
+    let mut s = String::from("hello");
+    change(&mut s);
+    println!("after change: {}", s);
+
+    // Only one mutable reference at a time
+    let r1 = &mut s;
+    // let r2 = &mut s;
+    println!("r1 = {}", r1);
+
+    // No mutable reference while immutable ones are in use
+    let r3 = &s;
+    let r4 = &s;
+    // let r5 = &mut s;
+    println!("r3 = {}, r4 = {}", r3, r4);
+}
+
+fn change(some_string: &mut String) {
+    some_string.push_str(", world");
+}
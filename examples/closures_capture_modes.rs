@@ -0,0 +1,38 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Capture modes: three closures over one String
+
+struct Page {
+    title: String,
+}
+
+fn into_page(title: String) -> Page {
+    Page { title }
+}
+
+fn main() {
+    let mut title = String::from("Draft");
+
+    // Only reads title: captures &title
+    let show = || title.len();
+    println!("{} bytes, and title can still be read: {}", show(), title);
+
+    // Changes title: captures &mut title
+    let mut shout = || title.push('!');
+    shout();
+    // No other borrow while shout still holds &mut title
+    // println!("reading {}", title);
+    shout();
+    println!("after shouting: {}", title);
+
+    // Gives title away: captures the String by value
+    let publish = || into_page(title);
+    let page = publish();
+    // title was moved into the closure
+    // println!("after publish: {}", title);
+    // and the closure can only give it away once
+    // publish();
+    println!("published: {}", page.title);
+}
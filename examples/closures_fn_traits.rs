@@ -0,0 +1,51 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Fn, FnMut and FnOnce: which closures a function can take
+
+// Calls f twice through a shared reference, so f can't change what it captured
+fn call_twice<T>(f: impl Fn() -> T) -> (T, T) {
+    (f(), f())
+}
+
+// Calls f twice through &mut, so f may change what it captured
+fn call_mut_twice<T>(mut f: impl FnMut() -> T) -> (T, T) {
+    (f(), f())
+}
+
+// Calls f once, by value, so f may give what it captured away
+fn call_once<T>(f: impl FnOnce() -> T) -> T {
+    f()
+}
+
+fn main() {
+    let mut log = vec![String::from("opened")];
+
+    // Only reads log: Fn, and so FnMut and FnOnce too
+    let count = || log.len();
+    println!("Fn: {:?}", call_twice(count));
+    call_mut_twice(|| println!("FnMut is fine too: {}", count()));
+    println!("and FnOnce: {}", call_once(count));
+
+    // Changes log: FnMut and FnOnce, but not Fn
+    let mut record = || log.push(String::from("edited"));
+    // call_twice(record);
+    // Passing &mut record lends the closure instead of moving it
+    call_mut_twice(&mut record);
+    call_once(record);
+    println!("log: {:?}", log);
+
+    // Gives log away: FnOnce only
+    let archive = || log;
+    // call_mut_twice(archive);
+    let archived = call_once(archive);
+    // call_once moved archive, and archive moved log
+    // println!("{:?}", log);
+    println!("archived: {:?}", archived);
+
+    // move decides how a closure captures, not which traits it implements
+    let owned = String::from("owned by the closure");
+    let measure = move || owned.len();
+    println!("a move closure that only reads is still Fn: {:?}", call_twice(&measure));
+}
@@ -0,0 +1,54 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Closures stored in structs
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::mpsc;
+
+struct Button {
+    clicks: u32,
+    on_click: Box<dyn FnMut()>, // really Box<dyn FnMut() + 'static>
+}
+
+struct Counter {
+    clicks: u32,
+    on_click: Box<dyn FnMut(&mut u32)>,
+}
+
+impl Counter {
+    fn click(&mut self) {
+        // Disjoint fields: the closure and the state are borrowed separately
+        (self.on_click)(&mut self.clicks);
+    }
+}
+
+fn main() {
+    let mut button = Button { clicks: 0, on_click: Box::new(|| {}) };
+
+    // The closure would borrow the button that owns it
+    // button.on_click = Box::new(|| button.clicks += 1);
+
+    // Fix 1: pass the state in as an argument
+    let mut counter = Counter { clicks: 0, on_click: Box::new(|clicks| *clicks += 1) };
+    counter.click();
+    println!("counter clicks: {}", counter.clicks);
+
+    // Fix 2: share the state with Rc<RefCell<_>>
+    let clicks = Rc::new(RefCell::new(0));
+    let shared = Rc::clone(&clicks);
+    button.on_click = Box::new(move || *shared.borrow_mut() += 1);
+    (button.on_click)();
+    println!("shared clicks: {}, button.clicks: {}", clicks.borrow(), button.clicks);
+
+    // Fix 3: send messages and let the owner apply them
+    let (events, inbox) = mpsc::channel();
+    button.on_click = Box::new(move || events.send("clicked").unwrap());
+    (button.on_click)();
+    for event in inbox.try_iter() {
+        button.clicks += 1;
+        println!("{}, button.clicks: {}", event, button.clicks);
+    }
+}
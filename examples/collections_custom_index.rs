@@ -0,0 +1,69 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Index for your own collection: why it lends, and how to hand out owned values
+
+use std::mem;
+use std::ops::{Index, IndexMut};
+
+// A team's players in batting order
+struct Roster {
+    players: Vec<String>,
+}
+
+impl Index<usize> for Roster {
+    type Output = String;
+
+    // The trait fixes the return type as &Self::Output: a String can only be lent
+    fn index(&self, position: usize) -> &String {
+        &self.players[position]
+    }
+}
+
+impl IndexMut<usize> for Roster {
+    fn index_mut(&mut self, position: usize) -> &mut String {
+        &mut self.players[position]
+    }
+}
+
+impl Roster {
+    // Takes the player out and closes the gap, keeping the order
+    fn remove(&mut self, position: usize) -> String {
+        self.players.remove(position)
+    }
+
+    // Takes the player out and moves the last one into the gap
+    fn swap_remove(&mut self, position: usize) -> String {
+        self.players.swap_remove(position)
+    }
+
+    // Takes the player's name and leaves an empty one, so no position changes
+    fn take_at(&mut self, position: usize) -> String {
+        mem::take(&mut self.players[position])
+    }
+}
+
+fn main() {
+    let mut roster = Roster {
+        players: ["ana", "bo", "cai", "dee", "eli"].map(String::from).to_vec(),
+    };
+
+    // roster[0] is *roster.index(0): a place inside roster, which roster still owns
+    // let first = roster[0];
+    let leader = &roster[0];
+    println!("leader: {}", leader);
+
+    // IndexMut lends the place mutably, so it can be changed or replaced in place
+    roster[1].push_str(" (captain)");
+    // leader would still borrow roster while roster[1] is changed through &mut roster
+    // println!("{}", leader);
+    roster[2] = String::from("cy");
+
+    // Owned values come out through methods that also take them out of the roster
+    let taken = roster.take_at(2);
+    let removed = roster.remove(1);
+    let swapped = roster.swap_remove(0);
+    println!("took {}, removed {}, swap_removed {}", taken, removed, swapped);
+    println!("left: {:?}", roster.players);
+}
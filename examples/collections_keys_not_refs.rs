@@ -0,0 +1,44 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Keeping keys instead of references in long-lived state
+
+use std::collections::HashMap;
+
+struct Item {
+    name: String,
+    price: u32,
+}
+
+fn main() {
+    let mut items = vec![
+        Item { name: String::from("lamp"), price: 30 },
+        Item { name: String::from("desk"), price: 120 },
+    ];
+
+    // Caching a reference keeps items borrowed for as long as the reference is used
+    let cheapest: &Item = items.iter().min_by_key(|item| item.price).unwrap();
+    // items.push(Item { name: String::from("pen"), price: 2 });
+    println!("cheapest: {}", cheapest.name);
+
+    // Storing an index instead leaves items free to change...
+    let cheapest = items.iter().enumerate().min_by_key(|(_, item)| item.price).unwrap().0;
+    items.push(Item { name: String::from("pen"), price: 2 });
+    println!("cheapest (by index): {}", items[cheapest].name);
+    // ...but removing an earlier element makes the index point at something else
+    items.remove(0);
+    println!("index {} is now: {:?}", cheapest, items.get(cheapest).map(|item| &item.name));
+
+    // Keys into a HashMap stay valid until that entry is removed
+    let mut by_id: HashMap<u32, Item> = HashMap::new();
+    by_id.insert(1, Item { name: String::from("lamp"), price: 30 });
+    by_id.insert(2, Item { name: String::from("desk"), price: 120 });
+    let selected = 2;
+    by_id.insert(3, Item { name: String::from("pen"), price: 2 });
+    by_id.remove(&1);
+    match by_id.get(&selected) {
+        Some(item) => println!("selected: {} for {}", item.name, item.price),
+        None => println!("selected item was removed"),
+    }
+}
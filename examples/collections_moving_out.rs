@@ -0,0 +1,26 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Why v[0] can't move a String out of a Vec
+
+fn main() {
+    let numbers = vec![10, 20, 30];
+    let x = numbers[0]; // i32 is Copy, so the element is copied out
+    println!("x = {}, numbers = {:?}", x, numbers);
+
+    let mut words = vec![String::from("alpha"), String::from("beta"), String::from("gamma")];
+
+    // Indexing gives a place inside the Vec: borrowing from it is fine
+    let first = &words[0];
+    println!("first = {}", first);
+
+    // Moving out of it is not, because the Vec still owns (and will drop) the element
+    // let first = words[0];
+
+    // Ways to get an owned String out
+    let copy = words[0].clone(); // words is unchanged, but this allocates
+    let removed = words.remove(0); // shifts the remaining elements left
+    let swapped = words.swap_remove(0); // moves the last element into the gap
+    println!("{} {} {} {:?}", copy, removed, swapped, words);
+}
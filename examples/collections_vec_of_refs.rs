@@ -0,0 +1,37 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Accumulating borrows in a Vec<&str>
+
+fn main() {
+    let mut names = vec![String::from("Ada"), String::from("Grace"), String::from("Alan")];
+
+    // Every &str in short borrows a String owned by names
+    let short: Vec<&str> = names.iter().map(String::as_str).filter(|name| name.len() <= 4).collect();
+
+    // While short is still used below, names can't be changed or dropped
+    // names.push(String::from("Eve"));
+    // drop(names);
+
+    println!("short names: {:?}", short);
+
+    // Fix 1: collect owned data instead
+    let short_owned: Vec<String> = names.iter().filter(|name| name.len() <= 4).cloned().collect();
+    names.push(String::from("Eve"));
+    println!("owned short names: {:?}, names: {:?}", short_owned, names);
+
+    // Fix 2: finish with the borrows before changing names
+    {
+        let short: Vec<&str> = names.iter().map(String::as_str).filter(|name| name.len() <= 4).collect();
+        println!("short names: {:?}", short);
+    }
+    names.push(String::from("Ken"));
+
+    // Fix 3: store indices and look the values up when needed
+    let short_indices: Vec<usize> = (0..names.len()).filter(|&i| names[i].len() <= 4).collect();
+    names.push(String::from("Linus"));
+    for i in short_indices {
+        println!("short name at {}: {}", i, names[i]);
+    }
+}
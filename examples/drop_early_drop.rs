@@ -0,0 +1,44 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Dropping early with drop(): files, borrows and MutexGuards
+
+use std::cell::RefCell;
+use std::sync::Mutex;
+
+struct TempFile(&'static str);
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        println!("removed {}", self.0);
+    }
+}
+
+fn record(log: &Mutex<Vec<String>>, entry: &str) {
+    log.lock().unwrap().push(entry.to_string());
+}
+
+fn main() {
+    // A resource released as soon as it's done with
+    let upload = TempFile("upload.tmp");
+    println!("sent {}", upload.0);
+    drop(upload);
+    println!("compressing the next batch");
+
+    // A runtime borrow ended early
+    let settings = RefCell::new(vec![String::from("dark mode")]);
+    let mut editing = settings.borrow_mut();
+    editing.push(String::from("large text"));
+    drop(editing); // without this, borrow() below panics: already mutably borrowed
+    println!("{:?}", settings.borrow());
+
+    // A lock released before calling something that takes it again
+    let log = Mutex::new(vec![String::from("started")]);
+    let guard = log.lock().unwrap();
+    println!("{} entries", guard.len());
+    drop(guard); // without this, record() waits for the lock forever
+    record(&log, "checked");
+    // println!("{} entries", guard.len());
+    println!("{:?}", log.lock().unwrap());
+}
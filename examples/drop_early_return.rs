@@ -0,0 +1,48 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// What gets dropped on each early return and ?
+
+struct Traced(&'static str);
+
+impl Drop for Traced {
+    fn drop(&mut self) {
+        println!("drop {}", self.0);
+    }
+}
+
+fn open(fail: bool) -> Result<Traced, String> {
+    if fail {
+        return Err(String::from("could not connect"));
+    }
+    Ok(Traced("connection"))
+}
+
+fn publish(fail_open: bool, fail_check: bool) -> Result<Traced, String> {
+    let _config = Traced("config");
+    let _connection = open(fail_open)?; // on Err: drops config, returns
+
+    if fail_check {
+        return Err(String::from("check failed")); // drops connection, then config
+    }
+
+    let report = Traced("report");
+    Ok(report) // report moves to the caller; connection and config are dropped
+}
+
+fn main() {
+    println!("-- ? on a failed open");
+    let _ = publish(true, false);
+    println!("-- early return");
+    let _ = publish(false, true);
+    println!("-- success");
+    let report = publish(false, false);
+    println!("caller got the report");
+    drop(report);
+
+    // let _ drops immediately, let _guard at the end of the scope
+    let _ = Traced("unbound");
+    let _guard = Traced("guard");
+    println!("end of main");
+}
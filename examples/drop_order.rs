@@ -0,0 +1,50 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// The order values are dropped in
+
+struct Traced(&'static str);
+
+impl Drop for Traced {
+    fn drop(&mut self) {
+        println!("drop {}", self.0);
+    }
+}
+
+struct Pair {
+    first: Traced,
+    second: Traced,
+}
+
+impl Drop for Pair {
+    // Runs before the fields are dropped, so they can still be used here
+    fn drop(&mut self) {
+        println!("drop pair (before {} and {})", self.first.0, self.second.0);
+    }
+}
+
+fn main() {
+    let a = Traced("a");
+    let _b = Traced("b");
+    {
+        let _c = Traced("c");
+        let _d = Traced("d");
+        println!("end of the inner block");
+    } // drops d, then c
+    let _e = Traced("e");
+
+    // A moved value is dropped where its new owner goes out of scope
+    let f = Traced("f");
+    let _g = Traced("g");
+    let _h = f;
+
+    // Fields go in declaration order, after the struct's own drop;
+    // a Vec drops its elements front to back
+    let _pair = Pair { first: Traced("pair.first"), second: Traced("pair.second") };
+    let _list = Vec::from([Traced("list[0]"), Traced("list[1]")]);
+
+    // Calling the destructor directly isn't allowed; drop(a) would be
+    // a.drop();
+    println!("end of main {}", a.0);
+} // drops list[0], list[1], pair, pair.first, pair.second, f (as _h), g, e, b, a
@@ -0,0 +1,48 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Partial moves: taking one String field out of a struct
+
+struct Profile {
+    name: String,
+    email: String,
+    visits: u32,
+}
+
+fn describe(profile: &Profile) -> String {
+    format!("{} <{}>, {} visits", profile.name, profile.email, profile.visits)
+}
+
+fn main() {
+    let mut profile = Profile {
+        name: String::from("Ferris"),
+        email: String::from("ferris@example.org"),
+        visits: 3,
+    };
+
+    // Moves only the name field out of profile
+    let name = profile.name;
+    println!("moved out: {}", name);
+    // The fields that weren't moved can still be used
+    println!("still there: {}, {} visits", profile.email, profile.visits);
+
+    // The moved field is gone
+    // println!("{}", profile.name);
+    // and so is profile as a whole
+    // println!("{}", describe(&profile));
+
+    // Putting a value back makes profile whole again
+    profile.name = String::from("Ferris the crab");
+    println!("whole again: {}", describe(&profile));
+
+    // Borrowing the field leaves the struct whole
+    let other = Profile {
+        name: String::from("Corro"),
+        email: String::from("corro@example.org"),
+        visits: 2,
+    };
+    let borrowed = &other.name;
+    println!("borrowed: {}", borrowed);
+    println!("{}", describe(&other));
+}
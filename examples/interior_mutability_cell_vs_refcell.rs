@@ -0,0 +1,45 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Cell<i32> copies in and out, RefCell<String> lends
+
+use std::cell::{Cell, RefCell};
+
+struct Visitor {
+    visits: Cell<i32>,
+    log: RefCell<String>,
+}
+
+impl Visitor {
+    // Only &self, yet both fields change
+    fn visit(&self, page: &str) {
+        // Cell: copy the number out, copy a new one in. No reference to the inside ever exists
+        self.visits.set(self.visits.get() + 1);
+        // RefCell: borrow the String mutably, checked at runtime, and edit it in place
+        self.log.borrow_mut().push_str(page);
+    }
+}
+
+fn main() {
+    let visitor = Visitor { visits: Cell::new(0), log: RefCell::new(String::new()) };
+    let (first, second) = (&visitor, &visitor);
+    first.visit("home ");
+    second.visit("about");
+
+    // A Cell never lends out its contents, so this doesn't compile
+    // let inside: &i32 = &*visitor.visits;
+    println!("{} visits: {}", visitor.visits.get(), visitor.log.borrow());
+
+    // While a Ref is alive, the String can't also be lent mutably
+    let page = visitor.log.borrow();
+    println!("{}", visitor.log.try_borrow_mut().is_err());
+    drop(page);
+
+    // Cell<String> works too, but only by moving the whole String out and back
+    let text = Cell::new(String::from("home"));
+    let mut edited = text.take();
+    edited.push_str(" about");
+    text.set(edited);
+    println!("{}", text.take());
+}
@@ -0,0 +1,50 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Cell vs RefCell vs Mutex vs atomics: one counter, four ways
+
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+fn main() {
+    // Single thread, Copy payload: Cell copies values in and out, nothing is borrowed
+    let cell = Cell::new(0);
+    for _ in 0..1000 {
+        cell.set(cell.get() + 1);
+    }
+
+    // Single thread, any payload: RefCell checks borrows at runtime
+    let refcell = RefCell::new(0);
+    for _ in 0..1000 {
+        *refcell.borrow_mut() += 1;
+    }
+
+    // Many threads, any payload: Mutex locks around each access
+    let mutex = Mutex::new(0);
+    // Many threads, integer or bool payload: atomics, no lock at all
+    let atomic = AtomicUsize::new(0);
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for _ in 0..250 {
+                    *mutex.lock().unwrap() += 1;
+                    atomic.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+        // Cell and RefCell aren't Sync, so threads can't share them
+        // s.spawn(|| cell.set(cell.get() + 1));
+    });
+
+    println!("Cell: {}, RefCell: {}", cell.get(), refcell.borrow());
+    println!("Mutex: {}, Atomic: {}", mutex.lock().unwrap(), atomic.load(Ordering::Relaxed));
+
+    // Cell::get copies the value out, so it needs a Copy payload
+    let name = Cell::new(String::from("counter"));
+    // let copy = name.get();
+    let taken = name.take(); // take, replace and set work for any payload
+    println!("{}", taken);
+}
@@ -0,0 +1,55 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Guards that hold a borrow for too long
+
+use std::cell::RefCell;
+use std::sync::Mutex;
+
+fn record(log: &RefCell<Vec<String>>, entry: &str) {
+    log.borrow_mut().push(entry.to_string());
+}
+
+fn add(total: &Mutex<i32>, amount: i32) {
+    *total.lock().unwrap() += amount;
+}
+
+fn main() {
+    let log = RefCell::new(vec![String::from("start")]);
+
+    // Holding the Ref while record calls borrow_mut panics at runtime:
+    //     let entries = log.borrow();
+    //     record(&log, "oops");
+    // Fix: copy out what you need, so the Ref is dropped on the same line
+    let count = log.borrow().len();
+    record(&log, &format!("had {} entries", count));
+
+    // A guard also keeps the RefCell borrowed, so it can't be moved or dropped
+    let entries = log.borrow();
+    // drop(log);
+    println!("{:?}", entries);
+    drop(entries);
+
+    // The guard in a while let condition lives for the whole loop body, so
+    // pushing inside `while let Some(job) = queue.borrow_mut().pop()` panics.
+    // Fix: pop into a local first
+    let queue = RefCell::new(vec![1, 2, 3]);
+    loop {
+        let job = queue.borrow_mut().pop(); // the RefMut is dropped here
+        match job {
+            Some(3) => queue.borrow_mut().push(10),
+            Some(job) => println!("job {}", job),
+            None => break,
+        }
+    }
+
+    // Same with Mutex, except locking twice on one thread deadlocks instead
+    let total = Mutex::new(0);
+    {
+        let mut guard = total.lock().unwrap();
+        *guard += 1;
+    } // unlocked here
+    add(&total, 2);
+    println!("total = {}", total.lock().unwrap());
+}
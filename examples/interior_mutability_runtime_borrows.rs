@@ -0,0 +1,40 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// RefCell<T>: the borrowing rules, checked at runtime
+
+use std::cell::RefCell;
+use std::panic;
+
+fn main() {
+    // With plain references the compiler enforces many & or one &mut
+    let mut plain = vec![1, 2, 3];
+    let first = &mut plain;
+    // let second = &mut plain;
+    first.push(4);
+
+    // RefCell enforces the same rule while the program runs
+    let names = RefCell::new(vec![String::from("ada")]);
+    {
+        let a = names.borrow();
+        let b = names.borrow(); // any number of shared borrows at once
+        println!("{} {}", a.len(), b.len());
+    } // both Ref guards dropped here
+    names.borrow_mut().push(String::from("grace")); // then one mutable borrow
+
+    // Overlapping borrows compile, and panic when they happen
+    let result = panic::catch_unwind(|| {
+        let cell = RefCell::new(0);
+        let reading = cell.borrow();
+        let writing = cell.borrow_mut(); // panics: already borrowed
+        println!("{} {}", reading, writing);
+    });
+    println!("overlapping borrows panicked: {}", result.is_err());
+
+    // try_borrow_mut reports the conflict instead of panicking
+    let reading = names.borrow();
+    println!("conflict: {}", names.try_borrow_mut().is_err());
+    drop(reading);
+    println!("{:?}", names.borrow());
+}
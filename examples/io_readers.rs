@@ -0,0 +1,43 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// A File inside a BufReader: into_inner and try_clone
+
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+
+fn main() -> std::io::Result<()> {
+    let path = std::env::temp_dir().join("readers-example.csv");
+    fs::write(&path, "name,score\nana,7\nbo,9\n")?;
+
+    let file = File::open(&path)?;
+    // BufReader::new takes the File by value
+    let mut reader = BufReader::new(file);
+    // The File belongs to reader now
+    // println!("{:?}", file.metadata());
+    // so a second reader can't own it as well
+    // let second = BufReader::new(file);
+
+    let mut header = String::new();
+    reader.read_line(&mut header)?;
+    println!("header: {}", header.trim_end());
+    println!("buffered but not read yet: {} bytes", reader.buffer().len());
+
+    // into_inner gives the File back and drops whatever was buffered
+    let mut file = reader.into_inner();
+    // into_inner consumed reader
+    // reader.read_line(&mut header).unwrap();
+    println!("file position: {}", file.stream_position()?);
+    file.seek(SeekFrom::Start(header.len() as u64))?;
+
+    // try_clone opens a second handle to the same file, sharing its position
+    let clone = file.try_clone()?;
+    let rows = BufReader::new(clone).lines().collect::<Result<Vec<String>, _>>()?;
+    println!("rows: {:?}", rows);
+    println!("file position after reading the clone: {}", file.stream_position()?);
+
+    // Dropping the File closes it
+    drop(file);
+    fs::remove_file(&path)
+}
@@ -0,0 +1,56 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// The lifetimes rustc fills in for you
+
+// Rule 1 gives each reference parameter its own lifetime; nothing is returned by reference
+fn count_words(s: &str) -> usize {
+    s.split_whitespace().count()
+}
+#[allow(clippy::needless_lifetimes)] // written out on purpose
+fn count_words_explicit<'a>(s: &'a str) -> usize {
+    s.split_whitespace().count()
+}
+
+// Rule 2: with exactly one input lifetime, the output gets it
+fn get_first_word(s: &str) -> &str {
+    s.split(' ').next().unwrap_or(s)
+}
+#[allow(clippy::needless_lifetimes)] // written out on purpose
+fn get_first_word_explicit<'a>(s: &'a str) -> &'a str {
+    s.split(' ').next().unwrap_or(s)
+}
+
+struct Tag {
+    name: String,
+}
+
+impl Tag {
+    // Rule 3: in a method, the output gets self's lifetime
+    fn label(&self, fallback: &str) -> &str {
+        if self.name.is_empty() || self.name == fallback { "(untagged)" } else { &self.name }
+    }
+    #[allow(clippy::needless_lifetimes)] // written out on purpose
+    fn label_explicit<'a, 'b>(&'a self, fallback: &'b str) -> &'a str {
+        if self.name.is_empty() || self.name == fallback { "(untagged)" } else { &self.name }
+    }
+}
+
+// Two input lifetimes and no self: no rule applies, so this needs an annotation
+// fn key_of(entry: &str, separator: &str) -> &str { entry }
+
+fn main() {
+    let text = String::from("borrowed words");
+    println!("{} {}", count_words(&text), count_words_explicit(&text));
+    println!("{} {}", get_first_word(&text), get_first_word_explicit(&text));
+
+    let tag = Tag { name: String::from("urgent") };
+    let label;
+    {
+        let fallback = String::from("none");
+        label = tag.label(&fallback);
+        println!("{}", tag.label_explicit(&fallback));
+    } // fallback is dropped, but label borrows only from tag
+    println!("{}", label);
+}
@@ -0,0 +1,51 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Structs that hold references
+
+// part borrows from text someone else owns, so the struct needs a lifetime parameter
+struct Excerpt<'a> {
+    part: &'a str,
+}
+
+// A reference field can't be left to elision
+// struct Unannotated { part: &str }
+
+impl<'a> Excerpt<'a> {
+    fn level(&self) -> i32 {
+        3
+    }
+
+    // Elision rule 3: the result borrows from self
+    fn announce_and_return_part(&self, announcement: &str) -> &str {
+        println!("Attention please: {}", announcement);
+        self.part
+    }
+
+    // 'a: the result borrows from the text, so it can outlive the Excerpt
+    fn part(&self) -> &'a str {
+        self.part
+    }
+}
+
+fn main() {
+    let novel = String::from("Call me Ishmael. Some years ago...");
+    let first_sentence = novel.split('.').next().unwrap_or(&novel);
+    let excerpt = Excerpt { part: first_sentence };
+    println!("{} {}", excerpt.level(), excerpt.announce_and_return_part("here it is"));
+
+    let kept = {
+        let short_lived = Excerpt { part: first_sentence };
+        short_lived.part()
+    }; // short_lived is gone, kept still borrows novel
+    println!("{}", kept);
+
+    let outlived;
+    {
+        let text = String::from("Short-lived. Gone soon.");
+        outlived = Excerpt { part: text.split('.').next().unwrap_or(&text) };
+        println!("inside the block: {}", outlived.part);
+    } // text is dropped here, and outlived still borrows it
+    // println!("after the block: {}", outlived.part);
+}
@@ -0,0 +1,27 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Connecting input and output borrows
+
+// 'a links the output to both inputs: it's valid only while both of them are
+fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+    if x.len() > y.len() { x } else { y }
+}
+
+// Without the annotation, rustc can't tell which input the result borrows from
+// fn longest_unannotated(x: &str, y: &str) -> &str { if x.len() > y.len() { x } else { y } }
+
+fn main() {
+    let string1 = String::from("long string is long");
+    let result;
+    {
+        let string2 = String::from("xyz");
+        result = longest(string1.as_str(), string2.as_str());
+        println!("longest: {}", result);
+    }
+    // result might borrow string2, which was dropped at the end of the block
+    // println!("still longest: {}", result);
+
+    println!("string1 is still here: {}", string1);
+}
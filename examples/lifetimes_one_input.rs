@@ -0,0 +1,24 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Tying the output to only one input
+
+// Only entry is tied to the result; separator just has to last for the call
+fn key_of<'a>(entry: &'a str, separator: &str) -> &'a str {
+    entry.split(separator).next().unwrap_or(entry)
+}
+
+// Returning separator would break the promise that the result borrows from entry
+// fn key_of_wrong<'a>(entry: &'a str, separator: &str) -> &'a str { separator }
+
+fn main() {
+    let entry = String::from("editor=helix");
+    let key;
+    {
+        let separator = String::from("=");
+        key = key_of(&entry, &separator);
+    } // separator is dropped here, but key only borrows entry
+
+    println!("key: {}", key);
+}
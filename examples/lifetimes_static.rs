@@ -0,0 +1,41 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// 'static: lives forever, or owns everything it holds
+
+use std::fmt::Display;
+use std::thread;
+
+// A string literal is part of the program, so a borrow of it is valid until the program exits
+fn level_name(level: u8) -> &'static str {
+    match level {
+        0 => "quiet",
+        1..=2 => "normal",
+        _ => "verbose",
+    }
+}
+
+// T: 'static means T borrows nothing short-lived, not that the value lives forever
+fn remember<T: Display + 'static>(log: &mut Vec<Box<dyn Display>>, value: T) {
+    log.push(Box::new(value));
+}
+
+fn main() {
+    let level = level_name(3);
+    println!("level: {}", level);
+
+    let mut log: Vec<Box<dyn Display>> = Vec::new();
+    remember(&mut log, String::from("ticket-7")); // owned: fine
+    remember(&mut log, 42);
+    remember(&mut log, level); // &'static str: fine
+
+    let name = String::from("worker");
+    // A borrow of a local only lasts until main returns, which isn't 'static
+    // remember(&mut log, &name);
+    log.clear(); // the String passed the bound and is freed here anyway
+
+    // spawn needs a 'static closure; move makes it own name instead of borrowing it
+    let handle = thread::spawn(move || name.len());
+    println!("the thread saw {} bytes", handle.join().unwrap());
+}
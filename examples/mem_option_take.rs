@@ -0,0 +1,43 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Option::take: moving the next node out of a linked list
+
+struct Node {
+    value: String,
+    next: Option<Box<Node>>,
+}
+
+struct Stack {
+    head: Option<Box<Node>>,
+}
+
+impl Stack {
+    fn push(&mut self, value: String) {
+        // take() moves the old head out and leaves None behind
+        let node = Box::new(Node { value, next: self.head.take() });
+        self.head = Some(node);
+    }
+
+    fn pop(&mut self) -> Option<String> {
+        // Can't move the head out from behind &mut self
+        // let old_head = self.head;
+        // map takes the Option by value, so this moves it too
+        // return self.head.map(|node| node.value);
+        let node = self.head.take()?;
+        self.head = node.next;
+        Some(node.value)
+    }
+}
+
+fn main() {
+    let mut stack = Stack { head: None };
+    for value in ["first", "second", "third"] {
+        stack.push(String::from(value));
+    }
+    while let Some(value) = stack.pop() {
+        println!("popped {}", value);
+    }
+    println!("empty: {}", stack.head.is_none());
+}
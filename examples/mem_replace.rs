@@ -0,0 +1,41 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Replacing a field and keeping the old value
+
+use std::mem;
+
+enum State {
+    Buffering(Vec<usize>),
+    Sent(usize),
+}
+
+struct Upload {
+    state: State,
+}
+
+impl Upload {
+    fn flush(&mut self) -> usize {
+        // Can't move out of a field behind &mut self
+        // let old = self.state;
+        // take needs State: Default
+        // let old = mem::take(&mut self.state);
+        let old = mem::replace(&mut self.state, State::Sent(0));
+        let bytes = match old {
+            State::Buffering(chunks) => chunks.iter().sum(),
+            State::Sent(_) => 0,
+        };
+        self.state = State::Sent(bytes);
+        bytes
+    }
+}
+
+fn main() {
+    let mut upload = Upload { state: State::Buffering(vec![4096, 4096, 1200]) };
+    println!("sent {} bytes", upload.flush());
+    println!("then {} bytes", upload.flush());
+    if let State::Sent(bytes) = upload.state {
+        println!("state: sent {}", bytes);
+    }
+}
@@ -0,0 +1,26 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Swapping two owned values behind &mut
+
+use std::mem;
+
+fn swap_pages(first: &mut String, second: &mut String) {
+    // Moving out would leave *first empty while its owner still has it
+    // let held = *first;
+    mem::swap(first, second);
+}
+
+fn main() {
+    let mut front = String::from("front page draft");
+    let mut back = String::from("back page draft");
+    swap_pages(&mut front, &mut back);
+    println!("front = {}, back = {}", front, back);
+
+    // Two &mut into the same Vec at once aren't allowed
+    let mut pages = vec![String::from("one"), String::from("two"), String::from("three")];
+    // mem::swap(&mut pages[0], &mut pages[1]);
+    pages.swap(0, 2);
+    println!("{:?}", pages);
+}
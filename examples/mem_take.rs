@@ -0,0 +1,27 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Taking a String out of a struct with mem::take
+
+use std::mem;
+
+struct Message {
+    to: String,
+    body: String,
+}
+
+impl Message {
+    fn send(&mut self) -> String {
+        // Can't move out of a field behind &mut self
+        // let body = self.body;
+        mem::take(&mut self.body) // leaves String::new(), which doesn't allocate
+    }
+}
+
+fn main() {
+    let mut message = Message { to: String::from("ana"), body: String::from("Lunch at noon?") };
+    let body = message.send();
+    println!("sent '{}' to {}", body, message.to);
+    println!("body is now {:?}", message.body);
+}
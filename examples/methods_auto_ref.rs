@@ -0,0 +1,51 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// value.method(): the &, &mut or * the compiler adds for you
+
+use std::rc::Rc;
+
+struct Playlist {
+    songs: Vec<String>,
+}
+
+impl Playlist {
+    fn count(&self) -> usize {
+        self.songs.len()
+    }
+
+    fn add(&mut self, song: &str) {
+        self.songs.push(song.to_string());
+    }
+
+    fn into_songs(self) -> Vec<String> {
+        self.songs
+    }
+}
+
+fn main() {
+    let mut list = Playlist { songs: Vec::new() };
+    // list.add(..) is Playlist::add(&mut list, ..): the call borrows list mutably
+    list.add("Intro");
+    // list.count() is Playlist::count(&list): a shared borrow
+    println!("{} song", list.count());
+
+    // Through a Box the call derefs first: Playlist::add(&mut *boxed, ..)
+    let mut boxed = Box::new(list);
+    boxed.add("Outro");
+    println!("{} songs in the box", boxed.count());
+
+    // An Rc derefs to &Playlist, never to &mut Playlist
+    let shared = Rc::new(Playlist { songs: vec![String::from("Theme")] });
+    println!("{} shared song", shared.count());
+    // shared.add("Encore");
+    // and into_songs can't move the Playlist out of an Rc others may share
+    // let songs = shared.into_songs();
+
+    // A Box owns its Playlist, so a self method moves it out of the box
+    let songs = boxed.into_songs();
+    // into_songs consumed the Playlist inside boxed
+    // println!("{}", boxed.count());
+    println!("{:?}", songs);
+}
@@ -0,0 +1,45 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// When the method found isn't the one you meant: clone and into_iter
+
+use std::rc::Rc;
+
+struct Ticket {
+    seat: String,
+}
+
+fn main() {
+    // Rc<String> has a clone method of its own, and the call finds it before String's
+    let shared = Rc::new(String::from("opening night"));
+    let another = shared.clone();
+    // so it returns an Rc<String>, not a String
+    // let text: String = shared.clone();
+    // Naming the type picks String's clone, which copies the text
+    let text = String::clone(&shared);
+    println!("{} owners of '{}', and a copy: '{}'", Rc::strong_count(&shared), another, text);
+
+    // Ticket isn't Clone, but &Ticket is, so clone() copies the reference
+    let ticket = Ticket { seat: String::from("B12") };
+    let borrowed = &ticket;
+    #[allow(noop_method_call)] // the surprise this example is about
+    let copied = borrowed.clone();
+    // no Ticket was cloned
+    // let owned: Ticket = borrowed.clone();
+    println!("seat {} and seat {}: the same Ticket", borrowed.seat, copied.seat);
+
+    // Through a reference, into_iter() finds IntoIterator for &Vec, which only borrows
+    let names = vec![String::from("ana"), String::from("bo")];
+    let view = &names;
+    #[allow(clippy::into_iter_on_ref)] // the call this example is about
+    for name in view.into_iter() {
+        println!("borrowed {}", name);
+    }
+    println!("names is still here: {:?}", names);
+    // On the Vec itself it's IntoIterator for Vec, which moves it
+    let owned: Vec<String> = names.into_iter().collect();
+    // names was moved into the iterator
+    // println!("{:?}", names);
+    println!("{:?}", owned);
+}
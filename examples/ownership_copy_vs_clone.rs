@@ -0,0 +1,51 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Copy vs Clone: implicit bit copies and explicit deep copies
+
+// Every field is Copy, so the whole struct can be
+#[derive(Debug, Clone, Copy)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+// A String owns heap memory: cloning is fine, implicit copying isn't
+#[derive(Debug, Clone)]
+struct Label {
+    text: String,
+}
+
+// A bitwise copy of a String would give its buffer two owners
+// #[derive(Clone, Copy)]
+struct Tag {
+    name: String,
+}
+
+fn shift(mut point: Point) -> Point {
+    point.x += 10;
+    point
+}
+
+fn print_label(label: Label) {
+    println!("label: {}", label.text);
+} // label is dropped here
+
+fn main() {
+    let p1 = Point { x: 1, y: 2 };
+    let p2 = p1; // copied: both are usable
+    let shifted = shift(p1); // the function gets a copy too
+    println!("{:?} {:?} {:?}", p1, p2, shifted);
+
+    let label = Label { text: String::from("origin") };
+    let moved = label; // moved: label can't be used any more
+    // println!("{}", label.text);
+
+    let copy = moved.clone(); // a deep copy has to be asked for
+    print_label(copy); // and the function takes ownership of it
+    println!("still have {:?}", moved);
+
+    let tag = Tag { name: String::from("v1") };
+    println!("tag {}", tag.name);
+}
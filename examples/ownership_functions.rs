@@ -0,0 +1,25 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Ownership with functions
+
+fn main() {
+    let s = String::from("hello world");
+    takes_ownership(s); // s moves into the function
+
+    // s was moved into takes_ownership, so this is a compile error
+    // println!("s = {}", s);
+
+    let x = 5;
+    makes_copy(x); // i32 is Copy, so x is copied instead of moved
+    println!("x is still usable: {}", x);
+}
+
+fn takes_ownership(some_string: String) {
+    println!("took ownership of: {}", some_string);
+} // some_string is dropped here
+
+fn makes_copy(some_integer: i32) {
+    println!("got a copy of: {}", some_integer);
+}
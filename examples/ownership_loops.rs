@@ -0,0 +1,54 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Moving values inside loop bodies
+
+struct Card {
+    to: String,
+    signature: String,
+}
+
+struct BorrowedCard<'a> {
+    to: &'a str,
+    signature: &'a str,
+}
+
+fn main() {
+    let guests = vec![String::from("Ada"), String::from("Grace"), String::from("Alan")];
+    let signature = String::from("the host");
+    let mut cards = Vec::new();
+
+    for guest in &guests {
+        // signature would move into the first card, leaving nothing for the second iteration
+        // cards.push(Card { to: guest.clone(), signature });
+
+        // Fix 1: clone it on every iteration
+        cards.push(Card { to: guest.clone(), signature: signature.clone() });
+    }
+
+    // Fix 2: create the value inside the loop, so each iteration owns a fresh one
+    for guest in &guests {
+        let signature = format!("the host, for {}", guest);
+        cards.push(Card { to: guest.clone(), signature });
+    }
+
+    // Fix 3: borrow instead of moving
+    let mut borrowed = Vec::new();
+    for guest in &guests {
+        borrowed.push(BorrowedCard { to: guest, signature: &signature });
+    }
+    for card in &borrowed {
+        println!("Dear {}, from {}", card.to, card.signature);
+    }
+
+    // `for guest in guests` moves the Vec into the loop, so its Strings can move out
+    for guest in guests {
+        cards.push(Card { to: guest, signature: signature.clone() });
+    }
+    // println!("guests = {:?}", guests);
+
+    for card in &cards {
+        println!("Dear {}, from {}", card.to, card.signature);
+    }
+}
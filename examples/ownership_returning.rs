@@ -0,0 +1,25 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Returning ownership
+
+fn main() {
+    let s1 = gives_ownership();
+    println!("s1 = {}", s1);
+
+    let s2 = String::from("hello");
+    let s3 = takes_and_gives_back(s2);
+    println!("s3 = {}", s3);
+
+    // s2 was moved into takes_and_gives_back, so this is a compile error
+    // println!("s2 = {}", s2);
+}
+
+fn gives_ownership() -> String {
+    String::from("yours") // ownership moves out to the caller
+}
+
+fn takes_and_gives_back(a_string: String) -> String {
+    a_string // ownership moves back out to the caller
+}
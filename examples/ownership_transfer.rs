@@ -0,0 +1,14 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Transfer of ownership
+
+fn main() {
+    let s1 = String::from("hello"); // s1 is the owner of this String
+    let s2 = s1; // ownership moves from s1 to s2
+    println!("s2 = {}", s2);
+
+    // s1 no longer owns the String, so using it is a compile error
+    // println!("s1 = {}", s1);
+} // s2 goes out of scope and the String is freed
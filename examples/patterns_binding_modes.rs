@@ -0,0 +1,33 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Matching on an owned value vs a reference: default binding modes
+
+fn main() {
+    let mut reply: Result<String, String> = Ok(String::from("200 OK"));
+
+    // Matching on a reference: body binds as &String, and nothing moves
+    match &reply {
+        Ok(body) => {
+            // body is a &String here, not a String
+            // let owned: String = body;
+            println!("borrowed: {}", body);
+        }
+        Err(error) => println!("error: {}", error),
+    }
+
+    // Through &mut, body is a &mut String
+    if let Ok(body) = &mut reply {
+        body.push_str(" (cached)");
+    }
+
+    // Matching on the value itself moves the String out of reply
+    let kept = match reply {
+        Ok(body) => body,
+        Err(error) => error,
+    };
+    // reply has been moved from
+    // println!("{:?}", reply);
+    println!("kept: {}", kept);
+}
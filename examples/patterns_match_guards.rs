@@ -0,0 +1,42 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// What a match guard may do with the values it sees
+
+fn register(name: String) -> bool {
+    println!("registered {}", name);
+    true
+}
+
+fn main() {
+    let name = Some(String::from("ferris"));
+    match name {
+        // A guard may only look at the bindings, not move them...
+        // Some(name) if register(name) => println!("registered"),
+        // ...or mutate them
+        // Some(mut name) if { name.push('!'); true } => println!("{}", name),
+        Some(name) if name.len() > 3 => println!("long name, moved into the arm: {}", name),
+        Some(name) => println!("short name: {}", name),
+        None => {}
+    }
+
+    // A guard can't change the value being matched, either
+    let mut slot = Some(String::from("ferris"));
+    match slot {
+        // Some(_) if { slot = None; true } => {}
+        Some(ref name) if name.starts_with('f') => println!("starts with f: {}", name),
+        _ => {}
+    }
+    slot = None; // fine once the match is over
+    println!("{:?}", slot);
+
+    // Binding the whole value and a part of it needs a borrowed scrutinee
+    let name = Some(String::from("ferris"));
+    #[allow(clippy::single_match)] // the @ pattern is what's shown here
+    match name {
+        // whole @ Some(inner) => println!("{:?} holds {}", whole, inner),
+        ref whole @ Some(ref inner) => println!("{:?} holds {}", whole, inner),
+        None => {}
+    }
+}
@@ -0,0 +1,34 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// ref and ref mut: borrowing in a pattern on an owned value
+
+struct Contact {
+    name: String,
+    email: String,
+}
+
+fn main() {
+    let mut nickname = Some(String::from("ferris"));
+
+    // ref borrows from an owned scrutinee, like matching on &nickname
+    if let Some(ref name) = nickname {
+        println!("borrowed: {}", name);
+    }
+    // ref mut borrows mutably
+    if let Some(ref mut name) = nickname {
+        name.push_str("_the_crab");
+    }
+    // Spelling out the & switches back to moving, out of a reference
+    // if let &Some(name) = &nickname { println!("{}", name); }
+    println!("{:?}", nickname);
+
+    // Move one field out and borrow another in the same pattern
+    let contact = Contact { name: String::from("Ferris"), email: String::from("ferris@example.org") };
+    let Contact { name, ref email } = contact;
+    println!("moved {}, borrowed {}", name, email);
+    println!("still readable: {}", contact.email);
+    // but name has moved out of contact
+    // println!("{}", contact.name);
+}
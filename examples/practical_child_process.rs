@@ -0,0 +1,43 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Owning a child process's pipes: stdin closes when it's dropped
+
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::thread;
+
+fn main() {
+    let mut child = Command::new("sort")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("could not start sort");
+
+    // take() moves each handle out of child, leaving None behind
+    let mut stdin = child.stdin.take().expect("stdin was piped");
+    // Moves stdout out of child for good, so it can't be taken below
+    // let stdout = child.stdout.unwrap();
+    let mut stdout = child.stdout.take().expect("stdout was piped");
+
+    // The reader thread owns stdout; sort prints nothing until its input ends
+    let reader = thread::spawn(move || {
+        let mut sorted = String::new();
+        stdout.read_to_string(&mut sorted).expect("could not read from sort");
+        sorted
+    });
+
+    let fruit = vec![String::from("pear"), String::from("apple"), String::from("fig")];
+    for name in fruit {
+        // The bytes are copied into the pipe, and name is dropped after the write
+        writeln!(stdin, "{}", name).expect("could not write to sort");
+    }
+    // Dropping stdin closes the pipe: that's how sort learns its input has ended
+    drop(stdin);
+    // The handle is gone
+    // stdin.write_all(b"kiwi\n").unwrap();
+
+    print!("{}", reader.join().expect("the reader thread panicked"));
+    println!("sort exited with {}", child.wait().expect("sort wasn't running"));
+}
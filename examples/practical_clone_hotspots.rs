@@ -0,0 +1,80 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Finding the clones that slow a pipeline down
+
+use std::time::Instant;
+
+#[derive(Clone)]
+struct Record {
+    name: String,
+    score: u32,
+    role: String,
+}
+
+// Clones wherever the borrow checker complained: every field is copied, twice
+fn parse_owned(lines: &[String]) -> Vec<Record> {
+    let mut records = Vec::new();
+    for line in lines {
+        let fields: Vec<String> = line.split(',').map(String::from).collect();
+        records.push(Record {
+            name: fields[0].clone(),
+            score: fields[1].parse().unwrap_or(0),
+            role: fields[2].clone(),
+        });
+    }
+    records
+}
+
+fn admins_cloned(records: &[Record]) -> Vec<Record> {
+    records.iter().filter(|record| record.role == "admin").cloned().collect()
+}
+
+fn names_cloned(records: &[Record]) -> Vec<String> {
+    records.iter().filter(|record| record.score > 0).map(|record| record.name.clone()).collect()
+}
+
+// The same pipeline, borrowing from the input all the way through
+struct RecordRef<'a> {
+    name: &'a str,
+    score: u32,
+    role: &'a str,
+}
+
+fn parse_borrowed(lines: &[String]) -> Vec<RecordRef<'_>> {
+    let mut records = Vec::with_capacity(lines.len());
+    for line in lines {
+        let mut fields = line.split(',');
+        let name = fields.next().unwrap_or("");
+        let score = fields.next().unwrap_or("").parse().unwrap_or(0);
+        let role = fields.next().unwrap_or("");
+        records.push(RecordRef { name, score, role });
+    }
+    records
+}
+
+fn admins_borrowed<'a, 'r>(records: &'r [RecordRef<'a>]) -> Vec<&'r RecordRef<'a>> {
+    records.iter().filter(|record| record.role == "admin").collect()
+}
+
+fn names_borrowed<'a>(records: &[&RecordRef<'a>]) -> Vec<&'a str> {
+    records.iter().filter(|record| record.score > 0).map(|record| record.name).collect()
+}
+
+fn main() {
+    let input: Vec<String> = (0..100_000)
+        .map(|i| format!("user{},{},{}", i, i * 7 % 100, if i % 4 == 0 { "admin" } else { "member" }))
+        .collect();
+
+    let started = Instant::now();
+    let cloned = names_cloned(&admins_cloned(&parse_owned(&input)));
+    println!("cloning:   {} names in {:?}", cloned.len(), started.elapsed());
+
+    let started = Instant::now();
+    let records = parse_borrowed(&input);
+    let borrowed = names_borrowed(&admins_borrowed(&records));
+    println!("borrowing: {} names in {:?}", borrowed.len(), started.elapsed());
+
+    assert_eq!(cloned, borrowed);
+}
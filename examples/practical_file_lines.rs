@@ -0,0 +1,58 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Returning lines read from a file
+
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+// The String is dropped at the end of the function, so its lines can't be returned
+// fn read_lines(path: &Path) -> Vec<&str> { let text = fs::read_to_string(path).unwrap(); text.lines().collect() }
+
+// Fix 1: return the buffer together with the positions of the lines in it
+struct Lines {
+    buffer: String,
+    offsets: Vec<Range<usize>>,
+}
+
+impl Lines {
+    fn read(path: &Path) -> Lines {
+        let buffer = fs::read_to_string(path).unwrap();
+        let mut offsets = Vec::new();
+        let mut start = 0;
+        for line in buffer.split_inclusive('\n') {
+            offsets.push(start..start + line.trim_end_matches('\n').len());
+            start += line.len();
+        }
+        Lines { buffer, offsets }
+    }
+
+    fn get(&self, index: usize) -> &str {
+        &self.buffer[self.offsets[index].clone()]
+    }
+}
+
+// Fix 2: return owned Strings
+fn read_lines_owned(path: &Path) -> Vec<String> {
+    fs::read_to_string(path).unwrap().lines().map(String::from).collect()
+}
+
+// Fix 3: let the caller own the buffer
+fn split_lines(text: &str) -> Vec<&str> {
+    text.lines().collect()
+}
+
+fn main() {
+    let path = std::env::temp_dir().join("lines.txt");
+    fs::write(&path, "alpha\nbeta\ngamma\n").unwrap();
+
+    let lines = Lines::read(&path);
+    println!("second line: {}", lines.get(1));
+
+    println!("owned: {:?}", read_lines_owned(&path));
+
+    let text = fs::read_to_string(&path).unwrap();
+    println!("borrowed: {:?}", split_lines(&text));
+}
@@ -0,0 +1,33 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Borrowing the first word of a string
+
+fn main() {
+    let mut text = String::from("Hello world");
+
+    // word borrows part of text
+    let word = get_first_word(&text);
+
+    // text can't be modified while word is still in use
+    // text.clear();
+
+    println!("first word: {}", word);
+
+    // word is no longer used, so text can be modified again
+    text.clear();
+    println!("after clear: '{}'", text);
+}
+
+fn get_first_word(s: &str) -> &str {
+    let bytes = s.as_bytes();
+
+    for (i, &item) in bytes.iter().enumerate() {
+        if item == b' ' {
+            return &s[0..i];
+        }
+    }
+
+    s
+}
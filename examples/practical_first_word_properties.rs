@@ -0,0 +1,52 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Testing get_first_word with thousands of random strings
+
+fn get_first_word(s: &str) -> &str {
+    let bytes = s.as_bytes();
+
+    for (i, &item) in bytes.iter().enumerate() {
+        if item == b' ' {
+            return &s[0..i];
+        }
+    }
+
+    s
+}
+
+// Looks as harmless, but slicing at byte 5 can land inside a character
+fn preview(text: &str) -> &str {
+    &text[..text.len().min(5)]
+}
+
+fn main() {
+    println!("{}", get_first_word("héllo wörld"));
+    println!("{}", preview("hello world"));
+    println!("run `cargo test --example practical_first_word_properties --features proptest`");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        // Unicode words joined by spaces
+        #[test]
+        fn first_word_is_a_borrowed_prefix(words in prop::collection::vec("\\PC{0,8}", 0..6)) {
+            let text = words.join(" ");
+            let word = get_first_word(&text);
+            prop_assert_eq!(word.as_ptr(), text.as_ptr());
+            prop_assert!(text.starts_with(word) && !word.contains(' '));
+        }
+
+        // Fails: proptest shrinks it to a short string with a multi-byte character
+        #[test]
+        #[should_panic]
+        fn preview_never_panics(text in any::<String>()) {
+            preview(&text);
+        }
+    }
+}
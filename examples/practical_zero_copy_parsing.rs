@@ -0,0 +1,74 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Zero-copy parsing at scale: owned tokens vs borrowed slices over megabytes
+
+use std::time::Instant;
+
+// Every field in its own String: one allocation per token, and the text copied
+struct LogEntry {
+    user: String,
+    path: String,
+    status: u16,
+}
+
+fn parse_entries_owned(log: &str) -> Vec<LogEntry> {
+    let mut entries = Vec::new();
+    for line in log.lines() {
+        let tokens: Vec<String> = line.split(' ').map(String::from).collect();
+        if let [_time, _level, user, _method, path, status, _size] = &tokens[..] {
+            entries.push(LogEntry { user: user.clone(), path: path.clone(), status: status.parse().unwrap_or(0) });
+        }
+    }
+    entries
+}
+
+// Every field a slice of the log: no text is copied, but the entries can't outlive it
+struct LogEntryRef<'a> {
+    user: &'a str,
+    path: &'a str,
+    status: u16,
+}
+
+fn parse_entries_borrowed(log: &str) -> Vec<LogEntryRef<'_>> {
+    let mut entries = Vec::with_capacity(log.lines().count());
+    for line in log.lines() {
+        let tokens: Vec<&str> = line.splitn(7, ' ').collect();
+        if let [_time, _level, user, _method, path, status, _size] = tokens[..] {
+            entries.push(LogEntryRef { user, path, status: status.parse().unwrap_or(0) });
+        }
+    }
+    entries
+}
+
+fn main() {
+    // About 4 MB of access log
+    let paths = ["/", "/api/items", "/api/items/42", "/login", "/static/app.js"];
+    let mut log = String::new();
+    let mut i: usize = 0;
+    while log.len() < 4 << 20 {
+        let status = match i % 50 {
+            0 => 500,
+            _ => 200,
+        };
+        let line = format!("2026-10-15T09:{:02}:{:02}Z INFO user{} GET {} {} {}\n", i / 60 % 60, i % 60, i * 7 % 1000, paths[i % 5], status, i % 20_000);
+        log.push_str(&line);
+        i += 1;
+    }
+
+    let started = Instant::now();
+    let owned = parse_entries_owned(&log);
+    let errors = owned.iter().filter(|entry| entry.status >= 500).count();
+    println!("owned:    {} entries, {} errors in {:?}", owned.len(), errors, started.elapsed());
+    println!("first: {} {}", owned[0].user, owned[0].path);
+
+    let started = Instant::now();
+    let borrowed = parse_entries_borrowed(&log);
+    let errors = borrowed.iter().filter(|entry| entry.status >= 500).count();
+    println!("borrowed: {} entries, {} errors in {:?}", borrowed.len(), errors, started.elapsed());
+
+    // The entries point into log, so log has to outlive them
+    // drop(log);
+    println!("first: {} {}", borrowed[0].user, borrowed[0].path);
+}
@@ -0,0 +1,21 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// String slices
+
+fn main() {
+    let mut s = String::from("hello world");
+
+    let hello = &s[0..5];
+    let world = &s[6..11];
+
+    // The slices borrow s, so it can't be modified while they are in use
+    // s.clear();
+
+    println!("'{}' and '{}'", hello, world);
+
+    // Once the slices are no longer used, s can be modified again
+    s.clear();
+    println!("after clear: '{}'", s);
+}
@@ -0,0 +1,28 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Owning values of unknown type with Box<dyn Any>
+
+use std::any::Any;
+
+fn main() {
+    let values: Vec<Box<dyn Any>> = vec![Box::new(String::from("hello")), Box::new(42_i32)];
+
+    for boxed in values {
+        // The type is erased, so we can't just move the value out
+        // let text: String = *boxed;
+
+        // downcast takes ownership of the box; on failure it gives the box back
+        match boxed.downcast::<String>() {
+            Ok(text) => {
+                let text: String = *text; // move the String out of its Box
+                println!("recovered a String: {}", text);
+            }
+            Err(boxed) => match boxed.downcast::<i32>() {
+                Ok(number) => println!("recovered an i32: {}", number),
+                Err(_) => println!("unknown type, the box is dropped here"),
+            },
+        }
+    }
+}
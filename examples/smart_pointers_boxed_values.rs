@@ -0,0 +1,39 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Box<T>: one owner, data on the heap
+
+struct Reading {
+    celsius: f64,
+    sensor: u32,
+}
+
+impl Reading {
+    fn fahrenheit(&self) -> f64 {
+        self.celsius * 9.0 / 5.0 + 32.0
+    }
+}
+
+fn main() {
+    // The Reading moves to the heap; boxed is a pointer to it
+    let boxed = Box::new(Reading { celsius: 21.5, sensor: 7 });
+
+    // Moving the Box copies the pointer; the Reading stays where it is
+    let moved = boxed;
+    // println!("{}", boxed.sensor);
+
+    // Deref lets fields and methods reach through the Box
+    println!("{} {}", moved.celsius, moved.fahrenheit());
+    let reading: &Reading = &moved;
+    println!("{}", reading.sensor);
+
+    // *moved moves the Reading back out and frees the heap block
+    let unboxed = *moved;
+    println!("{}", unboxed.sensor);
+
+    {
+        let temporary = Box::new([0u8; 4096]);
+        println!("{}", temporary.len());
+    } // temporary goes out of scope, and its 4096 bytes are freed
+}
@@ -0,0 +1,30 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Clone-on-write with Arc::make_mut
+
+use std::sync::Arc;
+
+fn main() {
+    let mut config = Arc::new(vec![1, 2, 3]);
+
+    // Cloning the Arc shares the Vec, it doesn't copy it
+    let snapshot = Arc::clone(&config);
+    println!("strong count: {}", Arc::strong_count(&config));
+
+    // Data behind a shared Arc can't be mutated directly
+    // config.push(4);
+
+    // get_mut only works for the sole owner
+    println!("get_mut while shared: {:?}", Arc::get_mut(&mut config).is_some());
+
+    // make_mut clones the Vec because snapshot still shares it...
+    Arc::make_mut(&mut config).push(4);
+    println!("config = {:?}, snapshot = {:?}", config, snapshot);
+
+    // ...and after that config owns its copy, so no further clone happens
+    Arc::make_mut(&mut config)[0] = 42;
+    println!("same data: {}", Arc::ptr_eq(&config, &snapshot));
+    println!("config = {:?}, strong count: {}", config, Arc::strong_count(&config));
+}
@@ -0,0 +1,26 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Recursive types need a Box
+
+// Without indirection the type would contain itself, and have no size
+// enum Unboxed { Cons(i32, Unboxed), Nil }
+
+// A Box is a pointer, so List has a fixed size however long the list is
+enum List {
+    Cons(i32, Box<List>),
+    Nil,
+}
+
+use List::{Cons, Nil};
+
+fn main() {
+    let list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
+
+    let mut node = &list;
+    while let Cons(value, next) = node {
+        println!("{}", value);
+        node = next;
+    }
+} // list is dropped, and each Cons drops the Box it owns, down the chain
@@ -0,0 +1,61 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Rc cycles leak; Weak breaks them
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+struct Peer {
+    name: &'static str,
+    other: RefCell<Option<Rc<Peer>>>,
+}
+
+struct TreeNode {
+    name: &'static str,
+    parent: RefCell<Weak<TreeNode>>,
+    children: RefCell<Vec<Rc<TreeNode>>>,
+}
+
+// Each node says when it's freed, so a missing line means a leak
+impl Drop for Peer {
+    fn drop(&mut self) {
+        println!("{} dropped", self.name);
+    }
+}
+
+impl Drop for TreeNode {
+    fn drop(&mut self) {
+        println!("{} dropped", self.name);
+    }
+}
+
+fn main() {
+    {
+        let a = Rc::new(Peer { name: "a", other: RefCell::new(None) });
+        let b = Rc::new(Peer { name: "b", other: RefCell::new(None) });
+        *a.other.borrow_mut() = Some(Rc::clone(&b));
+        *b.other.borrow_mut() = Some(Rc::clone(&a));
+        println!("a strong = {}, b strong = {}", Rc::strong_count(&a), Rc::strong_count(&b));
+    } // Each count drops to 1, never 0: neither "dropped" line is printed
+
+    let leaf = Rc::new(TreeNode { name: "leaf", parent: RefCell::new(Weak::new()), children: RefCell::new(vec![]) });
+    {
+        let branch = Rc::new(TreeNode {
+            name: "branch",
+            parent: RefCell::new(Weak::new()),
+            children: RefCell::new(vec![Rc::clone(&leaf)]),
+        });
+        // The child refers back with a Weak, which doesn't keep branch alive
+        *leaf.parent.borrow_mut() = Rc::downgrade(&branch);
+        println!("branch strong = {}, weak = {}", Rc::strong_count(&branch), Rc::weak_count(&branch));
+        println!("leaf strong = {}, weak = {}", Rc::strong_count(&leaf), Rc::weak_count(&leaf));
+
+        // A Weak has to be upgraded to reach the value, since it may be gone
+        // println!("{}", leaf.parent.borrow().name);
+        println!("parent = {:?}", leaf.parent.borrow().upgrade().map(|parent| parent.name));
+        println!("children = {}", branch.children.borrow().len());
+    } // branch dropped: its strong count was 1
+    println!("parent = {:?}", leaf.parent.borrow().upgrade().map(|parent| parent.name));
+} // leaf dropped
@@ -0,0 +1,44 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Rc<T>: several owners of one list
+
+use std::rc::Rc;
+
+// Each list shares its tail through an Rc instead of owning it outright
+enum List {
+    Cons(i32, Rc<List>),
+    Nil,
+}
+
+use List::{Cons, Nil};
+
+impl Drop for List {
+    fn drop(&mut self) {
+        if let Cons(value, _) = self {
+            println!("dropping Cons({}, ..)", value);
+        }
+    }
+}
+
+fn main() {
+    let a = Rc::new(Cons(5, Rc::new(Cons(10, Rc::new(Nil)))));
+    println!("after creating a: {}", Rc::strong_count(&a));
+
+    // Rc::clone copies the pointer and adds one to the count; the list isn't copied
+    let b = Cons(3, Rc::clone(&a));
+    println!("after creating b: {}", Rc::strong_count(&a));
+    {
+        let _c = Cons(4, Rc::clone(&a));
+        println!("after creating c: {}", Rc::strong_count(&a));
+    } // c is dropped, and the count goes down by one
+    println!("after c goes out of scope: {}", Rc::strong_count(&a));
+
+    drop(b);
+    println!("after dropping b: {}", Rc::strong_count(&a));
+
+    // Each Rc is still a value with one owner: this moves a
+    let _d = Cons(2, a);
+    // println!("{}", Rc::strong_count(&a));
+} // d is dropped, the count reaches 0, and the list is freed
@@ -0,0 +1,35 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Box<str> and Arc<str>: smaller and shared strings
+
+use std::mem::size_of;
+use std::sync::Arc;
+
+fn main() {
+    println!("String: {} bytes, Box<str>: {} bytes, Arc<str>: {} bytes",
+        size_of::<String>(), size_of::<Box<str>>(), size_of::<Arc<str>>());
+
+    // A String built up piece by piece usually has spare capacity
+    let mut name = String::with_capacity(64);
+    name.push_str("ferris");
+    println!("len {}, capacity {}", name.len(), name.capacity());
+
+    // into_boxed_str drops the spare capacity and the capacity field with it
+    let name: Box<str> = name.into_boxed_str();
+    println!("boxed: {} ({} bytes)", name, name.len());
+
+    // Box<str> is immutable in size: there is no capacity to grow into
+    // name.push_str("!");
+
+    // Arc<str> shares one immutable copy; cloning only bumps a count
+    let endpoint: Arc<str> = Arc::from("https://example.com/api");
+    let handlers: Vec<Arc<str>> = (0..3).map(|_| Arc::clone(&endpoint)).collect();
+    println!("{} handlers share {}, strong count {}", handlers.len(), endpoint, Arc::strong_count(&endpoint));
+
+    // Going back to String reuses the same buffer; push then grows it
+    let mut name = String::from(name);
+    name.push('!');
+    println!("{}", name);
+}
@@ -0,0 +1,43 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Reclaiming unique ownership with Rc::try_unwrap and Arc::into_inner
+
+use std::rc::Rc;
+use std::sync::Arc;
+use std::thread;
+
+fn main() {
+    let log = Rc::new(vec![String::from("started")]);
+    let worker = Rc::clone(&log);
+
+    // The Vec is shared, so we can't just move it out
+    // let entries: Vec<String> = *log;
+
+    // try_unwrap fails while another owner exists, and gives the Rc back
+    let log = match Rc::try_unwrap(log) {
+        Ok(entries) => panic!("worker still owns the log, got {:?}", entries),
+        Err(log) => log,
+    };
+    println!("still shared, strong count: {}", Rc::strong_count(&log));
+
+    // Once the other owner is gone, the last one reclaims the Vec without cloning
+    drop(worker);
+    let entries: Vec<String> = Rc::try_unwrap(log).unwrap();
+    println!("reclaimed: {:?}", entries);
+
+    // Across threads, Arc::into_inner gives the value to exactly one owner
+    let results = Arc::new(vec![1, 2, 3]);
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let results = Arc::clone(&results);
+            thread::spawn(move || Arc::into_inner(results))
+        })
+        .collect();
+    let mut winners = vec![Arc::into_inner(results)];
+    for handle in handles {
+        winners.push(handle.join().unwrap());
+    }
+    println!("{:?}", winners.into_iter().flatten().collect::<Vec<_>>());
+}
@@ -0,0 +1,33 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Weak::upgrade and observers that go away
+
+use std::rc::{Rc, Weak};
+
+struct Observer {
+    name: String,
+}
+
+fn main() {
+    let logger = Rc::new(Observer { name: String::from("logger") });
+
+    // The watcher doesn't own the observer, it can only ask whether it's still there
+    let watcher: Weak<Observer> = Rc::downgrade(&logger);
+    println!("strong = {}, weak = {}", Rc::strong_count(&logger), Rc::weak_count(&logger));
+
+    // A Weak has to be upgraded before the value can be used
+    // println!("{}", watcher.name);
+    if let Some(observer) = watcher.upgrade() {
+        println!("{} is alive, strong = {}", observer.name, Rc::strong_count(&observer));
+    }
+
+    // Once the last strong owner is gone, upgrade returns None
+    drop(logger);
+    println!("strong = {}, weak = {}", watcher.strong_count(), watcher.weak_count());
+    match watcher.upgrade() {
+        Some(observer) => println!("still alive: {}", observer.name),
+        None => println!("the observer was dropped"),
+    }
+}
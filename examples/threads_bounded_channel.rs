@@ -0,0 +1,47 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// sync_channel: handing over ownership through a bounded buffer
+
+use std::sync::mpsc::{self, TrySendError};
+use std::thread;
+use std::time::Duration;
+
+fn main() {
+    // Room for two messages: sending transfers ownership into the channel's buffer
+    let (sender, receiver) = mpsc::sync_channel::<String>(2);
+    let first = String::from("first");
+    sender.send(first).unwrap();
+    // first moved into the channel, it's the receiver's to take now
+    // println!("{}", first);
+    sender.send(String::from("second")).unwrap();
+
+    // The buffer is full: try_send fails and gives the String back instead of blocking
+    let third = String::from("third");
+    // sender.send(third.clone()) would block here until someone receives
+    match sender.try_send(third) {
+        Ok(()) => println!("sent"),
+        Err(TrySendError::Full(third)) => println!("full, still own {:?}", third),
+        Err(TrySendError::Disconnected(third)) => println!("no receiver, still own {:?}", third),
+    }
+
+    // A producer thread blocks in send until the consumer makes room
+    let producer = thread::spawn(move || {
+        for i in 0..4 {
+            sender.send(format!("job {}", i)).unwrap();
+        }
+        sender
+    });
+    thread::sleep(Duration::from_millis(50));
+    for _ in 0..4 {
+        println!("received {}", receiver.recv().unwrap());
+    }
+    let sender = producer.join().unwrap();
+
+    // Once the receiver is gone, send returns the value inside the error
+    drop(receiver);
+    let error = sender.send(String::from("late")).unwrap_err();
+    let late: String = error.0;
+    println!("nobody will receive {:?}, so it came back to us", late);
+}
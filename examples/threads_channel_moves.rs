@@ -0,0 +1,40 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// mpsc::channel: send moves a String to another thread
+
+use std::sync::mpsc;
+use std::thread;
+
+fn main() {
+    let (sender, receiver) = mpsc::channel::<String>();
+
+    thread::spawn(move || {
+        let greeting = String::from("hello from the worker");
+        // send takes the String by value, like takes_ownership(s) does
+        sender.send(greeting).unwrap();
+        // greeting now belongs to whoever receives it
+        // println!("worker still has {}", greeting);
+    });
+
+    // recv hands ownership to this thread
+    let greeting: String = receiver.recv().unwrap();
+    println!("main got {:?}", greeting);
+
+    // Several producers, one receiver: every clone of the Sender feeds the same channel
+    let (sender, receiver) = mpsc::channel::<String>();
+    for i in 0..3 {
+        let sender = sender.clone();
+        thread::spawn(move || {
+            for j in 0..2 {
+                sender.send(format!("worker {} job {}", i, j)).unwrap();
+            }
+        });
+    }
+    // The loop below ends when the last Sender is dropped, so drop ours too
+    drop(sender);
+    for message in receiver {
+        println!("received {}", message);
+    }
+}
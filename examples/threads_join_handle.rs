@@ -0,0 +1,35 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Moving data into a thread and getting it back from join
+
+use std::thread;
+
+fn main() {
+    let numbers = vec![1, 2, 3, 4];
+
+    // Without move the closure would borrow numbers, but the thread may outlive main's stack frame
+    // let borrowed = thread::spawn(|| numbers.iter().sum::<i32>());
+
+    // move gives the Vec to the thread; the result, and the Vec itself, come back through join
+    let handle = thread::spawn(move || {
+        let sum: i32 = numbers.iter().sum();
+        (sum, numbers)
+    });
+    // numbers now belongs to the thread
+    // println!("{:?}", numbers);
+
+    let (sum, numbers) = handle.join().unwrap();
+    println!("sum of {:?} is {}", numbers, sum);
+
+    // A panic's payload is an owned Box<dyn Any + Send> handed to whoever joins
+    let handle = thread::spawn(|| -> i32 { panic!("bad input") });
+    match handle.join() {
+        Ok(value) => println!("got {}", value),
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => println!("thread panicked: {}", message),
+            Err(_) => println!("thread panicked with something else"),
+        },
+    }
+}
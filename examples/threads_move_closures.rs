@@ -0,0 +1,35 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// move closures: what a spawned thread takes, and what main has left
+
+use std::thread;
+
+fn main() {
+    let greeting = String::from("hello from main");
+    let retries = 3;
+
+    // Without move the closure only borrows greeting, but the thread may outlive main's frame
+    // let handle = thread::spawn(|| println!("{}", greeting));
+
+    // A clone made beforehand stays with main
+    let kept = greeting.clone();
+    // move makes the closure own greeting, and spawn gives the closure to the new thread
+    let handle = thread::spawn(move || {
+        println!("thread: {} (retries: {})", greeting.to_uppercase(), retries);
+        greeting.len()
+    });
+    // greeting belongs to the thread now
+    // println!("{}", greeting);
+    // retries is a Copy i32: the closure took a copy, and main still has its own
+    println!("main: {} (retries: {})", kept, retries);
+    println!("the thread's greeting was {} bytes", handle.join().unwrap());
+
+    // move puts a copy of the reference in the closure, but it still borrows main's local
+    let label = String::from("worker");
+    let name = &label;
+    // thread::spawn(move || println!("{}", name));
+    let owned = name.clone();
+    thread::spawn(move || println!("{}", owned)).join().unwrap();
+}
@@ -0,0 +1,37 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// RwLock<T>: many readers or one writer, like &T and &mut T
+
+use std::sync::{Arc, RwLock};
+use std::thread;
+
+fn main() {
+    let settings = Arc::new(RwLock::new(vec![String::from("large font")]));
+
+    // Like many &T: every reader can hold a read guard at the same time
+    let readers: Vec<_> = (0..4)
+        .map(|i| {
+            let settings = Arc::clone(&settings);
+            thread::spawn(move || {
+                let settings = settings.read().unwrap();
+                // A read guard derefs to &Vec<String>, so it can't change the Vec
+                // settings.push(String::from("dark mode"));
+                println!("reader {} sees {:?}", i, *settings);
+            })
+        })
+        .collect();
+    for reader in readers {
+        reader.join().unwrap();
+    }
+
+    // Like one &mut T: the writer waits until no reader holds a guard, then excludes them all
+    {
+        let mut writer = settings.write().unwrap();
+        println!("try_read while writing fails: {}", settings.try_read().is_err());
+        writer.push(String::from("dark mode"));
+    } // the write guard drops here, and readers can get in again
+
+    println!("now: {:?}", *settings.read().unwrap());
+}
@@ -0,0 +1,46 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Send and Sync: which types may move to, or be shared with, another thread
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+// These compile only for types that are Send, or Sync
+fn assert_send<T: Send>() {}
+fn assert_sync<T: Sync>() {}
+
+fn main() {
+    assert_send::<String>();
+    assert_sync::<String>();
+    assert_send::<Arc<Mutex<i32>>>();
+    assert_sync::<Mutex<i32>>();
+
+    // A Cell can move to another thread, but not be shared: two threads could set it at once
+    assert_send::<Cell<i32>>();
+    // assert_sync::<Cell<i32>>();
+
+    // Rc's count isn't atomic, so an Rc can neither move to nor be shared with another thread
+    // assert_send::<Rc<String>>();
+    let local = Rc::new(String::from("stays on this thread"));
+    println!("{}", local);
+
+    // Arc<T> is Send only if T is Sync: every clone shares the same T
+    #[allow(clippy::arc_with_non_send_sync)] // the point: this Arc can't go to another thread
+    let shared = Arc::new(Cell::new(0));
+    // thread::spawn(move || shared.set(1));
+    shared.set(1);
+    println!("shared = {}", shared.get());
+
+    // With a Mutex inside, the Arc can go to any thread
+    let counter = Arc::new(Mutex::new(0));
+    let worker = {
+        let counter = Arc::clone(&counter);
+        thread::spawn(move || *counter.lock().unwrap() += 1)
+    };
+    worker.join().unwrap();
+    println!("count = {}", *counter.lock().unwrap());
+}
@@ -0,0 +1,29 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Arc<T>: several threads owning the same Vec
+
+use std::rc::Rc;
+use std::sync::Arc;
+use std::thread;
+
+fn main() {
+    // Rc's count isn't updated atomically, so an Rc can't be sent to another thread
+    let local = Rc::new(vec![1, 2, 3]);
+    println!("{}", Rc::strong_count(&local));
+    // thread::spawn(move || println!("{}", local.len()));
+
+    // Arc's count is atomic: Arc<Vec<i32>> is Send + Sync because Vec<i32> is
+    let numbers: Arc<Vec<i32>> = Arc::new((1..=100).collect());
+    let mut workers = Vec::new();
+    for i in 0..4 {
+        // Each thread gets its own clone: a new owner of the same Vec
+        let numbers = Arc::clone(&numbers);
+        workers.push(thread::spawn(move || numbers[i * 25..(i + 1) * 25].iter().sum::<i32>()));
+    }
+    println!("owners while the threads run: up to {}", Arc::strong_count(&numbers));
+
+    let total: i32 = workers.into_iter().map(|worker| worker.join().unwrap()).sum();
+    println!("total {}, owners now {}", total, Arc::strong_count(&numbers));
+}
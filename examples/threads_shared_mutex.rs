@@ -0,0 +1,42 @@
+// Generated from the lesson registry by `rust_ownership_demo gen-examples`.
+// Edit the lesson instead of this file.
+// Lesson code sometimes shows non-idiomatic forms on purpose.
+#![allow(unused)]
+// Arc<Mutex<T>>: threads taking turns to change one counter
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+fn main() {
+    // Arc: every thread owns the Mutex. Mutex: one of them at a time changes the i32
+    let counter = Arc::new(Mutex::new(0));
+    let mut workers = Vec::new();
+    for _ in 0..4 {
+        let counter = Arc::clone(&counter);
+        workers.push(thread::spawn(move || {
+            for _ in 0..1000 {
+                // lock() returns a MutexGuard, a borrow of the Mutex that derefs to the i32
+                let mut guard = counter.lock().unwrap();
+                *guard += 1;
+            } // the guard drops at the end of each pass, and dropping it unlocks the Mutex
+        }));
+    }
+    for worker in workers {
+        worker.join().unwrap();
+    }
+    println!("count = {}", *counter.lock().unwrap());
+
+    // While a guard is alive nobody else gets the lock, not even this thread
+    let guard = counter.lock().unwrap();
+    println!("try_lock while locked fails: {}", counter.try_lock().is_err());
+    drop(guard);
+    println!("try_lock after drop(guard) works: {}", counter.try_lock().is_ok());
+
+    // A reference into the Mutex lives only as long as the guard it came through
+    let mut guard = counter.lock().unwrap();
+    let inside: &mut i32 = &mut guard;
+    // Unlocking while that reference is still in use
+    // drop(guard);
+    *inside += 1;
+    println!("count = {}", inside);
+}
@@ -0,0 +1,1164 @@
+// Katas for the borrow checker's most common complaints: use after move
+// (E0382), two mutable borrows (E0499), mutating while borrowed (E0502,
+// E0506), moving while borrowed (E0505) and references that outlive what
+// they point to (E0515, E0716). Each starter is the kind of code people
+// actually write first.
+
+use super::{Exercise, Pack};
+
+pub const PACK: Pack = Pack {
+    name: "borrowck",
+    title: "BORROW CHECKER KATAS",
+    exercises: &[
+        Exercise {
+            name: "use_after_move",
+            title: "Shout without giving the text away",
+            level: 1,
+            task: "loud_and_quiet(text: String) -> (String, String) returns the text upper-cased and as it was.",
+            error: Some("E0382"),
+            starter: USE_AFTER_MOVE_STARTER,
+            tests: USE_AFTER_MOVE_TESTS,
+            hints: &[
+                "shout(text) moves text into shout. Does shout need to own it?",
+                "A function that only reads a string can take &str instead of String.",
+                "Change shout to take &str and call it as shout(&text).",
+            ],
+            solution: USE_AFTER_MOVE_SOLUTION,
+        },
+        Exercise {
+            name: "for_consumes_vec",
+            title: "Count after looping",
+            level: 1,
+            task: "total_and_count(words: Vec<String>) returns the total length of the words and how many there are.",
+            error: Some("E0382"),
+            starter: FOR_CONSUMES_VEC_STARTER,
+            tests: FOR_CONSUMES_VEC_TESTS,
+            hints: &[
+                "The error points at words.len(), but the move happens earlier.",
+                "`for word in words` calls words.into_iter(), which takes the Vec.",
+                "Loop over &words, so the loop only borrows it.",
+            ],
+            solution: FOR_CONSUMES_VEC_SOLUTION,
+        },
+        Exercise {
+            name: "moved_in_loop",
+            title: "One prefix, many labels",
+            level: 1,
+            task: "labels(names: &[&str], prefix: String) -> Vec<Label> gives every name a Label with the same prefix.",
+            error: Some("E0382"),
+            starter: MOVED_IN_LOOP_STARTER,
+            tests: MOVED_IN_LOOP_TESTS,
+            hints: &[
+                "The first iteration moves prefix into a Label. What's left for the second?",
+                "Each Label owns its prefix, so each one needs its own String.",
+                "Give each Label prefix.clone().",
+            ],
+            solution: MOVED_IN_LOOP_SOLUTION,
+        },
+        Exercise {
+            name: "longest_before_push",
+            title: "Remember the longest name",
+            level: 1,
+            task: "longest_then_add(names, extra) appends extra and returns the length of the longest name before it.",
+            error: Some("E0502"),
+            starter: LONGEST_BEFORE_PUSH_STARTER,
+            tests: LONGEST_BEFORE_PUSH_TESTS,
+            hints: &[
+                "longest is a reference into names, and push may move every element.",
+                "You only need the length, not the name itself.",
+                "Take .len() of the longest name before pushing, so no reference is kept.",
+            ],
+            solution: LONGEST_BEFORE_PUSH_SOLUTION,
+        },
+        Exercise {
+            name: "take_header",
+            title: "Keep the header, clear the buffer",
+            level: 1,
+            task: "take_header(buffer: &mut String) -> String returns the first line and leaves the buffer empty.",
+            error: Some("E0502"),
+            starter: TAKE_HEADER_STARTER,
+            tests: TAKE_HEADER_TESTS,
+            hints: &[
+                "header is a &str pointing into buffer.",
+                "clear() needs buffer mutably while header still borrows it.",
+                "Turn header into an owned String before clearing.",
+            ],
+            solution: TAKE_HEADER_SOLUTION,
+        },
+        Exercise {
+            name: "trimmed_upper",
+            title: "Return what you made",
+            level: 1,
+            task: "trimmed_upper(text) returns text trimmed and upper-cased; the return type is yours to choose.",
+            error: Some("E0515"),
+            starter: TRIMMED_UPPER_STARTER,
+            tests: TRIMMED_UPPER_TESTS,
+            hints: &[
+                "upper is created inside the function. When is it dropped?",
+                "A &str can't outlive the String it points into.",
+                "Return the String itself: -> String, and return upper.",
+            ],
+            solution: TRIMMED_UPPER_SOLUTION,
+        },
+        Exercise {
+            name: "move_into_struct",
+            title: "Measure before you move",
+            level: 1,
+            task: "start(user: String) -> (Session, usize) stores the user in a Session and returns its name length.",
+            error: Some("E0505"),
+            starter: MOVE_INTO_STRUCT_STARTER,
+            tests: MOVE_INTO_STRUCT_TESTS,
+            hints: &[
+                "name borrows user, then user moves into the Session.",
+                "After the move, name would point at a String the function no longer owns.",
+                "Compute user.len() before building the Session.",
+            ],
+            solution: MOVE_INTO_STRUCT_SOLUTION,
+        },
+        Exercise {
+            name: "partial_move",
+            title: "Take the customer, keep the order",
+            level: 2,
+            task: "receipt(order: Order) -> String says who bought how many items, e.g. \"Ada bought 2 items\".",
+            error: Some("E0382"),
+            starter: PARTIAL_MOVE_STARTER,
+            tests: PARTIAL_MOVE_TESTS,
+            hints: &[
+                "`let customer = order.customer;` moves one field out of order.",
+                "order.count() needs all of order, but part of it is gone.",
+                "Borrow the field instead: let customer = &order.customer;",
+            ],
+            solution: PARTIAL_MOVE_SOLUTION,
+        },
+        Exercise {
+            name: "swap_ends",
+            title: "Swap the first and last",
+            level: 2,
+            task: "swap_ends(values: &mut Vec<i32>) swaps the first and last values of a non-empty Vec.",
+            error: Some("E0499"),
+            starter: SWAP_ENDS_STARTER,
+            tests: SWAP_ENDS_TESTS,
+            hints: &[
+                "The compiler can't tell that values[0] and values[last] are different elements.",
+                "Two &mut into the same Vec at once is exactly what it forbids.",
+                "Slices have a method that swaps two positions for you: values.swap(0, last).",
+            ],
+            solution: SWAP_ENDS_SOLUTION,
+        },
+        Exercise {
+            name: "transfer",
+            title: "Move money between two accounts",
+            level: 2,
+            task: "transfer(balances, from, to, amount) takes amount from one account and adds it to another.",
+            error: Some("E0499"),
+            starter: TRANSFER_STARTER,
+            tests: TRANSFER_TESTS,
+            hints: &[
+                "Each get_mut borrows the whole map mutably.",
+                "You don't need both balances at the same time, one after the other is enough.",
+                "Update from, let that borrow end, then update to.",
+            ],
+            solution: TRANSFER_SOLUTION,
+        },
+        Exercise {
+            name: "remove_negatives",
+            title: "Remove while iterating",
+            level: 2,
+            task: "remove_negatives(values: &mut Vec<i32>) removes every negative value, keeping the order.",
+            error: Some("E0502"),
+            starter: REMOVE_NEGATIVES_STARTER,
+            tests: REMOVE_NEGATIVES_TESTS,
+            hints: &[
+                "The loop borrows values; remove() wants it mutably.",
+                "Even if it compiled, removing shifts the later elements and the loop would skip one.",
+                "Vec::retain keeps the elements a closure approves of, in one pass.",
+            ],
+            solution: REMOVE_NEGATIVES_SOLUTION,
+        },
+        Exercise {
+            name: "replace_and_report",
+            title: "Replace a value and report the old one",
+            level: 2,
+            task: "replace_and_report(current: &mut String, next: String) stores next and returns \"old -> new\".",
+            error: Some("E0506"),
+            starter: REPLACE_AND_REPORT_STARTER,
+            tests: REPLACE_AND_REPORT_TESTS,
+            hints: &[
+                "old points at the String you're about to overwrite.",
+                "You need the old value itself, not a reference to it.",
+                "std::mem::replace(current, next) stores next and hands back the old String.",
+            ],
+            solution: REPLACE_AND_REPORT_SOLUTION,
+        },
+        Exercise {
+            name: "raise_to_best",
+            title: "Raise every score to the best one",
+            level: 2,
+            task: "raise_to_best(scores: &mut [u32]) sets every score in a non-empty slice to the highest one.",
+            error: Some("E0506"),
+            starter: RAISE_TO_BEST_STARTER,
+            tests: RAISE_TO_BEST_TESTS,
+            hints: &[
+                "max() returns a reference into scores.",
+                "Writing to scores[i] while best points into scores could change what best says.",
+                "u32 is Copy: take the value out with * (or .copied()) before the loop.",
+            ],
+            solution: RAISE_TO_BEST_SOLUTION,
+        },
+        Exercise {
+            name: "first_and_consume",
+            title: "Look before you hand it over",
+            level: 2,
+            task: "first_and_consume(words: Vec<String>) returns the first word and how many words consume() saw.",
+            error: Some("E0505"),
+            starter: FIRST_AND_CONSUME_STARTER,
+            tests: FIRST_AND_CONSUME_TESTS,
+            hints: &[
+                "first borrows words, then words moves into consume().",
+                "Once consume() owns the Vec it may drop it, taking first's target with it.",
+                "Clone the first word before calling consume().",
+            ],
+            solution: FIRST_AND_CONSUME_SOLUTION,
+        },
+        Exercise {
+            name: "segments",
+            title: "Split a normalized path",
+            level: 2,
+            task: "segments(path) splits a path on both / and \\ into its parts; the return type is yours to choose.",
+            error: Some("E0515"),
+            starter: SEGMENTS_STARTER,
+            tests: SEGMENTS_TESTS,
+            hints: &[
+                "The &strs point into normalized, a local String.",
+                "Either the parts own their text or something outside the function must own the buffer.",
+                "Return Vec<String> by mapping each part through String::from.",
+            ],
+            solution: SEGMENTS_SOLUTION,
+        },
+        Exercise {
+            name: "header_width",
+            title: "Borrowing from a temporary",
+            level: 2,
+            task: "header_width(columns) returns how many characters columns.join(\",\") has.",
+            error: Some("E0716"),
+            starter: HEADER_WIDTH_STARTER,
+            tests: HEADER_WIDTH_TESTS,
+            hints: &[
+                "columns.join(\",\") creates a String nobody owns.",
+                "That temporary is dropped at the end of the statement, but header still points into it.",
+                "Bind the joined String to a variable first, then borrow from it.",
+            ],
+            solution: HEADER_WIDTH_SOLUTION,
+        },
+        Exercise {
+            name: "shout_words",
+            title: "Collect words from a temporary",
+            level: 2,
+            task: "shout_words(text) -> Vec<String> upper-cases each word of text and adds a \"!\".",
+            error: Some("E0716"),
+            starter: SHOUT_WORDS_STARTER,
+            tests: SHOUT_WORDS_TESTS,
+            hints: &[
+                "words holds &strs into the String returned by to_uppercase().",
+                "Nothing keeps that String alive past the `let` statement.",
+                "Store text.to_uppercase() in its own variable and split that.",
+            ],
+            solution: SHOUT_WORDS_SOLUTION,
+        },
+        Exercise {
+            name: "publish_with_title",
+            title: "Keep the title after publishing",
+            level: 3,
+            task: "publish_with_title(draft) publishes the draft and also returns its title upper-cased.",
+            error: Some("E0505"),
+            starter: PUBLISH_WITH_TITLE_STARTER,
+            tests: PUBLISH_WITH_TITLE_TESTS,
+            hints: &[
+                "publish(self) consumes the draft, including the title field title borrows.",
+                "Everything you need from the draft has to be taken before it's consumed.",
+                "Compute the upper-cased title before calling publish().",
+            ],
+            solution: PUBLISH_WITH_TITLE_SOLUTION,
+        },
+        Exercise {
+            name: "event_log",
+            title: "A closure and a direct push",
+            level: 3,
+            task: "build_log(events) records \"event: <e>\" for each event, or \"(blank)\" for empty ones.",
+            error: Some("E0499"),
+            starter: EVENT_LOG_STARTER,
+            tests: EVENT_LOG_TESTS,
+            hints: &[
+                "record captures log mutably for as long as record exists.",
+                "The direct log.push is a second mutable borrow while the closure still holds the first.",
+                "Pass the log to the closure as a parameter instead of capturing it, or do both pushes in it.",
+            ],
+            solution: EVENT_LOG_SOLUTION,
+        },
+        Exercise {
+            name: "push_while_iterating",
+            title: "Grow a Vec while walking it",
+            level: 3,
+            task: "double_up(values) doubles each value and appends half of every original value over 10.",
+            error: Some("E0499"),
+            starter: PUSH_WHILE_ITERATING_STARTER,
+            tests: PUSH_WHILE_ITERATING_TESTS,
+            hints: &[
+                "iter_mut() borrows values until the loop ends; push() wants it too.",
+                "A push can reallocate the Vec, leaving the iterator pointing at freed memory.",
+                "Collect the new values in a second Vec and extend values after the loop.",
+            ],
+            solution: PUSH_WHILE_ITERATING_SOLUTION,
+        },
+        Exercise {
+            name: "audit",
+            title: "Calling a method while a field is borrowed",
+            level: 3,
+            task: "Inventory::audit(&mut self) adds \"checked <item>\" to the log for every item.",
+            error: Some("E0502"),
+            starter: AUDIT_STARTER,
+            tests: AUDIT_TESTS,
+            hints: &[
+                "The loop borrows self.items; self.note() borrows all of self mutably.",
+                "The compiler can't see inside note() to know it only touches self.log.",
+                "Borrowing two different fields is fine: push to self.log directly.",
+            ],
+            solution: AUDIT_SOLUTION,
+        },
+    ],
+};
+
+const USE_AFTER_MOVE_STARTER: &str = r#"fn shout(text: String) -> String {
+    text.to_uppercase()
+}
+
+fn loud_and_quiet(text: String) -> (String, String) {
+    let loud = shout(text);
+    (loud, text)
+}
+
+fn main() {
+    println!("{:?}", loud_and_quiet(String::from("hello")));
+}
+"#;
+
+const USE_AFTER_MOVE_TESTS: &str = r#"    #[test]
+    fn returns_both_versions() {
+        let (loud, quiet) = loud_and_quiet(String::from("hello"));
+        assert_eq!(loud, "HELLO");
+        assert_eq!(quiet, "hello");
+    }
+"#;
+
+const USE_AFTER_MOVE_SOLUTION: &str = r#"// shout only reads the text, so it borrows it instead of taking it
+fn shout(text: &str) -> String {
+    text.to_uppercase()
+}
+
+fn loud_and_quiet(text: String) -> (String, String) {
+    let loud = shout(&text);
+    (loud, text)
+}
+
+fn main() {
+    println!("{:?}", loud_and_quiet(String::from("hello")));
+}
+"#;
+
+const FOR_CONSUMES_VEC_STARTER: &str = r#"fn total_and_count(words: Vec<String>) -> (usize, usize) {
+    let mut total = 0;
+    for word in words {
+        total += word.len();
+    }
+    (total, words.len())
+}
+
+fn main() {
+    let words = vec![String::from("own"), String::from("borrow")];
+    println!("{:?}", total_and_count(words));
+}
+"#;
+
+const FOR_CONSUMES_VEC_TESTS: &str = r#"    #[test]
+    fn totals_and_counts() {
+        let words = vec![String::from("own"), String::from("borrow")];
+        assert_eq!(total_and_count(words), (9, 2));
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(total_and_count(Vec::new()), (0, 0));
+    }
+"#;
+
+const FOR_CONSUMES_VEC_SOLUTION: &str = r#"fn total_and_count(words: Vec<String>) -> (usize, usize) {
+    let mut total = 0;
+    // Looping over &words borrows the Vec, so it's still ours afterwards
+    for word in &words {
+        total += word.len();
+    }
+    (total, words.len())
+}
+
+fn main() {
+    let words = vec![String::from("own"), String::from("borrow")];
+    println!("{:?}", total_and_count(words));
+}
+"#;
+
+const MOVED_IN_LOOP_STARTER: &str = r#"#[derive(Debug)]
+struct Label {
+    name: String,
+    prefix: String,
+}
+
+fn labels(names: &[&str], prefix: String) -> Vec<Label> {
+    let mut out = Vec::new();
+    for name in names {
+        out.push(Label { name: name.to_string(), prefix });
+    }
+    out
+}
+
+fn main() {
+    println!("{:?}", labels(&["a", "b"], String::from("item-")));
+}
+"#;
+
+const MOVED_IN_LOOP_TESTS: &str = r#"    #[test]
+    fn every_label_gets_the_prefix() {
+        let labels = labels(&["a", "b", "c"], String::from("item-"));
+        assert_eq!(labels.len(), 3);
+        for (label, name) in labels.iter().zip(["a", "b", "c"]) {
+            assert_eq!(label.name, name);
+            assert_eq!(label.prefix, "item-");
+        }
+    }
+"#;
+
+const MOVED_IN_LOOP_SOLUTION: &str = r#"#[derive(Debug)]
+struct Label {
+    name: String,
+    prefix: String,
+}
+
+fn labels(names: &[&str], prefix: String) -> Vec<Label> {
+    let mut out = Vec::new();
+    for name in names {
+        // Every Label owns its prefix, so each iteration needs a copy
+        out.push(Label { name: name.to_string(), prefix: prefix.clone() });
+    }
+    out
+}
+
+fn main() {
+    println!("{:?}", labels(&["a", "b"], String::from("item-")));
+}
+"#;
+
+const LONGEST_BEFORE_PUSH_STARTER: &str = r#"fn longest_then_add(names: &mut Vec<String>, extra: &str) -> usize {
+    let longest = names.iter().max_by_key(|name| name.len()).unwrap();
+    names.push(extra.to_string());
+    longest.len()
+}
+
+fn main() {
+    let mut names = vec![String::from("Ada"), String::from("Grace")];
+    println!("{}", longest_then_add(&mut names, "Barbara"));
+}
+"#;
+
+const LONGEST_BEFORE_PUSH_TESTS: &str = r#"    #[test]
+    fn measures_before_adding() {
+        let mut names = vec![String::from("Ada"), String::from("Grace")];
+        assert_eq!(longest_then_add(&mut names, "Barbara"), 5);
+        assert_eq!(names, ["Ada", "Grace", "Barbara"]);
+    }
+"#;
+
+const LONGEST_BEFORE_PUSH_SOLUTION: &str = r#"fn longest_then_add(names: &mut Vec<String>, extra: &str) -> usize {
+    // Keep the length, a plain number, instead of a reference into names
+    let longest = names.iter().map(|name| name.len()).max().unwrap();
+    names.push(extra.to_string());
+    longest
+}
+
+fn main() {
+    let mut names = vec![String::from("Ada"), String::from("Grace")];
+    println!("{}", longest_then_add(&mut names, "Barbara"));
+}
+"#;
+
+const TAKE_HEADER_STARTER: &str = r#"fn take_header(buffer: &mut String) -> String {
+    let header = buffer.lines().next().unwrap_or("");
+    buffer.clear();
+    header.to_string()
+}
+
+fn main() {
+    let mut buffer = String::from("name,age\nAda,36\n");
+    println!("{}", take_header(&mut buffer));
+}
+"#;
+
+const TAKE_HEADER_TESTS: &str = r#"    #[test]
+    fn returns_first_line_and_clears() {
+        let mut buffer = String::from("name,age\nAda,36\n");
+        assert_eq!(take_header(&mut buffer), "name,age");
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn empty_buffer() {
+        let mut buffer = String::new();
+        assert_eq!(take_header(&mut buffer), "");
+    }
+"#;
+
+const TAKE_HEADER_SOLUTION: &str = r#"fn take_header(buffer: &mut String) -> String {
+    // Copy the line out while buffer is only borrowed for reading
+    let header = buffer.lines().next().unwrap_or("").to_string();
+    buffer.clear();
+    header
+}
+
+fn main() {
+    let mut buffer = String::from("name,age\nAda,36\n");
+    println!("{}", take_header(&mut buffer));
+}
+"#;
+
+const TRIMMED_UPPER_STARTER: &str = r#"fn trimmed_upper(text: &str) -> &str {
+    let upper = text.trim().to_uppercase();
+    &upper
+}
+
+fn main() {
+    println!("{}", trimmed_upper("  hello  "));
+}
+"#;
+
+const TRIMMED_UPPER_TESTS: &str = r#"    #[test]
+    fn trims_and_upper_cases() {
+        assert_eq!(trimmed_upper("  hello  "), "HELLO");
+    }
+"#;
+
+const TRIMMED_UPPER_SOLUTION: &str = r#"// to_uppercase makes a new String, so the caller has to own the result
+fn trimmed_upper(text: &str) -> String {
+    text.trim().to_uppercase()
+}
+
+fn main() {
+    println!("{}", trimmed_upper("  hello  "));
+}
+"#;
+
+const MOVE_INTO_STRUCT_STARTER: &str = r#"struct Session {
+    user: String,
+}
+
+fn start(user: String) -> (Session, usize) {
+    let name = user.as_str();
+    let session = Session { user };
+    (session, name.len())
+}
+
+fn main() {
+    let (session, len) = start(String::from("ada"));
+    println!("{} ({} bytes)", session.user, len);
+}
+"#;
+
+const MOVE_INTO_STRUCT_TESTS: &str = r#"    #[test]
+    fn stores_user_and_measures_it() {
+        let (session, len) = start(String::from("ada"));
+        assert_eq!(session.user, "ada");
+        assert_eq!(len, 3);
+    }
+"#;
+
+const MOVE_INTO_STRUCT_SOLUTION: &str = r#"struct Session {
+    user: String,
+}
+
+fn start(user: String) -> (Session, usize) {
+    // Take what we need from user before it moves into the Session
+    let len = user.len();
+    let session = Session { user };
+    (session, len)
+}
+
+fn main() {
+    let (session, len) = start(String::from("ada"));
+    println!("{} ({} bytes)", session.user, len);
+}
+"#;
+
+const PARTIAL_MOVE_STARTER: &str = r#"struct Order {
+    customer: String,
+    items: Vec<String>,
+}
+
+impl Order {
+    fn count(&self) -> usize {
+        self.items.len()
+    }
+}
+
+fn receipt(order: Order) -> String {
+    let customer = order.customer;
+    format!("{} bought {} items", customer, order.count())
+}
+
+fn main() {
+    let order = Order { customer: String::from("Ada"), items: vec![String::from("pen"), String::from("ink")] };
+    println!("{}", receipt(order));
+}
+"#;
+
+const PARTIAL_MOVE_TESTS: &str = r#"    #[test]
+    fn names_customer_and_count() {
+        let order = Order { customer: String::from("Ada"), items: vec![String::from("pen"), String::from("ink")] };
+        assert_eq!(receipt(order), "Ada bought 2 items");
+    }
+"#;
+
+const PARTIAL_MOVE_SOLUTION: &str = r#"struct Order {
+    customer: String,
+    items: Vec<String>,
+}
+
+impl Order {
+    fn count(&self) -> usize {
+        self.items.len()
+    }
+}
+
+fn receipt(order: Order) -> String {
+    // Borrowing the field leaves order whole, so its methods still work
+    let customer = &order.customer;
+    format!("{} bought {} items", customer, order.count())
+}
+
+fn main() {
+    let order = Order { customer: String::from("Ada"), items: vec![String::from("pen"), String::from("ink")] };
+    println!("{}", receipt(order));
+}
+"#;
+
+const SWAP_ENDS_STARTER: &str = r#"fn swap_ends(values: &mut Vec<i32>) {
+    let last = values.len() - 1;
+    let first = &mut values[0];
+    let end = &mut values[last];
+    std::mem::swap(first, end);
+}
+
+fn main() {
+    let mut values = vec![1, 2, 3];
+    swap_ends(&mut values);
+    println!("{:?}", values);
+}
+"#;
+
+const SWAP_ENDS_TESTS: &str = r#"    #[test]
+    fn swaps_first_and_last() {
+        let mut values = vec![1, 2, 3];
+        swap_ends(&mut values);
+        assert_eq!(values, [3, 2, 1]);
+    }
+
+    #[test]
+    fn single_value() {
+        let mut values = vec![7];
+        swap_ends(&mut values);
+        assert_eq!(values, [7]);
+    }
+"#;
+
+const SWAP_ENDS_SOLUTION: &str = r#"fn swap_ends(values: &mut Vec<i32>) {
+    let last = values.len() - 1;
+    // swap borrows the Vec once and handles both positions itself
+    values.swap(0, last);
+}
+
+fn main() {
+    let mut values = vec![1, 2, 3];
+    swap_ends(&mut values);
+    println!("{:?}", values);
+}
+"#;
+
+const TRANSFER_STARTER: &str = r#"use std::collections::HashMap;
+
+fn transfer(balances: &mut HashMap<String, i64>, from: &str, to: &str, amount: i64) {
+    let source = balances.get_mut(from).unwrap();
+    let target = balances.get_mut(to).unwrap();
+    *source -= amount;
+    *target += amount;
+}
+
+fn main() {
+    let mut balances = HashMap::from([(String::from("ada"), 100), (String::from("grace"), 50)]);
+    transfer(&mut balances, "ada", "grace", 30);
+    println!("{:?}", balances);
+}
+"#;
+
+const TRANSFER_TESTS: &str = r#"    #[test]
+    fn moves_the_amount() {
+        let mut balances = HashMap::from([(String::from("ada"), 100), (String::from("grace"), 50)]);
+        transfer(&mut balances, "ada", "grace", 30);
+        assert_eq!(balances["ada"], 70);
+        assert_eq!(balances["grace"], 80);
+    }
+"#;
+
+const TRANSFER_SOLUTION: &str = r#"use std::collections::HashMap;
+
+fn transfer(balances: &mut HashMap<String, i64>, from: &str, to: &str, amount: i64) {
+    // Each borrow of the map ends with its statement, so they never overlap
+    *balances.get_mut(from).unwrap() -= amount;
+    *balances.get_mut(to).unwrap() += amount;
+}
+
+fn main() {
+    let mut balances = HashMap::from([(String::from("ada"), 100), (String::from("grace"), 50)]);
+    transfer(&mut balances, "ada", "grace", 30);
+    println!("{:?}", balances);
+}
+"#;
+
+const REMOVE_NEGATIVES_STARTER: &str = r#"fn remove_negatives(values: &mut Vec<i32>) {
+    for (i, value) in values.iter().enumerate() {
+        if *value < 0 {
+            values.remove(i);
+        }
+    }
+}
+
+fn main() {
+    let mut values = vec![1, -2, -3, 4];
+    remove_negatives(&mut values);
+    println!("{:?}", values);
+}
+"#;
+
+const REMOVE_NEGATIVES_TESTS: &str = r#"    #[test]
+    fn removes_neighbouring_negatives() {
+        let mut values = vec![1, -2, -3, 4, -5];
+        remove_negatives(&mut values);
+        assert_eq!(values, [1, 4]);
+    }
+"#;
+
+const REMOVE_NEGATIVES_SOLUTION: &str = r#"fn remove_negatives(values: &mut Vec<i32>) {
+    // retain walks the Vec itself, so nothing else borrows it meanwhile
+    values.retain(|value| *value >= 0);
+}
+
+fn main() {
+    let mut values = vec![1, -2, -3, 4];
+    remove_negatives(&mut values);
+    println!("{:?}", values);
+}
+"#;
+
+const REPLACE_AND_REPORT_STARTER: &str = r#"fn replace_and_report(current: &mut String, next: String) -> String {
+    let old = &*current;
+    *current = next;
+    format!("{} -> {}", old, current)
+}
+
+fn main() {
+    let mut status = String::from("draft");
+    println!("{}", replace_and_report(&mut status, String::from("published")));
+}
+"#;
+
+const REPLACE_AND_REPORT_TESTS: &str = r#"    #[test]
+    fn reports_both_values() {
+        let mut status = String::from("draft");
+        assert_eq!(replace_and_report(&mut status, String::from("published")), "draft -> published");
+        assert_eq!(status, "published");
+    }
+"#;
+
+const REPLACE_AND_REPORT_SOLUTION: &str = r#"fn replace_and_report(current: &mut String, next: String) -> String {
+    // replace moves the old String out as it moves the new one in
+    let old = std::mem::replace(current, next);
+    format!("{} -> {}", old, current)
+}
+
+fn main() {
+    let mut status = String::from("draft");
+    println!("{}", replace_and_report(&mut status, String::from("published")));
+}
+"#;
+
+const RAISE_TO_BEST_STARTER: &str = r#"fn raise_to_best(scores: &mut [u32]) {
+    let best = scores.iter().max().unwrap();
+    for i in 0..scores.len() {
+        scores[i] = *best;
+    }
+}
+
+fn main() {
+    let mut scores = [3, 9, 4];
+    raise_to_best(&mut scores);
+    println!("{:?}", scores);
+}
+"#;
+
+const RAISE_TO_BEST_TESTS: &str = r#"    #[test]
+    fn everyone_gets_the_best() {
+        let mut scores = [3, 9, 4];
+        raise_to_best(&mut scores);
+        assert_eq!(scores, [9, 9, 9]);
+    }
+"#;
+
+const RAISE_TO_BEST_SOLUTION: &str = r#"fn raise_to_best(scores: &mut [u32]) {
+    // Copy the number out, so nothing points into scores while we write
+    let best = *scores.iter().max().unwrap();
+    for score in scores.iter_mut() {
+        *score = best;
+    }
+}
+
+fn main() {
+    let mut scores = [3, 9, 4];
+    raise_to_best(&mut scores);
+    println!("{:?}", scores);
+}
+"#;
+
+const FIRST_AND_CONSUME_STARTER: &str = r#"fn consume(words: Vec<String>) -> usize {
+    words.len()
+}
+
+fn first_and_consume(words: Vec<String>) -> (String, usize) {
+    let first = &words[0];
+    let count = consume(words);
+    (first.clone(), count)
+}
+
+fn main() {
+    let words = vec![String::from("own"), String::from("borrow")];
+    println!("{:?}", first_and_consume(words));
+}
+"#;
+
+const FIRST_AND_CONSUME_TESTS: &str = r#"    #[test]
+    fn first_word_and_count() {
+        let words = vec![String::from("own"), String::from("borrow")];
+        assert_eq!(first_and_consume(words), (String::from("own"), 2));
+    }
+"#;
+
+const FIRST_AND_CONSUME_SOLUTION: &str = r#"fn consume(words: Vec<String>) -> usize {
+    words.len()
+}
+
+fn first_and_consume(words: Vec<String>) -> (String, usize) {
+    // Our own copy of the first word doesn't care what consume does with the Vec
+    let first = words[0].clone();
+    let count = consume(words);
+    (first, count)
+}
+
+fn main() {
+    let words = vec![String::from("own"), String::from("borrow")];
+    println!("{:?}", first_and_consume(words));
+}
+"#;
+
+const SEGMENTS_STARTER: &str = r#"fn segments(path: &str) -> Vec<&str> {
+    let normalized = path.replace('\\', "/");
+    normalized.split('/').collect()
+}
+
+fn main() {
+    println!("{:?}", segments("docs\\book/ch04"));
+}
+"#;
+
+const SEGMENTS_TESTS: &str = r#"    #[test]
+    fn splits_on_both_separators() {
+        assert_eq!(segments("docs\\book/ch04"), ["docs", "book", "ch04"]);
+    }
+"#;
+
+const SEGMENTS_SOLUTION: &str = r#"// normalized is dropped when we return, so the parts have to own their text
+fn segments(path: &str) -> Vec<String> {
+    let normalized = path.replace('\\', "/");
+    normalized.split('/').map(String::from).collect()
+}
+
+fn main() {
+    println!("{:?}", segments("docs\\book/ch04"));
+}
+"#;
+
+const HEADER_WIDTH_STARTER: &str = r#"fn header_width(columns: &[&str]) -> usize {
+    let header = columns.join(",").as_str();
+    header.chars().count()
+}
+
+fn main() {
+    println!("{}", header_width(&["name", "age"]));
+}
+"#;
+
+const HEADER_WIDTH_TESTS: &str = r#"    #[test]
+    fn counts_the_joined_header() {
+        assert_eq!(header_width(&["name", "age"]), 8);
+        assert_eq!(header_width(&[]), 0);
+    }
+"#;
+
+const HEADER_WIDTH_SOLUTION: &str = r#"fn header_width(columns: &[&str]) -> usize {
+    // A named variable keeps the String alive for as long as we borrow it
+    let joined = columns.join(",");
+    let header = joined.as_str();
+    header.chars().count()
+}
+
+fn main() {
+    println!("{}", header_width(&["name", "age"]));
+}
+"#;
+
+const SHOUT_WORDS_STARTER: &str = r#"fn shout_words(text: &str) -> Vec<String> {
+    let words: Vec<&str> = text.to_uppercase().split_whitespace().collect();
+    words.iter().map(|word| format!("{}!", word)).collect()
+}
+
+fn main() {
+    println!("{:?}", shout_words("own borrow"));
+}
+"#;
+
+const SHOUT_WORDS_TESTS: &str = r#"    #[test]
+    fn shouts_each_word() {
+        assert_eq!(shout_words("own  borrow"), ["OWN!", "BORROW!"]);
+    }
+"#;
+
+const SHOUT_WORDS_SOLUTION: &str = r#"fn shout_words(text: &str) -> Vec<String> {
+    // upper owns the text that words points into
+    let upper = text.to_uppercase();
+    let words: Vec<&str> = upper.split_whitespace().collect();
+    words.iter().map(|word| format!("{}!", word)).collect()
+}
+
+fn main() {
+    println!("{:?}", shout_words("own borrow"));
+}
+"#;
+
+const PUBLISH_WITH_TITLE_STARTER: &str = r#"struct Draft {
+    title: String,
+    body: String,
+}
+
+struct Post {
+    text: String,
+}
+
+impl Draft {
+    fn publish(self) -> Post {
+        Post { text: format!("{}\n\n{}", self.title, self.body) }
+    }
+}
+
+fn publish_with_title(draft: Draft) -> (Post, String) {
+    let title = &draft.title;
+    let post = draft.publish();
+    (post, title.to_uppercase())
+}
+
+fn main() {
+    let draft = Draft { title: String::from("Ownership"), body: String::from("Every value has one owner.") };
+    let (post, title) = publish_with_title(draft);
+    println!("{}\n{}", title, post.text);
+}
+"#;
+
+const PUBLISH_WITH_TITLE_TESTS: &str = r#"    #[test]
+    fn publishes_and_keeps_title() {
+        let draft = Draft { title: String::from("Ownership"), body: String::from("One owner.") };
+        let (post, title) = publish_with_title(draft);
+        assert_eq!(post.text, "Ownership\n\nOne owner.");
+        assert_eq!(title, "OWNERSHIP");
+    }
+"#;
+
+const PUBLISH_WITH_TITLE_SOLUTION: &str = r#"struct Draft {
+    title: String,
+    body: String,
+}
+
+struct Post {
+    text: String,
+}
+
+impl Draft {
+    fn publish(self) -> Post {
+        Post { text: format!("{}\n\n{}", self.title, self.body) }
+    }
+}
+
+fn publish_with_title(draft: Draft) -> (Post, String) {
+    // publish consumes the draft, so take what we need from it first
+    let title = draft.title.to_uppercase();
+    let post = draft.publish();
+    (post, title)
+}
+
+fn main() {
+    let draft = Draft { title: String::from("Ownership"), body: String::from("Every value has one owner.") };
+    let (post, title) = publish_with_title(draft);
+    println!("{}\n{}", title, post.text);
+}
+"#;
+
+const EVENT_LOG_STARTER: &str = r#"fn build_log(events: &[&str]) -> Vec<String> {
+    let mut log = Vec::new();
+    let mut record = |event: &str| log.push(format!("event: {}", event));
+    for event in events {
+        if event.is_empty() {
+            log.push(String::from("(blank)"));
+        } else {
+            record(event);
+        }
+    }
+    log
+}
+
+fn main() {
+    println!("{:?}", build_log(&["start", "", "stop"]));
+}
+"#;
+
+const EVENT_LOG_TESTS: &str = r#"    #[test]
+    fn records_in_order() {
+        assert_eq!(build_log(&["start", "", "stop"]), ["event: start", "(blank)", "event: stop"]);
+    }
+"#;
+
+const EVENT_LOG_SOLUTION: &str = r#"fn build_log(events: &[&str]) -> Vec<String> {
+    let mut log = Vec::new();
+    // The closure borrows log only while it runs, because log is passed in
+    let record = |log: &mut Vec<String>, event: &str| log.push(format!("event: {}", event));
+    for event in events {
+        if event.is_empty() {
+            log.push(String::from("(blank)"));
+        } else {
+            record(&mut log, event);
+        }
+    }
+    log
+}
+
+fn main() {
+    println!("{:?}", build_log(&["start", "", "stop"]));
+}
+"#;
+
+const PUSH_WHILE_ITERATING_STARTER: &str = r#"fn double_up(values: &mut Vec<i32>) {
+    for value in values.iter_mut() {
+        if *value > 10 {
+            values.push(*value / 2);
+        }
+        *value *= 2;
+    }
+}
+
+fn main() {
+    let mut values = vec![3, 20];
+    double_up(&mut values);
+    println!("{:?}", values);
+}
+"#;
+
+const PUSH_WHILE_ITERATING_TESTS: &str = r#"    #[test]
+    fn doubles_and_appends_halves() {
+        let mut values = vec![3, 20, 12];
+        double_up(&mut values);
+        assert_eq!(values, [6, 40, 24, 10, 6]);
+    }
+"#;
+
+const PUSH_WHILE_ITERATING_SOLUTION: &str = r#"fn double_up(values: &mut Vec<i32>) {
+    // New values wait here until the loop has finished borrowing values
+    let mut halves = Vec::new();
+    for value in values.iter_mut() {
+        if *value > 10 {
+            halves.push(*value / 2);
+        }
+        *value *= 2;
+    }
+    values.extend(halves);
+}
+
+fn main() {
+    let mut values = vec![3, 20];
+    double_up(&mut values);
+    println!("{:?}", values);
+}
+"#;
+
+const AUDIT_STARTER: &str = r#"struct Inventory {
+    items: Vec<String>,
+    log: Vec<String>,
+}
+
+impl Inventory {
+    fn note(&mut self, message: String) {
+        self.log.push(message);
+    }
+
+    fn audit(&mut self) {
+        for item in &self.items {
+            self.note(format!("checked {}", item));
+        }
+    }
+}
+
+fn main() {
+    let mut inventory = Inventory { items: vec![String::from("pen")], log: Vec::new() };
+    inventory.audit();
+    println!("{:?}", inventory.log);
+}
+"#;
+
+const AUDIT_TESTS: &str = r#"    #[test]
+    fn logs_every_item() {
+        let mut inventory = Inventory { items: vec![String::from("pen"), String::from("ink")], log: Vec::new() };
+        inventory.audit();
+        assert_eq!(inventory.log, ["checked pen", "checked ink"]);
+        assert_eq!(inventory.items, ["pen", "ink"]);
+    }
+"#;
+
+const AUDIT_SOLUTION: &str = r#"struct Inventory {
+    items: Vec<String>,
+    log: Vec<String>,
+}
+
+impl Inventory {
+    fn audit(&mut self) {
+        // Borrowing self.items and self.log separately is fine: they don't overlap
+        for item in &self.items {
+            self.log.push(format!("checked {}", item));
+        }
+    }
+}
+
+fn main() {
+    let mut inventory = Inventory { items: vec![String::from("pen")], log: Vec::new() };
+    inventory.audit();
+    println!("{:?}", inventory.log);
+}
+"#;
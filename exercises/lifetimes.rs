@@ -0,0 +1,697 @@
+// Katas on signatures: adding the lifetime annotations a function or struct
+// needs (and no tighter than that), choosing parameter types that accept
+// what callers actually have, and deciding when a struct should own its data
+// instead of borrowing it. The hidden tests call the code the way real
+// callers would, so a signature that compiles but is too strict still fails.
+
+use super::{Exercise, Pack};
+
+pub const PACK: Pack = Pack {
+    name: "lifetimes",
+    title: "LIFETIMES AND API DESIGN KATAS",
+    exercises: &[
+        Exercise {
+            name: "longest",
+            title: "Which input does the result borrow from?",
+            level: 1,
+            task: "longest(a: &str, b: &str) returns whichever of a and b is longer.",
+            error: Some("E0106"),
+            starter: LONGEST_STARTER,
+            tests: LONGEST_TESTS,
+            hints: &[
+                "With two reference parameters, elision can't guess which one the result borrows from.",
+                "The result could be either a or b, so it must not outlive either of them.",
+                "Name one lifetime and use it for a, b and the result: fn longest<'a>(a: &'a str, b: &'a str) -> &'a str",
+            ],
+            solution: LONGEST_SOLUTION,
+        },
+        Exercise {
+            name: "count_vowels",
+            title: "Accept any text",
+            level: 1,
+            task: "count_vowels(text) counts a, e, i, o and u. Callers pass literals and Strings they keep using.",
+            error: Some("E0308"),
+            starter: COUNT_VOWELS_STARTER,
+            tests: COUNT_VOWELS_TESTS,
+            hints: &[
+                "A String parameter only accepts an owned String, and takes it away from the caller.",
+                "count_vowels only reads the text.",
+                "Take &str: literals already are one, and &String turns into one automatically.",
+            ],
+            solution: COUNT_VOWELS_SOLUTION,
+        },
+        Exercise {
+            name: "sum",
+            title: "Accept any run of numbers",
+            level: 1,
+            task: "sum(values) adds up numbers from a Vec, an array or part of either, without taking them.",
+            error: Some("E0308"),
+            starter: SUM_STARTER,
+            tests: SUM_TESTS,
+            hints: &[
+                "Vec<i32> only accepts a Vec, and moves it into the function.",
+                "Arrays, Vecs and ranges of either can all be borrowed as the same type.",
+                "Take &[i32], a slice.",
+            ],
+            solution: SUM_SOLUTION,
+        },
+        Exercise {
+            name: "highlight",
+            title: "A struct that borrows",
+            level: 1,
+            task: "Highlight holds a &str into a document without copying it; highlight(doc, word) finds one.",
+            error: Some("E0106"),
+            starter: HIGHLIGHT_STARTER,
+            tests: HIGHLIGHT_TESTS,
+            hints: &[
+                "A struct field that's a reference needs to say how long the reference is valid.",
+                "The struct has to take a lifetime parameter and pass it to the field.",
+                "struct Highlight<'a> { text: &'a str }, and return Highlight<'_> (or Highlight<'a>).",
+            ],
+            solution: HIGHLIGHT_SOLUTION,
+        },
+        Exercise {
+            name: "pick_first",
+            title: "Don't tie the result to both inputs",
+            level: 2,
+            task: "pick_first(a, b) returns a; callers may drop b while they still use the result.",
+            error: Some("E0106"),
+            starter: PICK_FIRST_STARTER,
+            tests: PICK_FIRST_TESTS,
+            hints: &[
+                "Like longest, the compiler needs to be told which input the result borrows from.",
+                "Giving a and b the same lifetime would make the result unusable once b is gone.",
+                "Only a needs the lifetime: fn pick_first<'a>(a: &'a str, b: &str) -> &'a str",
+            ],
+            solution: PICK_FIRST_SOLUTION,
+        },
+        Exercise {
+            name: "append_signature",
+            title: "Change the caller's String in place",
+            level: 2,
+            task: "append_signature(message) adds \"\\n-- the team\" to the end of the caller's message.",
+            error: Some("E0308"),
+            starter: APPEND_SIGNATURE_STARTER,
+            tests: APPEND_SIGNATURE_TESTS,
+            hints: &[
+                "Callers pass &mut message, and expect message itself to change.",
+                "A function that edits the caller's value needs a mutable reference to it.",
+                "Take &mut String and push_str onto it; there's nothing to return.",
+            ],
+            solution: APPEND_SIGNATURE_SOLUTION,
+        },
+        Exercise {
+            name: "user_new",
+            title: "A constructor that stores its argument",
+            level: 2,
+            task: "User::new(name) stores the name. Callers pass both literals and Strings they're done with.",
+            error: Some("E0308"),
+            starter: USER_NEW_STARTER,
+            tests: USER_NEW_TESTS,
+            hints: &[
+                "The struct owns a String, so new has to end up with one either way.",
+                "Taking &str forces a copy even when the caller had a String to give away.",
+                "Take impl Into<String> and call name.into(): a String moves in, a &str is copied once.",
+            ],
+            solution: USER_NEW_SOLUTION,
+        },
+        Exercise {
+            name: "config_owned",
+            title: "A struct that should own its text",
+            level: 2,
+            task: "parse_config(line) returns a Config whose name is the trimmed, lower-cased line.",
+            error: Some("E0515"),
+            starter: CONFIG_OWNED_STARTER,
+            tests: CONFIG_OWNED_TESTS,
+            hints: &[
+                "name points at lowered, which is created inside parse_config.",
+                "No lifetime annotation can make a reference outlive the String it points into.",
+                "The Config has to own its name: make the field a String and drop the lifetime.",
+            ],
+            solution: CONFIG_OWNED_SOLUTION,
+        },
+        Exercise {
+            name: "extension_of",
+            title: "Accept every kind of path",
+            level: 3,
+            task: "extension_of(path) returns a path's extension. Callers have &str, String, PathBuf and &Path.",
+            error: Some("E0308"),
+            starter: EXTENSION_OF_STARTER,
+            tests: EXTENSION_OF_TESTS,
+            hints: &[
+                "&str accepts the first two, but not a PathBuf or a &Path.",
+                "All four can be borrowed as a &Path through one trait.",
+                "Take impl AsRef<Path> and call path.as_ref() inside.",
+            ],
+            solution: EXTENSION_OF_SOLUTION,
+        },
+        Exercise {
+            name: "parser_words",
+            title: "Borrow from the input, not the parser",
+            level: 3,
+            task: "Parser::next_word() returns the next word; callers keep several words while parsing on.",
+            error: Some("E0499"),
+            starter: PARSER_WORDS_STARTER,
+            tests: PARSER_WORDS_TESTS,
+            hints: &[
+                "Elision ties the returned &str to &mut self, so the parser stays borrowed while it's used.",
+                "The words are slices of input, which lives longer than any one call.",
+                "Return &'a str, the struct's own lifetime parameter, instead of an elided one.",
+            ],
+            solution: PARSER_WORDS_SOLUTION,
+        },
+        Exercise {
+            name: "roster_filter",
+            title: "What an impl Trait return type captures",
+            level: 3,
+            task: "Roster::starting_with(prefix) iterates over the names that start with prefix.",
+            error: Some("E0700"),
+            starter: ROSTER_FILTER_STARTER,
+            tests: ROSTER_FILTER_TESTS,
+            hints: &[
+                "The returned iterator holds on to prefix, a borrow the signature doesn't mention.",
+                "Either the signature has to say so, or the iterator has to stop borrowing prefix.",
+                "Use one lifetime for self, prefix and the result, and add + 'a to the return type.",
+            ],
+            solution: ROSTER_FILTER_SOLUTION,
+        },
+        Exercise {
+            name: "two_lifetimes",
+            title: "A struct that borrows from two places",
+            level: 3,
+            task: "first_field(line, separator) returns the part of line before the first separator.",
+            error: Some("E0515"),
+            starter: TWO_LIFETIMES_STARTER,
+            tests: TWO_LIFETIMES_TESTS,
+            hints: &[
+                "Fields uses one lifetime for rest and separator, so its results can't outlive either.",
+                "separator is a local String, but the fields are slices of line, which outlives the call.",
+                "Give Fields two lifetimes, <'t, 's>, and have next_field return &'t str.",
+            ],
+            solution: TWO_LIFETIMES_SOLUTION,
+        },
+    ],
+};
+
+const LONGEST_STARTER: &str = r#"fn longest(a: &str, b: &str) -> &str {
+    if a.len() >= b.len() {
+        a
+    } else {
+        b
+    }
+}
+
+fn main() {
+    println!("{}", longest("borrow", "own"));
+}
+"#;
+
+const LONGEST_TESTS: &str = r#"    #[test]
+    fn picks_the_longer() {
+        assert_eq!(longest("borrow", "own"), "borrow");
+        assert_eq!(longest("own", "borrow"), "borrow");
+    }
+"#;
+
+const LONGEST_SOLUTION: &str = r#"// The result may be either input, so it lives only as long as both do
+fn longest<'a>(a: &'a str, b: &'a str) -> &'a str {
+    if a.len() >= b.len() {
+        a
+    } else {
+        b
+    }
+}
+
+fn main() {
+    println!("{}", longest("borrow", "own"));
+}
+"#;
+
+const COUNT_VOWELS_STARTER: &str = r#"fn count_vowels(text: String) -> usize {
+    text.chars().filter(|c| "aeiou".contains(*c)).count()
+}
+
+fn main() {
+    let title = String::from("ownership");
+    println!("{}", count_vowels(&title));
+    println!("{} has {} vowels", title, count_vowels("borrowing"));
+}
+"#;
+
+const COUNT_VOWELS_TESTS: &str = r#"    #[test]
+    fn counts_literals_and_borrowed_strings() {
+        assert_eq!(count_vowels("borrowing"), 3);
+        let title = String::from("ownership");
+        assert_eq!(count_vowels(&title), 3);
+        assert_eq!(title, "ownership");
+    }
+"#;
+
+const COUNT_VOWELS_SOLUTION: &str = r#"// &str accepts literals and borrowed Strings alike, and leaves them with the caller
+fn count_vowels(text: &str) -> usize {
+    text.chars().filter(|c| "aeiou".contains(*c)).count()
+}
+
+fn main() {
+    let title = String::from("ownership");
+    println!("{}", count_vowels(&title));
+    println!("{} has {} vowels", title, count_vowels("borrowing"));
+}
+"#;
+
+const SUM_STARTER: &str = r#"fn sum(values: Vec<i32>) -> i32 {
+    values.iter().sum()
+}
+
+fn main() {
+    let scores = vec![3, 4, 5];
+    println!("{}", sum(&scores));
+    println!("{}", sum(&[1, 2, 3]));
+}
+"#;
+
+const SUM_TESTS: &str = r#"    #[test]
+    fn sums_vecs_arrays_and_parts() {
+        let scores = vec![3, 4, 5];
+        assert_eq!(sum(&scores), 12);
+        assert_eq!(sum(&scores[1..]), 9);
+        assert_eq!(sum(&[1, 2, 3]), 6);
+        assert_eq!(scores.len(), 3);
+    }
+"#;
+
+const SUM_SOLUTION: &str = r#"// A slice borrows any contiguous run of i32s, whoever owns them
+fn sum(values: &[i32]) -> i32 {
+    values.iter().sum()
+}
+
+fn main() {
+    let scores = vec![3, 4, 5];
+    println!("{}", sum(&scores));
+    println!("{}", sum(&[1, 2, 3]));
+}
+"#;
+
+const HIGHLIGHT_STARTER: &str = r#"struct Highlight {
+    text: &str,
+}
+
+fn highlight(document: &str, word: &str) -> Option<Highlight> {
+    let start = document.find(word)?;
+    Some(Highlight { text: &document[start..start + word.len()] })
+}
+
+fn main() {
+    let document = String::from("Every value has one owner");
+    if let Some(found) = highlight(&document, "owner") {
+        println!("found '{}'", found.text);
+    }
+}
+"#;
+
+const HIGHLIGHT_TESTS: &str = r#"    #[test]
+    fn points_into_the_document() {
+        let document = String::from("Every value has one owner");
+        let found = highlight(&document, "owner").unwrap();
+        assert_eq!(found.text, "owner");
+        assert_eq!(found.text.as_ptr(), document[20..].as_ptr());
+        assert!(highlight(&document, "borrow").is_none());
+    }
+"#;
+
+const HIGHLIGHT_SOLUTION: &str = r#"// 'a is how long the document the text points into lives
+struct Highlight<'a> {
+    text: &'a str,
+}
+
+fn highlight<'a>(document: &'a str, word: &str) -> Option<Highlight<'a>> {
+    let start = document.find(word)?;
+    Some(Highlight { text: &document[start..start + word.len()] })
+}
+
+fn main() {
+    let document = String::from("Every value has one owner");
+    if let Some(found) = highlight(&document, "owner") {
+        println!("found '{}'", found.text);
+    }
+}
+"#;
+
+const PICK_FIRST_STARTER: &str = r#"fn pick_first(a: &str, b: &str) -> &str {
+    println!("ignoring {}", b);
+    a
+}
+
+fn main() {
+    println!("{}", pick_first("kept", "ignored"));
+}
+"#;
+
+const PICK_FIRST_TESTS: &str = r#"    #[test]
+    fn result_outlives_second_argument() {
+        let a = String::from("kept");
+        let result;
+        {
+            let b = String::from("dropped early");
+            result = pick_first(&a, &b);
+        }
+        assert_eq!(result, "kept");
+    }
+"#;
+
+const PICK_FIRST_SOLUTION: &str = r#"// Only a is returned, so only a's lifetime constrains the result
+fn pick_first<'a>(a: &'a str, b: &str) -> &'a str {
+    println!("ignoring {}", b);
+    a
+}
+
+fn main() {
+    println!("{}", pick_first("kept", "ignored"));
+}
+"#;
+
+const APPEND_SIGNATURE_STARTER: &str = r#"fn append_signature(message: String) -> String {
+    message + "\n-- the team"
+}
+
+fn main() {
+    let mut message = String::from("Thanks for the report!");
+    append_signature(&mut message);
+    println!("{}", message);
+}
+"#;
+
+const APPEND_SIGNATURE_TESTS: &str = r#"    #[test]
+    fn edits_in_place() {
+        let mut message = String::from("Thanks!");
+        append_signature(&mut message);
+        assert_eq!(message, "Thanks!\n-- the team");
+    }
+"#;
+
+const APPEND_SIGNATURE_SOLUTION: &str = r#"// A mutable borrow lets us change the caller's String without taking it
+fn append_signature(message: &mut String) {
+    message.push_str("\n-- the team");
+}
+
+fn main() {
+    let mut message = String::from("Thanks for the report!");
+    append_signature(&mut message);
+    println!("{}", message);
+}
+"#;
+
+const USER_NEW_STARTER: &str = r#"struct User {
+    name: String,
+}
+
+impl User {
+    fn new(name: &str) -> User {
+        User { name: name.to_string() }
+    }
+}
+
+fn main() {
+    let ada = User::new("ada");
+    let input = String::from("grace");
+    let grace = User::new(input);
+    println!("{} {}", ada.name, grace.name);
+}
+"#;
+
+const USER_NEW_TESTS: &str = r#"    #[test]
+    fn accepts_literals_and_strings() {
+        assert_eq!(User::new("ada").name, "ada");
+        let input = String::from("grace");
+        let buffer = input.as_ptr();
+        let grace = User::new(input);
+        assert_eq!(grace.name, "grace");
+        assert_eq!(grace.name.as_ptr(), buffer, "an owned String should move in, not be copied");
+    }
+"#;
+
+const USER_NEW_SOLUTION: &str = r#"struct User {
+    name: String,
+}
+
+impl User {
+    // A String moves straight into the struct; a &str is copied into a new one
+    fn new(name: impl Into<String>) -> User {
+        User { name: name.into() }
+    }
+}
+
+fn main() {
+    let ada = User::new("ada");
+    let input = String::from("grace");
+    let grace = User::new(input);
+    println!("{} {}", ada.name, grace.name);
+}
+"#;
+
+const CONFIG_OWNED_STARTER: &str = r#"struct Config<'a> {
+    name: &'a str,
+}
+
+fn parse_config(line: &str) -> Config {
+    let lowered = line.trim().to_lowercase();
+    Config { name: &lowered }
+}
+
+fn main() {
+    println!("{}", parse_config("  Ada ").name);
+}
+"#;
+
+const CONFIG_OWNED_TESTS: &str = r#"    #[test]
+    fn trims_and_lowers() {
+        let config = parse_config("  Ada ");
+        assert_eq!(config.name, "ada");
+    }
+"#;
+
+const CONFIG_OWNED_SOLUTION: &str = r#"// The name is built inside parse_config, so the Config has to own it
+struct Config {
+    name: String,
+}
+
+fn parse_config(line: &str) -> Config {
+    Config { name: line.trim().to_lowercase() }
+}
+
+fn main() {
+    println!("{}", parse_config("  Ada ").name);
+}
+"#;
+
+const EXTENSION_OF_STARTER: &str = r#"use std::path::{Path, PathBuf};
+
+fn extension_of(path: &str) -> Option<String> {
+    let path = Path::new(path);
+    path.extension().map(|ext| ext.to_string_lossy().into_owned())
+}
+
+fn main() {
+    println!("{:?}", extension_of("notes.txt"));
+    println!("{:?}", extension_of(PathBuf::from("lesson.rs")));
+}
+"#;
+
+const EXTENSION_OF_TESTS: &str = r#"    #[test]
+    fn accepts_every_path_type() {
+        assert_eq!(extension_of("notes.txt").as_deref(), Some("txt"));
+        assert_eq!(extension_of(String::from("a.md")).as_deref(), Some("md"));
+        let owned = PathBuf::from("lesson.rs");
+        assert_eq!(extension_of(&owned).as_deref(), Some("rs"));
+        assert_eq!(extension_of(owned.as_path()).as_deref(), Some("rs"));
+        assert_eq!(extension_of(owned).as_deref(), Some("rs"));
+        assert_eq!(extension_of("Makefile"), None);
+    }
+"#;
+
+const EXTENSION_OF_SOLUTION: &str = r#"use std::path::{Path, PathBuf};
+
+// AsRef<Path> is implemented by str, String, Path, PathBuf and references to them
+fn extension_of(path: impl AsRef<Path>) -> Option<String> {
+    let path = path.as_ref();
+    path.extension().map(|ext| ext.to_string_lossy().into_owned())
+}
+
+fn main() {
+    println!("{:?}", extension_of("notes.txt"));
+    println!("{:?}", extension_of(PathBuf::from("lesson.rs")));
+}
+"#;
+
+const PARSER_WORDS_STARTER: &str = r#"struct Parser<'a> {
+    input: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn next_word(&mut self) -> &str {
+        let trimmed = self.input.trim_start();
+        let end = trimmed.find(' ').unwrap_or(trimmed.len());
+        let (word, rest) = trimmed.split_at(end);
+        self.input = rest;
+        word
+    }
+}
+
+fn main() {
+    let mut parser = Parser { input: "own borrow move" };
+    let first = parser.next_word();
+    let second = parser.next_word();
+    println!("{} {}", first, second);
+}
+"#;
+
+const PARSER_WORDS_TESTS: &str = r#"    #[test]
+    fn keeps_earlier_words() {
+        let mut parser = Parser { input: "own  borrow move" };
+        let first = parser.next_word();
+        let second = parser.next_word();
+        let third = parser.next_word();
+        assert_eq!([first, second, third], ["own", "borrow", "move"]);
+        assert_eq!(parser.next_word(), "");
+    }
+"#;
+
+const PARSER_WORDS_SOLUTION: &str = r#"struct Parser<'a> {
+    input: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    // The words point into the input, so they can outlive this borrow of the parser
+    fn next_word(&mut self) -> &'a str {
+        let trimmed = self.input.trim_start();
+        let end = trimmed.find(' ').unwrap_or(trimmed.len());
+        let (word, rest) = trimmed.split_at(end);
+        self.input = rest;
+        word
+    }
+}
+
+fn main() {
+    let mut parser = Parser { input: "own borrow move" };
+    let first = parser.next_word();
+    let second = parser.next_word();
+    println!("{} {}", first, second);
+}
+"#;
+
+const ROSTER_FILTER_STARTER: &str = r#"struct Roster {
+    names: Vec<String>,
+}
+
+impl Roster {
+    fn starting_with(&self, prefix: &str) -> impl Iterator<Item = &str> {
+        self.names.iter().map(|name| name.as_str()).filter(move |name| name.starts_with(prefix))
+    }
+}
+
+fn main() {
+    let roster = Roster { names: vec![String::from("Ada"), String::from("Alan"), String::from("Grace")] };
+    for name in roster.starting_with("A") {
+        println!("{}", name);
+    }
+}
+"#;
+
+const ROSTER_FILTER_TESTS: &str = r#"    #[test]
+    fn filters_by_prefix() {
+        let roster = Roster { names: vec![String::from("Ada"), String::from("Alan"), String::from("Grace")] };
+        let prefix = String::from("A");
+        let names: Vec<&str> = roster.starting_with(&prefix).collect();
+        assert_eq!(names, ["Ada", "Alan"]);
+    }
+"#;
+
+const ROSTER_FILTER_SOLUTION: &str = r#"struct Roster {
+    names: Vec<String>,
+}
+
+impl Roster {
+    // The iterator borrows both self and prefix, and the signature has to say so
+    fn starting_with<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.names.iter().map(|name| name.as_str()).filter(move |name| name.starts_with(prefix))
+    }
+}
+
+fn main() {
+    let roster = Roster { names: vec![String::from("Ada"), String::from("Alan"), String::from("Grace")] };
+    for name in roster.starting_with("A") {
+        println!("{}", name);
+    }
+}
+"#;
+
+const TWO_LIFETIMES_STARTER: &str = r#"struct Fields<'a> {
+    rest: &'a str,
+    separator: &'a str,
+}
+
+impl<'a> Fields<'a> {
+    fn next_field(&mut self) -> Option<&'a str> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        match self.rest.split_once(self.separator) {
+            Some((field, rest)) => {
+                self.rest = rest;
+                Some(field)
+            }
+            None => Some(std::mem::take(&mut self.rest)),
+        }
+    }
+}
+
+fn first_field(line: &str, separator: char) -> &str {
+    let separator = separator.to_string();
+    let mut fields = Fields { rest: line, separator: &separator };
+    fields.next_field().unwrap_or("")
+}
+
+fn main() {
+    println!("{}", first_field("ada,36,london", ','));
+}
+"#;
+
+const TWO_LIFETIMES_TESTS: &str = r#"    #[test]
+    fn returns_the_first_field() {
+        assert_eq!(first_field("ada,36,london", ','), "ada");
+        assert_eq!(first_field("ada", ','), "ada");
+        assert_eq!(first_field("", ','), "");
+    }
+"#;
+
+const TWO_LIFETIMES_SOLUTION: &str = r#"// 't is the text being split and 's the separator; fields only borrow from 't
+struct Fields<'t, 's> {
+    rest: &'t str,
+    separator: &'s str,
+}
+
+impl<'t, 's> Fields<'t, 's> {
+    fn next_field(&mut self) -> Option<&'t str> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        match self.rest.split_once(self.separator) {
+            Some((field, rest)) => {
+                self.rest = rest;
+                Some(field)
+            }
+            None => Some(std::mem::take(&mut self.rest)),
+        }
+    }
+}
+
+fn first_field(line: &str, separator: char) -> &str {
+    let separator = separator.to_string();
+    let mut fields = Fields { rest: line, separator: &separator };
+    fields.next_field().unwrap_or("")
+}
+
+fn main() {
+    println!("{}", first_field("ada,36,london", ','));
+}
+"#;
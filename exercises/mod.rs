@@ -0,0 +1,89 @@
+// The exercise registry: packs of small programs that don't compile (or
+// compile but misbehave), for learners to fix. `exercise start` copies one into practice/, and
+// `exercise check` builds the learner's version together with tests they
+// never see, so a fix that compiles but changes the behaviour doesn't pass.
+
+use crate::aliases;
+use crate::packs;
+
+mod borrow_checker;
+mod lifetimes;
+mod smart_pointers;
+
+// One kata: a broken starter program, the tests a fix has to pass, and a way out
+pub struct Exercise {
+    // Short name, unique within its pack (e.g. "use_after_move")
+    pub name: &'static str,
+    pub title: &'static str,
+    // 1 (warm-up) to 3 (needs a change of approach, not just a tweak)
+    pub level: u8,
+    // What the learner should achieve, shown when they start the exercise
+    pub task: &'static str,
+    // The error rustc reports for the starter code, e.g. "E0382", or None
+    // when the starter compiles and it's the hidden tests that fail
+    pub error: Option<&'static str>,
+    // The program the learner starts from
+    pub starter: &'static str,
+    // #[test] functions that call into the learner's code. They're appended
+    // at check time, so they only rely on the signatures `task` promises
+    pub tests: &'static str,
+    // Revealed one at a time, from a nudge to nearly the answer
+    pub hints: &'static [&'static str],
+    // A fix that passes the tests, with comments on why it works
+    pub solution: &'static str,
+}
+
+// A graded set of exercises on one topic
+pub struct Pack {
+    // Short name used as the prefix of exercise ids (e.g. "borrowck")
+    pub name: &'static str,
+    pub title: &'static str,
+    pub exercises: &'static [Exercise],
+}
+
+const INSTRUMENT: &str = include_str!("../instrument.rs");
+
+// Old exercise ids and where they went, e.g. ("borrowck_moved", "borrowck_use_after_move").
+// Progress files are migrated to the new ids when they're loaded
+pub const ALIASES: &[(&str, &str)] = &[];
+
+pub const PACKS: &[Pack] = &[borrow_checker::PACK, lifetimes::PACK, smart_pointers::PACK];
+
+impl Exercise {
+    // The id used on the command line, e.g. "borrowck_use_after_move"
+    pub fn id(&self, pack: &Pack) -> String {
+        format!("{}_{}", pack.name, self.name)
+    }
+
+    // The given code with the hidden tests appended, ready for harness::test
+    pub fn with_tests(&self, code: &str) -> String {
+        with_hidden_tests(code, self.tests)
+    }
+}
+
+// `code` with `tests` appended in a test module. The tests get the demo's own
+// instrument module, so they can count allocations (and leaks) with
+// `instrument::measure`
+pub fn with_hidden_tests(code: &str, tests: &str) -> String {
+    format!(
+        "{}\n#[cfg(test)]\nmod hidden_tests {{\n    use super::*;\n\n{}\n    mod instrument {{\n{}    }}\n}}\n",
+        code, tests, INSTRUMENT
+    )
+}
+
+// The built-in packs, then any installed ones (see packs.rs)
+pub fn all_packs() -> impl Iterator<Item = &'static Pack> {
+    PACKS.iter().chain(packs::installed().iter().copied())
+}
+
+// Every exercise in pack order, paired with its pack
+pub fn exercises() -> impl Iterator<Item = (&'static Pack, &'static Exercise)> {
+    all_packs()
+        .flat_map(|pack| pack.exercises.iter().map(move |exercise| (pack, exercise)))
+}
+
+// Looks up an exercise by its id, or an id it used to have
+pub fn find(id: &str) -> Option<(&'static Pack, &'static Exercise)> {
+    let id = aliases::resolve(ALIASES, id);
+    exercises().find(|(pack, exercise)| exercise.id(pack) == id)
+}
@@ -0,0 +1,791 @@
+// Katas on picking the right wrapper for a described situation: Box for
+// recursive and unsized types, Rc for shared ownership, Cell and RefCell
+// for changing something behind &self, Arc and Mutex once threads are
+// involved, and Weak for back-pointers that mustn't keep their target alive.
+// Two of the starters compile: RefCell's rules are checked at runtime, and a
+// leak isn't an error at all, so the tests have to catch those.
+
+use super::{Exercise, Pack};
+
+pub const PACK: Pack = Pack {
+    name: "pointers",
+    title: "SMART POINTER AND INTERIOR MUTABILITY KATAS",
+    exercises: &[
+        Exercise {
+            name: "recursive_list",
+            title: "A type that contains itself",
+            level: 1,
+            task: "List is a linked list: Cons holds a value and the rest of the list. from_slice and sum work on it.",
+            error: Some("E0072"),
+            starter: RECURSIVE_LIST_STARTER,
+            tests: RECURSIVE_LIST_TESTS,
+            hints: &[
+                "To know List's size, the compiler needs the size of the List inside it, and so on forever.",
+                "A pointer has a fixed size however long the list behind it is.",
+                "Store the rest as Box<List>, and wrap it with Box::new when building a Cons.",
+            ],
+            solution: RECURSIVE_LIST_SOLUTION,
+        },
+        Exercise {
+            name: "shape_list",
+            title: "A Vec of different types",
+            level: 1,
+            task: "shapes() returns a square and a circle together; total_area adds up their areas.",
+            error: Some("E0277"),
+            starter: SHAPE_LIST_STARTER,
+            tests: SHAPE_LIST_TESTS,
+            hints: &[
+                "A Vec stores its elements side by side, so they all need the same, known size.",
+                "Square and Circle have different sizes; dyn Shape has no size at all.",
+                "Store Box<dyn Shape>: every box is one pointer (plus vtable) wide.",
+            ],
+            solution: SHAPE_LIST_SOLUTION,
+        },
+        Exercise {
+            name: "lookup_counter",
+            title: "Counting reads through &self",
+            level: 1,
+            task: "Cache::get(&self) counts every lookup. It has to keep taking &self, so readers can share the cache.",
+            error: Some("E0594"),
+            starter: LOOKUP_COUNTER_STARTER,
+            tests: LOOKUP_COUNTER_TESTS,
+            hints: &[
+                "&self doesn't allow changing fields, and get can't take &mut self.",
+                "For a small Copy value, there's a wrapper that lets you replace it through &self.",
+                "Make lookups a Cell<u32> and use get() and set().",
+            ],
+            solution: LOOKUP_COUNTER_SOLUTION,
+        },
+        Exercise {
+            name: "shared_config",
+            title: "Two owners for one value",
+            level: 2,
+            task: "build_services() returns two Services that share one Config, without copying it.",
+            error: Some("E0382"),
+            starter: SHARED_CONFIG_STARTER,
+            tests: SHARED_CONFIG_TESTS,
+            hints: &[
+                "The config can only move into one Service.",
+                "Cloning it would compile, but then each service has its own copy.",
+                "Wrap it in Rc<Config> and give each service an Rc::clone, which shares it.",
+            ],
+            solution: SHARED_CONFIG_SOLUTION,
+        },
+        Exercise {
+            name: "shared_hits",
+            title: "Shared and mutable",
+            level: 2,
+            task: "Two Handlers share one Counter; handle(&self) adds a hit that every handler sees.",
+            error: Some("E0594"),
+            starter: SHARED_HITS_STARTER,
+            tests: SHARED_HITS_TESTS,
+            hints: &[
+                "Rc gives shared ownership, and shared means read-only.",
+                "To change something many owners can see, the change has to be checked at runtime.",
+                "Use Rc<RefCell<Counter>> and borrow_mut() it to add a hit.",
+            ],
+            solution: SHARED_HITS_SOLUTION,
+        },
+        Exercise {
+            name: "playlist_repeat",
+            title: "A RefCell borrowed twice",
+            level: 2,
+            task: "Playlist::repeat_all(&self) appends a copy of every song. It compiles, but panics when it runs.",
+            error: None,
+            starter: PLAYLIST_REPEAT_STARTER,
+            tests: PLAYLIST_REPEAT_TESTS,
+            hints: &[
+                "Run the tests and read the panic message: already borrowed.",
+                "The loop holds borrow() for its whole length, and borrow_mut() can't coexist with it.",
+                "Copy the songs out first, let that borrow end, then borrow_mut() to extend.",
+            ],
+            solution: PLAYLIST_REPEAT_SOLUTION,
+        },
+        Exercise {
+            name: "threaded_sum",
+            title: "Sharing data with threads",
+            level: 2,
+            task: "sum_in_threads(data) sums each half of data in its own thread, without copying the data.",
+            error: Some("E0277"),
+            starter: THREADED_SUM_STARTER,
+            tests: THREADED_SUM_TESTS,
+            hints: &[
+                "The error says Rc can't be sent between threads.",
+                "Rc's reference count isn't updated atomically, so two threads could corrupt it.",
+                "Use Arc, the thread-safe Rc; nothing else has to change.",
+            ],
+            solution: THREADED_SUM_SOLUTION,
+        },
+        Exercise {
+            name: "threaded_log",
+            title: "Mutating shared data from threads",
+            level: 2,
+            task: "collect_logs(workers) has each worker thread add a line to one shared log, then returns it sorted.",
+            error: Some("E0277"),
+            starter: THREADED_LOG_STARTER,
+            tests: THREADED_LOG_TESTS,
+            hints: &[
+                "Arc makes the sharing thread-safe, but RefCell's borrow flag isn't.",
+                "Threads need a lock that makes the others wait, not a flag that panics.",
+                "Use Arc<Mutex<Vec<String>>> and lock().unwrap() where the code calls borrow_mut().",
+            ],
+            solution: THREADED_LOG_SOLUTION,
+        },
+        Exercise {
+            name: "tree_parent",
+            title: "Break the cycle",
+            level: 3,
+            task: "Tree nodes know their parent. It works, but a dropped tree is never freed; fix the leak.",
+            error: None,
+            starter: TREE_PARENT_STARTER,
+            tests: TREE_PARENT_TESTS,
+            hints: &[
+                "A parent owns its children through Rc, and each child owns its parent the same way.",
+                "Counts in a cycle never reach zero, so nothing in it is ever dropped.",
+                "Make parent a RefCell<Weak<Node>>: Rc::downgrade to set it, upgrade() to read it.",
+            ],
+            solution: TREE_PARENT_SOLUTION,
+        },
+    ],
+};
+
+const RECURSIVE_LIST_STARTER: &str = r#"use List::{Cons, Nil};
+
+enum List {
+    Cons(i32, List),
+    Nil,
+}
+
+fn from_slice(values: &[i32]) -> List {
+    match values.split_first() {
+        Some((first, rest)) => Cons(*first, from_slice(rest)),
+        None => Nil,
+    }
+}
+
+fn sum(list: &List) -> i32 {
+    match list {
+        Cons(value, rest) => value + sum(rest),
+        Nil => 0,
+    }
+}
+
+fn main() {
+    println!("{}", sum(&from_slice(&[1, 2, 3])));
+}
+"#;
+
+const RECURSIVE_LIST_TESTS: &str = r#"    #[test]
+    fn builds_and_sums() {
+        assert_eq!(sum(&from_slice(&[1, 2, 3])), 6);
+        assert_eq!(sum(&from_slice(&[])), 0);
+    }
+"#;
+
+const RECURSIVE_LIST_SOLUTION: &str = r#"use List::{Cons, Nil};
+
+// The Box is a pointer, so a Cons has the same size however long the list is
+enum List {
+    Cons(i32, Box<List>),
+    Nil,
+}
+
+fn from_slice(values: &[i32]) -> List {
+    match values.split_first() {
+        Some((first, rest)) => Cons(*first, Box::new(from_slice(rest))),
+        None => Nil,
+    }
+}
+
+fn sum(list: &List) -> i32 {
+    match list {
+        Cons(value, rest) => value + sum(rest),
+        Nil => 0,
+    }
+}
+
+fn main() {
+    println!("{}", sum(&from_slice(&[1, 2, 3])));
+}
+"#;
+
+const SHAPE_LIST_STARTER: &str = r#"use std::f64::consts::PI;
+
+trait Shape {
+    fn area(&self) -> f64;
+}
+
+struct Square(f64);
+struct Circle(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        PI * self.0 * self.0
+    }
+}
+
+fn shapes() -> Vec<dyn Shape> {
+    vec![Square(2.0), Circle(1.0)]
+}
+
+fn total_area(shapes: &[dyn Shape]) -> f64 {
+    shapes.iter().map(|shape| shape.area()).sum()
+}
+
+fn main() {
+    println!("{:.2}", total_area(&shapes()));
+}
+"#;
+
+const SHAPE_LIST_TESTS: &str = r#"    #[test]
+    fn adds_up_both_shapes() {
+        let total = total_area(&shapes());
+        assert!((total - (4.0 + std::f64::consts::PI)).abs() < 1e-9, "total was {}", total);
+    }
+"#;
+
+const SHAPE_LIST_SOLUTION: &str = r#"use std::f64::consts::PI;
+
+trait Shape {
+    fn area(&self) -> f64;
+}
+
+struct Square(f64);
+struct Circle(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        PI * self.0 * self.0
+    }
+}
+
+// Each shape lives in its own box; the Vec only holds the (equally sized) boxes
+fn shapes() -> Vec<Box<dyn Shape>> {
+    vec![Box::new(Square(2.0)), Box::new(Circle(1.0))]
+}
+
+fn total_area(shapes: &[Box<dyn Shape>]) -> f64 {
+    shapes.iter().map(|shape| shape.area()).sum()
+}
+
+fn main() {
+    println!("{:.2}", total_area(&shapes()));
+}
+"#;
+
+const LOOKUP_COUNTER_STARTER: &str = r#"use std::collections::HashMap;
+
+struct Cache {
+    values: HashMap<String, u32>,
+    lookups: u32,
+}
+
+impl Cache {
+    fn new() -> Cache {
+        Cache { values: HashMap::new(), lookups: 0 }
+    }
+
+    fn insert(&mut self, key: &str, value: u32) {
+        self.values.insert(key.to_string(), value);
+    }
+
+    fn get(&self, key: &str) -> Option<u32> {
+        self.lookups += 1;
+        self.values.get(key).copied()
+    }
+
+    fn lookups(&self) -> u32 {
+        self.lookups
+    }
+}
+
+fn main() {
+    let mut cache = Cache::new();
+    cache.insert("answer", 42);
+    println!("{:?} after {} lookups", cache.get("answer"), cache.lookups());
+}
+"#;
+
+const LOOKUP_COUNTER_TESTS: &str = r#"    #[test]
+    fn counts_lookups_through_shared_references() {
+        let mut cache = Cache::new();
+        cache.insert("answer", 42);
+        let (reader, other_reader) = (&cache, &cache);
+        assert_eq!(reader.get("answer"), Some(42));
+        assert_eq!(other_reader.get("question"), None);
+        assert_eq!(cache.lookups(), 2);
+    }
+"#;
+
+const LOOKUP_COUNTER_SOLUTION: &str = r#"use std::cell::Cell;
+use std::collections::HashMap;
+
+struct Cache {
+    values: HashMap<String, u32>,
+    // Cell lets get() replace the count through &self; u32 is Copy, so no borrows are handed out
+    lookups: Cell<u32>,
+}
+
+impl Cache {
+    fn new() -> Cache {
+        Cache { values: HashMap::new(), lookups: Cell::new(0) }
+    }
+
+    fn insert(&mut self, key: &str, value: u32) {
+        self.values.insert(key.to_string(), value);
+    }
+
+    fn get(&self, key: &str) -> Option<u32> {
+        self.lookups.set(self.lookups.get() + 1);
+        self.values.get(key).copied()
+    }
+
+    fn lookups(&self) -> u32 {
+        self.lookups.get()
+    }
+}
+
+fn main() {
+    let mut cache = Cache::new();
+    cache.insert("answer", 42);
+    println!("{:?} after {} lookups", cache.get("answer"), cache.lookups());
+}
+"#;
+
+const SHARED_CONFIG_STARTER: &str = r#"struct Config {
+    endpoint: String,
+    retries: u32,
+}
+
+struct Service {
+    name: String,
+    config: Config,
+}
+
+fn build_services() -> (Service, Service) {
+    let config = Config { endpoint: String::from("https://example.com"), retries: 3 };
+    let api = Service { name: String::from("api"), config };
+    let worker = Service { name: String::from("worker"), config };
+    (api, worker)
+}
+
+fn main() {
+    let (api, worker) = build_services();
+    println!("{} and {} both use {}", api.name, worker.name, api.config.endpoint);
+}
+"#;
+
+const SHARED_CONFIG_TESTS: &str = r#"    #[test]
+    fn services_share_one_config() {
+        let (api, worker) = build_services();
+        assert_eq!(api.config.retries, 3);
+        assert!(std::rc::Rc::ptr_eq(&api.config, &worker.config), "each service should point at the same Config");
+    }
+"#;
+
+const SHARED_CONFIG_SOLUTION: &str = r#"use std::rc::Rc;
+
+struct Config {
+    endpoint: String,
+    retries: u32,
+}
+
+struct Service {
+    name: String,
+    // Every service owns a share of the config; it's freed when the last one goes
+    config: Rc<Config>,
+}
+
+fn build_services() -> (Service, Service) {
+    let config = Rc::new(Config { endpoint: String::from("https://example.com"), retries: 3 });
+    let api = Service { name: String::from("api"), config: Rc::clone(&config) };
+    let worker = Service { name: String::from("worker"), config };
+    (api, worker)
+}
+
+fn main() {
+    let (api, worker) = build_services();
+    println!("{} and {} both use {}", api.name, worker.name, api.config.endpoint);
+}
+"#;
+
+const SHARED_HITS_STARTER: &str = r#"use std::rc::Rc;
+
+struct Counter {
+    hits: u32,
+}
+
+struct Handler {
+    route: String,
+    counter: Rc<Counter>,
+}
+
+impl Handler {
+    fn handle(&self) -> String {
+        self.counter.hits += 1;
+        format!("handled {}", self.route)
+    }
+}
+
+fn handlers() -> (Handler, Handler) {
+    let counter = Rc::new(Counter { hits: 0 });
+    let home = Handler { route: String::from("/"), counter: Rc::clone(&counter) };
+    let about = Handler { route: String::from("/about"), counter };
+    (home, about)
+}
+
+fn total_hits(handler: &Handler) -> u32 {
+    handler.counter.hits
+}
+
+fn main() {
+    let (home, about) = handlers();
+    println!("{}", home.handle());
+    println!("{}", about.handle());
+    println!("{} hits", total_hits(&home));
+}
+"#;
+
+const SHARED_HITS_TESTS: &str = r#"    #[test]
+    fn every_handler_sees_every_hit() {
+        let (home, about) = handlers();
+        assert_eq!(home.handle(), "handled /");
+        about.handle();
+        about.handle();
+        assert_eq!(total_hits(&home), 3);
+        assert_eq!(total_hits(&about), 3);
+    }
+"#;
+
+const SHARED_HITS_SOLUTION: &str = r#"use std::cell::RefCell;
+use std::rc::Rc;
+
+struct Counter {
+    hits: u32,
+}
+
+struct Handler {
+    route: String,
+    // Rc shares the counter, RefCell lets any of the owners change it
+    counter: Rc<RefCell<Counter>>,
+}
+
+impl Handler {
+    fn handle(&self) -> String {
+        self.counter.borrow_mut().hits += 1;
+        format!("handled {}", self.route)
+    }
+}
+
+fn handlers() -> (Handler, Handler) {
+    let counter = Rc::new(RefCell::new(Counter { hits: 0 }));
+    let home = Handler { route: String::from("/"), counter: Rc::clone(&counter) };
+    let about = Handler { route: String::from("/about"), counter };
+    (home, about)
+}
+
+fn total_hits(handler: &Handler) -> u32 {
+    handler.counter.borrow().hits
+}
+
+fn main() {
+    let (home, about) = handlers();
+    println!("{}", home.handle());
+    println!("{}", about.handle());
+    println!("{} hits", total_hits(&home));
+}
+"#;
+
+const PLAYLIST_REPEAT_STARTER: &str = r#"use std::cell::RefCell;
+
+struct Playlist {
+    songs: RefCell<Vec<String>>,
+}
+
+impl Playlist {
+    fn add(&self, song: &str) {
+        self.songs.borrow_mut().push(song.to_string());
+    }
+
+    // Appends a copy of every song, so the playlist plays through twice
+    fn repeat_all(&self) {
+        for song in self.songs.borrow().iter() {
+            self.songs.borrow_mut().push(song.clone());
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.songs.borrow().len()
+    }
+}
+
+fn main() {
+    let playlist = Playlist { songs: RefCell::new(Vec::new()) };
+    playlist.add("Clair de Lune");
+    playlist.add("Gymnopedie No. 1");
+    playlist.repeat_all();
+    println!("{} songs", playlist.len());
+}
+"#;
+
+const PLAYLIST_REPEAT_TESTS: &str = r#"    #[test]
+    fn repeats_every_song_once() {
+        let playlist = Playlist { songs: RefCell::new(Vec::new()) };
+        playlist.add("a");
+        playlist.add("b");
+        playlist.repeat_all();
+        assert_eq!(playlist.len(), 4);
+        assert_eq!(*playlist.songs.borrow(), ["a", "b", "a", "b"]);
+    }
+"#;
+
+const PLAYLIST_REPEAT_SOLUTION: &str = r#"use std::cell::RefCell;
+
+struct Playlist {
+    songs: RefCell<Vec<String>>,
+}
+
+impl Playlist {
+    fn add(&self, song: &str) {
+        self.songs.borrow_mut().push(song.to_string());
+    }
+
+    // Appends a copy of every song, so the playlist plays through twice
+    fn repeat_all(&self) {
+        // The shared borrow ends with this statement, before borrow_mut() starts
+        let copies = self.songs.borrow().clone();
+        self.songs.borrow_mut().extend(copies);
+    }
+
+    fn len(&self) -> usize {
+        self.songs.borrow().len()
+    }
+}
+
+fn main() {
+    let playlist = Playlist { songs: RefCell::new(Vec::new()) };
+    playlist.add("Clair de Lune");
+    playlist.add("Gymnopedie No. 1");
+    playlist.repeat_all();
+    println!("{} songs", playlist.len());
+}
+"#;
+
+const THREADED_SUM_STARTER: &str = r#"use std::rc::Rc;
+use std::thread;
+
+fn sum_in_threads(data: Vec<u64>) -> u64 {
+    let data = Rc::new(data);
+    let mid = data.len() / 2;
+
+    let left = {
+        let data = Rc::clone(&data);
+        thread::spawn(move || data[..mid].iter().sum::<u64>())
+    };
+    let right = {
+        let data = Rc::clone(&data);
+        thread::spawn(move || data[mid..].iter().sum::<u64>())
+    };
+    left.join().unwrap() + right.join().unwrap()
+}
+
+fn main() {
+    println!("{}", sum_in_threads((1..=100).collect()));
+}
+"#;
+
+const THREADED_SUM_TESTS: &str = r#"    #[test]
+    fn sums_both_halves() {
+        assert_eq!(sum_in_threads((1..=100).collect()), 5050);
+        assert_eq!(sum_in_threads(vec![7]), 7);
+    }
+"#;
+
+const THREADED_SUM_SOLUTION: &str = r#"use std::sync::Arc;
+use std::thread;
+
+fn sum_in_threads(data: Vec<u64>) -> u64 {
+    // Arc updates its count atomically, so clones can move to other threads
+    let data = Arc::new(data);
+    let mid = data.len() / 2;
+
+    let left = {
+        let data = Arc::clone(&data);
+        thread::spawn(move || data[..mid].iter().sum::<u64>())
+    };
+    let right = {
+        let data = Arc::clone(&data);
+        thread::spawn(move || data[mid..].iter().sum::<u64>())
+    };
+    left.join().unwrap() + right.join().unwrap()
+}
+
+fn main() {
+    println!("{}", sum_in_threads((1..=100).collect()));
+}
+"#;
+
+const THREADED_LOG_STARTER: &str = r#"use std::cell::RefCell;
+use std::sync::Arc;
+use std::thread;
+
+fn collect_logs(workers: usize) -> Vec<String> {
+    let log = Arc::new(RefCell::new(Vec::new()));
+    let mut handles = Vec::new();
+    for id in 0..workers {
+        let log = Arc::clone(&log);
+        handles.push(thread::spawn(move || {
+            log.borrow_mut().push(format!("worker {} done", id));
+        }));
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let mut lines = log.borrow().clone();
+    lines.sort();
+    lines
+}
+
+fn main() {
+    println!("{:?}", collect_logs(3));
+}
+"#;
+
+const THREADED_LOG_TESTS: &str = r#"    #[test]
+    fn every_worker_logs() {
+        assert_eq!(collect_logs(3), ["worker 0 done", "worker 1 done", "worker 2 done"]);
+    }
+"#;
+
+const THREADED_LOG_SOLUTION: &str = r#"use std::sync::{Arc, Mutex};
+use std::thread;
+
+fn collect_logs(workers: usize) -> Vec<String> {
+    // A Mutex makes other threads wait for the lock instead of panicking
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let mut handles = Vec::new();
+    for id in 0..workers {
+        let log = Arc::clone(&log);
+        handles.push(thread::spawn(move || {
+            log.lock().unwrap().push(format!("worker {} done", id));
+        }));
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let mut lines = log.lock().unwrap().clone();
+    lines.sort();
+    lines
+}
+
+fn main() {
+    println!("{:?}", collect_logs(3));
+}
+"#;
+
+const TREE_PARENT_STARTER: &str = r#"use std::cell::RefCell;
+use std::rc::Rc;
+
+struct Node {
+    name: String,
+    parent: RefCell<Option<Rc<Node>>>,
+    children: RefCell<Vec<Rc<Node>>>,
+}
+
+fn node(name: &str) -> Rc<Node> {
+    Rc::new(Node { name: name.to_string(), parent: RefCell::new(None), children: RefCell::new(Vec::new()) })
+}
+
+fn add_child(parent: &Rc<Node>, child: Rc<Node>) {
+    *child.parent.borrow_mut() = Some(Rc::clone(parent));
+    parent.children.borrow_mut().push(child);
+}
+
+fn parent_name(node: &Node) -> Option<String> {
+    node.parent.borrow().as_ref().map(|parent| parent.name.clone())
+}
+
+fn build_tree() -> Rc<Node> {
+    let root = node("root");
+    add_child(&root, node("left"));
+    add_child(&root, node("right"));
+    root
+}
+
+fn main() {
+    let root = build_tree();
+    let left = Rc::clone(&root.children.borrow()[0]);
+    println!("{}'s parent is {:?}", left.name, parent_name(&left));
+}
+"#;
+
+const TREE_PARENT_TESTS: &str = r#"    #[test]
+    fn children_know_their_parent() {
+        let root = build_tree();
+        let left = Rc::clone(&root.children.borrow()[0]);
+        assert_eq!(parent_name(&left).as_deref(), Some("root"));
+        assert_eq!(parent_name(&root), None);
+    }
+
+    #[test]
+    fn dropping_the_tree_frees_it() {
+        let (_, allocations) = instrument::measure(|| drop(build_tree()));
+        assert_eq!(
+            allocations.unfreed, 0,
+            "{} allocations were never freed: the tree is keeping itself alive",
+            allocations.unfreed
+        );
+    }
+"#;
+
+const TREE_PARENT_SOLUTION: &str = r#"use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+struct Node {
+    name: String,
+    // A Weak parent doesn't count as an owner, so children don't keep it alive
+    parent: RefCell<Weak<Node>>,
+    children: RefCell<Vec<Rc<Node>>>,
+}
+
+fn node(name: &str) -> Rc<Node> {
+    Rc::new(Node { name: name.to_string(), parent: RefCell::new(Weak::new()), children: RefCell::new(Vec::new()) })
+}
+
+fn add_child(parent: &Rc<Node>, child: Rc<Node>) {
+    *child.parent.borrow_mut() = Rc::downgrade(parent);
+    parent.children.borrow_mut().push(child);
+}
+
+// upgrade() gives None once the parent is gone, instead of a dangling pointer
+fn parent_name(node: &Node) -> Option<String> {
+    node.parent.borrow().upgrade().map(|parent| parent.name.clone())
+}
+
+fn build_tree() -> Rc<Node> {
+    let root = node("root");
+    add_child(&root, node("left"));
+    add_child(&root, node("right"));
+    root
+}
+
+fn main() {
+    let root = build_tree();
+    let left = Rc::clone(&root.children.borrow()[0]);
+    println!("{}'s parent is {:?}", left.name, parent_name(&left));
+}
+"#;
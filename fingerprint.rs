@@ -0,0 +1,12 @@
+// Stable fingerprints of text, for noticing that something changed since it
+// was last checked. FNV-1a is tiny and, unlike std's DefaultHasher, gives the
+// same answer on every Rust version, so fingerprints can be stored on disk.
+
+const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+// The fingerprint as 16 hex digits, e.g. "af63bd4c8601b7df"
+pub fn of(text: &str) -> String {
+    let hash = text.bytes().fold(OFFSET, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(PRIME));
+    format!("{:016x}", hash)
+}
@@ -0,0 +1,147 @@
+// Compiles example code with the installed rustc, so the claims the lessons
+// make ("this line doesn't compile, it's E0382") can be checked against the
+// real compiler.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::confine;
+use crate::lessons::{BrokenLine, Example};
+use crate::toolchain;
+
+// What rustc made of a piece of code
+pub struct Outcome {
+    // Error codes in the order rustc reported them, e.g. ["E0382"]
+    pub error_codes: Vec<String>,
+    // Whether the code compiled without errors
+    pub success: bool,
+    // rustc's full diagnostic output
+    pub diagnostics: String,
+}
+
+// Checks that the installed toolchain can compile the example's code
+pub fn ready_for(example: &Example) -> Result<(), String> {
+    let installed = toolchain::require("compiling examples")?;
+    match installed
+        .check(&[toolchain::EDITION_2021])
+        .or_else(|| installed.check(example.requires))
+    {
+        Some(reason) => Err(reason),
+        None => Ok(()),
+    }
+}
+
+// Type-checks a complete program without producing a binary
+pub fn compile(code: &str) -> Result<Outcome, String> {
+    toolchain::require("compiling code")?;
+    let dir = scratch_dir()?;
+    let result = write_source(&dir, code).and_then(|source| {
+        // --emit=metadata stops after borrow checking, which is all we need
+        let mut rustc = Command::new("rustc");
+        rustc
+            .args(["--edition", "2021", "--crate-type", "bin", "--emit=metadata", "-A", "warnings", "--out-dir"])
+            .arg(&dir)
+            .arg(&source);
+        rustc_outcome(&mut rustc, &dir)
+    });
+    let _ = fs::remove_dir_all(&dir);
+    result
+}
+
+// What happened when a program was built with its tests and the tests were run
+pub struct TestRun {
+    // The build; the tests only ran if it succeeded
+    pub build: Outcome,
+    // Whether the tests ran and every one of them passed
+    pub passed: bool,
+    // What the test binary printed
+    pub output: String,
+    // Why the test binary was killed (see confine.rs), if it was
+    pub stopped: Option<String>,
+}
+
+// Builds a program as a test binary and runs its #[test] functions, within
+// confine::TESTS. Anything the tests write to the working directory or
+// TMPDIR is removed afterwards
+pub fn test(code: &str) -> Result<TestRun, String> {
+    toolchain::require("running tests")?;
+    let dir = scratch_dir()?;
+    let result = write_source(&dir, code).and_then(|source| build_and_run_tests(&source, &dir));
+    let _ = fs::remove_dir_all(&dir);
+    result
+}
+
+fn build_and_run_tests(source: &Path, dir: &Path) -> Result<TestRun, String> {
+    let binary = dir.join("tests");
+    let mut rustc = Command::new("rustc");
+    rustc.args(["--edition", "2021", "--test", "-A", "warnings", "-o"]).arg(&binary).arg(source);
+    let build = rustc_outcome(&mut rustc, dir)?;
+    if !build.success {
+        return Ok(TestRun { build, passed: false, output: String::new(), stopped: None });
+    }
+
+    // One test at a time, so allocation counts from instrument::measure are
+    // exact, and without capturing, so what the tests print is held to the
+    // output limit instead of piling up in the test binary's memory
+    let mut tests = Command::new(&binary);
+    tests.args(["--test-threads=1", "--nocapture"]);
+    let run = confine::run(&mut tests, dir, &confine::TESTS)?;
+    Ok(TestRun {
+        build,
+        passed: run.success,
+        output: run.stdout,
+        stopped: run.stopped,
+    })
+}
+
+// A fresh directory for one compile, unique within this run
+fn scratch_dir() -> Result<PathBuf, String> {
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+    let dir = env::temp_dir().join(format!(
+        "ownership-demo-{}-{}",
+        process::id(),
+        NEXT_ID.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::create_dir_all(&dir).map_err(|e| format!("could not create {}: {}", dir.display(), e))?;
+    Ok(dir)
+}
+
+fn write_source(dir: &Path, code: &str) -> Result<PathBuf, String> {
+    let source = dir.join("main.rs");
+    fs::write(&source, code).map_err(|e| format!("could not write {}: {}", source.display(), e))?;
+    Ok(source)
+}
+
+fn rustc_outcome(rustc: &mut Command, dir: &Path) -> Result<Outcome, String> {
+    let run = confine::run(rustc, dir, &confine::COMPILE)?;
+    if let Some(reason) = run.stopped {
+        return Err(format!("rustc was stopped because {}", reason));
+    }
+    // Paths like /tmp/ownership-demo-123-4/main.rs say nothing to a learner
+    let diagnostics = run.stderr.replace(&format!("{}{}", dir.display(), std::path::MAIN_SEPARATOR), "");
+    Ok(Outcome {
+        error_codes: error_codes(&diagnostics),
+        success: run.success,
+        diagnostics,
+    })
+}
+
+// The example's code with one of its commented-out lines switched back on
+pub fn with_line_enabled(example: &Example, broken: &BrokenLine) -> Option<String> {
+    let commented = format!("// {}", broken.line);
+    example.code.contains(&commented).then(|| example.code.replacen(&commented, broken.line, 1))
+}
+
+// Picks the codes out of headers like "error[E0382]: borrow of moved value"
+fn error_codes(diagnostics: &str) -> Vec<String> {
+    diagnostics
+        .lines()
+        .filter_map(|line| line.strip_prefix("error["))
+        .filter_map(|rest| rest.split_once(']'))
+        .map(|(code, _)| code.to_string())
+        .collect()
+}
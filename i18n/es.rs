@@ -0,0 +1,147 @@
+// Spanish. So far this covers the exercise UI and the smart pointer pack;
+// everything else is shown in English.
+
+use super::Catalog;
+
+pub const CATALOG: Catalog = Catalog {
+    lang: "es",
+    entries: &[
+        ("start.compile_error", "Esto todavía no compila (rustc informa {error})"),
+        ("start.test_failure", "Esto compila, pero todavía no pasa las pruebas"),
+        ("start.fix_it", "Corrígelo y luego comprueba tu solución con"),
+        ("start.stuck", "¿Atascado? `exercise hint {id}` revela una pista cada vez."),
+        ("pointers.title", "KATAS DE PUNTEROS INTELIGENTES Y MUTABILIDAD INTERIOR"),
+        ("pointers_recursive_list.title", "Un tipo que se contiene a sí mismo"),
+        (
+            "pointers_recursive_list.task",
+            "List es una lista enlazada: Cons guarda un valor y el resto de la lista. from_slice y sum trabajan con ella.",
+        ),
+        (
+            "pointers_recursive_list.hint1",
+            "Para conocer el tamaño de List, el compilador necesita el tamaño de la List que contiene, y así sin fin.",
+        ),
+        ("pointers_recursive_list.hint2", "Un puntero tiene un tamaño fijo, por larga que sea la lista detrás de él."),
+        (
+            "pointers_recursive_list.hint3",
+            "Guarda el resto como Box<List> y envuélvelo con Box::new al construir un Cons.",
+        ),
+        ("pointers_shape_list.title", "Un Vec de tipos distintos"),
+        (
+            "pointers_shape_list.task",
+            "shapes() devuelve un cuadrado y un círculo juntos; total_area suma sus áreas.",
+        ),
+        (
+            "pointers_shape_list.hint1",
+            "Un Vec guarda sus elementos uno junto a otro, así que todos necesitan el mismo tamaño, conocido.",
+        ),
+        (
+            "pointers_shape_list.hint2",
+            "Square y Circle tienen tamaños distintos; dyn Shape no tiene tamaño en absoluto.",
+        ),
+        (
+            "pointers_shape_list.hint3",
+            "Guarda Box<dyn Shape>: cada caja ocupa un puntero (más la vtable).",
+        ),
+        ("pointers_lookup_counter.title", "Contar lecturas a través de &self"),
+        (
+            "pointers_lookup_counter.task",
+            "Cache::get(&self) cuenta cada consulta. Tiene que seguir tomando &self para que varios lectores compartan la caché.",
+        ),
+        ("pointers_lookup_counter.hint1", "&self no permite cambiar campos, y get no puede tomar &mut self."),
+        (
+            "pointers_lookup_counter.hint2",
+            "Para un valor Copy pequeño, hay un envoltorio que permite reemplazarlo a través de &self.",
+        ),
+        ("pointers_lookup_counter.hint3", "Haz que lookups sea un Cell<u32> y usa get() y set()."),
+        ("pointers_shared_config.title", "Dos dueños para un valor"),
+        (
+            "pointers_shared_config.task",
+            "build_services() devuelve dos Service que comparten una Config, sin copiarla.",
+        ),
+        ("pointers_shared_config.hint1", "La config solo puede moverse a un Service."),
+        (
+            "pointers_shared_config.hint2",
+            "Clonarla compilaría, pero entonces cada servicio tendría su propia copia.",
+        ),
+        (
+            "pointers_shared_config.hint3",
+            "Envuélvela en Rc<Config> y da a cada servicio un Rc::clone, que la comparte.",
+        ),
+        ("pointers_shared_hits.title", "Compartido y mutable"),
+        (
+            "pointers_shared_hits.task",
+            "Dos Handler comparten un Counter; handle(&self) suma una visita que ven todos los handlers.",
+        ),
+        (
+            "pointers_shared_hits.hint1",
+            "Rc da propiedad compartida, y compartido significa de solo lectura.",
+        ),
+        (
+            "pointers_shared_hits.hint2",
+            "Para cambiar algo que ven muchos dueños, el cambio tiene que comprobarse en tiempo de ejecución.",
+        ),
+        ("pointers_shared_hits.hint3", "Usa Rc<RefCell<Counter>> y toma borrow_mut() para sumar una visita."),
+        ("pointers_playlist_repeat.title", "Un RefCell prestado dos veces"),
+        (
+            "pointers_playlist_repeat.task",
+            "Playlist::repeat_all(&self) añade una copia de cada canción. Compila, pero entra en pánico al ejecutarse.",
+        ),
+        (
+            "pointers_playlist_repeat.hint1",
+            "Ejecuta las pruebas y lee el mensaje del pánico: already borrowed.",
+        ),
+        (
+            "pointers_playlist_repeat.hint2",
+            "El bucle mantiene borrow() durante todo su recorrido, y borrow_mut() no puede convivir con él.",
+        ),
+        (
+            "pointers_playlist_repeat.hint3",
+            "Copia primero las canciones, deja que ese préstamo termine y luego usa borrow_mut() para ampliar.",
+        ),
+        ("pointers_threaded_sum.title", "Compartir datos con hilos"),
+        (
+            "pointers_threaded_sum.task",
+            "sum_in_threads(data) suma cada mitad de data en su propio hilo, sin copiar los datos.",
+        ),
+        ("pointers_threaded_sum.hint1", "El error dice que Rc no puede enviarse entre hilos."),
+        (
+            "pointers_threaded_sum.hint2",
+            "El contador de referencias de Rc no se actualiza de forma atómica, así que dos hilos podrían corromperlo.",
+        ),
+        ("pointers_threaded_sum.hint3", "Usa Arc, el Rc seguro entre hilos; no hace falta cambiar nada más."),
+        ("pointers_threaded_log.title", "Modificar datos compartidos desde hilos"),
+        (
+            "pointers_threaded_log.task",
+            "collect_logs(workers) hace que cada hilo añada una línea a un registro compartido, y lo devuelve ordenado.",
+        ),
+        (
+            "pointers_threaded_log.hint1",
+            "Arc hace que compartir sea seguro entre hilos, pero el indicador de préstamo de RefCell no lo es.",
+        ),
+        (
+            "pointers_threaded_log.hint2",
+            "Los hilos necesitan un cerrojo que haga esperar a los demás, no un indicador que entra en pánico.",
+        ),
+        (
+            "pointers_threaded_log.hint3",
+            "Usa Arc<Mutex<Vec<String>>> y lock().unwrap() donde el código llama a borrow_mut().",
+        ),
+        ("pointers_tree_parent.title", "Rompe el ciclo"),
+        (
+            "pointers_tree_parent.task",
+            "Los nodos del árbol conocen a su padre. Funciona, pero un árbol descartado nunca se libera; arregla la fuga.",
+        ),
+        (
+            "pointers_tree_parent.hint1",
+            "Un padre es dueño de sus hijos mediante Rc, y cada hijo es dueño de su padre de la misma forma.",
+        ),
+        (
+            "pointers_tree_parent.hint2",
+            "Los contadores de un ciclo nunca llegan a cero, así que nada en él se libera jamás.",
+        ),
+        (
+            "pointers_tree_parent.hint3",
+            "Haz que parent sea un RefCell<Weak<Node>>: Rc::downgrade para asignarlo, upgrade() para leerlo.",
+        ),
+    ],
+};
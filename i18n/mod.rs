@@ -0,0 +1,125 @@
+// Translations of the exercise text learners read: pack and exercise titles,
+// tasks, hints, and the comments `exercise start` writes above the starter.
+// English lives next to the content it describes (exercises/*.rs and UI
+// below); a catalog maps keys to another language's text, and anything it
+// doesn't have falls back to English, so a partial translation is still
+// useful. `lint-content` reports what each catalog is missing.
+//
+// Keys name what they translate:
+//
+//   pointers.title                      a pack's title
+//   pointers_recursive_list.title       an exercise's title, task or hint
+//   pointers_recursive_list.hint1
+//   start.fix_it                        a UI string from UI below
+//
+// The language comes from $RUST_OWNERSHIP_DEMO_LANG, or else the usual
+// locale variables ("es_ES.UTF-8" means "es").
+
+mod es;
+
+use std::env;
+use std::sync::OnceLock;
+
+use crate::exercises;
+
+pub struct Catalog {
+    // ISO 639-1 code, as in $LANG
+    pub lang: &'static str,
+    pub entries: &'static [(&'static str, &'static str)],
+}
+
+pub const CATALOGS: &[&Catalog] = &[&es::CATALOG];
+
+// UI strings that are translated, with their English text. `{name}`
+// placeholders are filled in by `ui`, and a translation has to keep them all
+pub const UI: &[(&str, &str)] = &[
+    ("start.compile_error", "This doesn't compile yet (rustc reports {error})"),
+    ("start.test_failure", "This compiles, but doesn't pass the tests yet"),
+    ("start.fix_it", "Fix it, then check your fix with"),
+    ("start.stuck", "Stuck? `exercise hint {id}` reveals one hint at a time."),
+];
+
+// The learner's language, detected once
+pub fn lang() -> &'static str {
+    static LANG: OnceLock<String> = OnceLock::new();
+    LANG.get_or_init(detect)
+}
+
+fn detect() -> String {
+    let setting = ["RUST_OWNERSHIP_DEMO_LANG", "LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default();
+    // "es_ES.UTF-8" -> "es"; "C" and "POSIX" aren't languages
+    let lang: String = setting.chars().take_while(|c| c.is_ascii_alphabetic()).collect::<String>().to_lowercase();
+    if lang.len() == 2 { lang } else { "en".to_string() }
+}
+
+// The text for `key` in the learner's language, or `english`
+pub fn text(key: &str, english: &'static str) -> &'static str {
+    CATALOGS
+        .iter()
+        .filter(|catalog| catalog.lang == lang())
+        .flat_map(|catalog| catalog.entries)
+        .find(|(k, _)| *k == key)
+        .map_or(english, |(_, text)| text)
+}
+
+// A UI string in the learner's language, with its placeholders filled in
+pub fn ui(key: &str, values: &[(&str, &str)]) -> String {
+    let english = UI.iter().find(|(k, _)| *k == key).map_or("", |(_, text)| text);
+    values
+        .iter()
+        .fold(text(key, english).to_string(), |out, (name, value)| out.replace(&format!("{{{}}}", name), value))
+}
+
+// Every translatable key with its English text, in registry order
+pub fn english() -> Vec<(String, &'static str)> {
+    let mut all: Vec<(String, &'static str)> = UI.iter().map(|(key, text)| (key.to_string(), *text)).collect();
+    for pack in exercises::PACKS {
+        all.push((format!("{}.title", pack.name), pack.title));
+        for exercise in pack.exercises {
+            let id = exercise.id(pack);
+            all.push((format!("{}.title", id), exercise.title));
+            all.push((format!("{}.task", id), exercise.task));
+            for (i, hint) in exercise.hints.iter().enumerate() {
+                all.push((format!("{}.hint{}", id, i + 1), *hint));
+            }
+        }
+    }
+    all
+}
+
+// What's wrong with the catalogs: keys that don't translate anything and
+// translations that lost a placeholder. Missing keys aren't problems (they
+// fall back to English), so they're returned separately, per language
+pub fn check() -> (Vec<String>, Vec<(&'static str, usize)>) {
+    let english = english();
+    let mut problems = Vec::new();
+    let mut missing = Vec::new();
+    for catalog in CATALOGS {
+        for (key, text) in catalog.entries {
+            match english.iter().find(|(k, _)| k == key) {
+                None => problems.push(format!("i18n/{}.rs: '{}' doesn't translate anything", catalog.lang, key)),
+                Some((_, original)) => {
+                    for placeholder in placeholders(original) {
+                        if !text.contains(placeholder) {
+                            problems.push(format!("i18n/{}.rs: '{}' lost the {} placeholder", catalog.lang, key, placeholder));
+                        }
+                    }
+                }
+            }
+        }
+        let untranslated = english.iter().filter(|(key, _)| !catalog.entries.iter().any(|(k, _)| k == key)).count();
+        missing.push((catalog.lang, untranslated));
+    }
+    (problems, missing)
+}
+
+// The `{name}` placeholders in a UI string
+fn placeholders(text: &str) -> Vec<&str> {
+    text.match_indices('{')
+        .filter_map(|(start, _)| text[start..].find('}').map(|end| &text[start..start + end + 1]))
+        .collect()
+}
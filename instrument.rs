@@ -0,0 +1,114 @@
+// Instrumentation for lessons that talk about the cost of ownership choices.
+//
+// The demo installs a counting global allocator, so a lesson can wrap a piece
+// of code in `measure` and report exactly how many heap allocations it made
+// (and how many bytes it asked for) instead of just claiming it. It also
+// tracks how many allocations are still live, so code that should clean up
+// after itself (like breaking an Rc cycle) can be checked for leaks, and how
+// many bytes are live, so a lesson can show the most memory some code held at
+// once and not only how much it asked for in total. Every example is played
+// inside a measure too, for the memory footer printed after it (see
+// ExampleReport in lessons/mod.rs).
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static FREES: AtomicUsize = AtomicUsize::new(0);
+// Bytes allocated and not yet freed, and the highest that has reached since
+// the last measure started
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+// SAFETY: every call is forwarded unchanged to the system allocator; we only
+// update counters on the way through.
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        grow(layout.size());
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        FREES.fetch_add(1, Ordering::Relaxed);
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    // Growing a Vec or String counts as a new allocation: that's what it costs.
+    // The old block is freed by the move, so it counts as a free as well
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        FREES.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(new_size, Ordering::Relaxed);
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+        grow(new_size);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+fn grow(size: usize) {
+    let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(live, Ordering::Relaxed);
+}
+
+// Heap activity recorded while some code ran
+#[derive(Clone, Copy)]
+pub struct Allocations {
+    // Number of allocations, including reallocations when something grew
+    pub count: usize,
+    // Total bytes requested by those allocations
+    pub bytes: usize,
+    // How many more allocations were made than freed, i.e. what the code
+    // left behind on the heap. Only a leak if it has dropped everything
+    pub unfreed: usize,
+    // The most bytes the code had allocated at any one moment, beyond what
+    // was already live when it started
+    pub peak: usize,
+}
+
+// Runs `f` and reports the heap allocations it made. The counters are
+// process-wide, so measure single-threaded code for exact numbers. Measures
+// nest: one inside another reports its own peak, and the outer one still
+// sees the highest point reached anywhere within it.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Allocations) {
+    let live_before = LIVE.load(Ordering::Relaxed);
+    let outer_peak = PEAK.swap(live_before, Ordering::Relaxed);
+    let count_before = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes_before = BYTES.load(Ordering::Relaxed);
+    let frees_before = FREES.load(Ordering::Relaxed);
+
+    let result = f();
+
+    let count = ALLOCATIONS.load(Ordering::Relaxed) - count_before;
+    let frees = FREES.load(Ordering::Relaxed) - frees_before;
+    let peak = PEAK.fetch_max(outer_peak, Ordering::Relaxed);
+    let allocations = Allocations {
+        count,
+        bytes: BYTES.load(Ordering::Relaxed) - bytes_before,
+        unfreed: count.saturating_sub(frees),
+        peak: peak.saturating_sub(live_before),
+    };
+    (result, allocations)
+}
+
+// Bytes allocated and not yet freed, process-wide
+pub fn live_bytes() -> usize {
+    LIVE.load(Ordering::Relaxed)
+}
+
+// A byte count the way people say it, e.g. "512 B", "3.2 KB", "15.8 MB"
+pub fn human_bytes(bytes: usize) -> String {
+    match bytes {
+        b if b >= 1 << 20 => format!("{:.1} MB", b as f64 / (1 << 20) as f64),
+        b if b >= 1 << 10 => format!("{:.1} KB", b as f64 / (1 << 10) as f64),
+        b => format!("{} B", b),
+    }
+}
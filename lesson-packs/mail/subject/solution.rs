@@ -0,0 +1,15 @@
+// Sends the message and returns how many bytes went out
+fn send(message: String) -> usize {
+    message.len()
+}
+
+fn subject_and_send(message: String) -> (String, usize) {
+    // An owned copy of the first line, so nothing borrows message any more
+    let subject = message.lines().next().unwrap_or("").to_string();
+    let sent = send(message);
+    (subject, sent)
+}
+
+fn main() {
+    println!("{:?}", subject_and_send(String::from("Lunch?\nNoon at the usual place")));
+}
@@ -0,0 +1,14 @@
+// Sends the message and returns how many bytes went out
+fn send(message: String) -> usize {
+    message.len()
+}
+
+fn subject_and_send(message: String) -> (String, usize) {
+    let subject = message.lines().next().unwrap_or("");
+    let sent = send(message);
+    (subject.to_string(), sent)
+}
+
+fn main() {
+    println!("{:?}", subject_and_send(String::from("Lunch?\nNoon at the usual place")));
+}
@@ -0,0 +1,11 @@
+    #[test]
+    fn keeps_the_first_line_and_sends_everything() {
+        let (subject, sent) = subject_and_send(String::from("Hi\nbody"));
+        assert_eq!(subject, "Hi");
+        assert_eq!(sent, 7);
+    }
+
+    #[test]
+    fn an_empty_message_has_an_empty_subject() {
+        assert_eq!(subject_and_send(String::new()), (String::new(), 0));
+    }
@@ -0,0 +1,1561 @@
+use std::borrow::{Borrow, Cow};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::ops::Deref;
+
+use super::{BrokenLine, Example, Section, Stop};
+use crate::instrument;
+use crate::narration::Narration;
+use crate::utils::calculate_length;
+
+pub const SECTION: Section = Section {
+    name: "api_design",
+    title: "DESIGNING APIS AROUND OWNERSHIP",
+    examples: &[
+        Example {
+            name: "split_words",
+            title: "Returning Vec<&str> vs Vec<String>",
+            run: split_words,
+            code: SPLIT_WORDS_CODE,
+            broken_lines: &[BrokenLine {
+                line: r#"println!("{:?}", words);"#,
+                error: "E0597",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let text",
+                    say: "main owns the text; both functions only borrow it",
+                },
+                Stop {
+                    line: "owned = split_words_owned",
+                    say: "Each word is copied into a String that owned now holds",
+                },
+                Stop {
+                    line: r#"println!("owned words outlive"#,
+                    say: "temporary is gone, but owned never pointed into it",
+                },
+                Stop {
+                    line: "words = split_words_borrowed",
+                    say: "These words are slices of text, so they borrow it",
+                },
+                Stop {
+                    line: "// println!",
+                    say: "text is dropped at the }, so using words after it is E0597",
+                },
+                Stop {
+                    line: "fn split_words_borrowed",
+                    say: "The signature says the slices live as long as 'a, the input's borrow",
+                },
+            ],
+        },
+        Example {
+            name: "borrowed_iterators",
+            title: "Returning iterators that borrow from &self",
+            run: borrowed_iterators,
+            code: BORROWED_ITERATORS_CODE,
+            broken_lines: &[BrokenLine {
+                line: "fn lengths(&self) -> impl Iterator<Item = usize> { self.words().map(str::len) }",
+                error: "E0700",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "fn words",
+                    say: "The iterator borrows self; + '_ says so in the return type",
+                },
+                Stop {
+                    line: "fn word_lengths",
+                    say: "Its items are plain usize, so only + '_ shows the borrow of self",
+                },
+                Stop {
+                    line: "// fn lengths",
+                    say: "Leaving + '_ out hides that borrow, which is E0700",
+                },
+                Stop {
+                    line: "for word in doc.words()",
+                    say: "Each loop borrows doc through the iterator, and the borrow ends with it",
+                },
+                Stop {
+                    line: "doc.text.push_str",
+                    say: "No iterator is alive any more, so doc can be changed",
+                },
+            ],
+        },
+        Example {
+            name: "conversions",
+            title: "From and Into consume their input",
+            run: conversions,
+            code: CONVERSIONS_CODE,
+            broken_lines: &[BrokenLine {
+                line: "let display = Username::from(name);",
+                error: "E0382",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "impl From<String> for Username",
+                    say: "From<String> takes the String and keeps its buffer: no copy",
+                },
+                Stop {
+                    line: "impl From<&str> for Username",
+                    say: "From<&str> only borrows, so it has to allocate a copy",
+                },
+                Stop {
+                    line: "let user = Username::from(name);",
+                    say: "name moves into user, buffer and all",
+                },
+                Stop {
+                    line: "// let display",
+                    say: "name was consumed, so converting it again is E0382",
+                },
+                Stop {
+                    line: "let borrowed",
+                    say: "A literal only lends its text, so this one copies it",
+                },
+                Stop {
+                    line: "let via_into",
+                    say: "into() is From seen from the other side, and it consumes too",
+                },
+                Stop {
+                    line: "let account",
+                    say: "user moves on into the Account, still without copying",
+                },
+            ],
+        },
+        Example {
+            name: "string_params",
+            title: "Taking &str (or AsRef<str>) instead of &String",
+            run: string_params,
+            code: STRING_PARAMS_CODE,
+            broken_lines: &[BrokenLine {
+                line: r#"let direct = length_of_string("hello");"#,
+                error: "E0308",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "fn length_of_string",
+                    say: "&String only accepts a String that already exists",
+                },
+                Stop {
+                    line: "fn length_of_str",
+                    say: "&str accepts literals, slices and &String alike",
+                },
+                Stop {
+                    line: "fn calculate_length",
+                    say: "impl AsRef<str> takes owned values too",
+                },
+                Stop {
+                    line: "// let direct",
+                    say: "A literal is a &str, not a &String, so this is E0308",
+                },
+                Stop {
+                    line: r#"println!("{}", length_of_string(&"hello""#,
+                    say: "The workaround allocates a String just to make the call",
+                },
+                Stop {
+                    line: r#"println!("{}", length_of_str(&text));"#,
+                    say: "&String coerces to &str, so nothing is lost",
+                },
+                Stop {
+                    line: r#"println!("{}", calculate_length(Box"#,
+                    say: "A Box<str> works as well, with no conversion at the call",
+                },
+            ],
+        },
+        Example {
+            name: "owned_borrowed_pair",
+            title: "Your own owned/borrowed pair with Borrow and ToOwned",
+            run: owned_borrowed_pair,
+            code: OWNED_BORROWED_PAIR_CODE,
+            broken_lines: &[BrokenLine {
+                line: r#"println!("{:?}", scores.get("ada"));"#,
+                error: "E0277",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "struct UserName(String);",
+                    say: "The owned half, like String or PathBuf",
+                },
+                Stop {
+                    line: "struct UserNameRef(str);",
+                    say: "The borrowed half: an unsized view of the same bytes, like str",
+                },
+                Stop {
+                    line: "impl Borrow<UserNameRef> for UserName",
+                    say: "Borrow lets a UserName be looked at as a &UserNameRef",
+                },
+                Stop {
+                    line: "impl ToOwned for UserNameRef",
+                    say: "ToOwned is the way back, from borrowed to owned",
+                },
+                Stop {
+                    line: r#"let key = UserNameRef::new("ada");"#,
+                    say: "The lookup key is a borrowed view, so nothing is allocated",
+                },
+                Stop {
+                    line: "// println!",
+                    say: "There's no Borrow<str>, so looking up by &str is E0277",
+                },
+                Stop {
+                    line: "let owned: UserName = name.into_owned();",
+                    say: "Cow uses ToOwned to make an owned UserName when asked",
+                },
+            ],
+        },
+        Example {
+            name: "serde_fields",
+            title: "Deserializing into borrowed vs owned fields with serde",
+            run: serde_fields,
+            code: SERDE_FIELDS_CODE,
+            broken_lines: &[BrokenLine {
+                line: r#"println!("{}", event.name);"#,
+                error: "E0597",
+            }],
+            requires: &[],
+            crates: &[r#"serde = { version = "1", features = ["derive"] }"#, r#"serde_json = "1""#],
+            stops: &[
+                Stop {
+                    line: "struct OwnedEvent",
+                    say: "String fields: every string is copied out of the input",
+                },
+                Stop {
+                    line: "struct BorrowedEvent",
+                    say: "&str fields point into the input, so the struct borrows it",
+                },
+                Stop {
+                    line: "let result: Result<BorrowedEvent",
+                    say: "An escape must be decoded into new text, which a &str can't hold",
+                },
+                Stop {
+                    line: "let event: BorrowedEvent = serde_json",
+                    say: "The Cow field owns the decoded text, and borrows otherwise",
+                },
+                Stop {
+                    line: "event = serde_json::from_str(&input)",
+                    say: "This event borrows input, which ends at the }",
+                },
+                Stop {
+                    line: "// println!",
+                    say: "Using it after input is gone is E0597",
+                },
+                Stop {
+                    line: "owned = serde_json::from_str(&input)",
+                    say: "The owned event copied its strings, so it outlives input",
+                },
+            ],
+        },
+        Example {
+            name: "utf8_conversions",
+            title: "Vec<u8> and String: conversions that give ownership back",
+            run: utf8_conversions,
+            code: UTF8_CONVERSIONS_CODE,
+            broken_lines: &[BrokenLine {
+                line: "let bytes: &mut Vec<u8> = text.as_mut_vec();",
+                error: "E0133",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let text = String::from_utf8(bytes)",
+                    say: "from_utf8 takes the Vec by value and reuses its buffer",
+                },
+                Stop {
+                    line: "Err(error) => {",
+                    say: "On failure the error owns the bytes instead",
+                },
+                Stop {
+                    line: "error.into_bytes()",
+                    say: "and hands them back, so nothing is lost",
+                },
+                Stop {
+                    line: "let borrowed: &[u8] = text.as_bytes();",
+                    say: "A read-only view of the bytes is always safe",
+                },
+                Stop {
+                    line: "// let bytes: &mut Vec<u8>",
+                    say: "Writing to the bytes could break UTF-8, so it's unsafe (E0133)",
+                },
+                Stop {
+                    line: "let bytes: Vec<u8> = text.into_bytes();",
+                    say: "into_bytes gives the buffer back as a Vec without copying",
+                },
+                Stop {
+                    line: r#"println!("{}", String::from_utf8_lossy"#,
+                    say: "from_utf8_lossy borrows, and allocates only to replace bytes",
+                },
+            ],
+        },
+        Example {
+            name: "cow_sanitize",
+            title: "Returning Cow<str>: allocating only when something changes",
+            run: cow_sanitize,
+            code: COW_SANITIZE_CODE,
+            broken_lines: &[BrokenLine {
+                line: "return Cow::Borrowed(&escaped);",
+                error: "E0515",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "fn sanitize(input: &str) -> Cow",
+                    say: "The result may borrow the input or own new text",
+                },
+                Stop {
+                    line: "return Cow::Borrowed(input);",
+                    say: "Clean input is passed straight back, with no allocation",
+                },
+                Stop {
+                    line: "let escaped",
+                    say: "Only input with markup pays for a new String",
+                },
+                Stop {
+                    line: "// return Cow::Borrowed(&escaped);",
+                    say: "escaped dies here, so borrowing it is E0515",
+                },
+                Stop {
+                    line: "Cow::Owned(escaped)",
+                    say: "so the new String is handed over as Cow::Owned",
+                },
+                Stop {
+                    line: "fn sanitize_always",
+                    say: "This version allocates for every comment",
+                },
+                Stop {
+                    line: "match sanitize(comment)",
+                    say: "Three of the four comments come back borrowed",
+                },
+            ],
+        },
+        Example {
+            name: "trait_receivers",
+            title: "Trait methods taking self, &self and &mut self, and an owned or borrowed Output",
+            run: trait_receivers,
+            code: TRAIT_RECEIVERS_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: "let text = self.publish();",
+                    error: "E0507",
+                },
+                BrokenLine {
+                    line: "impl Editable for Preview<'_> { fn append(&mut self, line: &str) { self.draft.body.push_str(line); } }",
+                    error: "E0596",
+                },
+                BrokenLine {
+                    line: r#"draft.append("Late change");"#,
+                    error: "E0502",
+                },
+                BrokenLine {
+                    line: r#"draft.append("Too late");"#,
+                    error: "E0382",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "type Output;",
+                    say: "Publish says what it gives back: owned for a Draft, borrowed for a Preview",
+                },
+                Stop {
+                    line: "fn publish(self)",
+                    say: "publish takes self by value, so it uses the value up",
+                },
+                Stop {
+                    line: "// let text = self.publish();",
+                    say: "A default method with &self can't give self away: E0507",
+                },
+                Stop {
+                    line: "fn publish(self) -> String {",
+                    say: "A Draft publishes by handing over its body, no copy",
+                },
+                Stop {
+                    line: "struct Preview<'a>",
+                    say: "A Preview only borrows a Draft",
+                },
+                Stop {
+                    line: "fn publish(self) -> &'a str {",
+                    say: "so what it publishes borrows the draft too",
+                },
+                Stop {
+                    line: "// impl Editable for Preview",
+                    say: "Through & the draft can't change, even with &mut self: E0596",
+                },
+                Stop {
+                    line: "let shown = preview.publish();",
+                    say: "shown borrows draft for as long as it's used",
+                },
+                Stop {
+                    line: r#"// draft.append("Late change");"#,
+                    say: "so changing draft before the println is E0502",
+                },
+                Stop {
+                    line: "let published = draft.publish();",
+                    say: "Publishing the Draft itself moves it",
+                },
+                Stop {
+                    line: r#"// draft.append("Too late");"#,
+                    say: "and a moved draft can't be used again: E0382",
+                },
+            ],
+        },
+        Example {
+            name: "reference_impls",
+            title: "Why &T implements the traits T does, and generics that won't auto-ref",
+            run: reference_impls,
+            code: REFERENCE_IMPLS_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: "let shown = show(title);",
+                    error: "E0382",
+                },
+                BrokenLine {
+                    line: "announce(&mut title);",
+                    error: "E0277",
+                },
+                BrokenLine {
+                    line: "announce(title.as_str());",
+                    error: "E0277",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "impl<T: Describe + ?Sized> Describe for &T",
+                    say: "A reference describes itself as what it points to, like std's Display",
+                },
+                Stop {
+                    line: "fn show<T: Display>(value: T)",
+                    say: "Generic over T, so T can be an owned value or a reference",
+                },
+                Stop {
+                    line: "// let shown = show(title);",
+                    say: "With T = String title would be moved away: E0382",
+                },
+                Stop {
+                    line: r#"println!("{}", show(&title));"#,
+                    say: "T = &String borrows title instead",
+                },
+                Stop {
+                    line: r#"println!("{}", announce(&title));"#,
+                    say: "This works because of the forwarding impl for &T",
+                },
+                Stop {
+                    line: "// announce(&mut title);",
+                    say: "Generics don't coerce &mut String to &String: E0277",
+                },
+                Stop {
+                    line: "// announce(title.as_str());",
+                    say: "and str itself has no Describe impl: E0277 again",
+                },
+                Stop {
+                    line: "let described",
+                    say: "iter() yields &String, which the forwarding impl covers",
+                },
+            ],
+        },
+    ],
+};
+
+fn split_words() {
+    let mut n = Narration::new();
+
+    let text = String::from("the quick brown fox jumps over the lazy dog");
+    n.fact("text", &text);
+    n.say("Splitting '{text}' into words, two ways:");
+
+    // The borrowed version hands out slices of text, so only the Vec itself is allocated
+    let (borrowed, borrowed_allocs) = instrument::measure(|| split_words_borrowed(&text));
+    n.fact("words", borrowed.len()).fact("borrowed_allocs", borrowed_allocs.count);
+    n.fact("borrowed_bytes", borrowed_allocs.bytes);
+    n.say("split_words_borrowed returned {words} slices: {borrowed_allocs} allocations, {borrowed_bytes} bytes");
+
+    // The owned version copies every word into its own String
+    let (owned, owned_allocs) = instrument::measure(|| split_words_owned(&text));
+    n.fact("owned_allocs", owned_allocs.count).fact("owned_bytes", owned_allocs.bytes);
+    n.say("split_words_owned returned {words} Strings: {owned_allocs} allocations, {owned_bytes} bytes");
+    println!("  Both Vecs grew a few times while collecting; the difference is one String per word");
+
+    n.address("text_start", text.as_ptr()).address("first_slice", borrowed[0].as_ptr());
+    n.address("first_string", owned[0].as_ptr());
+    n.say("The first slice points into text ({first_slice}, text starts at {text_start}),");
+    n.say("while the first String has its own copy at {first_string}");
+
+    println!("\n  The signature of the borrowed version spells out the connection:");
+    println!("    fn split_words_borrowed<'a>(text: &'a str) -> Vec<&'a str>");
+    println!("  The words can't outlive text, so this doesn't compile:");
+    println!("    let words;");
+    println!("    {{ let text = String::from(\"...\"); words = split_words_borrowed(&text); }}");
+    println!("    println!(\"{{:?}}\", words); // error[E0597]: `text` does not live long enough");
+
+    println!("\n  Choosing between them:");
+    println!("  - Return Vec<&str> when callers keep the input alive and just need a view of it");
+    println!("  - Return Vec<String> when the results must outlive the input, be stored in a");
+    println!("    long-lived struct, or be sent to another thread");
+    println!("  - Start borrowed: a caller can always turn &str into String, but not the reverse for free");
+}
+
+// This function returns slices that borrow from text, tied together by 'a
+#[allow(clippy::needless_lifetimes)] // written out on purpose, elision would hide 'a
+fn split_words_borrowed<'a>(text: &'a str) -> Vec<&'a str> {
+    text.split_whitespace().collect()
+}
+
+// This function copies every word into a String the caller will own
+fn split_words_owned(text: &str) -> Vec<String> {
+    text.split_whitespace().map(String::from).collect()
+}
+
+fn borrowed_iterators() {
+    let mut n = Narration::new();
+
+    let mut doc = Document { text: String::from("ownership makes memory safety a compile time property") };
+    n.fact("text", &doc.text);
+    n.say("doc.text = '{text}'");
+
+    // words() allocates nothing: the iterator walks doc.text in place
+    let (first, allocs) = instrument::measure(|| doc.words().next());
+    let first = first.unwrap_or_default();
+    n.fact("first", first).fact("allocs", allocs.count);
+    n.address("text_start", doc.text.as_ptr()).address("first_at", first.as_ptr());
+    n.say("doc.words().next() = '{first}' at {first_at}, inside doc.text at {text_start}: {allocs} allocations");
+
+    println!("\n  The signature is written with elided lifetimes:");
+    println!("    fn words(&self) -> impl Iterator<Item = &str> + '_");
+    println!("  and expands to one lifetime shared by self, the items and the iterator itself:");
+    println!("    fn words<'a>(&'a self) -> impl Iterator<Item = &'a str> + 'a");
+    println!("  Elision on methods always gives output references the lifetime of &self, so the");
+    println!("  &str items are tied to doc, and + '_ says the iterator holds that borrow too");
+
+    let lengths: Vec<usize> = doc.lengths().collect();
+    n.fact("lengths", format!("{:?}", lengths));
+    n.say("doc.lengths() = {lengths}");
+    println!("  Here the items are plain usize, so without + '_ nothing in the return type mentions");
+    println!("  the borrow of self. Under edition 2021 that signature is rejected:");
+    println!("    fn lengths(&self) -> impl Iterator<Item = usize>");
+    println!("    error[E0700]: hidden type captures lifetime that does not appear in bounds");
+    println!("  (edition 2024 captures every lifetime in scope, so + '_ becomes implicit there)");
+
+    // The borrow lasts as long as the iterator, so doc can't change while it's alive
+    let longest = doc.words().max_by_key(|word| word.len()).unwrap_or_default().to_string();
+    doc.text.push_str(" and more");
+    n.fact("longest", &longest).fact("count", doc.words().count());
+    n.say("Copied out the longest word '{longest}', then doc could change: now {count} words");
+}
+
+// Owns its text and hands out views of it
+struct Document {
+    text: String,
+}
+
+impl Document {
+    // This function returns an iterator that borrows self, so doc stays borrowed
+    // for as long as the caller keeps the iterator
+    fn words(&self) -> impl Iterator<Item = &str> + '_ {
+        self.text.split_whitespace()
+    }
+
+    // This function's items don't borrow anything, but the iterator still does
+    fn lengths(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words().map(str::len)
+    }
+}
+
+fn conversions() {
+    let mut n = Narration::new();
+
+    // From<String> takes the String by value, so it can keep the same buffer
+    let name = String::from("ferris");
+    n.address("buffer", name.as_ptr());
+    let (user, owned_allocs) = instrument::measure(|| Username::from(name));
+    n.fact("user", &user.0).fact("owned_allocs", owned_allocs.count).address("user_at", user.0.as_ptr());
+    n.say("Username::from(String) = '{user}': {owned_allocs} allocations, buffer {buffer} became {user_at}");
+    println!("  The String moved into the Username; name can't be used or converted again:");
+    println!("    let display = Username::from(name); // error[E0382]: use of moved value: `name`");
+
+    // From<&str> only borrows, so it has to copy the text into a new String
+    let literal = "corro";
+    let (user, borrowed_allocs) = instrument::measure(|| Username::from(literal));
+    n.fact("user", &user.0).fact("borrowed_allocs", borrowed_allocs.count);
+    n.say("Username::from(&str) = '{user}': {borrowed_allocs} allocation, the caller keeps '{user}' as well");
+
+    println!("\n  Into is From seen from the other side, so it consumes just the same:");
+    let name = String::from("ferris");
+    let kept = name.clone();
+    let user: Username = name.into();
+    n.fact("user", &user.0).fact("kept", &kept);
+    n.say("let user: Username = name.into() moved name; we cloned it first to keep '{kept}'");
+
+    // Conversion chains move the value at every step
+    let (account, chain_allocs) = instrument::measure(|| Account::from(Username::from(String::from("bors"))));
+    n.fact("account", &account.owner.0).fact("chain_allocs", chain_allocs.count);
+    n.say("Account::from(Username::from(String)) = '{account}': {chain_allocs} allocation, for the String itself");
+    println!("  Each step hands the same buffer on; a .clone() anywhere in the chain (usually");
+    println!("  added to silence E0382) would copy it. Clone once, at the start, if you must");
+
+    println!("\n  Implement From<String> when the type stores a String: callers with an owned String");
+    println!("  give it up for free. Add From<&str> for convenience, knowing it allocates. Accepting");
+    println!("  impl Into<String> in constructors covers both with one signature");
+    let from_str = Username::new("ana");
+    let from_string = Username::new(String::from("bob"));
+    n.fact("a", &from_str.0).fact("b", &from_string.0);
+    n.say("Username::new(\"ana\") = '{a}', Username::new(String::from(\"bob\")) = '{b}'");
+}
+
+// A name that owns its text
+struct Username(String);
+
+// Reuses the caller's String, no allocation
+impl From<String> for Username {
+    fn from(name: String) -> Self {
+        Username(name)
+    }
+}
+
+// Only borrows the text, so it has to copy it
+impl From<&str> for Username {
+    fn from(name: &str) -> Self {
+        Username(name.to_string())
+    }
+}
+
+impl Username {
+    // This function takes anything that can become a String, moving it if it already is one
+    fn new(name: impl Into<String>) -> Self {
+        Username(name.into())
+    }
+}
+
+// Something that owns a Username, built by converting one
+struct Account {
+    owner: Username,
+}
+
+impl From<Username> for Account {
+    fn from(owner: Username) -> Self {
+        Account { owner }
+    }
+}
+
+fn string_params() {
+    let mut n = Narration::new();
+
+    println!("  fn length_of_string(s: &String) looks like the polite way to borrow text, but it");
+    println!("  only accepts a String that already exists. A literal has to become one first:");
+    let literal = "hello";
+    let (len, allocs) = instrument::measure(|| length_of_string(&literal.to_string()));
+    n.fact("len", len).fact("allocs", allocs.count);
+    n.say("length_of_string(&\"hello\".to_string()) = {len}: {allocs} allocation, only to make the call");
+    println!("  Passing \"hello\" directly is error[E0308]: expected `&String`, found `&str`");
+    let (len, allocs) = instrument::measure(|| calculate_length(literal));
+    n.fact("len", len).fact("allocs", allocs.count);
+    n.say("calculate_length(\"hello\") = {len}: {allocs} allocations\n");
+
+    // &String reaches the text through the String; &str points at the text itself
+    let text = String::from("hello world");
+    let as_string: &String = &text;
+    let as_str: &str = &text;
+    n.address("header", as_string as *const String).address("bytes", text.as_ptr());
+    n.address("str_at", as_str.as_ptr()).fact("string_ref", std::mem::size_of::<&String>());
+    n.fact("str_ref", std::mem::size_of::<&str>());
+    n.say("a &String ({string_ref} bytes) points at {header}, the String, which points at {bytes}");
+    n.say("a &str ({str_ref} bytes) points straight at {str_at} and carries the length");
+    println!("  &String gives the function next to nothing &str doesn't (capacity(), mostly); only");
+    println!("  &mut String adds real powers (push_str, clear). clippy flags &String parameters for");
+    println!("  this reason (clippy::ptr_arg)\n");
+
+    // AsRef<str> goes one step further and accepts owned values too
+    let word = &text[..5];
+    let lengths = [
+        calculate_length(String::from("owned")),
+        calculate_length(&text),
+        calculate_length(word),
+        calculate_length(Box::<str>::from("boxed")),
+        calculate_length(Cow::Borrowed("cow")),
+    ];
+    n.fact("lengths", format!("{:?}", lengths));
+    n.say("calculate_length(impl AsRef<str>) took a String, a &String, a &str, a Box<str> and a");
+    n.say("Cow<str>: {lengths}. The String was moved in and freed at the end of the call");
+    println!("  AsRef is generic, so each argument type gets its own compiled copy. Plain &str is");
+    println!("  enough when callers only lend; AsRef<str> suits APIs that are handed owned values");
+}
+
+#[allow(clippy::ptr_arg)] // the &String parameter is the mistake this example is about
+fn length_of_string(s: &String) -> usize {
+    s.len()
+}
+
+fn owned_borrowed_pair() {
+    let mut n = Narration::new();
+
+    println!("  String/&str and PathBuf/&Path are pairs: an owned type, and an unsized type you");
+    println!("  only ever see behind a reference. UserName(String) and UserNameRef(str) are one too");
+
+    let mut scores: HashMap<UserName, u32> = HashMap::new();
+    scores.insert(UserName(String::from("ada")), 10);
+    scores.insert(UserName(String::from("grace")), 7);
+    n.fact("count", scores.len());
+    n.say("scores owns {count} UserName keys");
+
+    // Borrow<UserNameRef> for UserName is what lets get take the borrowed half
+    let (score, lookup_allocs) = instrument::measure(|| scores.get(UserNameRef::new("ada")).copied());
+    n.fact("score", format!("{:?}", score)).fact("lookup_allocs", lookup_allocs.count);
+    n.say("scores.get(UserNameRef::new(\"ada\")) = {score}: {lookup_allocs} allocations, no UserName built");
+    println!("  HashMap<K, V>::get accepts any &Q where K: Borrow<Q>, as long as Q hashes and");
+    println!("  compares exactly like K. UserName only implements Borrow<UserNameRef>, so:");
+    println!("    scores.get(\"ada\") // error[E0277]: the trait bound `UserName: Borrow<str>` is not satisfied");
+
+    // Deref makes the borrowed half's methods available on the owned half
+    let owned = UserName(String::from("grace"));
+    n.fact("shout", owned.shout());
+    n.say("Methods live on UserNameRef and work on UserName through Deref: {shout}");
+
+    // ToOwned is the way back, and it's what Cow needs
+    let name: Cow<UserNameRef> = Cow::Borrowed(UserNameRef::new("linus"));
+    let (owned, to_owned_allocs) = instrument::measure(|| name.into_owned());
+    n.fact("owned", owned.as_str()).fact("to_owned_allocs", to_owned_allocs.count);
+    n.say("Cow<UserNameRef>::into_owned() = UserName('{owned}'): {to_owned_allocs} allocation via ToOwned");
+
+    println!("\n  The recipe: #[repr(transparent)] struct Ref(str), a constructor from &str, then");
+    println!("  Borrow<Ref> for the owned type, ToOwned<Owned = Owned> for Ref, and Deref to Ref.");
+    println!("  Keep Hash and Eq derived on both halves so lookups by either one agree");
+}
+
+// The owned half, like String or PathBuf
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct UserName(String);
+
+// The borrowed half, like str or Path: an unsized view of the same bytes
+#[derive(Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+struct UserNameRef(str);
+
+impl UserNameRef {
+    // This function reinterprets a &str as a &UserNameRef without copying anything
+    fn new(name: &str) -> &UserNameRef {
+        // SAFETY: UserNameRef is a repr(transparent) wrapper around str, so
+        // both references have the same layout and the same lifetime
+        unsafe { &*(name as *const str as *const UserNameRef) }
+    }
+
+    fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    fn shout(&self) -> String {
+        self.0.to_uppercase()
+    }
+}
+
+// Lets a UserName be viewed as a &UserNameRef, which is what HashMap::get needs
+impl Borrow<UserNameRef> for UserName {
+    fn borrow(&self) -> &UserNameRef {
+        UserNameRef::new(&self.0)
+    }
+}
+
+// The way back: make an owned UserName from a borrowed one
+impl ToOwned for UserNameRef {
+    type Owned = UserName;
+
+    fn to_owned(&self) -> UserName {
+        UserName(self.0.to_string())
+    }
+}
+
+// Methods on UserNameRef become available on UserName, like str methods on String
+impl Deref for UserName {
+    type Target = UserNameRef;
+
+    fn deref(&self) -> &UserNameRef {
+        self.borrow()
+    }
+}
+
+#[cfg(feature = "serde")]
+fn serde_fields() {
+    use serde::Deserialize;
+
+    // Owned fields: every string is copied out of the input
+    #[derive(Deserialize)]
+    struct OwnedEvent {
+        name: String,
+        user: String,
+    }
+
+    // Borrowed fields point straight into the input, so the struct borrows it
+    #[derive(Deserialize)]
+    struct BorrowedEvent<'a> {
+        name: &'a str,
+        #[serde(borrow)]
+        user: Cow<'a, str>,
+    }
+
+    let mut n = Narration::new();
+
+    let input = String::from(r#"{"name": "login", "user": "ferris"}"#);
+    n.fact("input", &input);
+    n.say("Parsing {input} two ways:");
+
+    let (owned, owned_allocs) = instrument::measure(|| serde_json::from_str::<OwnedEvent>(&input).unwrap());
+    n.fact("owned_allocs", owned_allocs.count).fact("owned_bytes", owned_allocs.bytes);
+    n.fact("owned_name", &owned.name).fact("owned_user", &owned.user);
+    n.say("OwnedEvent {{ name: String, user: String }}: {owned_allocs} allocations ({owned_bytes} bytes)");
+
+    let (borrowed, borrowed_allocs) = instrument::measure(|| serde_json::from_str::<BorrowedEvent>(&input).unwrap());
+    let offset = borrowed.name.as_ptr() as usize - input.as_ptr() as usize;
+    n.fact("borrowed_allocs", borrowed_allocs.count).fact("offset", offset).fact("name", borrowed.name);
+    n.say("BorrowedEvent<'a> {{ name: &'a str, .. }}: {borrowed_allocs} allocations;");
+    n.say("its name '{name}' is the input itself, {offset} bytes in");
+    println!("  from_str<'de, T: Deserialize<'de>>(s: &'de str) ties the struct's 'a to the input,");
+    println!("  so a BorrowedEvent can't outlive the String it was parsed from:");
+    println!("    event = serde_json::from_str(&input).unwrap(); }} // input dropped here");
+    println!("    println!(\"{{}}\", event.name); // error[E0597]: `input` does not live long enough");
+
+    println!("\n  Zero-copy only works when the text in the input is exactly the value:");
+    let escaped = r#"{"name": "log\"in", "user": "ferris"}"#;
+    let error = serde_json::from_str::<BorrowedEvent>(escaped).err().map(|e| e.to_string());
+    n.fact("error", error.unwrap_or_default());
+    n.say("An escaped name can't be a &str, it needs decoding into a new String:");
+    n.say("  {error}");
+    let escaped = r#"{"name": "login", "user": "fer\"ris"}"#;
+    let event = serde_json::from_str::<BorrowedEvent>(escaped).unwrap();
+    n.fact("user", &event.user).fact("owned", matches!(event.user, Cow::Owned(_)));
+    n.say("An escaped user is fine for Cow<'a, str>: '{user}', Cow::Owned = {owned}");
+    println!("  &'a str fields borrow automatically. Cow<'a, str> (and structs that borrow) need");
+    println!("  #[serde(borrow)], or serde deserializes them as owned and never borrows at all");
+
+    println!("\n  Borrowed structs also can't be used where T: DeserializeOwned is required (for");
+    println!("  example reading from a file or socket with from_reader): there is no buffer that");
+    println!("  outlives the call for them to point into");
+}
+
+#[cfg(not(feature = "serde"))]
+fn serde_fields() {
+    println!("  This example needs serde, which the demo only builds with the serde feature:");
+    println!("    cargo run --features serde -- run api_design_serde_fields");
+}
+
+fn utf8_conversions() {
+    let mut n = Narration::new();
+
+    // from_utf8 takes the Vec by value, so a valid buffer simply becomes the String's
+    let bytes = b"hello".to_vec();
+    n.address("bytes_at", bytes.as_ptr());
+    let (text, valid_allocs) = instrument::measure(|| String::from_utf8(bytes));
+    let text = text.expect("hello is valid UTF-8");
+    n.fact("text", &text).fact("valid_allocs", valid_allocs.count).address("text_at", text.as_ptr());
+    n.say("String::from_utf8(bytes) = Ok({text}): {valid_allocs} allocations, buffer {bytes_at} is now {text_at}");
+
+    // On failure the error owns the Vec, and gives it back
+    let invalid = vec![b'h', 0xff, b'i'];
+    n.address("invalid_at", invalid.as_ptr());
+    let error = String::from_utf8(invalid).expect_err("0xff is never valid UTF-8");
+    n.fact("valid_up_to", error.utf8_error().valid_up_to());
+    n.say("from_utf8 of [h, 0xff, i] = Err: valid up to byte {valid_up_to}");
+    let recovered = error.into_bytes();
+    n.fact("recovered", format!("{:?}", recovered)).address("recovered_at", recovered.as_ptr());
+    n.say("error.into_bytes() = {recovered}, the same buffer at {recovered_at} (was {invalid_at})");
+    println!("  from_utf8 had to take ownership to avoid copying on success. Returning the bytes");
+    println!("  inside the error means a failed conversion doesn't destroy the caller's data");
+
+    // into_bytes is free in the other direction; as_bytes only borrows
+    let mut text = String::from("hello");
+    n.fact("as_bytes", format!("{:?}", text.as_bytes()));
+    println!();
+    n.say("text.as_bytes() = {as_bytes}: a read-only &[u8] view, always safe");
+    println!("  There is no safe &mut Vec<u8> view, because arbitrary bytes could break UTF-8:");
+    println!("    let bytes: &mut Vec<u8> = text.as_mut_vec(); // error[E0133]: requires unsafe");
+    // SAFETY: we only push an ASCII byte, so the String stays valid UTF-8
+    unsafe { text.as_mut_vec().push(b'!') };
+    n.fact("text", &text);
+    n.say("Inside unsafe, pushing an ASCII b'!' keeps it valid: {text}");
+
+    let (bytes, into_allocs) = instrument::measure(|| text.into_bytes());
+    n.fact("bytes", format!("{:?}", bytes)).fact("into_allocs", into_allocs.count);
+    n.say("text.into_bytes() = {bytes}: {into_allocs} allocations, the String is gone");
+
+    // from_utf8_lossy borrows, and returns Cow::Borrowed unless something needed replacing
+    let clean = String::from_utf8_lossy(&bytes);
+    let dirty = String::from_utf8_lossy(&recovered);
+    n.fact("clean", matches!(clean, Cow::Borrowed(_))).fact("dirty", &dirty);
+    n.fact("dirty_borrowed", matches!(dirty, Cow::Borrowed(_)));
+    n.say("from_utf8_lossy: valid input is borrowed ({clean}); invalid becomes '{dirty}' (borrowed: {dirty_borrowed})");
+}
+
+const COMMENTS: usize = 1000;
+
+fn cow_sanitize() {
+    let mut n = Narration::new();
+
+    // Mostly clean input, like most real text: one comment in 50 has markup in it
+    let comments: Vec<String> = (0..COMMENTS)
+        .map(|i| match i % 50 {
+            0 => format!("comment {} with <b>markup</b>", i),
+            _ => format!("comment {}", i),
+        })
+        .collect();
+    n.fact("count", COMMENTS).fact("dirty", comments.iter().filter(|c| c.contains('<')).count());
+    n.say("Sanitizing {count} comments, {dirty} of which contain markup:");
+
+    let (always, always_allocs) =
+        instrument::measure(|| comments.iter().map(|c| sanitize_always(c)).collect::<Vec<_>>());
+    n.fact("always_allocs", always_allocs.count).fact("always_bytes", always_allocs.bytes);
+    n.say("sanitize_always(&str) -> String: {always_allocs} allocations, {always_bytes} bytes");
+
+    let (cows, cow_allocs) = instrument::measure(|| comments.iter().map(|c| sanitize(c)).collect::<Vec<_>>());
+    let borrowed = cows.iter().filter(|cow| matches!(cow, Cow::Borrowed(_))).count();
+    n.fact("cow_allocs", cow_allocs.count).fact("cow_bytes", cow_allocs.bytes).fact("borrowed", borrowed);
+    n.say("sanitize(&str) -> Cow<str>:     {cow_allocs} allocations, {cow_bytes} bytes");
+    n.say("  ({borrowed} results borrowed their input; only the dirty ones, plus the Vec, allocated)");
+    n.fact("same", always.iter().zip(&cows).all(|(a, b)| a.as_str() == b.as_ref()));
+    n.say("Both versions produced the same text: {same}");
+
+    println!("\n  The owned branch builds a new String, so it must return Cow::Owned. Borrowing");
+    println!("  the local instead doesn't compile:");
+    println!("    return Cow::Borrowed(&escaped); // error[E0515]: cannot return value referencing local");
+    println!("  Callers use the Cow like a &str through Deref, and call into_owned() only if they");
+    println!("  need a String, which is free for the Owned case and a copy for the Borrowed one");
+}
+
+// This function borrows the input when it's already clean and allocates only when
+// something must change
+fn sanitize(input: &str) -> Cow<'_, str> {
+    if !input.contains(['<', '>', '&']) {
+        return Cow::Borrowed(input);
+    }
+    Cow::Owned(sanitize_always(input))
+}
+
+// This function always returns a new String, even when nothing changed
+fn sanitize_always(input: &str) -> String {
+    input.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn trait_receivers() {
+    let mut n = Narration::new();
+
+    let mut draft = Draft { title: String::from("Release notes"), body: String::new() };
+    draft.append("Faster builds");
+    draft.append("Fewer clones");
+    n.fact("heading", draft.heading()).address("body", draft.body.as_ptr());
+    n.say("A Draft is Editable: append(&mut self) added two lines in place, to its body at {body}.");
+    n.say("title(&self), and heading(&self), a default method built on it, only read: {heading}");
+
+    let preview = Preview { draft: &draft };
+    let shown = preview.publish();
+    n.fact("shown", shown.replace('\n', " / ")).address("shown_at", shown.as_ptr());
+    println!();
+    n.say("Preview's Output is &'a str. publish(self) consumed the preview, which is only a");
+    n.say("reference, and returned text borrowed from the draft, at {shown_at}: {shown}");
+    n.say("While shown is in use the draft stays borrowed, so draft.append(..) is error[E0502]");
+
+    let published = draft.publish();
+    n.fact("bytes", published.len()).address("published_at", published.as_ptr());
+    println!();
+    n.say("Draft's Output is String. publish(self) took the whole draft and moved its body out:");
+    n.say("{bytes} bytes at {published_at}, the same buffer, not a copy. The draft is gone now, so");
+    n.say("draft.append(..) after it is error[E0382]");
+
+    println!("\n  Implementors are held to the receivers the trait chose:");
+    println!("  - heading is a default method with &self, so it can't call publish(self). In it,");
+    println!("    `let text = self.publish();` is error[E0507] (and E0161: a trait's Self may be");
+    println!("    unsized). A default method can only do what its receiver allows");
+    println!("  - Preview can't be Editable. Its append would get &mut self, but what a Preview holds");
+    println!("    is &Draft, so pushing onto self.draft.body is error[E0596]. &mut self is only as");
+    println!("    strong as what self owns");
+    println!("  Give each method the weakest receiver that does the job, &self, then &mut self, then");
+    println!("  self, and let the associated type say whether what comes out is owned or borrowed");
+}
+
+// Something that can be published; what that gives back is up to the implementor
+trait Publish {
+    type Output;
+
+    fn title(&self) -> &str;
+    fn publish(self) -> Self::Output;
+
+    fn heading(&self) -> String {
+        format!("# {}", self.title())
+    }
+}
+
+trait Editable: Publish {
+    fn append(&mut self, line: &str);
+}
+
+struct Draft {
+    title: String,
+    body: String,
+}
+
+impl Publish for Draft {
+    type Output = String;
+
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    // Consumes the draft and keeps its body's buffer
+    fn publish(self) -> String {
+        self.body
+    }
+}
+
+impl Editable for Draft {
+    fn append(&mut self, line: &str) {
+        if !self.body.is_empty() {
+            self.body.push('\n');
+        }
+        self.body.push_str(line);
+    }
+}
+
+// A read-only view of a draft, whose Output borrows from it
+struct Preview<'a> {
+    draft: &'a Draft,
+}
+
+impl<'a> Publish for Preview<'a> {
+    type Output = &'a str;
+
+    fn title(&self) -> &str {
+        &self.draft.title
+    }
+
+    fn publish(self) -> &'a str {
+        &self.draft.body
+    }
+}
+
+fn reference_impls() {
+    let mut n = Narration::new();
+
+    let mut title = String::from("Ferris");
+    title.push_str(" the crab");
+    n.fact("shown", show(&title)).fact("title", &title);
+    n.say("show<T: Display>(value: T) was given &title: show(&title) = {shown}, and title is");
+    n.say("still ours ('{title}'). T is &String there, which is Display because std has");
+    n.say("impl<T: Display + ?Sized> Display for &T, forwarding to the String. show(title) works");
+    n.say("too, with T = String, but then title is moved (using it afterwards is error[E0382])");
+
+    let (described, parameter) = announce(&title);
+    n.fact("described", described).fact("parameter", parameter);
+    println!();
+    n.say("Describe is the demo's own trait, implemented for String. announce<T: Describe>(item: T)");
+    n.say("accepts &title, with T = {parameter}, only because of a forwarding impl like std's:");
+    n.say("impl<T: Describe + ?Sized> Describe for &T. It returned '{described}'");
+
+    let names = Vec::from([String::from("ana"), String::from("bo")]);
+    let parameters: Vec<String> = names.iter().map(announce).map(|(_, parameter)| parameter).collect();
+    n.fact("parameters", parameters.join(", ")).fact("count", names.len());
+    n.say("The same impl lets names.iter().map(announce) work: iter() hands out references, so");
+    n.say("T was {parameters}, and the {count} names stay in their Vec");
+
+    println!("\n  A method call auto-refs: title.describe() finds describe(&self) on String by");
+    println!("  borrowing title for you. A generic parameter doesn't: T is exactly the type of the");
+    println!("  argument, and only impls for that type count. So without the forwarding impl,");
+    println!("  announce(&title) is error[E0277]: the trait bound `&String: Describe` is not");
+    println!("  satisfied, and rustc's help says to remove the &, which would move title. Even with");
+    println!("  it, announce(&mut title) is E0277 (&mut String isn't &String; std implements Display");
+    println!("  for &mut T separately), and so is announce(title.as_str()), since a generic argument");
+    println!("  isn't deref-coerced and str has no impl. For a trait of your own whose methods take");
+    println!("  &self, add impl<T: Trait + ?Sized> Trait for &T, so callers can lend instead of give");
+}
+
+trait Describe {
+    fn describe(&self) -> String;
+}
+
+impl Describe for String {
+    fn describe(&self) -> String {
+        format!("a String of {} bytes", self.len())
+    }
+}
+
+// The forwarding impl std has for Display: a reference describes itself as what it points to
+impl<T: Describe + ?Sized> Describe for &T {
+    fn describe(&self) -> String {
+        (**self).describe()
+    }
+}
+
+fn show<T: Display>(value: T) -> String {
+    format!("[{}]", value)
+}
+
+// Also says what T was, without the module paths
+fn announce<T: Describe>(item: T) -> (String, String) {
+    (item.describe(), std::any::type_name::<T>().replace("alloc::string::", ""))
+}
+
+const SPLIT_WORDS_CODE: &str = r#"fn main() {
+    let text = String::from("the quick brown fox");
+    println!("borrowed: {:?}", split_words_borrowed(&text));
+    println!("owned: {:?}", split_words_owned(&text));
+
+    // Owned words don't depend on the text they came from
+    let owned;
+    {
+        let temporary = String::from("jumps over the lazy dog");
+        owned = split_words_owned(&temporary);
+    }
+    println!("owned words outlive their text: {:?}", owned);
+
+    // Borrowed words can't outlive the text they point into
+    let words;
+    {
+        let text = String::from("jumps over the lazy dog");
+        words = split_words_borrowed(&text);
+        println!("borrowed words, used while text is alive: {:?}", words);
+    }
+    // println!("{:?}", words);
+}
+
+// The returned slices borrow from text, so they live at most as long as 'a
+#[allow(clippy::needless_lifetimes)] // written out on purpose, elision would hide 'a
+fn split_words_borrowed<'a>(text: &'a str) -> Vec<&'a str> {
+    text.split_whitespace().collect()
+}
+
+// Every word is copied into a new String owned by the caller
+fn split_words_owned(text: &str) -> Vec<String> {
+    text.split_whitespace().map(String::from).collect()
+}
+"#;
+
+const BORROWED_ITERATORS_CODE: &str = r#"struct Document {
+    text: String,
+}
+
+impl Document {
+    // Elided form of: fn words<'a>(&'a self) -> impl Iterator<Item = &'a str> + 'a
+    fn words(&self) -> impl Iterator<Item = &str> + '_ {
+        self.text.split_whitespace()
+    }
+
+    // The items are usize, so only + '_ tells the compiler the iterator borrows self
+    fn word_lengths(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words().map(str::len)
+    }
+
+    // Without it, edition 2021 rejects the signature
+    // fn lengths(&self) -> impl Iterator<Item = usize> { self.words().map(str::len) }
+}
+
+fn main() {
+    let mut doc = Document { text: String::from("ownership makes memory safety a compile time property") };
+
+    for word in doc.words() {
+        println!("{}", word);
+    }
+    println!("{:?}", doc.word_lengths().collect::<Vec<_>>());
+
+    // Once the iterators are gone, doc can be changed again
+    doc.text.push_str(" and more");
+    println!("{} words", doc.words().count());
+}
+"#;
+
+const CONVERSIONS_CODE: &str = r#"struct Username(String);
+
+// Takes ownership of the String and keeps its buffer
+impl From<String> for Username {
+    fn from(name: String) -> Self {
+        Username(name)
+    }
+}
+
+// Only borrows, so it must allocate a copy
+impl From<&str> for Username {
+    fn from(name: &str) -> Self {
+        Username(name.to_string())
+    }
+}
+
+struct Account {
+    owner: Username,
+}
+
+impl From<Username> for Account {
+    fn from(owner: Username) -> Self {
+        Account { owner }
+    }
+}
+
+fn main() {
+    let name = String::from("ferris");
+    let user = Username::from(name); // name moves into user
+
+    // name was consumed by the first conversion
+    // let display = Username::from(name);
+
+    let borrowed = Username::from("corro"); // copies the str
+    let via_into: Username = String::from("bors").into(); // Into consumes too
+
+    // Each step of a chain moves the value on, no copies
+    let account = Account::from(user);
+    println!("{} {} {}", account.owner.0, borrowed.0, via_into.0);
+}
+"#;
+
+const STRING_PARAMS_CODE: &str = r#"// Only accepts a String that already exists
+#[allow(clippy::ptr_arg)] // the &String parameter is the mistake this example is about
+fn length_of_string(s: &String) -> usize {
+    s.len()
+}
+
+// Accepts literals, slices and &String (which derefs to &str)
+fn length_of_str(s: &str) -> usize {
+    s.len()
+}
+
+// Also accepts owned values: String, Box<str>, Cow<str>
+fn calculate_length(s: impl AsRef<str>) -> usize {
+    s.as_ref().len()
+}
+
+fn main() {
+    let text = String::from("hello world");
+    println!("{}", length_of_string(&text));
+
+    // A literal is a &str, not a &String
+    // let direct = length_of_string("hello");
+    println!("{}", length_of_string(&"hello".to_string())); // allocates just to call
+
+    println!("{} {}", length_of_str("hello"), length_of_str(&text[..5]));
+    println!("{}", length_of_str(&text)); // &String coerces to &str
+
+    println!("{} {}", calculate_length(String::from("owned")), calculate_length(&text));
+    println!("{}", calculate_length(Box::<str>::from("boxed")));
+}
+"#;
+
+const OWNED_BORROWED_PAIR_CODE: &str = r#"use std::borrow::{Borrow, Cow};
+use std::collections::HashMap;
+use std::ops::Deref;
+
+// The owned half, like String or PathBuf
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct UserName(String);
+
+// The borrowed half, like str or Path: an unsized view of the same bytes
+#[derive(Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+struct UserNameRef(str);
+
+impl UserNameRef {
+    fn new(name: &str) -> &UserNameRef {
+        // SAFETY: UserNameRef is a repr(transparent) wrapper around str,
+        // so the two references have the same layout
+        unsafe { &*(name as *const str as *const UserNameRef) }
+    }
+
+    fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+// Lets a UserName be looked at as a &UserNameRef, which is what HashMap::get needs
+impl Borrow<UserNameRef> for UserName {
+    fn borrow(&self) -> &UserNameRef {
+        UserNameRef::new(&self.0)
+    }
+}
+
+// The way back: make an owned UserName from a borrowed one
+impl ToOwned for UserNameRef {
+    type Owned = UserName;
+
+    fn to_owned(&self) -> UserName {
+        UserName(self.0.to_string())
+    }
+}
+
+// Methods on UserNameRef become available on UserName, like str methods on String
+impl Deref for UserName {
+    type Target = UserNameRef;
+
+    fn deref(&self) -> &UserNameRef {
+        self.borrow()
+    }
+}
+
+fn main() {
+    let mut scores: HashMap<UserName, u32> = HashMap::new();
+    scores.insert(UserName(String::from("ada")), 10);
+
+    // Looking up by the borrowed form needs no UserName, so nothing is allocated
+    let key = UserNameRef::new("ada");
+    println!("ada: {:?}", scores.get(key));
+
+    // UserName only implements Borrow<UserNameRef>, not Borrow<str>
+    // println!("{:?}", scores.get("ada"));
+
+    // ToOwned makes Cow work with the pair, just like Cow<str>
+    let name: Cow<UserNameRef> = Cow::Borrowed(key);
+    let owned: UserName = name.into_owned();
+    println!("{} has {} letters", owned.as_str(), owned.as_str().len());
+}
+"#;
+
+const SERDE_FIELDS_CODE: &str = r##"use std::borrow::Cow;
+
+use serde::Deserialize;
+
+// Owned fields: every string is copied out of the input
+#[derive(Deserialize)]
+struct OwnedEvent {
+    name: String,
+    user: String,
+}
+
+// Borrowed fields point straight into the input, so the struct borrows it
+#[derive(Deserialize)]
+struct BorrowedEvent<'a> {
+    name: &'a str,
+    // &str fields borrow automatically; Cow needs #[serde(borrow)] to do so
+    #[serde(borrow)]
+    user: Cow<'a, str>,
+}
+
+fn main() {
+    let input = String::from(r#"{"name": "login", "user": "ferris"}"#);
+
+    let owned: OwnedEvent = serde_json::from_str(&input).unwrap();
+    let borrowed: BorrowedEvent = serde_json::from_str(&input).unwrap();
+    println!("{} {} / {} {}", owned.name, owned.user, borrowed.name, borrowed.user);
+
+    // Escapes have to be decoded into a new String, which a &str field can't hold
+    let escaped = r#"{"name": "log\"in", "user": "ferris"}"#;
+    let result: Result<BorrowedEvent, _> = serde_json::from_str(escaped);
+    println!("&str with escapes: {}", result.err().unwrap());
+
+    // A Cow can borrow when possible and own when it has to
+    let escaped = r#"{"name": "login", "user": "fer\"ris"}"#;
+    let event: BorrowedEvent = serde_json::from_str(escaped).unwrap();
+    println!("Cow with escapes: {}, owned: {}", event.user, matches!(event.user, Cow::Owned(_)));
+
+    // The borrowed struct can't outlive the buffer it was parsed from
+    let event: BorrowedEvent;
+    {
+        let input = String::from(r#"{"name": "logout", "user": "ferris"}"#);
+        event = serde_json::from_str(&input).unwrap();
+        println!("parsed {} while input is alive", event.name);
+    }
+    // println!("{}", event.name);
+
+    // Owned fields don't depend on the input at all
+    let owned: OwnedEvent;
+    {
+        let input = String::from(r#"{"name": "logout", "user": "ferris"}"#);
+        owned = serde_json::from_str(&input).unwrap();
+    }
+    println!("owned event outlives its input: {} {}", owned.name, owned.user);
+}
+"##;
+
+const UTF8_CONVERSIONS_CODE: &str = r#"fn main() {
+    // from_utf8 takes the Vec by value: on success the String reuses its buffer
+    let bytes = vec![b'h', b'i'];
+    let text = String::from_utf8(bytes).unwrap();
+    println!("{}", text);
+
+    // On failure nothing is lost: the error owns the bytes and gives them back
+    let invalid = vec![b'h', 0xff, b'i'];
+    let bytes = match String::from_utf8(invalid) {
+        Ok(text) => text.into_bytes(),
+        Err(error) => {
+            println!("invalid at byte {}", error.utf8_error().valid_up_to());
+            error.into_bytes()
+        }
+    };
+    println!("got the bytes back: {:?}", bytes);
+
+    // into_bytes goes the other way, also without copying
+    let mut text = String::from("hello");
+    let borrowed: &[u8] = text.as_bytes(); // read-only view, always safe
+    println!("{:?}", borrowed);
+
+    // Mutable access to the bytes could break UTF-8, so it is unsafe
+    // let bytes: &mut Vec<u8> = text.as_mut_vec();
+    // SAFETY: we only push an ASCII byte, so the String stays valid UTF-8
+    unsafe { text.as_mut_vec().push(b'!') };
+
+    let bytes: Vec<u8> = text.into_bytes();
+    println!("{:?}", bytes);
+
+    // from_utf8_lossy only borrows, and only allocates when it has to replace something
+    println!("{}", String::from_utf8_lossy(&bytes));
+}
+"#;
+
+const COW_SANITIZE_CODE: &str = r#"use std::borrow::Cow;
+
+// Borrows the input when it's already clean, allocates only when something must change
+fn sanitize(input: &str) -> Cow<'_, str> {
+    if !input.contains(['<', '>', '&']) {
+        return Cow::Borrowed(input);
+    }
+    let escaped = input.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+    // A borrow of a local String can't leave the function
+    // return Cow::Borrowed(&escaped);
+    Cow::Owned(escaped)
+}
+
+// Always allocates, even when nothing changed
+fn sanitize_always(input: &str) -> String {
+    input.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn main() {
+    let comments = ["nice post", "thanks!", "a <b>bold</b> claim", "agreed"];
+    for comment in comments {
+        match sanitize(comment) {
+            Cow::Borrowed(text) => println!("borrowed: {}", text),
+            Cow::Owned(text) => println!("owned:    {}", text),
+        }
+    }
+    println!("{}", sanitize_always("always a new String"));
+}
+"#;
+
+const TRAIT_RECEIVERS_CODE: &str = r##"trait Publish {
+    // Owned for a Draft, borrowed for a Preview
+    type Output;
+
+    fn title(&self) -> &str;
+    fn publish(self) -> Self::Output;
+
+    fn heading(&self) -> String {
+        // publish needs self, and a default method with &self only has a reference
+        // let text = self.publish();
+        format!("# {}", self.title())
+    }
+}
+
+trait Editable: Publish {
+    fn append(&mut self, line: &str);
+}
+
+struct Draft {
+    title: String,
+    body: String,
+}
+
+impl Publish for Draft {
+    type Output = String;
+
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn publish(self) -> String {
+        self.body
+    }
+}
+
+impl Editable for Draft {
+    fn append(&mut self, line: &str) {
+        self.body.push_str(line);
+        self.body.push('\n');
+    }
+}
+
+struct Preview<'a> {
+    draft: &'a Draft,
+}
+
+impl<'a> Publish for Preview<'a> {
+    type Output = &'a str;
+
+    fn title(&self) -> &str {
+        &self.draft.title
+    }
+
+    fn publish(self) -> &'a str {
+        &self.draft.body
+    }
+}
+
+// &mut self on a Preview still only reaches the draft through &
+// impl Editable for Preview<'_> { fn append(&mut self, line: &str) { self.draft.body.push_str(line); } }
+
+fn main() {
+    let mut draft = Draft { title: String::from("Release notes"), body: String::new() };
+    draft.append("Faster builds");
+    println!("{}", draft.heading());
+
+    let preview = Preview { draft: &draft };
+    let shown = preview.publish();
+    // shown borrows from draft, so draft can't change while it's in use
+    // draft.append("Late change");
+    println!("preview: {}", shown);
+
+    let published = draft.publish();
+    // publish took the draft by value
+    // draft.append("Too late");
+    println!("published: {}", published);
+}
+"##;
+
+const REFERENCE_IMPLS_CODE: &str = r#"use std::fmt::Display;
+
+trait Describe {
+    fn describe(&self) -> String;
+}
+
+impl Describe for String {
+    fn describe(&self) -> String {
+        format!("a String of {} bytes", self.len())
+    }
+}
+
+// Like std's impl<T: Display + ?Sized> Display for &T: a reference
+// describes itself as what it points to. Without it, announce(&title)
+// below is error[E0277]: `&String: Describe` is not satisfied
+impl<T: Describe + ?Sized> Describe for &T {
+    fn describe(&self) -> String {
+        (**self).describe()
+    }
+}
+
+fn show<T: Display>(value: T) -> String {
+    format!("[{}]", value)
+}
+
+fn announce<T: Describe>(item: T) -> String {
+    item.describe()
+}
+
+fn main() {
+    let mut title = String::from("Ferris");
+    title.push_str(" the crab");
+
+    // T = String would take title by value
+    // let shown = show(title);
+    // T = &String borrows it instead
+    println!("{}", show(&title));
+    println!("{}", announce(&title));
+
+    // Generics don't turn &mut String into &String...
+    // announce(&mut title);
+    // ...or &String into &str, and str has no impl
+    // announce(title.as_str());
+
+    // iter() yields &String, which the forwarding impl covers
+    let names = [String::from("ana"), String::from("bo")];
+    let described: Vec<String> = names.iter().map(announce).collect();
+    println!("{:?}, and title is still {}", described, title);
+}
+"#;
@@ -0,0 +1,333 @@
+use super::{BrokenLine, Example, Section, Stop};
+use crate::diagnostics;
+use crate::lessons;
+use crate::narration::Narration;
+use crate::render;
+use crate::utils::calculate_length;
+
+pub const SECTION: Section = Section {
+    name: "borrowing",
+    title: "REFERENCES AND BORROWING",
+    examples: &[
+        Example {
+            name: "immutable_refs",
+            title: "Immutable references (borrowing)",
+            run: immutable_refs,
+            code: IMMUTABLE_REFS_CODE,
+            broken_lines: &[],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let s1",
+                    say: "s1 owns the String",
+                },
+                Stop {
+                    line: "let len = calculate_length(&s1);",
+                    say: "&s1 lends it to calculate_length without giving it away",
+                },
+                Stop {
+                    line: "println!",
+                    say: "s1 is still the owner, so it can be used again",
+                },
+                Stop {
+                    line: "fn calculate_length",
+                    say: "s is a reference: it can read the String but doesn't own it",
+                },
+                Stop {
+                    line: "}",
+                    say: "s goes out of scope, and since it owns nothing, nothing is dropped",
+                },
+            ],
+        },
+        Example {
+            name: "mutable_refs",
+            title: "Mutable references",
+            run: mutable_refs,
+            code: MUTABLE_REFS_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: "let r2 = &mut s;",
+                    error: "E0499",
+                },
+                BrokenLine {
+                    line: "let r5 = &mut s;",
+                    error: "E0502",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "change(&mut s);",
+                    say: "&mut s lends s mutably, so change can modify it",
+                },
+                Stop {
+                    line: "let r1 = &mut s;",
+                    say: "r1 is the one mutable reference to s",
+                },
+                Stop {
+                    line: "// let r2 = &mut s;",
+                    say: "A second one while r1 is used would be E0499",
+                },
+                Stop {
+                    line: "let r3 = &s;",
+                    say: "Any number of shared references is fine",
+                },
+                Stop {
+                    line: "// let r5 = &mut s;",
+                    say: "but not a mutable one while they're in use: E0502",
+                },
+                Stop {
+                    line: "fn change",
+                    say: "The &mut String parameter is what allows push_str",
+                },
+            ],
+        },
+        Example {
+            name: "dangling",
+            title: "Dangling references, in rustc's own words",
+            run: dangling,
+            code: DANGLING_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: r#"fn dangle() -> &String { let s = String::from("hello"); &s }"#,
+                    error: "E0106",
+                },
+                BrokenLine {
+                    line: r#"fn dangle_named<'a>() -> &'a String { let s = String::from("hello"); &s }"#,
+                    error: "E0515",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let s = no_dangle();",
+                    say: "main gets the String itself from no_dangle",
+                },
+                Stop {
+                    line: "fn no_dangle() -> String",
+                    say: "Returning the String moves ownership out to the caller",
+                },
+                Stop {
+                    line: "s",
+                    say: "s moves out, so nothing is dropped at the end of the function",
+                },
+                Stop {
+                    line: "// fn dangle()",
+                    say: "A reference to a local would dangle; Rust says E0106",
+                },
+                Stop {
+                    line: "// fn dangle_named",
+                    say: "Naming a lifetime doesn't help: it's still a local, E0515",
+                },
+            ],
+        },
+    ],
+};
+
+fn immutable_refs() {
+    let mut n = Narration::new();
+
+    let s1 = String::from("hello");
+    n.fact("s1", &s1);
+    n.say("Created string s1: {s1}");
+
+    // Here, calculate_length borrows s1 but doesn't take ownership
+    let len = calculate_length(&s1);
+
+    n.fact("len", len).fact("ref_size", std::mem::size_of::<&String>());
+    n.say("Length of '{s1}' is {len} bytes");
+    n.say("The function only received a reference ({ref_size} bytes: where s1 is), not the String itself");
+    println!("  Note: We can still use s1 here because we only passed a reference to the function");
+}
+
+fn mutable_refs() {
+    let mut n = Narration::new();
+
+    let mut s = String::from("hello");
+    n.fact("s", &s);
+    n.say("Created mutable string s: {s}");
+
+    // Here we pass a mutable reference
+    change(&mut s);
+
+    n.fact("s", &s).fact("len", s.len());
+    n.say("After change, s is now: {s} ({len} bytes)");
+    println!("  Note: We were able to modify s through a mutable reference");
+
+    println!("\n  Restriction: Only one mutable reference to a particular piece of data in a particular scope");
+
+    {
+        let mut s = String::from("multiple");
+        println!("  Created mutable string s: {}", s);
+
+        let r1 = &mut s;
+        println!("  Created mutable reference r1 to s");
+
+        // This would cause a compile error
+        // let r2 = &mut s;
+
+        println!("  Can't create another mutable reference while r1 exists");
+        println!("  Using r1: {}", r1);
+    } // r1 goes out of scope here, so we can create a new mutable reference to s
+
+    println!("\n  Restriction: Cannot have mutable and immutable references in the same scope");
+
+    {
+        #[allow(unused_mut)] // needed once the commented-out `&mut s` below is enabled
+        let mut s = String::from("hello");
+        println!("  Created mutable string s: {}", s);
+
+        let r1 = &s; // immutable reference
+        let r2 = &s; // another immutable reference - this is fine
+
+        println!("  Two immutable references: {} and {}", r1, r2);
+
+        // This would cause a compile error
+        // let r3 = &mut s;
+
+        println!("  Can't create a mutable reference while immutable references exist");
+    }
+}
+
+fn dangling() {
+    let mut n = Narration::new();
+
+    println!("  fn dangle() -> &String {{ let s = String::from(\"hello\"); &s }}");
+    println!("  s is dropped when dangle returns, so the &s it hands back would point at freed");
+    println!("  memory. There's no input for the result to borrow from, and rustc says so:\n");
+    quote_rustc(0);
+
+    println!("\n  Naming a lifetime only moves the error into the body: 'a has to come from");
+    println!("  somewhere the caller can see, and a local variable isn't it\n");
+    quote_rustc(1);
+
+    println!("\n  The fix is to return the String itself. Ownership moves out to the caller,");
+    println!("  so nothing is dropped and nothing dangles:");
+    let s = no_dangle(&mut n);
+    n.fact("s", &s).address("returned", s.as_ptr());
+    n.say("no_dangle() returned '{s}', heap data at {returned}: the buffer made inside, at {made}");
+}
+
+fn no_dangle(n: &mut Narration) -> String {
+    let s = String::from("hello");
+    n.address("made", s.as_ptr());
+    s
+}
+
+// Prints rustc's output for dangling's broken line number `index`, as bundled
+// into the program (see diagnostics.rs), fitted to the narration's width
+fn quote_rustc(index: usize) {
+    let quoted = lessons::find("borrowing_dangling").and_then(|(_, example)| {
+        let broken = example.broken_lines.get(index)?;
+        diagnostics::quote(example, broken)
+    });
+    let Some(captured) = quoted else {
+        println!("  (rustc's output for this line isn't bundled yet; `bundle-diagnostics` adds it)");
+        return;
+    };
+    println!("  {} says:", captured.rustc);
+    // The "aborting due to" summary says nothing the error itself doesn't
+    let text = captured.text.split("\nerror: aborting").next().unwrap_or_default();
+    for line in text.trim_end().lines() {
+        for part in fit(line) {
+            println!("    {}", part);
+        }
+    }
+}
+
+// A line of rustc output as one or more lines of at most QUOTE_WIDTH. A
+// marker line (`|    ^^ label`) that's too wide gets its label on the next
+// line, so the markers stay under the code they point at; prose is wrapped
+fn fit(line: &str) -> Vec<String> {
+    if render::width(line) <= QUOTE_WIDTH {
+        return vec![line.to_string()];
+    }
+    if let Some(gutter) = line.find('|') {
+        let after = &line[gutter + 1..];
+        let start = after.find(|c: char| c != ' ').unwrap_or(after.len());
+        let end = after[start..].find(|c: char| !"^-+~".contains(c)).map_or(after.len(), |i| start + i);
+        if end > start {
+            let label = after[end..].trim();
+            let mut lines = vec![line[..gutter + 1 + end].to_string()];
+            let prefix = &line[..gutter + 1];
+            for part in render::wrap(label, QUOTE_WIDTH - prefix.len() - 1) {
+                lines.push(format!("{} {}", prefix, part));
+            }
+            return lines;
+        }
+    }
+    let indent = line.len() - line.trim_start().len();
+    render::wrap(line.trim_start(), QUOTE_WIDTH - indent - 2)
+        .into_iter()
+        .enumerate()
+        .map(|(i, part)| format!("{}{}", " ".repeat(if i == 0 { indent } else { indent + 2 }), part))
+        .collect()
+}
+
+// Narration is at most 100 columns, and quotes are indented by 4, with 4 to spare
+const QUOTE_WIDTH: usize = 92;
+
+// This function takes a mutable reference and modifies the value
+fn change(some_string: &mut String) {
+    some_string.push_str(", world");
+}
+
+const IMMUTABLE_REFS_CODE: &str = r#"fn main() {
+    let s1 = String::from("hello");
+
+    // calculate_length borrows s1 but doesn't take ownership
+    let len = calculate_length(&s1);
+
+    // so s1 is still usable here
+    println!("The length of '{}' is {}.", s1, len);
+}
+
+#[allow(clippy::ptr_arg)] // &str would be better; see api_design_string_params
+fn calculate_length(s: &String) -> usize {
+    s.len()
+} // s goes out of scope, but it doesn't own the String, so nothing is dropped
+"#;
+
+const MUTABLE_REFS_CODE: &str = r#"fn main() {
+    let mut s = String::from("hello");
+    change(&mut s);
+    println!("after change: {}", s);
+
+    // Only one mutable reference at a time
+    let r1 = &mut s;
+    // let r2 = &mut s;
+    println!("r1 = {}", r1);
+
+    // No mutable reference while immutable ones are in use
+    let r3 = &s;
+    let r4 = &s;
+    // let r5 = &mut s;
+    println!("r3 = {}, r4 = {}", r3, r4);
+}
+
+fn change(some_string: &mut String) {
+    some_string.push_str(", world");
+}
+"#;
+
+const DANGLING_CODE: &str = r#"fn main() {
+    let s = no_dangle();
+    println!("{}", s);
+}
+
+// The String itself is returned: ownership moves out, and nothing is dropped
+#[allow(clippy::let_and_return)] // the same body as dangle below, minus the &
+fn no_dangle() -> String {
+    let s = String::from("hello");
+    s
+}
+
+// s is dropped at the end of the function, so a reference to it would dangle
+// fn dangle() -> &String { let s = String::from("hello"); &s }
+
+// A named lifetime doesn't help: there's still nothing for the result to borrow from
+// fn dangle_named<'a>() -> &'a String { let s = String::from("hello"); &s }
+"#;
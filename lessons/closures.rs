@@ -0,0 +1,489 @@
+use std::cell::RefCell;
+use std::mem;
+use std::rc::Rc;
+use std::sync::mpsc;
+
+use super::{BrokenLine, Example, Section, Stop};
+use crate::narration::Narration;
+
+pub const SECTION: Section = Section {
+    name: "closures",
+    title: "CLOSURES AND OWNERSHIP",
+    examples: &[
+        Example {
+            name: "stored_callbacks",
+            title: "Closures stored in structs",
+            run: stored_callbacks,
+            code: STORED_CALLBACKS_CODE,
+            broken_lines: &[BrokenLine {
+                line: "button.on_click = Box::new(|| button.clicks += 1);",
+                error: "E0597",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "on_click: Box<dyn FnMut()>,",
+                    say: "A boxed closure is really Box<dyn FnMut() + 'static>",
+                },
+                Stop {
+                    line: "(self.on_click)(&mut self.clicks);",
+                    say: "Disjoint fields: the closure and the count are borrowed separately",
+                },
+                Stop {
+                    line: "// button.on_click = Box::new(|| button.clicks += 1);",
+                    say: "The closure would borrow the button that owns it: E0597",
+                },
+                Stop {
+                    line: "let mut counter",
+                    say: "Fix 1: the state is passed in as an argument on each call",
+                },
+                Stop {
+                    line: "let shared = Rc::clone(&clicks);",
+                    say: "Fix 2: the closure owns a handle to shared state",
+                },
+                Stop {
+                    line: "button.on_click = Box::new(move || *shared",
+                    say: "move puts that handle inside the closure",
+                },
+                Stop {
+                    line: "let (events, inbox) = mpsc::channel();",
+                    say: "Fix 3: the closure sends messages, and the owner applies them",
+                },
+                Stop {
+                    line: "button.clicks += 1;",
+                    say: "Only main changes button, so nothing is borrowed twice",
+                },
+            ],
+        },
+        Example {
+            name: "capture_modes",
+            title: "Capture modes: three closures over one String",
+            run: capture_modes,
+            code: CAPTURE_MODES_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: r#"println!("reading {}", title);"#,
+                    error: "E0502",
+                },
+                BrokenLine {
+                    line: r#"println!("after publish: {}", title);"#,
+                    error: "E0382",
+                },
+                BrokenLine {
+                    line: "publish();",
+                    error: "E0382",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let show = || title.len();",
+                    say: "show only reads title, so it captures &title",
+                },
+                Stop {
+                    line: "let mut shout = || title.push('!');",
+                    say: "shout changes title, so it captures &mut title",
+                },
+                Stop {
+                    line: r#"// println!("reading {}", title);"#,
+                    say: "Reading title while shout still holds &mut is E0502",
+                },
+                Stop {
+                    line: r#"println!("after shouting"#,
+                    say: "shout isn't used any more, so title can be read again",
+                },
+                Stop {
+                    line: "let publish = || into_page(title);",
+                    say: "publish gives title away, so it captures the String by value",
+                },
+                Stop {
+                    line: r#"// println!("after publish"#,
+                    say: "title was moved into the closure: E0382",
+                },
+                Stop {
+                    line: "// publish();",
+                    say: "and a closure that gives its capture away runs once: E0382",
+                },
+            ],
+        },
+        Example {
+            name: "fn_traits",
+            title: "Fn, FnMut and FnOnce: which closures a function can take",
+            run: fn_traits,
+            code: FN_TRAITS_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: "call_twice(record);",
+                    error: "E0525",
+                },
+                BrokenLine {
+                    line: "call_mut_twice(archive);",
+                    error: "E0525",
+                },
+                BrokenLine {
+                    line: r#"println!("{:?}", log);"#,
+                    error: "E0382",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "fn call_twice",
+                    say: "Fn: called through &, so it can't change what it captured",
+                },
+                Stop {
+                    line: "fn call_mut_twice",
+                    say: "FnMut: called through &mut, so it may change its captures",
+                },
+                Stop {
+                    line: "fn call_once",
+                    say: "FnOnce: called by value, so it may give its captures away",
+                },
+                Stop {
+                    line: "let count = || log.len();",
+                    say: "count only reads log: Fn, and so FnMut and FnOnce too",
+                },
+                Stop {
+                    line: "let mut record",
+                    say: "record changes log: FnMut and FnOnce, but not Fn",
+                },
+                Stop {
+                    line: "// call_twice(record);",
+                    say: "so passing it where Fn is needed is E0525",
+                },
+                Stop {
+                    line: "call_mut_twice(&mut record);",
+                    say: "&mut record lends the closure instead of moving it",
+                },
+                Stop {
+                    line: "let archive = || log;",
+                    say: "archive gives log away: FnOnce only",
+                },
+                Stop {
+                    line: "// call_mut_twice(archive);",
+                    say: "so FnMut is E0525",
+                },
+                Stop {
+                    line: r#"// println!("{:?}", log);"#,
+                    say: "log went with archive into call_once: E0382",
+                },
+                Stop {
+                    line: "let measure = move || owned.len();",
+                    say: "move owns the String, but only reading it still makes this Fn",
+                },
+            ],
+        },
+    ],
+};
+
+fn stored_callbacks() {
+    let mut n = Narration::new();
+
+    println!("  A Button owns its callback as a Box<dyn FnMut()>. Without a lifetime, a boxed");
+    println!("  trait object means Box<dyn FnMut() + 'static>: the closure may only borrow data");
+    println!("  that lives forever. A callback that updates the button it belongs to would have");
+    println!("  to borrow the button while the button owns it:");
+    println!("    button.on_click = Box::new(|| button.clicks += 1);");
+    println!("    error[E0597]: `button.clicks` does not live long enough");
+    println!("  Even with a shorter lifetime than 'static, the button would stay borrowed for as");
+    println!("  long as it holds the closure, so nothing else could ever use it. Three ways out:");
+
+    // 1. The struct lends its state to the callback for the duration of each call
+    let mut counter = Counter { clicks: 0, on_click: Box::new(|clicks: &mut u32| *clicks += 1) };
+    counter.click();
+    counter.click();
+    n.fact("clicks", counter.clicks);
+    n.say("1. Pass state as an argument: on_click: Box<dyn FnMut(&mut u32)>, clicks = {clicks}");
+    println!("     click() borrows self.clicks and self.on_click separately, which is allowed");
+
+    // 2. The struct and the closure share ownership of the state
+    let clicks = Rc::new(RefCell::new(0));
+    let shared = Rc::clone(&clicks);
+    let mut button = Button { on_click: Box::new(move || *shared.borrow_mut() += 1) };
+    (button.on_click)();
+    (button.on_click)();
+    n.fact("clicks", *clicks.borrow()).fact("strong", Rc::strong_count(&clicks));
+    n.say("2. Share it with Rc<RefCell<u32>>: clicks = {clicks}, owned by {strong} (us and the closure)");
+    println!("     The move closure owns its Rc, so it is 'static, and RefCell allows the mutation");
+
+    // 3. The closure only owns a Sender and reports what happened; the owner applies it later
+    let (events, inbox) = mpsc::channel();
+    let mut button = Button { on_click: Box::new(move || events.send("clicked").unwrap()) };
+    (button.on_click)();
+    (button.on_click)();
+    let received: Vec<&str> = inbox.try_iter().collect();
+    n.fact("received", format!("{:?}", received));
+    n.say("3. Send messages: the closure owns a Sender, we drained {received} from the Receiver");
+    println!("     Nobody shares mutable state at all; whoever owns the Receiver decides what to do");
+
+    println!("\n  Prefer 1 when the struct owns the state, 3 when events cross components, and 2");
+    println!("  when several owners really need to reach the same state between calls");
+}
+
+// A button that owns a callback but nothing for it to change
+struct Button {
+    on_click: Box<dyn FnMut()>,
+}
+
+// A button that owns its state and lends it to the callback on every click
+struct Counter {
+    clicks: u32,
+    on_click: Box<dyn FnMut(&mut u32)>,
+}
+
+impl Counter {
+    // This function borrows two different fields mutably at once, which is fine
+    fn click(&mut self) {
+        (self.on_click)(&mut self.clicks);
+    }
+}
+
+fn capture_modes() {
+    let mut n = Narration::new();
+
+    let mut title = String::from("Draft");
+    n.fact("title", &title).address("buffer", title.as_ptr());
+    n.say("title = '{title}', its text at {buffer}. Three closures use it in three ways:");
+
+    let show = || title.len();
+    n.fact("len", show()).fact("size", mem::size_of_val(&show)).fact("still", &title);
+    println!();
+    n.say("1. let show = || title.len(); only reads title, so it captures &title. show() = {len},");
+    n.say("   the closure is {size} bytes (one reference), and title can still be read: '{still}'");
+
+    let mut shout = || title.push('!');
+    let shout_size = mem::size_of_val(&shout);
+    shout();
+    shout();
+    n.fact("size", shout_size).fact("title", &title).address("shouted_buffer", title.as_ptr());
+    n.say("2. let mut shout = || title.push('!'); changes title, so it captures &mut title");
+    n.say("   ({size} bytes). Called twice: title = '{title}', at {shouted_buffer}. Reading title between the calls is");
+    n.say("   error[E0502]: a &mut borrow excludes every other borrow for as long as it's used");
+
+    let publish = || into_page(title);
+    n.fact("size", mem::size_of_val(&publish)).fact("string", mem::size_of::<String>());
+    let page = publish();
+    n.fact("page", &page.title).address("page_buffer", page.title.as_ptr());
+    n.say("3. let publish = || into_page(title); hands title to a function that takes a String,");
+    n.say("   so it captures the String itself: {size} bytes, the size of a String ({string}).");
+    n.say("   publish() moved it on into the page: '{page}', still at {page_buffer}");
+
+    println!("\n  Nobody wrote & or &mut. For each variable a closure uses, the compiler looks at");
+    println!("  what the body does with it and captures it the least demanding way that works: by");
+    println!("  &, by &mut, or by value. publish owns title now, so using title afterwards is");
+    println!("  error[E0382], and since calling publish gives the String away, so is a second");
+    println!("  publish(). Writing `move ||` makes a closure capture everything by value, whatever");
+    println!("  its body does, which is what threads and returned closures need");
+}
+
+struct Page {
+    title: String,
+}
+
+fn into_page(title: String) -> Page {
+    Page { title }
+}
+
+fn fn_traits() {
+    let mut n = Narration::new();
+
+    println!("  Three functions, each asking for a different closure trait:");
+    println!("    call_twice(f: impl Fn() -> T)          calls f twice through &f");
+    println!("    call_mut_twice(f: impl FnMut() -> T)   calls f twice through &mut f");
+    println!("    call_once(f: impl FnOnce() -> T)       calls f once, by value");
+
+    let mut log = vec![String::from("opened")];
+    let count = || log.len();
+    let (first, second) = call_twice(count);
+    let (again, _) = call_mut_twice(count);
+    let last = call_once(count);
+    n.fact("first", first).fact("second", second).fact("again", again).fact("last", last);
+    println!();
+    n.say("let count = || log.len(); only reads log, so it's Fn, and every Fn is also FnMut and");
+    n.say("FnOnce: call_twice gave ({first}, {second}), call_mut_twice {again}, call_once {last}.");
+    n.say("Each call took count by value, yet count was still there for the next: a closure that");
+    n.say("only holds shared references is Copy, so passing it copied it");
+
+    let mut record = || log.push(String::from("edited"));
+    call_mut_twice(&mut record);
+    call_once(record);
+    n.fact("log", format!("{:?}", log));
+    println!();
+    n.say("let mut record = || log.push(..); changes log, so it's FnMut and FnOnce but not Fn:");
+    n.say("call_twice(record) is error[E0525], since call_twice may call it through a shared &.");
+    n.say("call_mut_twice(&mut record) lent the closure rather than moving it (&mut F is FnMut");
+    n.say("too), and call_once(record) then moved it for a last call.");
+    n.say("log = {log}");
+
+    let archive = || log;
+    let archived = call_once(archive);
+    n.fact("archived", archived.len()).address("buffer", archived.as_ptr());
+    println!();
+    n.say("let archive = || log; gives log away when called, so it's only FnOnce, and");
+    n.say("call_mut_twice(archive) is error[E0525]: there'd be nothing to give the second time.");
+    n.say("call_once(archive) returned the Vec itself ({archived} entries, at {buffer}), and log is");
+    n.say("gone from main: printing it is error[E0382]");
+
+    let owned = String::from("owned by the closure");
+    let measure = move || owned.len();
+    let (len, _) = call_twice(&measure);
+    n.fact("len", len).fact("size", mem::size_of_val(&measure));
+    println!();
+    n.say("move only decides how a closure captures: let measure = move || owned.len(); owns the");
+    n.say("String ({size} bytes of closure) but only reads it, so it's still Fn: call_twice(&measure)");
+    n.say("gave {len}");
+
+    println!("\n  The trait follows from what the body does with its captures: reads them (Fn),");
+    println!("  changes them (FnMut), or gives them away (FnOnce). A function should ask for the");
+    println!("  least it needs: FnOnce if it calls once, FnMut if it calls repeatedly, Fn only when");
+    println!("  it must call through a shared reference, e.g. from several threads at once");
+}
+
+// Calls f twice through a shared reference, so f can't change what it captured
+fn call_twice<T>(f: impl Fn() -> T) -> (T, T) {
+    (f(), f())
+}
+
+// Calls f twice through &mut, so f may change what it captured
+fn call_mut_twice<T>(mut f: impl FnMut() -> T) -> (T, T) {
+    (f(), f())
+}
+
+// Calls f once, by value, so f may give what it captured away
+fn call_once<T>(f: impl FnOnce() -> T) -> T {
+    f()
+}
+
+const STORED_CALLBACKS_CODE: &str = r#"use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::mpsc;
+
+struct Button {
+    clicks: u32,
+    on_click: Box<dyn FnMut()>, // really Box<dyn FnMut() + 'static>
+}
+
+struct Counter {
+    clicks: u32,
+    on_click: Box<dyn FnMut(&mut u32)>,
+}
+
+impl Counter {
+    fn click(&mut self) {
+        // Disjoint fields: the closure and the state are borrowed separately
+        (self.on_click)(&mut self.clicks);
+    }
+}
+
+fn main() {
+    let mut button = Button { clicks: 0, on_click: Box::new(|| {}) };
+
+    // The closure would borrow the button that owns it
+    // button.on_click = Box::new(|| button.clicks += 1);
+
+    // Fix 1: pass the state in as an argument
+    let mut counter = Counter { clicks: 0, on_click: Box::new(|clicks| *clicks += 1) };
+    counter.click();
+    println!("counter clicks: {}", counter.clicks);
+
+    // Fix 2: share the state with Rc<RefCell<_>>
+    let clicks = Rc::new(RefCell::new(0));
+    let shared = Rc::clone(&clicks);
+    button.on_click = Box::new(move || *shared.borrow_mut() += 1);
+    (button.on_click)();
+    println!("shared clicks: {}, button.clicks: {}", clicks.borrow(), button.clicks);
+
+    // Fix 3: send messages and let the owner apply them
+    let (events, inbox) = mpsc::channel();
+    button.on_click = Box::new(move || events.send("clicked").unwrap());
+    (button.on_click)();
+    for event in inbox.try_iter() {
+        button.clicks += 1;
+        println!("{}, button.clicks: {}", event, button.clicks);
+    }
+}
+"#;
+
+const CAPTURE_MODES_CODE: &str = r#"struct Page {
+    title: String,
+}
+
+fn into_page(title: String) -> Page {
+    Page { title }
+}
+
+fn main() {
+    let mut title = String::from("Draft");
+
+    // Only reads title: captures &title
+    let show = || title.len();
+    println!("{} bytes, and title can still be read: {}", show(), title);
+
+    // Changes title: captures &mut title
+    let mut shout = || title.push('!');
+    shout();
+    // No other borrow while shout still holds &mut title
+    // println!("reading {}", title);
+    shout();
+    println!("after shouting: {}", title);
+
+    // Gives title away: captures the String by value
+    let publish = || into_page(title);
+    let page = publish();
+    // title was moved into the closure
+    // println!("after publish: {}", title);
+    // and the closure can only give it away once
+    // publish();
+    println!("published: {}", page.title);
+}
+"#;
+
+const FN_TRAITS_CODE: &str = r#"// Calls f twice through a shared reference, so f can't change what it captured
+fn call_twice<T>(f: impl Fn() -> T) -> (T, T) {
+    (f(), f())
+}
+
+// Calls f twice through &mut, so f may change what it captured
+fn call_mut_twice<T>(mut f: impl FnMut() -> T) -> (T, T) {
+    (f(), f())
+}
+
+// Calls f once, by value, so f may give what it captured away
+fn call_once<T>(f: impl FnOnce() -> T) -> T {
+    f()
+}
+
+fn main() {
+    let mut log = vec![String::from("opened")];
+
+    // Only reads log: Fn, and so FnMut and FnOnce too
+    let count = || log.len();
+    println!("Fn: {:?}", call_twice(count));
+    call_mut_twice(|| println!("FnMut is fine too: {}", count()));
+    println!("and FnOnce: {}", call_once(count));
+
+    // Changes log: FnMut and FnOnce, but not Fn
+    let mut record = || log.push(String::from("edited"));
+    // call_twice(record);
+    // Passing &mut record lends the closure instead of moving it
+    call_mut_twice(&mut record);
+    call_once(record);
+    println!("log: {:?}", log);
+
+    // Gives log away: FnOnce only
+    let archive = || log;
+    // call_mut_twice(archive);
+    let archived = call_once(archive);
+    // call_once moved archive, and archive moved log
+    // println!("{:?}", log);
+    println!("archived: {:?}", archived);
+
+    // move decides how a closure captures, not which traits it implements
+    let owned = String::from("owned by the closure");
+    let measure = move || owned.len();
+    println!("a move closure that only reads is still Fn: {:?}", call_twice(&measure));
+}
+"#;
@@ -0,0 +1,616 @@
+use std::collections::HashMap;
+use std::mem;
+use std::ops::{Index, IndexMut};
+
+use super::{BrokenLine, Example, Section, Stop};
+use crate::instrument;
+use crate::narration::Narration;
+
+pub const SECTION: Section = Section {
+    name: "collections",
+    title: "COLLECTIONS OF OWNED AND BORROWED DATA",
+    examples: &[
+        Example {
+            name: "vec_of_refs",
+            title: "Accumulating borrows in a Vec<&str>",
+            run: vec_of_refs,
+            code: VEC_OF_REFS_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: r#"names.push(String::from("Eve"));"#,
+                    error: "E0502",
+                },
+                BrokenLine {
+                    line: "drop(names);",
+                    error: "E0505",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let short: Vec<&str>",
+                    say: "Every &str in short borrows a String that names owns",
+                },
+                Stop {
+                    line: "// names.push",
+                    say: "Pushing could move the Strings while short is in use: E0502",
+                },
+                Stop {
+                    line: "// drop(names);",
+                    say: "and dropping names would leave short dangling: E0505",
+                },
+                Stop {
+                    line: "let short_owned",
+                    say: "Fix 1: clone the short names, so nothing borrows names",
+                },
+                Stop {
+                    line: "let short: Vec<&str>",
+                    say: "Fix 2: the borrows end with this block",
+                },
+                Stop {
+                    line: r#"names.push(String::from("Ken"));"#,
+                    say: "so names can change after it",
+                },
+                Stop {
+                    line: "let short_indices",
+                    say: "Fix 3: indices are plain numbers and borrow nothing",
+                },
+                Stop {
+                    line: r#"println!("short name at"#,
+                    say: "The values are looked up only when they're needed",
+                },
+            ],
+        },
+        Example {
+            name: "moving_out",
+            title: "Why v[0] can't move a String out of a Vec",
+            run: moving_out,
+            code: MOVING_OUT_CODE,
+            broken_lines: &[BrokenLine {
+                line: "let first = words[0];",
+                error: "E0507",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let x = numbers[0];",
+                    say: "i32 is Copy, so indexing copies the element out",
+                },
+                Stop {
+                    line: "let first = &words[0];",
+                    say: "Borrowing an element in place is fine",
+                },
+                Stop {
+                    line: "// let first = words[0];",
+                    say: "Moving a String out would leave a hole the Vec still drops: E0507",
+                },
+                Stop {
+                    line: "let copy = words[0].clone();",
+                    say: "clone leaves words alone, but allocates",
+                },
+                Stop {
+                    line: "let removed = words.remove(0);",
+                    say: "remove takes it out and shifts the rest left",
+                },
+                Stop {
+                    line: "let swapped = words.swap_remove(0);",
+                    say: "swap_remove moves the last element into the gap instead",
+                },
+            ],
+        },
+        Example {
+            name: "custom_index",
+            title: "Index for your own collection: why it lends, and how to hand out owned values",
+            run: custom_index,
+            code: CUSTOM_INDEX_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: "let first = roster[0];",
+                    error: "E0507",
+                },
+                BrokenLine {
+                    line: r#"println!("{}", leader);"#,
+                    error: "E0502",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "fn index(&self",
+                    say: "Index must return &Self::Output, so a String can only be lent",
+                },
+                Stop {
+                    line: "fn index_mut",
+                    say: "IndexMut lends it mutably",
+                },
+                Stop {
+                    line: "fn take_at",
+                    say: "mem::take hands the name out and leaves an empty String behind",
+                },
+                Stop {
+                    line: "// let first = roster[0];",
+                    say: "roster[0] is a place in roster; moving out of it is E0507",
+                },
+                Stop {
+                    line: "let leader = &roster[0];",
+                    say: "Borrowing it is fine",
+                },
+                Stop {
+                    line: "roster[1].push_str",
+                    say: "IndexMut changes the element in place",
+                },
+                Stop {
+                    line: r#"// println!("{}", leader);"#,
+                    say: "leader would still borrow roster during that change: E0502",
+                },
+                Stop {
+                    line: "let taken = roster.take_at(2);",
+                    say: "Owned values come out through methods that take them out",
+                },
+            ],
+        },
+        Example {
+            name: "keys_not_refs",
+            title: "Keeping keys instead of references in long-lived state",
+            run: keys_not_refs,
+            code: KEYS_NOT_REFS_CODE,
+            broken_lines: &[BrokenLine {
+                line: r#"items.push(Item { name: String::from("pen"), price: 2 });"#,
+                error: "E0502",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let cheapest: &Item",
+                    say: "A cached reference keeps items borrowed while it's used",
+                },
+                Stop {
+                    line: "// items.push",
+                    say: "so pushing before the println is E0502",
+                },
+                Stop {
+                    line: "let cheapest = items.iter().enumerate()",
+                    say: "An index borrows nothing, so items is free to change",
+                },
+                Stop {
+                    line: "items.remove(0);",
+                    say: "but removing an earlier item shifts what the index points at",
+                },
+                Stop {
+                    line: "let mut by_id",
+                    say: "Keys into a HashMap stay valid until their own entry goes",
+                },
+                Stop {
+                    line: "match by_id.get(&selected)",
+                    say: "The key still finds the desk after other entries changed",
+                },
+            ],
+        },
+    ],
+};
+
+fn vec_of_refs() {
+    let mut n = Narration::new();
+
+    let mut names = vec![String::from("Ada"), String::from("Grace"), String::from("Alan"), String::from("Barbara")];
+    n.fact("names", format!("{:?}", names));
+    n.say("Created names: {names}");
+
+    // Every element of short borrows a String inside names
+    let short: Vec<&str> = names.iter().map(String::as_str).filter(|name| name.len() <= 4).collect();
+    n.fact("short", format!("{:?}", short)).fact("count", short.len());
+    n.say("Collected {count} borrowed short names: {short}");
+    println!("  Each &str in short points into a String owned by names, so names is borrowed");
+    println!("  for as long as short is in use. While it is, both of these fail to compile:");
+    println!("    names.push(String::from(\"Eve\")); // error[E0502]: names is also borrowed as immutable");
+    println!("    drop(names);                     // error[E0505]: cannot move out of names while borrowed");
+    n.say("Using short: {short}");
+
+    println!("\n  Fix 1: collect owned data, so the new Vec doesn't depend on names");
+    let short_owned: Vec<String> = names.iter().filter(|name| name.len() <= 4).cloned().collect();
+    names.push(String::from("Eve"));
+    n.fact("short_owned", format!("{:?}", short_owned)).fact("names", format!("{:?}", names));
+    n.say("short_owned = {short_owned}");
+    n.say("and names could still grow: {names}");
+
+    println!("\n  Fix 2: restructure scopes, finishing with the borrows before changing names");
+    {
+        let short: Vec<&str> = names.iter().map(String::as_str).filter(|name| name.len() <= 4).collect();
+        n.fact("short", format!("{:?}", short));
+        n.say("Borrowed {short} and used it inside its own block");
+    } // short ends here, and with it the borrow of names
+    names.push(String::from("Ken"));
+    n.fact("names", format!("{:?}", names));
+    n.say("Then names could change again: {names}");
+
+    println!("\n  Fix 3: remember positions instead of references");
+    let short_indices: Vec<usize> = (0..names.len()).filter(|&i| names[i].len() <= 4).collect();
+    names.push(String::from("Linus"));
+    let looked_up: Vec<&str> = short_indices.iter().map(|&i| names[i].as_str()).collect();
+    n.fact("indices", format!("{:?}", short_indices)).fact("looked_up", format!("{:?}", looked_up));
+    n.say("Stored indices {indices} and pushed another name to names");
+    n.say("Looking the indices up again still finds the short names: {looked_up}");
+    println!("  Indices don't borrow anything, but they are only meaningful while the order of");
+    println!("  names stays the same, so use them when elements are only ever appended");
+}
+
+fn moving_out() {
+    let mut n = Narration::new();
+
+    let numbers = vec![10, 20, 30];
+    let x = numbers[0]; // i32 is Copy, so this copies the element out
+    n.fact("x", x).fact("numbers", format!("{:?}", numbers));
+    n.say("let x = numbers[0] copies an i32 out: x = {x}, numbers is still {numbers}");
+
+    let mut words = vec![String::from("alpha"), String::from("beta"), String::from("gamma"), String::from("delta")];
+    n.fact("words", format!("{:?}", words));
+    n.say("Created words: {words}");
+
+    // Indexing gives us a place inside the Vec; we can borrow from it...
+    let first = &words[0];
+    n.fact("first", first);
+    n.say("let first = &words[0] borrows the element: {first}");
+
+    // ...but not move out of it
+    // let first = words[0];
+    println!("  let first = words[0] doesn't compile: error[E0507]: cannot move out of index of Vec<String>");
+    println!("  words[0] means *words.index(0), a place the Vec still owns. The Vec drops every");
+    println!("  element it holds when it goes away, for any T; if we could move one out, it would");
+    println!("  either drop that String a second time or have to track holes. So for non-Copy T,");
+    println!("  ownership can only leave a Vec through methods that also take the element out of it");
+
+    println!("\n  The options, and what they cost:");
+    let (copy, clone_allocs) = instrument::measure(|| words[0].clone());
+    n.fact("copy", &copy).fact("clone_allocs", clone_allocs.count).fact("clone_bytes", clone_allocs.bytes);
+    n.say("clone():        {copy}, words keeps its element; {clone_allocs} allocation ({clone_bytes} bytes)");
+
+    let (removed, remove_allocs) = instrument::measure(|| words.remove(0));
+    n.fact("removed", &removed).fact("remove_allocs", remove_allocs.count).fact("words", format!("{:?}", words));
+    n.say("remove(0):      {removed}, no copy ({remove_allocs} allocations) but shifts the rest: {words}");
+
+    let (swapped, swap_allocs) = instrument::measure(|| words.swap_remove(0));
+    n.fact("swapped", &swapped).fact("swap_allocs", swap_allocs.count).fact("words", format!("{:?}", words));
+    n.say("swap_remove(0): {swapped}, {swap_allocs} allocations, moves the last element into the gap: {words}");
+
+    println!("  Clone when the Vec must stay intact, remove when order matters, and swap_remove");
+    println!("  when it doesn't: it's O(1) instead of shifting every later element");
+}
+
+fn custom_index() {
+    let mut n = Narration::new();
+
+    let mut roster = Roster { players: ["ana", "bo", "cai", "dee", "eli"].map(String::from).to_vec() };
+    let leader = &roster[0];
+    n.fact("leader", leader).address("leader_text", leader.as_ptr()).address("stored", roster.players[0].as_ptr());
+    n.say("Roster implements Index<usize> with Output = String, and the trait's index(&self, ..)");
+    n.say("must return &String. roster[0] is *roster.index(0), a place roster still owns, so");
+    n.say("&roster[0] borrows it: leader = {leader}, at {leader_text}, the text stored at {stored}");
+    println!("  `let first = roster[0];` is error[E0507], just as for a Vec. Nor can index hand out a");
+    println!("  String: roster[i] has to be a place you can borrow from, or assign to through");
+    println!("  IndexMut, and a value moved out on every use would be neither. So the trait only lends");
+
+    roster[1].push_str(" (captain)");
+    roster[2] = String::from("cy");
+    n.fact("players", format!("{:?}", roster.players));
+    println!();
+    n.say("IndexMut lends the place mutably: roster[1].push_str(..) changed it, and roster[2] =");
+    n.say("String::from(\"cy\") dropped \"cai\" and put the new String in its place:");
+    n.say("{players}");
+    n.say("Each of those borrows all of roster mutably, so leader can't be used past them");
+    n.say("(error[E0502])");
+
+    println!("\n  Owned Strings come out through methods that take them out of the roster too:");
+    let cy = roster.players[2].as_ptr();
+    let (taken, allocations) = instrument::measure(|| roster.take_at(2));
+    n.address("before", cy).address("taken_text", taken.as_ptr()).fact("allocations", allocations.count);
+    n.fact("taken", &taken).fact("players", format!("{:?}", roster.players));
+    n.say("take_at(2):     {taken} ({before} -> {taken_text}, {allocations} allocations); an empty String");
+    n.say("                stays behind, so no position changes: {players}");
+
+    let (removed, allocations) = instrument::measure(|| roster.remove(1));
+    n.fact("removed", &removed).fact("allocations", allocations.count).fact("players", format!("{:?}", roster.players));
+    n.say("remove(1):      {removed}, {allocations} allocations; the rest shift left: {players}");
+
+    let (swapped, allocations) = instrument::measure(|| roster.swap_remove(0));
+    n.fact("swapped", &swapped).fact("allocations", allocations.count).fact("players", format!("{:?}", roster.players));
+    n.say("swap_remove(0): {swapped}, {allocations} allocations; the last moves into the gap: {players}");
+
+    println!("\n  Each of these takes &mut self and returns the String by value: the roster gives up");
+    println!("  the element in the same call that hands it over, so nothing is ever owned twice or");
+    println!("  left dangling. mem::take suits a collection whose positions mean something; if an");
+    println!("  empty String isn't a fair placeholder, store Option<String> and take() the Option");
+}
+
+// A team's players in batting order, indexed like a Vec
+struct Roster {
+    players: Vec<String>,
+}
+
+impl Index<usize> for Roster {
+    type Output = String;
+
+    fn index(&self, position: usize) -> &String {
+        &self.players[position]
+    }
+}
+
+impl IndexMut<usize> for Roster {
+    fn index_mut(&mut self, position: usize) -> &mut String {
+        &mut self.players[position]
+    }
+}
+
+impl Roster {
+    // Takes the player out and closes the gap, keeping the order
+    fn remove(&mut self, position: usize) -> String {
+        self.players.remove(position)
+    }
+
+    // Takes the player out and moves the last one into the gap
+    fn swap_remove(&mut self, position: usize) -> String {
+        self.players.swap_remove(position)
+    }
+
+    // Takes the player's name and leaves an empty one, so no position changes
+    fn take_at(&mut self, position: usize) -> String {
+        mem::take(&mut self.players[position])
+    }
+}
+
+fn keys_not_refs() {
+    let mut n = Narration::new();
+
+    let mut items = vec![Item::new("lamp", 30), Item::new("desk", 120), Item::new("chair", 45)];
+    let cheapest = items.iter().min_by_key(|item| item.price).unwrap();
+    n.fact("cheapest", &cheapest.name);
+    n.say("Found the cheapest item, {cheapest}, and kept a &Item to it");
+    println!("  As long as that reference is used, items is borrowed and can't change:");
+    println!("    items.push(Item {{ .. }}); // error[E0502]: cannot borrow `items` as mutable");
+    println!("  For a lookup that's fine. For state that lives as long as the program (a selected");
+    println!("  item, a player's target, a cursor), it freezes the collection for good");
+
+    // An index borrows nothing, so items can change while we hold it
+    let cheapest = (0..items.len()).min_by_key(|&i| items[i].price).unwrap();
+    items.push(Item::new("pen", 2));
+    n.fact("index", cheapest).fact("name", &items[cheapest].name);
+    println!();
+    n.say("Stored index {index} instead, pushed a new item, and items[{index}] is still {name}");
+    items.remove(0);
+    n.fact("name", items.get(cheapest).map_or("nothing", |item| item.name.as_str()));
+    n.say("But after items.remove(0), items[{index}] is {name}: the index silently went stale");
+    println!("  Indices are safe (no dangling pointers) but only correct while nothing before them");
+    println!("  is removed or reordered");
+
+    // Keys stay meaningful until their own entry is removed
+    let mut shop = Shop::default();
+    let lamp = shop.add(Item::new("lamp", 30));
+    let desk = shop.add(Item::new("desk", 120));
+    shop.featured = Some(desk);
+    n.fact("featured", shop.featured_name());
+    println!();
+    n.say("Shop keeps items in a HashMap<ItemId, Item> and the featured one as an ItemId: {featured}");
+    shop.add(Item::new("pen", 2));
+    shop.remove(lamp);
+    n.fact("featured", shop.featured_name()).fact("count", shop.items.len());
+    n.say("Added and removed other items ({count} left); the featured key still finds {featured}");
+    shop.remove(desk);
+    n.fact("featured", shop.featured_name());
+    n.say("Removed the featured item itself: the lookup now returns {featured} instead of dangling");
+    println!("  A Shop can't hold a &Item into its own items anyway: a struct can't borrow from");
+    println!("  itself. Keys are how long-lived state refers to other long-lived state");
+
+    println!("\n  What the re-lookup costs:");
+    println!("  - a reference: nothing, it is already the address");
+    println!("  - a Vec index: a bounds check and an add, a few instructions");
+    println!("  - a HashMap key: hashing the key (SipHash by default) and probing, tens of ns");
+    println!("  Look the key up once per operation and use the &Item within that scope; don't");
+    println!("  cache it across operations. In hot loops over many keys, a faster hasher or a");
+    println!("  Vec with generation-checked indices (the slotmap idea) closes most of the gap");
+}
+
+struct Item {
+    name: String,
+    price: u32,
+}
+
+impl Item {
+    fn new(name: &str, price: u32) -> Item {
+        Item { name: name.to_string(), price }
+    }
+}
+
+// A key into Shop::items, handed out once and never reused
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct ItemId(u32);
+
+// Long-lived state that refers to its own items by key
+#[derive(Default)]
+struct Shop {
+    items: HashMap<ItemId, Item>,
+    next_id: u32,
+    featured: Option<ItemId>,
+}
+
+impl Shop {
+    // This function takes ownership of the item and gives back a key to find it again
+    fn add(&mut self, item: Item) -> ItemId {
+        let id = ItemId(self.next_id);
+        self.next_id += 1;
+        self.items.insert(id, item);
+        id
+    }
+
+    fn remove(&mut self, id: ItemId) -> Option<Item> {
+        self.items.remove(&id)
+    }
+
+    // This function re-looks-up the featured item every time it's asked
+    fn featured_name(&self) -> String {
+        let item = self.featured.and_then(|id| self.items.get(&id));
+        item.map_or(String::from("None"), |item| format!("Some({} for {})", item.name, item.price))
+    }
+}
+
+const VEC_OF_REFS_CODE: &str = r#"fn main() {
+    let mut names = vec![String::from("Ada"), String::from("Grace"), String::from("Alan")];
+
+    // Every &str in short borrows a String owned by names
+    let short: Vec<&str> = names.iter().map(String::as_str).filter(|name| name.len() <= 4).collect();
+
+    // While short is still used below, names can't be changed or dropped
+    // names.push(String::from("Eve"));
+    // drop(names);
+
+    println!("short names: {:?}", short);
+
+    // Fix 1: collect owned data instead
+    let short_owned: Vec<String> = names.iter().filter(|name| name.len() <= 4).cloned().collect();
+    names.push(String::from("Eve"));
+    println!("owned short names: {:?}, names: {:?}", short_owned, names);
+
+    // Fix 2: finish with the borrows before changing names
+    {
+        let short: Vec<&str> = names.iter().map(String::as_str).filter(|name| name.len() <= 4).collect();
+        println!("short names: {:?}", short);
+    }
+    names.push(String::from("Ken"));
+
+    // Fix 3: store indices and look the values up when needed
+    let short_indices: Vec<usize> = (0..names.len()).filter(|&i| names[i].len() <= 4).collect();
+    names.push(String::from("Linus"));
+    for i in short_indices {
+        println!("short name at {}: {}", i, names[i]);
+    }
+}
+"#;
+
+const MOVING_OUT_CODE: &str = r#"fn main() {
+    let numbers = vec![10, 20, 30];
+    let x = numbers[0]; // i32 is Copy, so the element is copied out
+    println!("x = {}, numbers = {:?}", x, numbers);
+
+    let mut words = vec![String::from("alpha"), String::from("beta"), String::from("gamma")];
+
+    // Indexing gives a place inside the Vec: borrowing from it is fine
+    let first = &words[0];
+    println!("first = {}", first);
+
+    // Moving out of it is not, because the Vec still owns (and will drop) the element
+    // let first = words[0];
+
+    // Ways to get an owned String out
+    let copy = words[0].clone(); // words is unchanged, but this allocates
+    let removed = words.remove(0); // shifts the remaining elements left
+    let swapped = words.swap_remove(0); // moves the last element into the gap
+    println!("{} {} {} {:?}", copy, removed, swapped, words);
+}
+"#;
+
+const CUSTOM_INDEX_CODE: &str = r#"use std::mem;
+use std::ops::{Index, IndexMut};
+
+// A team's players in batting order
+struct Roster {
+    players: Vec<String>,
+}
+
+impl Index<usize> for Roster {
+    type Output = String;
+
+    // The trait fixes the return type as &Self::Output: a String can only be lent
+    fn index(&self, position: usize) -> &String {
+        &self.players[position]
+    }
+}
+
+impl IndexMut<usize> for Roster {
+    fn index_mut(&mut self, position: usize) -> &mut String {
+        &mut self.players[position]
+    }
+}
+
+impl Roster {
+    // Takes the player out and closes the gap, keeping the order
+    fn remove(&mut self, position: usize) -> String {
+        self.players.remove(position)
+    }
+
+    // Takes the player out and moves the last one into the gap
+    fn swap_remove(&mut self, position: usize) -> String {
+        self.players.swap_remove(position)
+    }
+
+    // Takes the player's name and leaves an empty one, so no position changes
+    fn take_at(&mut self, position: usize) -> String {
+        mem::take(&mut self.players[position])
+    }
+}
+
+fn main() {
+    let mut roster = Roster {
+        players: ["ana", "bo", "cai", "dee", "eli"].map(String::from).to_vec(),
+    };
+
+    // roster[0] is *roster.index(0): a place inside roster, which roster still owns
+    // let first = roster[0];
+    let leader = &roster[0];
+    println!("leader: {}", leader);
+
+    // IndexMut lends the place mutably, so it can be changed or replaced in place
+    roster[1].push_str(" (captain)");
+    // leader would still borrow roster while roster[1] is changed through &mut roster
+    // println!("{}", leader);
+    roster[2] = String::from("cy");
+
+    // Owned values come out through methods that also take them out of the roster
+    let taken = roster.take_at(2);
+    let removed = roster.remove(1);
+    let swapped = roster.swap_remove(0);
+    println!("took {}, removed {}, swap_removed {}", taken, removed, swapped);
+    println!("left: {:?}", roster.players);
+}
+"#;
+
+const KEYS_NOT_REFS_CODE: &str = r#"use std::collections::HashMap;
+
+struct Item {
+    name: String,
+    price: u32,
+}
+
+fn main() {
+    let mut items = vec![
+        Item { name: String::from("lamp"), price: 30 },
+        Item { name: String::from("desk"), price: 120 },
+    ];
+
+    // Caching a reference keeps items borrowed for as long as the reference is used
+    let cheapest: &Item = items.iter().min_by_key(|item| item.price).unwrap();
+    // items.push(Item { name: String::from("pen"), price: 2 });
+    println!("cheapest: {}", cheapest.name);
+
+    // Storing an index instead leaves items free to change...
+    let cheapest = items.iter().enumerate().min_by_key(|(_, item)| item.price).unwrap().0;
+    items.push(Item { name: String::from("pen"), price: 2 });
+    println!("cheapest (by index): {}", items[cheapest].name);
+    // ...but removing an earlier element makes the index point at something else
+    items.remove(0);
+    println!("index {} is now: {:?}", cheapest, items.get(cheapest).map(|item| &item.name));
+
+    // Keys into a HashMap stay valid until that entry is removed
+    let mut by_id: HashMap<u32, Item> = HashMap::new();
+    by_id.insert(1, Item { name: String::from("lamp"), price: 30 });
+    by_id.insert(2, Item { name: String::from("desk"), price: 120 });
+    let selected = 2;
+    by_id.insert(3, Item { name: String::from("pen"), price: 2 });
+    by_id.remove(&1);
+    match by_id.get(&selected) {
+        Some(item) => println!("selected: {} for {}", item.name, item.price),
+        None => println!("selected item was removed"),
+    }
+}
+"#;
@@ -0,0 +1,450 @@
+use std::cell::RefCell;
+use std::sync::{Mutex, TryLockError};
+
+use super::{BrokenLine, Example, Section, Stop};
+use crate::narration::Narration;
+
+pub const SECTION: Section = Section {
+    name: "drop",
+    title: "DROP AND CLEANUP",
+    examples: &[
+        Example {
+            name: "order",
+            title: "The order values are dropped in",
+            run: order,
+            code: ORDER_CODE,
+            broken_lines: &[BrokenLine {
+                line: "a.drop();",
+                error: "E0040",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "impl Drop for Traced",
+                    say: "drop runs when a Traced goes out of scope",
+                },
+                Stop {
+                    line: "fn drop(&mut self) {",
+                    say: "A struct's own drop runs first, while its fields are still there",
+                },
+                Stop {
+                    line: r#"let _d = Traced("d");"#,
+                    say: "Locals are dropped in reverse order of declaration",
+                },
+                Stop {
+                    line: "} // drops d, then c",
+                    say: "so the block drops d, then c",
+                },
+                Stop {
+                    line: "let _h = f;",
+                    say: "f moves to _h, so it's dropped where _h goes, not f",
+                },
+                Stop {
+                    line: "let _pair",
+                    say: "The pair's own drop, then its fields in declaration order",
+                },
+                Stop {
+                    line: "let _list",
+                    say: "A Vec drops its elements front to back",
+                },
+                Stop {
+                    line: "// a.drop();",
+                    say: "Calling drop directly isn't allowed (E0040); drop(a) is",
+                },
+                Stop {
+                    line: "} // drops list[0]",
+                    say: "main ends: everything left is dropped, last declared first",
+                },
+            ],
+        },
+        Example {
+            name: "early_drop",
+            title: "Dropping early with drop(): files, borrows and MutexGuards",
+            run: early_drop,
+            code: EARLY_DROP_CODE,
+            broken_lines: &[BrokenLine {
+                line: "println!(\"{} entries\", guard.len());",
+                error: "E0382",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let upload = TempFile",
+                    say: "upload would normally live until the end of main",
+                },
+                Stop {
+                    line: "drop(upload);",
+                    say: "drop moves it into a function that ends at once, removing the file now",
+                },
+                Stop {
+                    line: "let mut editing = settings.borrow_mut();",
+                    say: "A RefMut guard keeps the RefCell borrowed",
+                },
+                Stop {
+                    line: "drop(editing);",
+                    say: "Dropping it ends the borrow, so borrow() below doesn't panic",
+                },
+                Stop {
+                    line: "let guard = log.lock().unwrap();",
+                    say: "A MutexGuard holds the lock until it's dropped",
+                },
+                Stop {
+                    line: "drop(guard);",
+                    say: "Released here, so record can take the lock again",
+                },
+                Stop {
+                    line: r#"// println!("{} entries", guard.len());"#,
+                    say: "guard was moved into drop, so using it is E0382",
+                },
+            ],
+        },
+        Example {
+            name: "early_return",
+            title: "What gets dropped on each early return and ?",
+            run: early_return,
+            code: EARLY_RETURN_CODE,
+            broken_lines: &[],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: r#"let _config = Traced("config");"#,
+                    say: "config lives until publish returns, however it returns",
+                },
+                Stop {
+                    line: "let _connection = open(fail_open)?;",
+                    say: "If open fails, ? returns early and config is dropped",
+                },
+                Stop {
+                    line: r#"return Err(String::from("check failed"));"#,
+                    say: "An early return drops connection, then config",
+                },
+                Stop {
+                    line: "Ok(report)",
+                    say: "report moves to the caller; the rest is dropped here",
+                },
+                Stop {
+                    line: "drop(report);",
+                    say: "The caller owns the report, so the caller drops it",
+                },
+                Stop {
+                    line: r#"let _ = Traced("unbound");"#,
+                    say: "let _ binds nothing, so the value is dropped at once",
+                },
+                Stop {
+                    line: r#"let _guard = Traced("guard");"#,
+                    say: "_guard is a binding, so it lives to the end of main",
+                },
+            ],
+        },
+    ],
+};
+
+fn order() {
+    println!("  Every value here prints its name when it's dropped. A block declares a and b,");
+    println!("  opens an inner block with c and d, and declares e after it:");
+    {
+        let _a = Traced("a");
+        let _b = Traced("b");
+        {
+            let _c = Traced("c");
+            let _d = Traced("d");
+            println!("    (end of the inner block)");
+        }
+        let _e = Traced("e");
+        println!("    (end of the outer block)");
+    }
+
+    println!("\n  When a scope ends, its locals are dropped in reverse order of declaration: the");
+    println!("  inner block's d and c as soon as it closes, then e, b and a. A later value may");
+    println!("  borrow an earlier one, so the earlier one has to be the last to go");
+
+    println!("\n  A moved value is dropped where its new owner goes out of scope. f is declared");
+    println!("  before g, but moving it into h makes it the newest value:");
+    {
+        let f = Traced("f");
+        let _g = Traced("g");
+        let _h = f;
+        println!("    (end of the block)");
+    }
+
+    println!("\n  Inside a value the order is the other way round. A struct's own drop runs first,");
+    println!("  then its fields in declaration order, and a Vec drops its elements front to back:");
+    {
+        let _pair = Pair { first: Traced("pair.first"), second: Traced("pair.second") };
+        let _list = Vec::from([Traced("list[0]"), Traced("list[1]")]);
+        println!("    (end of the block)");
+    }
+
+    println!("\n  Drop::drop can't be called by hand: a.drop() is error[E0040], since a would");
+    println!("  be dropped again at the end of its scope. std::mem::drop(a) moves it instead,");
+    println!("  so it's dropped right there and only once");
+}
+
+fn early_drop() {
+    let mut n = Narration::new();
+
+    println!("  std::mem::drop(value) takes the value by move and does nothing with it, so it's");
+    println!("  dropped right there instead of at the end of the scope:");
+    {
+        let upload = Traced("temporary upload file");
+        println!("    (upload written and sent)");
+        drop(upload);
+        println!("    (still in the same scope, compressing the next batch)");
+    }
+    println!("  The file was cleaned up as soon as it had been sent, not after the slow work");
+
+    // A RefMut is a borrow that lasts until it's dropped, checked at runtime
+    let settings = RefCell::new(vec![String::from("dark mode")]);
+    let mut editing = settings.borrow_mut();
+    editing.push(String::from("large text"));
+    n.fact("while_editing", settings.try_borrow().is_ok());
+    drop(editing);
+    n.fact("after_drop", settings.try_borrow().is_ok());
+    n.fact("settings", format!("{:?}", settings.borrow()));
+    println!();
+    n.say("A RefCell's borrow_mut() lasts until its RefMut is dropped. Can we read settings");
+    n.say("while it's being edited? {while_editing}. After drop(editing)? {after_drop}: {settings}");
+
+    // The classic: a guard held across a call that takes the same lock
+    let log = Mutex::new(vec![String::from("started")]);
+    let guard = log.lock().unwrap();
+    let entries = guard.len();
+    let locked = matches!(log.try_lock(), Err(TryLockError::WouldBlock));
+    drop(guard);
+    record(&log, "checked");
+    n.fact("entries", entries).fact("locked", locked);
+    n.fact("log", format!("{:?}", log.lock().unwrap()));
+    println!();
+    n.say("A MutexGuard holds its lock until it's dropped. With the guard still alive after");
+    n.say("reading the log's {entries} entry, is the mutex locked? {locked}. Calling record(&log, ..)");
+    println!("  then would lock it again on the same thread, which deadlocks (or panics): lock()");
+    println!("  waits for a guard that can't be dropped until record returns. drop(guard) before");
+    n.say("the call releases it, and the log is {log}");
+
+    println!("\n  After drop(guard), guard has been moved, so using it again is error[E0382].");
+    println!("  A block around the guard ends the lock the same way, when that's clearer");
+}
+
+// Adds an entry to the log, taking the lock itself
+fn record(log: &Mutex<Vec<String>>, entry: &str) {
+    log.lock().unwrap().push(entry.to_string());
+}
+
+fn early_return() {
+    let mut n = Narration::new();
+
+    println!("  publish() creates a config, opens a connection with ?, may return early, then");
+    println!("  builds a report and hands it to the caller. Every value announces its drop:");
+
+    let paths = [("? on a failed open", Some(Stage::Open)), ("early return", Some(Stage::Check)), ("success", None)];
+    for (path, fail_at) in paths {
+        println!();
+        n.fact("path", path);
+        n.say("{path}:");
+        match publish(fail_at) {
+            Ok(report) => {
+                n.fact("report", report.0);
+                n.say("  publish returned Ok({report}), which the caller now owns");
+            } // the report is dropped here, by the caller
+            Err(error) => {
+                n.fact("error", error);
+                n.say("  publish returned Err(\"{error}\")");
+            }
+        }
+    }
+
+    println!("\n  Only values that exist at the point of the return are dropped, newest first.");
+    println!("  ? is just an early return: the connection never existed when open failed, so");
+    println!("  only config was cleaned up. The report was moved out on success, so publish");
+    println!("  didn't drop it; ownership (and the duty to drop) went to the caller");
+
+    println!("\n  One pitfall: `let _ = Traced(..)` doesn't bind anything, so the value is");
+    println!("  dropped at the end of that statement. `let _guard = ..` keeps it to the end of scope:");
+    {
+        let _ = Traced("unbound");
+        let _guard = Traced("guard");
+        n.say("  (end of the block)");
+    }
+}
+
+// Where publish should fail, if anywhere
+#[derive(Clone, Copy, PartialEq)]
+enum Stage {
+    Open,
+    Check,
+}
+
+// A value that prints a line when it is dropped
+struct Traced(&'static str);
+
+impl Drop for Traced {
+    fn drop(&mut self) {
+        println!("    drop {}", self.0);
+    }
+}
+
+// Runs its own drop before its fields', to show the order inside a value
+struct Pair {
+    first: Traced,
+    second: Traced,
+}
+
+impl Drop for Pair {
+    fn drop(&mut self) {
+        println!("    drop pair (before {} and {})", self.first.0, self.second.0);
+    }
+}
+
+// This function creates several values and leaves by a different path depending on fail_at
+fn publish(fail_at: Option<Stage>) -> Result<Traced, String> {
+    let _config = Traced("config");
+    let _connection = open(fail_at == Some(Stage::Open))?; // returns here on Err, dropping config
+
+    if fail_at == Some(Stage::Check) {
+        return Err(String::from("check failed")); // drops connection, then config
+    }
+
+    let report = Traced("report");
+    Ok(report) // report moves out; connection and config are dropped
+}
+
+// This function gives back an owned connection, or nothing at all if it fails
+fn open(fail: bool) -> Result<Traced, String> {
+    if fail {
+        return Err(String::from("could not connect"));
+    }
+    Ok(Traced("connection"))
+}
+
+const ORDER_CODE: &str = r#"struct Traced(&'static str);
+
+impl Drop for Traced {
+    fn drop(&mut self) {
+        println!("drop {}", self.0);
+    }
+}
+
+struct Pair {
+    first: Traced,
+    second: Traced,
+}
+
+impl Drop for Pair {
+    // Runs before the fields are dropped, so they can still be used here
+    fn drop(&mut self) {
+        println!("drop pair (before {} and {})", self.first.0, self.second.0);
+    }
+}
+
+fn main() {
+    let a = Traced("a");
+    let _b = Traced("b");
+    {
+        let _c = Traced("c");
+        let _d = Traced("d");
+        println!("end of the inner block");
+    } // drops d, then c
+    let _e = Traced("e");
+
+    // A moved value is dropped where its new owner goes out of scope
+    let f = Traced("f");
+    let _g = Traced("g");
+    let _h = f;
+
+    // Fields go in declaration order, after the struct's own drop;
+    // a Vec drops its elements front to back
+    let _pair = Pair { first: Traced("pair.first"), second: Traced("pair.second") };
+    let _list = Vec::from([Traced("list[0]"), Traced("list[1]")]);
+
+    // Calling the destructor directly isn't allowed; drop(a) would be
+    // a.drop();
+    println!("end of main {}", a.0);
+} // drops list[0], list[1], pair, pair.first, pair.second, f (as _h), g, e, b, a
+"#;
+
+const EARLY_DROP_CODE: &str = r#"use std::cell::RefCell;
+use std::sync::Mutex;
+
+struct TempFile(&'static str);
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        println!("removed {}", self.0);
+    }
+}
+
+fn record(log: &Mutex<Vec<String>>, entry: &str) {
+    log.lock().unwrap().push(entry.to_string());
+}
+
+fn main() {
+    // A resource released as soon as it's done with
+    let upload = TempFile("upload.tmp");
+    println!("sent {}", upload.0);
+    drop(upload);
+    println!("compressing the next batch");
+
+    // A runtime borrow ended early
+    let settings = RefCell::new(vec![String::from("dark mode")]);
+    let mut editing = settings.borrow_mut();
+    editing.push(String::from("large text"));
+    drop(editing); // without this, borrow() below panics: already mutably borrowed
+    println!("{:?}", settings.borrow());
+
+    // A lock released before calling something that takes it again
+    let log = Mutex::new(vec![String::from("started")]);
+    let guard = log.lock().unwrap();
+    println!("{} entries", guard.len());
+    drop(guard); // without this, record() waits for the lock forever
+    record(&log, "checked");
+    // println!("{} entries", guard.len());
+    println!("{:?}", log.lock().unwrap());
+}
+"#;
+
+const EARLY_RETURN_CODE: &str = r#"struct Traced(&'static str);
+
+impl Drop for Traced {
+    fn drop(&mut self) {
+        println!("drop {}", self.0);
+    }
+}
+
+fn open(fail: bool) -> Result<Traced, String> {
+    if fail {
+        return Err(String::from("could not connect"));
+    }
+    Ok(Traced("connection"))
+}
+
+fn publish(fail_open: bool, fail_check: bool) -> Result<Traced, String> {
+    let _config = Traced("config");
+    let _connection = open(fail_open)?; // on Err: drops config, returns
+
+    if fail_check {
+        return Err(String::from("check failed")); // drops connection, then config
+    }
+
+    let report = Traced("report");
+    Ok(report) // report moves to the caller; connection and config are dropped
+}
+
+fn main() {
+    println!("-- ? on a failed open");
+    let _ = publish(true, false);
+    println!("-- early return");
+    let _ = publish(false, true);
+    println!("-- success");
+    let report = publish(false, false);
+    println!("caller got the report");
+    drop(report);
+
+    // let _ drops immediately, let _guard at the end of the scope
+    let _ = Traced("unbound");
+    let _guard = Traced("guard");
+    println!("end of main");
+}
+"#;
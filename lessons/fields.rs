@@ -0,0 +1,145 @@
+use super::{BrokenLine, Example, Section, Stop};
+use crate::narration::Narration;
+
+pub const SECTION: Section = Section {
+    name: "fields",
+    title: "MOVING FIELDS OUT OF STRUCTS",
+    examples: &[Example {
+        name: "partial_move",
+        title: "Partial moves: taking one String field out of a struct",
+        run: partial_move,
+        code: PARTIAL_MOVE_CODE,
+        broken_lines: &[
+            BrokenLine {
+                line: r#"println!("{}", profile.name);"#,
+                error: "E0382",
+            },
+            BrokenLine {
+                line: r#"println!("{}", describe(&profile));"#,
+                error: "E0382",
+            },
+        ],
+        requires: &[],
+        crates: &[],
+        stops: &[
+                Stop {
+                    line: "let name = profile.name;",
+                    say: "Only the name field moves out of profile",
+                },
+                Stop {
+                    line: r#"println!("still there"#,
+                    say: "The fields that weren't moved can still be used",
+                },
+                Stop {
+                    line: r#"// println!("{}", profile.name);"#,
+                    say: "The moved field is gone: E0382",
+                },
+                Stop {
+                    line: r#"// println!("{}", describe(&profile));"#,
+                    say: "and profile can't be used as a whole either: E0382",
+                },
+                Stop {
+                    line: "profile.name = String::from",
+                    say: "Putting a value back makes profile whole again",
+                },
+                Stop {
+                    line: "let borrowed = &other.name;",
+                    say: "Borrowing a field leaves the struct whole",
+                },
+            ],
+    }],
+};
+
+fn partial_move() {
+    let mut n = Narration::new();
+
+    let mut profile = Profile { name: String::from("Ferris"), email: String::from("ferris@example.org"), visits: 3 };
+    n.address("name_buffer", profile.name.as_ptr()).address("email_buffer", profile.email.as_ptr());
+    n.say("profile owns two Strings: its name's text is at {name_buffer}, its email's at {email_buffer}");
+
+    let name = profile.name;
+    n.fact("name", &name).address("moved_buffer", name.as_ptr());
+    n.say("let name = profile.name; moved that one field out: name = {name}, its text still at");
+    n.say("{moved_buffer}. Only the String's pointer, length and capacity changed owner");
+
+    let visits = profile.visits;
+    n.fact("email", &profile.email).fact("visits", visits);
+    n.say("The rest of profile is still usable: profile.email = {email}, and profile.visits = {visits}");
+    n.say("(a u32 is Copy, so reading it into visits copied it and moved nothing)");
+    println!("  profile.name itself is gone: `println!(\"{{}}\", profile.name);` is error[E0382]. So is");
+    println!("  anything that needs profile as a whole, like describe(&profile): error[E0382], borrow");
+    println!("  of partially moved value");
+
+    profile.name = String::from("Ferris the crab");
+    n.fact("summary", describe(&profile));
+    n.say("Assigning a new String to profile.name makes profile whole again:");
+    n.say("describe(&profile) = {summary}");
+
+    let other = Profile { name: String::from("Corro"), email: String::from("corro@example.org"), visits: 2 };
+    let borrowed = &other.name;
+    n.fact("borrowed", borrowed).address("borrowed_at", borrowed.as_ptr()).address("field_text", other.name.as_ptr());
+    n.fact("summary", describe(&other));
+    println!();
+    n.say("Borrowing the field instead, let borrowed = &other.name; reads {borrowed} at {borrowed_at},");
+    n.say("the field's own text at {field_text}, and other stays whole: {summary}");
+
+    println!("\n  Moving a field out is fine while the struct is a local you own; the compiler tracks");
+    println!("  each field separately. It isn't allowed through a reference (that's error[E0507],");
+    println!("  see mem_take), or out of a type that implements Drop (error[E0509]), since its drop");
+    println!("  would run on a struct with a hole in it. Borrow the field when you only need to read");
+    println!("  it, and move it when the struct is done with it anyway");
+}
+
+struct Profile {
+    name: String,
+    email: String,
+    visits: u32,
+}
+
+fn describe(profile: &Profile) -> String {
+    format!("{} <{}>, {} visits", profile.name, profile.email, profile.visits)
+}
+
+const PARTIAL_MOVE_CODE: &str = r#"struct Profile {
+    name: String,
+    email: String,
+    visits: u32,
+}
+
+fn describe(profile: &Profile) -> String {
+    format!("{} <{}>, {} visits", profile.name, profile.email, profile.visits)
+}
+
+fn main() {
+    let mut profile = Profile {
+        name: String::from("Ferris"),
+        email: String::from("ferris@example.org"),
+        visits: 3,
+    };
+
+    // Moves only the name field out of profile
+    let name = profile.name;
+    println!("moved out: {}", name);
+    // The fields that weren't moved can still be used
+    println!("still there: {}, {} visits", profile.email, profile.visits);
+
+    // The moved field is gone
+    // println!("{}", profile.name);
+    // and so is profile as a whole
+    // println!("{}", describe(&profile));
+
+    // Putting a value back makes profile whole again
+    profile.name = String::from("Ferris the crab");
+    println!("whole again: {}", describe(&profile));
+
+    // Borrowing the field leaves the struct whole
+    let other = Profile {
+        name: String::from("Corro"),
+        email: String::from("corro@example.org"),
+        visits: 2,
+    };
+    let borrowed = &other.name;
+    println!("borrowed: {}", borrowed);
+    println!("{}", describe(&other));
+}
+"#;
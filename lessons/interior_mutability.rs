@@ -0,0 +1,704 @@
+use std::cell::{Cell, RefCell};
+use std::mem::size_of;
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use super::{BrokenLine, Example, Section, Stop};
+use crate::narration::Narration;
+use crate::toolchain::SCOPED_THREADS;
+
+pub const SECTION: Section = Section {
+    name: "interior_mutability",
+    title: "INTERIOR MUTABILITY",
+    examples: &[
+        Example {
+            name: "runtime_borrows",
+            title: "RefCell<T>: the borrowing rules, checked at runtime",
+            run: runtime_borrows,
+            code: RUNTIME_BORROWS_CODE,
+            broken_lines: &[BrokenLine {
+                line: "let second = &mut plain;",
+                error: "E0499",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let first = &mut plain;",
+                    say: "The compiler allows many & or one &mut",
+                },
+                Stop {
+                    line: "// let second = &mut plain;",
+                    say: "so a second &mut is E0499",
+                },
+                Stop {
+                    line: "let names = RefCell::new",
+                    say: "RefCell enforces the same rule while the program runs",
+                },
+                Stop {
+                    line: "let b = names.borrow();",
+                    say: "Any number of shared borrows at once",
+                },
+                Stop {
+                    line: "} // both Ref guards dropped here",
+                    say: "The borrows end when the guards are dropped",
+                },
+                Stop {
+                    line: "names.borrow_mut().push",
+                    say: "then one mutable borrow is allowed",
+                },
+                Stop {
+                    line: "let writing = cell.borrow_mut();",
+                    say: "Overlapping borrows compile, and panic when they happen",
+                },
+                Stop {
+                    line: r#"println!("conflict: {}", names.try_borrow_mut()"#,
+                    say: "try_borrow_mut reports the conflict instead",
+                },
+            ],
+        },
+        Example {
+            name: "cell_vs_refcell",
+            title: "Cell<i32> copies in and out, RefCell<String> lends",
+            run: cell_vs_refcell,
+            code: CELL_VS_REFCELL_CODE,
+            broken_lines: &[BrokenLine {
+                line: "let inside: &i32 = &*visitor.visits;",
+                error: "E0614",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "fn visit(&self, page: &str)",
+                    say: "Only &self, yet both fields change",
+                },
+                Stop {
+                    line: "self.visits.set(self.visits.get() + 1);",
+                    say: "Cell copies the number out and a new one in",
+                },
+                Stop {
+                    line: "self.log.borrow_mut().push_str(page);",
+                    say: "RefCell lends the String mutably, checked at runtime",
+                },
+                Stop {
+                    line: "let (first, second) = (&visitor, &visitor);",
+                    say: "Two shared references, and both can call visit",
+                },
+                Stop {
+                    line: "// let inside: &i32 = &*visitor.visits;",
+                    say: "A Cell never lends its contents out: E0614",
+                },
+                Stop {
+                    line: "let page = visitor.log.borrow();",
+                    say: "While this Ref is alive, the log can't be lent mutably",
+                },
+                Stop {
+                    line: "let mut edited = text.take();",
+                    say: "Cell<String> works by moving the whole String out",
+                },
+                Stop {
+                    line: "text.set(edited);",
+                    say: "and back in again",
+                },
+            ],
+        },
+        Example {
+            name: "choosing",
+            title: "Cell vs RefCell vs Mutex vs atomics: one counter, four ways",
+            run: choosing,
+            code: CHOOSING_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: "s.spawn(|| cell.set(cell.get() + 1));",
+                    error: "E0277",
+                },
+                BrokenLine {
+                    line: "let copy = name.get();",
+                    error: "E0599",
+                },
+            ],
+            requires: &[SCOPED_THREADS],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let cell = Cell::new(0);",
+                    say: "One thread, Copy payload: Cell copies values in and out",
+                },
+                Stop {
+                    line: "let refcell = RefCell::new(0);",
+                    say: "One thread, any payload: RefCell checks borrows at runtime",
+                },
+                Stop {
+                    line: "let mutex = Mutex::new(0);",
+                    say: "Many threads, any payload: Mutex locks around each access",
+                },
+                Stop {
+                    line: "let atomic = AtomicUsize::new(0);",
+                    say: "Many threads, integers or bools: atomics, no lock at all",
+                },
+                Stop {
+                    line: "// s.spawn(|| cell.set(cell.get() + 1));",
+                    say: "Cell isn't Sync, so threads can't share it: E0277",
+                },
+                Stop {
+                    line: "// let copy = name.get();",
+                    say: "Cell::get copies, so it needs a Copy payload: E0599",
+                },
+                Stop {
+                    line: "let taken = name.take();",
+                    say: "take, replace and set work for any payload",
+                },
+            ],
+        },
+        Example {
+            name: "held_guards",
+            title: "Guards that hold a borrow for too long",
+            run: held_guards,
+            code: HELD_GUARDS_CODE,
+            broken_lines: &[BrokenLine {
+                line: "drop(log);",
+                error: "E0505",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "fn record",
+                    say: "record borrows the log mutably for one line",
+                },
+                Stop {
+                    line: "//     let entries = log.borrow();",
+                    say: "Holding a Ref across a call to record would panic",
+                },
+                Stop {
+                    line: "let count = log.borrow().len();",
+                    say: "Copying out what's needed drops the Ref on the same line",
+                },
+                Stop {
+                    line: "let entries = log.borrow();",
+                    say: "A live guard keeps the RefCell borrowed",
+                },
+                Stop {
+                    line: "// drop(log);",
+                    say: "so moving the RefCell into drop is E0505",
+                },
+                Stop {
+                    line: "let job = queue.borrow_mut().pop();",
+                    say: "Popping into a local drops the RefMut before the body",
+                },
+                Stop {
+                    line: "Some(3) => queue.borrow_mut().push(10),",
+                    say: "so the body can borrow the queue again",
+                },
+                Stop {
+                    line: "let mut guard = total.lock().unwrap();",
+                    say: "A MutexGuard holds the lock",
+                },
+                Stop {
+                    line: "} // unlocked here",
+                    say: "It's unlocked at the end of the block",
+                },
+                Stop {
+                    line: "add(&total, 2);",
+                    say: "so add can lock it; holding it would deadlock",
+                },
+            ],
+        },
+    ],
+};
+
+// One row of the chart: a type, where it works, and what it costs
+struct Choice {
+    name: &'static str,
+    threads: &'static str,
+    payload: &'static str,
+    access: &'static str,
+    cost: &'static str,
+}
+
+const CHART: &[Choice] = &[
+    Choice {
+        name: "Cell<T>",
+        threads: "one",
+        payload: "Copy values",
+        access: "get/set",
+        cost: "nothing, never fails",
+    },
+    Choice {
+        name: "RefCell<T>",
+        threads: "one",
+        payload: "anything",
+        access: "borrow/borrow_mut",
+        cost: "borrow flag, panics on conflict",
+    },
+    Choice {
+        name: "Mutex<T>",
+        threads: "many",
+        payload: "anything",
+        access: "lock",
+        cost: "a lock, blocks when contended",
+    },
+    Choice {
+        name: "AtomicUsize",
+        threads: "many",
+        payload: "ints, bool, pointers",
+        access: "load/fetch_add",
+        cost: "atomic ops, no lock",
+    },
+];
+
+fn runtime_borrows() {
+    let mut n = Narration::new();
+
+    println!("  Section 2's rule: any number of & borrows, or one &mut, never both. With plain");
+    println!("  references the compiler enforces it (a second &mut is error[E0499]). A RefCell");
+    println!("  enforces the same rule while the program runs, so it can hand out &mut through &:\n");
+
+    let names = RefCell::new(vec![String::from("ada")]);
+    {
+        let first = names.borrow();
+        let second = names.borrow();
+        n.fact("first", first.len()).fact("second", second.len());
+        n.say("borrow() twice: two Ref guards alive at once, both reading ({first} and {second} names)");
+    }
+    names.borrow_mut().push(String::from("grace"));
+    n.fact("names", format!("{:?}", names.borrow()));
+    n.say("Both guards dropped, then borrow_mut().push(..) got the only RefMut: {names}");
+
+    println!("\n  Overlapping them compiles, since the compiler only sees & references. The RefCell");
+    println!("  keeps a count of live guards and panics when a new one would break the rule:");
+    let message = panic_message(|| {
+        let reading = names.borrow();
+        let mut writing = names.borrow_mut();
+        writing.push(format!("{} again", reading[0]));
+    });
+    n.fact("message", message.unwrap_or_default());
+    n.say("borrow_mut() while a borrow() is alive: panicked with '{message}'");
+    let message = panic_message(|| {
+        let mut one = names.borrow_mut();
+        let two = names.borrow_mut();
+        one.push(two[0].clone());
+    });
+    n.fact("message", message.unwrap_or_default());
+    n.say("borrow_mut() twice: panicked with '{message}'");
+    println!("  catch_unwind stopped each panic here. Both guards were dropped while unwinding,");
+    println!("  so the RefCell is usable again:");
+    n.fact("names", format!("{:?}", names.borrow()));
+    n.say("names = {names}, unchanged by either attempt");
+
+    // try_borrow_mut turns the conflict into a value instead of a panic
+    let reading = names.borrow();
+    n.fact("conflict", names.try_borrow_mut().is_err());
+    println!();
+    n.say("try_borrow_mut() with a Ref alive = Err: {conflict}, an error to handle rather than a panic");
+    drop(reading);
+    n.fact("ok", names.try_borrow_mut().is_ok());
+    n.say("After drop(reading), try_borrow_mut() = Ok: {ok}");
+    println!("  Same rule, different moment: the compiler rejects the program before it runs,");
+    println!("  RefCell stops it at the first conflicting borrow. That's the price of the freedom:");
+    println!("  a mistake becomes a crash in testing instead of a compile error");
+}
+
+fn cell_vs_refcell() {
+    let mut n = Narration::new();
+
+    // &self methods can still update both fields, each in its own way
+    let visitor = Visitor { visits: Cell::new(0), log: RefCell::new(String::new()) };
+    let (first, second) = (&visitor, &visitor);
+    first.visit("home");
+    second.visit("about");
+    n.fact("visits", visitor.visits.get()).fact("log", format!("{:?}", visitor.log.borrow()));
+    n.say("Two & references to one Visitor both called visit(&self): visits = {visits}, log = {log}");
+
+    println!("\n  Cell<i32>: get() copies the number out and set() copies a new one in");
+    n.fact("cell", size_of::<Cell<i32>>()).fact("plain", size_of::<i32>());
+    n.say("It's {cell} bytes, like a plain i32 ({plain}): there's nothing to track, because");
+    println!("  nobody can hold a &i32 into the cell (&*visitor.visits is error[E0614]: Cell has no");
+    println!("  Deref). With no reference to the inside, replacing the value can't invalidate one,");
+    println!("  so any number of &Cell<i32> can all set it. That's why get() needs T: Copy");
+
+    println!("\n  RefCell<String>: a String can't be copied out cheaply, and push_str wants &mut String,");
+    println!("  so RefCell lends the inside instead, and counts the loans:");
+    let page = visitor.log.borrow();
+    n.fact("page", &*page).fact("locked", visitor.log.try_borrow_mut().is_err());
+    n.say("  log.borrow() gives a Ref<String> (\"{page}\"); while it's alive try_borrow_mut() fails: {locked}");
+    drop(page);
+    n.fact("refcell", size_of::<RefCell<String>>()).fact("string", size_of::<String>());
+    n.say("It's {refcell} bytes to String's {string}: the extra word is that count of loans");
+    println!("  Every &mut String it hands out is the only reference to the String at that moment,");
+    println!("  checked as it happens. Shared XOR mutable still holds; it's just checked later\n");
+
+    // Cell works for non-Copy values too, but only by moving them in and out whole
+    let taken = visitor.log.take();
+    let as_cell = Cell::new(taken);
+    let mut text = as_cell.take();
+    text.push_str(" contact");
+    as_cell.set(text);
+    n.fact("text", format!("{:?}", as_cell.take()));
+    n.say("A Cell<String> can still be edited by take(), change, set(): {text}");
+    println!("  That works, but it moves the String out and back for every change. Rule of thumb:");
+    println!("  Cell for small Copy values (counters, flags, ids), RefCell when you need to reach");
+    println!("  into the value and change it in place. Neither is Sync, so neither can be raced on");
+}
+
+// Both fields change behind &self: a Copy counter and a growing String
+struct Visitor {
+    visits: Cell<i32>,
+    log: RefCell<String>,
+}
+
+impl Visitor {
+    fn visit(&self, page: &str) {
+        self.visits.set(self.visits.get() + 1);
+        let mut log = self.log.borrow_mut();
+        if !log.is_empty() {
+            log.push(' ');
+        }
+        log.push_str(page);
+    }
+}
+
+const INCREMENTS: usize = 1000;
+const THREADS: usize = 4;
+
+fn choosing() {
+    let mut n = Narration::new();
+
+    n.fact("increments", INCREMENTS);
+    n.say("Each counter below is shared through & references and still counts to {increments}");
+
+    // Single thread, Copy payload: Cell copies values in and out, nothing is ever borrowed
+    let cell = Cell::new(0_usize);
+    for _ in 0..INCREMENTS {
+        cell.set(cell.get() + 1);
+    }
+    n.fact("cell", cell.get()).fact("cell_size", size_of::<Cell<usize>>());
+    n.say("Cell<usize>:    {cell} ({cell_size} bytes, same as a plain usize)");
+
+    // Single thread, any payload: RefCell hands out guards and tracks them at runtime
+    let refcell = RefCell::new(0_usize);
+    for _ in 0..INCREMENTS {
+        *refcell.borrow_mut() += 1;
+    }
+    n.fact("refcell", *refcell.borrow()).fact("refcell_size", size_of::<RefCell<usize>>());
+    n.say("RefCell<usize>: {refcell} ({refcell_size} bytes, the extra word is the borrow flag)");
+
+    // Many threads: the Mutex locks around every increment, the atomic needs no lock
+    let mutex = Mutex::new(0_usize);
+    let atomic = AtomicUsize::new(0);
+    thread::scope(|s| {
+        for _ in 0..THREADS {
+            s.spawn(|| {
+                for _ in 0..INCREMENTS / THREADS {
+                    *mutex.lock().unwrap() += 1;
+                    atomic.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+    });
+    n.fact("threads", THREADS).fact("mutex", *mutex.lock().unwrap());
+    n.fact("mutex_size", size_of::<Mutex<usize>>()).fact("atomic", atomic.load(Ordering::Relaxed));
+    n.fact("atomic_size", size_of::<AtomicUsize>());
+    n.say("Mutex<usize>:   {mutex} from {threads} threads ({mutex_size} bytes, lock state included)");
+    n.say("AtomicUsize:    {atomic} from {threads} threads ({atomic_size} bytes, no lock at all)");
+    println!("  Cell and RefCell can't join in: they aren't Sync, so sharing them with a thread");
+    println!("  doesn't compile (error[E0277]: Cell<usize> cannot be shared between threads safely)");
+
+    println!("\n  Where each one fails:");
+    let guard = refcell.borrow();
+    n.fact("conflict", refcell.try_borrow_mut().is_err());
+    n.say("RefCell: try_borrow_mut() while a borrow() is alive is Err = {conflict}; borrow_mut() would panic");
+    drop(guard);
+    let name = Cell::new(String::from("counter"));
+    let taken = name.take();
+    n.fact("taken", &taken);
+    n.say("Cell<String>: get() needs T: Copy (error[E0599]), but take() moved out '{taken}'");
+
+    println!("\n  The chart:");
+    print_row(["type", "threads", "payload", "access", "cost"]);
+    for choice in CHART {
+        print_row([choice.name, choice.threads, choice.payload, choice.access, choice.cost]);
+    }
+    println!("\n  Rule of thumb: stay single-threaded with Cell for Copy values and RefCell for");
+    println!("  the rest; across threads, reach for an atomic when the state is one number or flag,");
+    println!("  and a Mutex when it is anything bigger or several values that must change together");
+}
+
+fn held_guards() {
+    let mut n = Narration::new();
+
+    let log = RefCell::new(vec![String::from("start")]);
+
+    // borrow() returns a Ref guard; the RefCell counts it as borrowed until the guard drops
+    let message = panic_message(|| {
+        let entries = log.borrow();
+        record(&log, "oops"); // needs borrow_mut while entries is still alive
+        println!("  unreachable: {:?}", entries);
+    });
+    n.fact("message", message.unwrap_or_default());
+    n.say("Calling record(&log, ..) while holding log.borrow() panicked: '{message}'");
+    println!("  The compiler can't see this one: record only gets &log, and RefCell moved the");
+    println!("  aliasing check to runtime. The bug is the guard's scope, not record");
+
+    // Fix: take what you need out of the guard so it drops on the same line
+    let count = log.borrow().len();
+    record(&log, &format!("had {} entries", count));
+    n.fact("log", format!("{:?}", log.borrow()));
+    n.say("Fix: let count = log.borrow().len(); drops the guard right away. log = {log}");
+
+    // A guard also borrows the RefCell itself, so this one the compiler does catch
+    println!("  Holding a guard while moving the RefCell away is a compile error instead:");
+    println!("  drop(log); with a live Ref doesn't compile (error[E0505]: log is borrowed)");
+
+    println!("\n  Temporaries in a while let (or match) scrutinee live until the end of the body:");
+    let queue = RefCell::new(vec![1, 2, 3]);
+    let message = panic_message(|| {
+        while let Some(job) = queue.borrow_mut().pop() {
+            if job == 3 {
+                queue.borrow_mut().push(10); // the RefMut from the condition is still alive
+            }
+        }
+    });
+    n.fact("message", message.unwrap_or_default());
+    println!("    while let Some(job) = queue.borrow_mut().pop() {{ queue.borrow_mut().push(10); }}");
+    n.say("panicked with '{message}'");
+
+    let queue = RefCell::new(vec![1, 2, 3]);
+    let mut done = Vec::new();
+    loop {
+        let job = queue.borrow_mut().pop(); // the RefMut drops at the end of this statement
+        match job {
+            Some(3) => {
+                queue.borrow_mut().push(10);
+                done.push(3);
+            }
+            Some(job) => done.push(job),
+            None => break,
+        }
+    }
+    n.fact("done", format!("{:?}", done));
+    n.say("Fix: pop into a local first, then match on it. Jobs run: {done}");
+
+    println!("\n  Mutex guards have the same shape, but the failure is worse: locking a std Mutex");
+    println!("  the current thread already holds deadlocks (or panics), it never returns an error");
+    let total = Mutex::new(0);
+    let guard = total.lock().unwrap();
+    n.fact("would_block", total.try_lock().is_err());
+    n.say("try_lock() while our own guard is alive fails: {would_block}; lock() would wait forever");
+    drop(guard);
+    {
+        let mut guard = total.lock().unwrap();
+        *guard += 1;
+    } // unlocked here, before add locks again
+    add(&total, 2);
+    n.fact("total", *total.lock().unwrap());
+    n.say("Fix: lock in a small block, then call add(&total, 2). total = {total}");
+    println!("  The same goes for .await in async code: a guard held across an await point stays");
+    println!("  locked while the task is suspended, and a std MutexGuard isn't Send, so the future");
+    println!("  can't move between threads. Drop the guard before awaiting");
+}
+
+// This function needs to mutate the log, so it takes a borrow_mut for one line
+fn record(log: &RefCell<Vec<String>>, entry: &str) {
+    log.borrow_mut().push(entry.to_string());
+}
+
+// This function locks total itself, so its caller must not be holding the lock
+fn add(total: &Mutex<i32>, amount: i32) {
+    *total.lock().unwrap() += amount;
+}
+
+// Runs f and returns its panic message, if it panicked, without printing the
+// usual panic report
+fn panic_message(f: impl FnOnce()) -> Option<String> {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(f));
+    panic::set_hook(previous);
+
+    let payload = result.err()?;
+    let message = payload.downcast_ref::<String>().cloned();
+    message.or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+}
+
+// This function prints one line of the chart with its columns lined up
+fn print_row([name, threads, payload, access, cost]: [&str; 5]) {
+    println!("  {:<12} {:<8} {:<21} {:<18} {}", name, threads, payload, access, cost);
+}
+
+const RUNTIME_BORROWS_CODE: &str = r#"use std::cell::RefCell;
+use std::panic;
+
+fn main() {
+    // With plain references the compiler enforces many & or one &mut
+    let mut plain = vec![1, 2, 3];
+    let first = &mut plain;
+    // let second = &mut plain;
+    first.push(4);
+
+    // RefCell enforces the same rule while the program runs
+    let names = RefCell::new(vec![String::from("ada")]);
+    {
+        let a = names.borrow();
+        let b = names.borrow(); // any number of shared borrows at once
+        println!("{} {}", a.len(), b.len());
+    } // both Ref guards dropped here
+    names.borrow_mut().push(String::from("grace")); // then one mutable borrow
+
+    // Overlapping borrows compile, and panic when they happen
+    let result = panic::catch_unwind(|| {
+        let cell = RefCell::new(0);
+        let reading = cell.borrow();
+        let writing = cell.borrow_mut(); // panics: already borrowed
+        println!("{} {}", reading, writing);
+    });
+    println!("overlapping borrows panicked: {}", result.is_err());
+
+    // try_borrow_mut reports the conflict instead of panicking
+    let reading = names.borrow();
+    println!("conflict: {}", names.try_borrow_mut().is_err());
+    drop(reading);
+    println!("{:?}", names.borrow());
+}
+"#;
+
+const CELL_VS_REFCELL_CODE: &str = r#"use std::cell::{Cell, RefCell};
+
+struct Visitor {
+    visits: Cell<i32>,
+    log: RefCell<String>,
+}
+
+impl Visitor {
+    // Only &self, yet both fields change
+    fn visit(&self, page: &str) {
+        // Cell: copy the number out, copy a new one in. No reference to the inside ever exists
+        self.visits.set(self.visits.get() + 1);
+        // RefCell: borrow the String mutably, checked at runtime, and edit it in place
+        self.log.borrow_mut().push_str(page);
+    }
+}
+
+fn main() {
+    let visitor = Visitor { visits: Cell::new(0), log: RefCell::new(String::new()) };
+    let (first, second) = (&visitor, &visitor);
+    first.visit("home ");
+    second.visit("about");
+
+    // A Cell never lends out its contents, so this doesn't compile
+    // let inside: &i32 = &*visitor.visits;
+    println!("{} visits: {}", visitor.visits.get(), visitor.log.borrow());
+
+    // While a Ref is alive, the String can't also be lent mutably
+    let page = visitor.log.borrow();
+    println!("{}", visitor.log.try_borrow_mut().is_err());
+    drop(page);
+
+    // Cell<String> works too, but only by moving the whole String out and back
+    let text = Cell::new(String::from("home"));
+    let mut edited = text.take();
+    edited.push_str(" about");
+    text.set(edited);
+    println!("{}", text.take());
+}
+"#;
+
+const CHOOSING_CODE: &str = r#"use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+fn main() {
+    // Single thread, Copy payload: Cell copies values in and out, nothing is borrowed
+    let cell = Cell::new(0);
+    for _ in 0..1000 {
+        cell.set(cell.get() + 1);
+    }
+
+    // Single thread, any payload: RefCell checks borrows at runtime
+    let refcell = RefCell::new(0);
+    for _ in 0..1000 {
+        *refcell.borrow_mut() += 1;
+    }
+
+    // Many threads, any payload: Mutex locks around each access
+    let mutex = Mutex::new(0);
+    // Many threads, integer or bool payload: atomics, no lock at all
+    let atomic = AtomicUsize::new(0);
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for _ in 0..250 {
+                    *mutex.lock().unwrap() += 1;
+                    atomic.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+        // Cell and RefCell aren't Sync, so threads can't share them
+        // s.spawn(|| cell.set(cell.get() + 1));
+    });
+
+    println!("Cell: {}, RefCell: {}", cell.get(), refcell.borrow());
+    println!("Mutex: {}, Atomic: {}", mutex.lock().unwrap(), atomic.load(Ordering::Relaxed));
+
+    // Cell::get copies the value out, so it needs a Copy payload
+    let name = Cell::new(String::from("counter"));
+    // let copy = name.get();
+    let taken = name.take(); // take, replace and set work for any payload
+    println!("{}", taken);
+}
+"#;
+
+const HELD_GUARDS_CODE: &str = r#"use std::cell::RefCell;
+use std::sync::Mutex;
+
+fn record(log: &RefCell<Vec<String>>, entry: &str) {
+    log.borrow_mut().push(entry.to_string());
+}
+
+fn add(total: &Mutex<i32>, amount: i32) {
+    *total.lock().unwrap() += amount;
+}
+
+fn main() {
+    let log = RefCell::new(vec![String::from("start")]);
+
+    // Holding the Ref while record calls borrow_mut panics at runtime:
+    //     let entries = log.borrow();
+    //     record(&log, "oops");
+    // Fix: copy out what you need, so the Ref is dropped on the same line
+    let count = log.borrow().len();
+    record(&log, &format!("had {} entries", count));
+
+    // A guard also keeps the RefCell borrowed, so it can't be moved or dropped
+    let entries = log.borrow();
+    // drop(log);
+    println!("{:?}", entries);
+    drop(entries);
+
+    // The guard in a while let condition lives for the whole loop body, so
+    // pushing inside `while let Some(job) = queue.borrow_mut().pop()` panics.
+    // Fix: pop into a local first
+    let queue = RefCell::new(vec![1, 2, 3]);
+    loop {
+        let job = queue.borrow_mut().pop(); // the RefMut is dropped here
+        match job {
+            Some(3) => queue.borrow_mut().push(10),
+            Some(job) => println!("job {}", job),
+            None => break,
+        }
+    }
+
+    // Same with Mutex, except locking twice on one thread deadlocks instead
+    let total = Mutex::new(0);
+    {
+        let mut guard = total.lock().unwrap();
+        *guard += 1;
+    } // unlocked here
+    add(&total, 2);
+    println!("total = {}", total.lock().unwrap());
+}
+"#;
@@ -0,0 +1,187 @@
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom};
+use std::path::Path;
+use std::process;
+
+use super::{BrokenLine, Example, Section, Stop};
+use crate::narration::Narration;
+
+pub const SECTION: Section = Section {
+    name: "io",
+    title: "FILES AND READERS: OWNING I/O HANDLES",
+    examples: &[Example {
+        name: "readers",
+        title: "A File inside a BufReader: into_inner and try_clone",
+        run: readers,
+        code: READERS_CODE,
+        broken_lines: &[
+            BrokenLine {
+                line: r#"println!("{:?}", file.metadata());"#,
+                error: "E0382",
+            },
+            BrokenLine {
+                line: "let second = BufReader::new(file);",
+                error: "E0382",
+            },
+            BrokenLine {
+                line: "reader.read_line(&mut header).unwrap();",
+                error: "E0382",
+            },
+        ],
+        requires: &[],
+        crates: &[],
+        stops: &[
+                Stop {
+                    line: "let file = File::open(&path)?;",
+                    say: "file owns the open file",
+                },
+                Stop {
+                    line: "let mut reader = BufReader::new(file);",
+                    say: "BufReader::new takes the File by value",
+                },
+                Stop {
+                    line: r#"// println!("{:?}", file.metadata());"#,
+                    say: "so file can't be used any more: E0382",
+                },
+                Stop {
+                    line: "// let second = BufReader::new(file);",
+                    say: "and a second reader can't own it too: E0382",
+                },
+                Stop {
+                    line: r#"println!("buffered but not read yet"#,
+                    say: "reader read ahead into its own buffer",
+                },
+                Stop {
+                    line: "let mut file = reader.into_inner();",
+                    say: "into_inner gives the File back and drops the buffer",
+                },
+                Stop {
+                    line: "// reader.read_line(&mut header).unwrap();",
+                    say: "into_inner consumed reader: E0382",
+                },
+                Stop {
+                    line: "let clone = file.try_clone()?;",
+                    say: "try_clone opens a second handle sharing the position",
+                },
+                Stop {
+                    line: "drop(file);",
+                    say: "Dropping the File closes it",
+                },
+            ],
+    }],
+};
+
+const SCORES: &str = "name,score\nana,7\nbo,9\ncai,4\n";
+
+fn readers() {
+    let mut n = Narration::new();
+
+    let path = env::temp_dir().join(format!("ownership-demo-{}-scores.csv", process::id()));
+    if let Err(e) = fs::write(&path, SCORES) {
+        println!("  Could not write a sample file to {}: {}", path.display(), e);
+        return;
+    }
+    n.fact("bytes", SCORES.len());
+    n.say("Wrote a sample CSV of {bytes} bytes: a header and three rows");
+
+    if let Err(e) = read_scores(&path, &mut n) {
+        println!("  Could not read the sample file back: {}", e);
+    }
+    let _ = fs::remove_file(&path);
+
+    println!("\n  A File is an owned OS handle, closed when it's dropped, so the usual rules decide");
+    println!("  who may use it and when it goes away. Wrappers like BufReader take it by value and");
+    println!("  give it back with into_inner(), minus anything they were holding. Two owners need two");
+    println!("  handles: try_clone() for the same open file (one shared position), or File::open");
+    println!("  again for a separate one");
+}
+
+fn read_scores(path: &Path, n: &mut Narration) -> io::Result<()> {
+    let before = open_descriptors();
+    let file = File::open(path)?;
+    if let (Some(before), Some(open)) = (before, open_descriptors()) {
+        n.fact("before", before).fact("open", open);
+        n.say("File::open gave us a File, which owns an open file descriptor ({before} -> {open} open)");
+    }
+
+    let mut reader = BufReader::new(file);
+    n.fact("capacity", reader.capacity());
+    n.say("BufReader::new(file) took the File by value: reader owns it now, behind a buffer of");
+    n.say("{capacity} bytes. Touching file again, even file.metadata(), is error[E0382], and so is");
+    n.say("a second BufReader::new(file): one File can't have two owners");
+
+    let mut header = String::new();
+    reader.read_line(&mut header)?;
+    n.fact("header", header.trim_end()).fact("buffered", reader.buffer().len());
+    n.say("read_line gave the header '{header}'. To find its newline, BufReader read a whole");
+    n.say("chunk from the file, and {buffered} bytes of that are still waiting in its buffer");
+
+    let mut file = reader.into_inner();
+    n.fact("position", file.stream_position()?).fact("header_len", header.len());
+    println!();
+    n.say("reader.into_inner() consumed the reader (using it afterwards is error[E0382]) and");
+    n.say("handed back the File. Its position is {position}, not {header_len}: the buffered bytes");
+    n.say("were dropped with the reader, so we seek back to {header_len} to carry on after the header");
+    file.seek(SeekFrom::Start(header.len() as u64))?;
+
+    let clone = file.try_clone()?;
+    let rows = BufReader::new(clone).lines().collect::<io::Result<Vec<String>>>()?;
+    n.fact("rows", format!("{:?}", rows)).fact("after", file.stream_position()?);
+    println!();
+    n.say("file.try_clone() asked the OS for a second handle to the same open file. A BufReader");
+    n.say("that owned the clone read {rows}, and file's own position moved to {after}");
+    n.say("as well: the two handles share one position in the file");
+
+    drop(file);
+    if let (Some(before), Some(now)) = (before, open_descriptors()) {
+        n.fact("before", before).fact("now", now);
+        n.say("The clone was closed when its BufReader was dropped, and drop(file) closed the");
+        n.say("original: {now} descriptors open, as before ({before})");
+    }
+    Ok(())
+}
+
+// How many file descriptors the process has open, where /proc lists them
+fn open_descriptors() -> Option<usize> {
+    fs::read_dir("/proc/self/fd").ok().map(|entries| entries.count())
+}
+
+const READERS_CODE: &str = r#"use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+
+fn main() -> std::io::Result<()> {
+    let path = std::env::temp_dir().join("readers-example.csv");
+    fs::write(&path, "name,score\nana,7\nbo,9\n")?;
+
+    let file = File::open(&path)?;
+    // BufReader::new takes the File by value
+    let mut reader = BufReader::new(file);
+    // The File belongs to reader now
+    // println!("{:?}", file.metadata());
+    // so a second reader can't own it as well
+    // let second = BufReader::new(file);
+
+    let mut header = String::new();
+    reader.read_line(&mut header)?;
+    println!("header: {}", header.trim_end());
+    println!("buffered but not read yet: {} bytes", reader.buffer().len());
+
+    // into_inner gives the File back and drops whatever was buffered
+    let mut file = reader.into_inner();
+    // into_inner consumed reader
+    // reader.read_line(&mut header).unwrap();
+    println!("file position: {}", file.stream_position()?);
+    file.seek(SeekFrom::Start(header.len() as u64))?;
+
+    // try_clone opens a second handle to the same file, sharing its position
+    let clone = file.try_clone()?;
+    let rows = BufReader::new(clone).lines().collect::<Result<Vec<String>, _>>()?;
+    println!("rows: {:?}", rows);
+    println!("file position after reading the clone: {}", file.stream_position()?);
+
+    // Dropping the File closes it
+    drop(file);
+    fs::remove_file(&path)
+}
+"#;
@@ -0,0 +1,642 @@
+use std::fmt::{self, Display};
+use std::thread;
+
+use super::{BrokenLine, Example, Section, Stop};
+use crate::narration::Narration;
+use crate::utils::get_first_word;
+
+pub const SECTION: Section = Section {
+    name: "lifetimes",
+    title: "LIFETIMES",
+    examples: &[
+        Example {
+            name: "longest",
+            title: "Connecting input and output borrows",
+            run: longest_example,
+            code: LONGEST_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: "fn longest_unannotated(x: &str, y: &str) -> &str { if x.len() > y.len() { x } else { y } }",
+                    error: "E0106",
+                },
+                BrokenLine {
+                    line: r#"println!("still longest: {}", result);"#,
+                    error: "E0597",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "fn longest<'a>",
+                    say: "'a ties the result to both inputs: valid only while both are",
+                },
+                Stop {
+                    line: "// fn longest_unannotated",
+                    say: "Without it rustc can't tell which input is borrowed: E0106",
+                },
+                Stop {
+                    line: "let string2",
+                    say: "string2 only lives until the end of the block",
+                },
+                Stop {
+                    line: "result = longest(",
+                    say: "so result, which may borrow string2, is limited to the block too",
+                },
+                Stop {
+                    line: r#"println!("longest: {}", result);"#,
+                    say: "Inside the block both strings are alive, so this is fine",
+                },
+                Stop {
+                    line: r#"// println!("still longest"#,
+                    say: "After it, result may point at freed text: E0597",
+                },
+            ],
+        },
+        Example {
+            name: "one_input",
+            title: "Tying the output to only one input",
+            run: one_input,
+            code: ONE_INPUT_CODE,
+            broken_lines: &[BrokenLine {
+                line: "fn key_of_wrong<'a>(entry: &'a str, separator: &str) -> &'a str { separator }",
+                error: "E0621",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "fn key_of<'a>",
+                    say: "Only entry is tied to the result; separator just lasts the call",
+                },
+                Stop {
+                    line: "// fn key_of_wrong",
+                    say: "Returning separator would break that promise: E0621",
+                },
+                Stop {
+                    line: "key = key_of(&entry, &separator);",
+                    say: "key borrows from entry alone",
+                },
+                Stop {
+                    line: "} // separator is dropped here",
+                    say: "so separator can go away first",
+                },
+                Stop {
+                    line: r#"println!("key: {}", key);"#,
+                    say: "and key is still valid, because entry is",
+                },
+            ],
+        },
+        Example {
+            name: "elision",
+            title: "The lifetimes rustc fills in for you",
+            run: elision,
+            code: ELISION_CODE,
+            broken_lines: &[BrokenLine {
+                line: "fn key_of(entry: &str, separator: &str) -> &str { entry }",
+                error: "E0106",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "fn count_words(s: &str)",
+                    say: "Rule 1: each reference parameter gets a lifetime of its own",
+                },
+                Stop {
+                    line: "fn get_first_word(s: &str)",
+                    say: "Rule 2: with one input lifetime, the output gets it",
+                },
+                Stop {
+                    line: "fn label(&self",
+                    say: "Rule 3: in a method, the output gets self's lifetime",
+                },
+                Stop {
+                    line: "fn label_explicit",
+                    say: "The same signature written out: the result is tied to 'a, not 'b",
+                },
+                Stop {
+                    line: "// fn key_of",
+                    say: "Two inputs and no self: no rule applies, so it's E0106",
+                },
+                Stop {
+                    line: "label = tag.label(&fallback);",
+                    say: "label borrows only from tag",
+                },
+                Stop {
+                    line: "} // fallback is dropped",
+                    say: "so fallback going away doesn't matter",
+                },
+            ],
+        },
+        Example {
+            name: "excerpt",
+            title: "Structs that hold references",
+            run: excerpt,
+            code: EXCERPT_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: "struct Unannotated { part: &str }",
+                    error: "E0106",
+                },
+                BrokenLine {
+                    line: r#"println!("after the block: {}", outlived.part);"#,
+                    error: "E0597",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "struct Excerpt<'a>",
+                    say: "part borrows text someone else owns, so Excerpt needs a lifetime",
+                },
+                Stop {
+                    line: "// struct Unannotated",
+                    say: "A reference field can't be left to elision: E0106",
+                },
+                Stop {
+                    line: "fn announce_and_return_part",
+                    say: "Rule 3: the result borrows from self",
+                },
+                Stop {
+                    line: "fn part(&self) -> &'a str",
+                    say: "With 'a the result borrows the text, so it can outlive the Excerpt",
+                },
+                Stop {
+                    line: "let excerpt = Excerpt { part: first_sentence };",
+                    say: "The Excerpt borrows novel through first_sentence",
+                },
+                Stop {
+                    line: "}; // short_lived is gone",
+                    say: "short_lived is gone, and kept still borrows novel",
+                },
+                Stop {
+                    line: "} // text is dropped here",
+                    say: "text is dropped, but outlived still points into it",
+                },
+                Stop {
+                    line: r#"// println!("after the block"#,
+                    say: "so using outlived after the block is E0597",
+                },
+            ],
+        },
+        Example {
+            name: "static",
+            title: "'static: lives forever, or owns everything it holds",
+            run: static_example,
+            code: STATIC_CODE,
+            broken_lines: &[BrokenLine {
+                line: "remember(&mut log, &name);",
+                error: "E0597",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "fn level_name(level: u8) -> &'static str",
+                    say: "Literals are part of the program, so they're valid until it exits",
+                },
+                Stop {
+                    line: "fn remember<T: Display + 'static>",
+                    say: "T: 'static means T borrows nothing short-lived",
+                },
+                Stop {
+                    line: "remember(&mut log, String::from",
+                    say: "An owned String borrows nothing, so it passes the bound",
+                },
+                Stop {
+                    line: "remember(&mut log, level);",
+                    say: "A &'static str passes too",
+                },
+                Stop {
+                    line: "// remember(&mut log, &name);",
+                    say: "A borrow of a local isn't 'static: E0597",
+                },
+                Stop {
+                    line: "log.clear();",
+                    say: "'static didn't make the String live forever: it's freed here",
+                },
+                Stop {
+                    line: "let handle = thread::spawn(move || name.len());",
+                    say: "move makes the thread own name, which satisfies 'static",
+                },
+            ],
+        },
+    ],
+};
+
+fn longest_example() {
+    let mut n = Narration::new();
+
+    println!("  fn longest<'a>(x: &'a str, y: &'a str) -> &'a str");
+    println!("  The result borrows from x or from y, and the caller can't know which, so 'a says");
+    println!("  it's valid only while both are. Without 'a, rustc can't tell (error[E0106]).\n");
+
+    let string1 = String::from("long string is long");
+    n.fact("string1", &string1).address("string1_buffer", string1.as_ptr());
+    n.say("string1 = '{string1}' (heap data at {string1_buffer})");
+    {
+        let string2 = String::from("xyz");
+        n.fact("string2", &string2).address("string2_buffer", string2.as_ptr());
+        n.say("string2 = '{string2}' (heap data at {string2_buffer}), inside an inner block");
+
+        let result = longest(string1.as_str(), string2.as_str());
+        n.fact("result", result).address("result_at", result.as_ptr());
+        n.say("longest(&string1, &string2) = '{result}', pointing at {result_at}: string1's data, not a copy");
+        println!("  Here 'a is the inner block: the shorter of the two borrows");
+    }
+    println!("  string2 is dropped at the end of the block, so the result can't be used after it,");
+    println!("  even though it happens to point into string1 (error[E0597]: string2 doesn't live");
+    println!("  long enough). The signature decides, not the values at runtime");
+}
+
+fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+    if x.len() > y.len() { x } else { y }
+}
+
+fn one_input() {
+    let mut n = Narration::new();
+
+    println!("  fn key_of<'a>(entry: &'a str, separator: &str) -> &'a str");
+    println!("  Only entry carries 'a, so the key borrows from entry alone. separator gets its own");
+    println!("  lifetime and only has to live for the call.\n");
+
+    let entry = String::from("editor=helix");
+    n.fact("entry", &entry).address("entry_buffer", entry.as_ptr());
+    n.say("entry = '{entry}' (heap data at {entry_buffer})");
+
+    let key;
+    {
+        let separator = String::from("=");
+        n.fact("separator", &separator);
+        key = key_of(&entry, &separator);
+        n.fact("key", key).address("key_at", key.as_ptr());
+        n.say("key_of(&entry, \"{separator}\") = '{key}', at {key_at} inside entry");
+    }
+    n.say("separator has been dropped, and key is still fine: '{key}'");
+    println!("  Returning separator instead would break the promise the signature makes, and");
+    println!("  rustc rejects the function itself (error[E0621]), before any caller is involved");
+}
+
+fn key_of<'a>(entry: &'a str, separator: &str) -> &'a str {
+    entry.split(separator).next().unwrap_or(entry)
+}
+
+fn elision() {
+    let mut n = Narration::new();
+
+    println!("  Most signatures with references have no lifetimes written out, because rustc");
+    println!("  applies three elision rules to fill them in:");
+    println!("    1. every reference parameter gets its own lifetime");
+    println!("    2. if there's exactly one input lifetime, every output reference gets it");
+    println!("    3. in a method taking &self or &mut self, output references get self's\n");
+
+    for (elided, explicit, rules) in [
+        ("fn count_words(s: &str) -> usize", "fn count_words<'a>(s: &'a str) -> usize", "rule 1"),
+        ("fn get_first_word(s: &str) -> &str", "fn get_first_word<'a>(s: &'a str) -> &'a str", "rules 1, 2"),
+        ("fn label(&self, fallback: &str) -> &str", "fn label<'a, 'b>(&'a self, fallback: &'b str) -> &'a str", "rules 1, 3"),
+        ("fn key_of(entry: &str, sep: &str) -> &str", "fn key_of<'a, 'b>(entry: &'a str, sep: &'b str) -> &'? str", "stuck"),
+    ] {
+        println!("  {}", elided);
+        println!("    reads as {}  ({})", explicit, rules);
+    }
+    println!("  key_of has two input lifetimes and no self, so no rule picks one: error[E0106]\n");
+
+    let text = String::from("borrowed words");
+    n.address("text_buffer", text.as_ptr()).fact("count", count_words(&text));
+    let word = get_first_word(&text);
+    n.fact("word", word).address("word_at", word.as_ptr());
+    n.say("get_first_word(&text) = '{word}' at {word_at}, inside text at {text_buffer} (rule 2)");
+    n.say("count_words(&text) = {count}: no output reference, so rule 1 is all it needs");
+
+    let tag = Tag { name: String::from("urgent") };
+    n.address("tag_buffer", tag.name.as_ptr());
+    let fallback = String::from("none");
+    let label = tag.label(&fallback);
+    n.fact("label", label).address("label_at", label.as_ptr());
+    drop(fallback);
+    n.say("tag.label(&fallback) = '{label}' at {label_at}, the tag's own name at {tag_buffer} (rule 3),");
+    n.say("so it's still usable after fallback is dropped: '{label}'");
+}
+
+fn count_words(s: &str) -> usize {
+    s.split_whitespace().count()
+}
+
+struct Tag {
+    name: String,
+}
+
+impl Tag {
+    // Rule 3 ties the result to self, so fallback can only be checked, not returned
+    fn label(&self, fallback: &str) -> &str {
+        if self.name.is_empty() || self.name == fallback { "(untagged)" } else { &self.name }
+    }
+}
+
+fn excerpt() {
+    let mut n = Narration::new();
+
+    println!("  struct Excerpt<'a> {{ part: &'a str }}");
+    println!("  An Excerpt doesn't own its text, it borrows it. 'a names that borrow, and says an");
+    println!("  Excerpt can't outlive the text it points into. Without it, struct Excerpt {{ part:");
+    println!("  &str }} is error[E0106]: a struct field can't leave its lifetime to elision\n");
+
+    let novel = String::from("Call me Ishmael. Some years ago, never mind how long precisely...");
+    n.address("novel_buffer", novel.as_ptr());
+    let first_sentence = novel.split('.').next().unwrap_or(&novel);
+    let excerpt = Excerpt { part: first_sentence };
+    n.fact("part", excerpt.part).address("part_at", excerpt.part.as_ptr());
+    n.fact("size", std::mem::size_of::<Excerpt>());
+    n.say("excerpt.part = '{part}' at {part_at}, the start of novel's text at {novel_buffer}");
+    n.say("An Excerpt is {size} bytes: the &str inside it, and nothing copied");
+
+    // Methods: elision ties &self results to the borrow of self, but a method can promise 'a
+    n.fact("level", excerpt.level());
+    n.say("excerpt.level() = {level}: a method that returns no reference needs no lifetimes");
+    let announced = excerpt.announce_and_return_part("here it is");
+    n.fact("announced", announced);
+    n.say("announce_and_return_part returned '{announced}', tied to &self by elision rule 3");
+
+    let kept = {
+        let short_lived = Excerpt { part: first_sentence };
+        short_lived.part()
+    };
+    n.fact("kept", kept).address("kept_at", kept.as_ptr());
+    n.say("part() returns &'a str, so '{kept}' (at {kept_at}) outlives the Excerpt it came from:");
+    println!("  it borrows from novel, not from the struct. With -> &str it would borrow the");
+    println!("  struct, and couldn't leave the block it was made in\n");
+
+    println!("  The other way around doesn't work: if novel were dropped while an Excerpt of it");
+    println!("  was still in use, that Excerpt would point at freed memory. rustc rejects it");
+    println!("  (error[E0597]: the owner does not live long enough)");
+}
+
+struct Excerpt<'a> {
+    part: &'a str,
+}
+
+impl<'a> Excerpt<'a> {
+    fn level(&self) -> i32 {
+        3
+    }
+
+    fn announce_and_return_part(&self, announcement: &str) -> &str {
+        println!("  Attention please: {}", announcement);
+        self.part
+    }
+
+    // 'a instead of the elided lifetime of &self: the result outlives this Excerpt
+    fn part(&self) -> &'a str {
+        self.part
+    }
+}
+
+fn static_example() {
+    let mut n = Narration::new();
+
+    // &'static str: the bytes are part of the program, so the borrow is valid until it exits
+    let level = level_name(3);
+    n.fact("level", level).address("level_at", level.as_ptr());
+    n.say("level_name(3) = '{level}' at {level_at}, a &'static str: the literal is stored in the");
+    println!("  program itself, not on the heap, so it can be returned from anywhere and kept");
+    println!("  as long as you like. That's the \"lives forever\" meaning of 'static\n");
+
+    // T: 'static: T holds no borrow shorter than 'static, which says nothing about how long T lives
+    println!("  fn remember<T: Display + 'static>(log: &mut Vec<Box<dyn Display>>, value: T)");
+    println!("  The bound asks that T borrows nothing short-lived; it says nothing about how long");
+    println!("  the value itself lives. A Ticket(String) passes because it owns its text\n");
+    let mut log: Vec<Box<dyn Display>> = Vec::new();
+    let ticket = Ticket(String::from("ticket-7"));
+    n.address("ticket_buffer", ticket.0.as_ptr());
+    remember(&mut log, ticket);
+    remember(&mut log, 42);
+    remember(&mut log, level);
+    n.fact("entries", log.len());
+    n.say("log holds {entries} entries: an owned Ticket (text at {ticket_buffer}), 42 and '{level}'");
+    println!("  &name, a borrow of a local String, would be refused: it's valid only until main");
+    println!("  returns, not for 'static (error[E0597]: name does not live long enough)");
+    log.clear();
+    println!("  The Ticket passed the 'static bound and was still freed the moment the log let go\n");
+
+    // thread::spawn's closure must be 'static, so it has to own what it uses
+    let name = String::from("worker");
+    n.address("name_buffer", name.as_ptr());
+    n.say("name's text is at {name_buffer}; thread::spawn(move || ...) takes ownership of it");
+    let handle = thread::spawn(move || {
+        let seen = name.as_ptr() as usize;
+        (name.len(), seen)
+    });
+    let (len, seen) = handle.join().expect("the thread doesn't panic");
+    n.fact("len", len).address("seen", seen as *const u8);
+    n.say("the thread read {len} bytes at {seen} and dropped the String when it finished");
+    println!("  spawn requires F: 'static because the thread may outlive the caller; a move");
+    println!("  closure owns its data, so it meets the bound without living forever (see");
+    println!("  threads_join_handle for the borrowing version that's rejected)");
+}
+
+fn level_name(level: u8) -> &'static str {
+    match level {
+        0 => "quiet",
+        1..=2 => "normal",
+        _ => "verbose",
+    }
+}
+
+fn remember<T: Display + 'static>(log: &mut Vec<Box<dyn Display>>, value: T) {
+    log.push(Box::new(value));
+}
+
+struct Ticket(String);
+
+impl Display for Ticket {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Drop for Ticket {
+    fn drop(&mut self) {
+        println!("  dropping Ticket '{}'", self.0);
+    }
+}
+
+const LONGEST_CODE: &str = r#"// 'a links the output to both inputs: it's valid only while both of them are
+fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+    if x.len() > y.len() { x } else { y }
+}
+
+// Without the annotation, rustc can't tell which input the result borrows from
+// fn longest_unannotated(x: &str, y: &str) -> &str { if x.len() > y.len() { x } else { y } }
+
+fn main() {
+    let string1 = String::from("long string is long");
+    let result;
+    {
+        let string2 = String::from("xyz");
+        result = longest(string1.as_str(), string2.as_str());
+        println!("longest: {}", result);
+    }
+    // result might borrow string2, which was dropped at the end of the block
+    // println!("still longest: {}", result);
+
+    println!("string1 is still here: {}", string1);
+}
+"#;
+
+const ONE_INPUT_CODE: &str = r#"// Only entry is tied to the result; separator just has to last for the call
+fn key_of<'a>(entry: &'a str, separator: &str) -> &'a str {
+    entry.split(separator).next().unwrap_or(entry)
+}
+
+// Returning separator would break the promise that the result borrows from entry
+// fn key_of_wrong<'a>(entry: &'a str, separator: &str) -> &'a str { separator }
+
+fn main() {
+    let entry = String::from("editor=helix");
+    let key;
+    {
+        let separator = String::from("=");
+        key = key_of(&entry, &separator);
+    } // separator is dropped here, but key only borrows entry
+
+    println!("key: {}", key);
+}
+"#;
+
+const ELISION_CODE: &str = r#"// Rule 1 gives each reference parameter its own lifetime; nothing is returned by reference
+fn count_words(s: &str) -> usize {
+    s.split_whitespace().count()
+}
+#[allow(clippy::needless_lifetimes)] // written out on purpose
+fn count_words_explicit<'a>(s: &'a str) -> usize {
+    s.split_whitespace().count()
+}
+
+// Rule 2: with exactly one input lifetime, the output gets it
+fn get_first_word(s: &str) -> &str {
+    s.split(' ').next().unwrap_or(s)
+}
+#[allow(clippy::needless_lifetimes)] // written out on purpose
+fn get_first_word_explicit<'a>(s: &'a str) -> &'a str {
+    s.split(' ').next().unwrap_or(s)
+}
+
+struct Tag {
+    name: String,
+}
+
+impl Tag {
+    // Rule 3: in a method, the output gets self's lifetime
+    fn label(&self, fallback: &str) -> &str {
+        if self.name.is_empty() || self.name == fallback { "(untagged)" } else { &self.name }
+    }
+    #[allow(clippy::needless_lifetimes)] // written out on purpose
+    fn label_explicit<'a, 'b>(&'a self, fallback: &'b str) -> &'a str {
+        if self.name.is_empty() || self.name == fallback { "(untagged)" } else { &self.name }
+    }
+}
+
+// Two input lifetimes and no self: no rule applies, so this needs an annotation
+// fn key_of(entry: &str, separator: &str) -> &str { entry }
+
+fn main() {
+    let text = String::from("borrowed words");
+    println!("{} {}", count_words(&text), count_words_explicit(&text));
+    println!("{} {}", get_first_word(&text), get_first_word_explicit(&text));
+
+    let tag = Tag { name: String::from("urgent") };
+    let label;
+    {
+        let fallback = String::from("none");
+        label = tag.label(&fallback);
+        println!("{}", tag.label_explicit(&fallback));
+    } // fallback is dropped, but label borrows only from tag
+    println!("{}", label);
+}
+"#;
+
+const EXCERPT_CODE: &str = r#"// part borrows from text someone else owns, so the struct needs a lifetime parameter
+struct Excerpt<'a> {
+    part: &'a str,
+}
+
+// A reference field can't be left to elision
+// struct Unannotated { part: &str }
+
+impl<'a> Excerpt<'a> {
+    fn level(&self) -> i32 {
+        3
+    }
+
+    // Elision rule 3: the result borrows from self
+    fn announce_and_return_part(&self, announcement: &str) -> &str {
+        println!("Attention please: {}", announcement);
+        self.part
+    }
+
+    // 'a: the result borrows from the text, so it can outlive the Excerpt
+    fn part(&self) -> &'a str {
+        self.part
+    }
+}
+
+fn main() {
+    let novel = String::from("Call me Ishmael. Some years ago...");
+    let first_sentence = novel.split('.').next().unwrap_or(&novel);
+    let excerpt = Excerpt { part: first_sentence };
+    println!("{} {}", excerpt.level(), excerpt.announce_and_return_part("here it is"));
+
+    let kept = {
+        let short_lived = Excerpt { part: first_sentence };
+        short_lived.part()
+    }; // short_lived is gone, kept still borrows novel
+    println!("{}", kept);
+
+    let outlived;
+    {
+        let text = String::from("Short-lived. Gone soon.");
+        outlived = Excerpt { part: text.split('.').next().unwrap_or(&text) };
+        println!("inside the block: {}", outlived.part);
+    } // text is dropped here, and outlived still borrows it
+    // println!("after the block: {}", outlived.part);
+}
+"#;
+
+const STATIC_CODE: &str = r#"use std::fmt::Display;
+use std::thread;
+
+// A string literal is part of the program, so a borrow of it is valid until the program exits
+fn level_name(level: u8) -> &'static str {
+    match level {
+        0 => "quiet",
+        1..=2 => "normal",
+        _ => "verbose",
+    }
+}
+
+// T: 'static means T borrows nothing short-lived, not that the value lives forever
+fn remember<T: Display + 'static>(log: &mut Vec<Box<dyn Display>>, value: T) {
+    log.push(Box::new(value));
+}
+
+fn main() {
+    let level = level_name(3);
+    println!("level: {}", level);
+
+    let mut log: Vec<Box<dyn Display>> = Vec::new();
+    remember(&mut log, String::from("ticket-7")); // owned: fine
+    remember(&mut log, 42);
+    remember(&mut log, level); // &'static str: fine
+
+    let name = String::from("worker");
+    // A borrow of a local only lasts until main returns, which isn't 'static
+    // remember(&mut log, &name);
+    log.clear(); // the String passed the bound and is freed here anyway
+
+    // spawn needs a 'static closure; move makes it own name instead of borrowing it
+    let handle = thread::spawn(move || name.len());
+    println!("the thread saw {} bytes", handle.join().unwrap());
+}
+"#;
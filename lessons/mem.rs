@@ -0,0 +1,464 @@
+use std::mem;
+
+use super::{BrokenLine, Example, Section, Stop};
+use crate::instrument;
+use crate::narration::Narration;
+
+pub const SECTION: Section = Section {
+    name: "mem",
+    title: "MOVING OUT FROM BEHIND &mut: SWAP, REPLACE AND TAKE",
+    examples: &[
+        Example {
+            name: "swap",
+            title: "Swapping two owned values behind &mut",
+            run: swap,
+            code: SWAP_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: "let held = *first;",
+                    error: "E0507",
+                },
+                BrokenLine {
+                    line: "mem::swap(&mut pages[0], &mut pages[1]);",
+                    error: "E0499",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "fn swap_pages",
+                    say: "Both Strings are behind &mut, owned by the caller",
+                },
+                Stop {
+                    line: "// let held = *first;",
+                    say: "Moving out would leave *first empty: E0507",
+                },
+                Stop {
+                    line: "mem::swap(first, second);",
+                    say: "swap exchanges them, so both stay valid throughout",
+                },
+                Stop {
+                    line: r#"println!("front = {}"#,
+                    say: "front and back traded contents without any copying",
+                },
+                Stop {
+                    line: "// mem::swap(&mut pages[0], &mut pages[1]);",
+                    say: "Two &mut into one Vec at once is E0499",
+                },
+                Stop {
+                    line: "pages.swap(0, 2);",
+                    say: "Vec::swap does it with a single &mut",
+                },
+            ],
+        },
+        Example {
+            name: "replace",
+            title: "Replacing a field and keeping the old value",
+            run: replace,
+            code: REPLACE_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: "let old = self.state;",
+                    error: "E0507",
+                },
+                BrokenLine {
+                    line: "let old = mem::take(&mut self.state);",
+                    error: "E0277",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "fn flush(&mut self)",
+                    say: "flush only has &mut self, so state can't simply move out",
+                },
+                Stop {
+                    line: "// let old = self.state;",
+                    say: "Moving out of a field behind &mut is E0507",
+                },
+                Stop {
+                    line: "// let old = mem::take",
+                    say: "mem::take needs State: Default, which it isn't: E0277",
+                },
+                Stop {
+                    line: "let old = mem::replace",
+                    say: "replace moves the old state out and puts a new one in",
+                },
+                Stop {
+                    line: "self.state = State::Sent(bytes);",
+                    say: "The placeholder is then overwritten with the real state",
+                },
+            ],
+        },
+        Example {
+            name: "take",
+            title: "Taking a String out of a struct with mem::take",
+            run: take,
+            code: TAKE_CODE,
+            broken_lines: &[BrokenLine {
+                line: "let body = self.body;",
+                error: "E0507",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "fn send(&mut self)",
+                    say: "send has &mut self, not the Message",
+                },
+                Stop {
+                    line: "// let body = self.body;",
+                    say: "so moving the body out is E0507",
+                },
+                Stop {
+                    line: "mem::take(&mut self.body)",
+                    say: "take moves it out and leaves an empty String, which doesn't allocate",
+                },
+                Stop {
+                    line: r#"println!("body is now"#,
+                    say: "The message is still whole, with an empty body",
+                },
+            ],
+        },
+        Example {
+            name: "option_take",
+            title: "Option::take: moving the next node out of a linked list",
+            run: option_take,
+            code: OPTION_TAKE_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: "let old_head = self.head;",
+                    error: "E0507",
+                },
+                BrokenLine {
+                    line: "return self.head.map(|node| node.value);",
+                    error: "E0507",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let node = Box::new(Node { value, next: self.head.take() });",
+                    say: "take() moves the old head out and leaves None",
+                },
+                Stop {
+                    line: "self.head = Some(node);",
+                    say: "The new node becomes the head",
+                },
+                Stop {
+                    line: "// let old_head = self.head;",
+                    say: "Moving the head out from behind &mut self is E0507",
+                },
+                Stop {
+                    line: "// return self.head.map",
+                    say: "map takes the Option by value, so that's E0507 too",
+                },
+                Stop {
+                    line: "let node = self.head.take()?;",
+                    say: "take() again: the node is ours and head is None",
+                },
+                Stop {
+                    line: "self.head = node.next;",
+                    say: "The rest of the list moves back into head",
+                },
+            ],
+        },
+    ],
+};
+
+fn swap() {
+    let mut n = Narration::new();
+
+    let mut front = String::from("front page draft");
+    let mut back = String::from("back page draft");
+    n.address("front_buffer", front.as_ptr()).address("back_buffer", back.as_ptr());
+    n.say("front owns its text at {front_buffer}, back owns its text at {back_buffer}");
+
+    // Through &mut we may change a value, but not leave it empty even for a moment
+    swap_pages(&mut front, &mut back);
+    n.fact("front", &front).fact("back", &back);
+    n.address("front_buffer", front.as_ptr()).address("back_buffer", back.as_ptr());
+    n.say("After swap_pages(&mut front, &mut back): front = '{front}' at {front_buffer},");
+    n.say("back = '{back}' at {back_buffer}. The Strings traded places; no text was copied");
+
+    println!("\n  swap_pages only has &mut String, so `let held = *first;` is error[E0507]: moving");
+    println!("  the String out would leave *first with nothing in it while the caller still owns");
+    println!("  it. mem::swap(first, second) exchanges the two values in one step instead");
+
+    let mut pages = vec![String::from("one"), String::from("two"), String::from("three")];
+    pages.swap(0, 2);
+    n.fact("pages", format!("{:?}", pages));
+    println!();
+    n.say("Two elements of one Vec are a different story: mem::swap(&mut pages[0], &mut");
+    n.say("pages[1]) is error[E0499], two &mut into pages at once. The Vec swaps its own");
+    n.say("elements instead: pages.swap(0, 2) gives {pages}");
+}
+
+// Swaps the two pages' contents, with only &mut access to them
+fn swap_pages(first: &mut String, second: &mut String) {
+    mem::swap(first, second);
+}
+
+fn replace() {
+    let mut n = Narration::new();
+
+    let mut upload = Upload { name: "report.pdf", state: State::Buffering(vec![4096, 4096, 1200]) };
+    n.fact("name", upload.name).fact("state", upload.state.describe());
+    n.say("{name} is {state}");
+
+    let sent = upload.flush();
+    n.fact("sent", sent).fact("state", upload.state.describe());
+    n.say("flush() sent {sent} bytes; the upload is now {state}");
+    let again = upload.flush();
+    n.fact("again", again);
+    n.say("Flushing again sends {again} bytes: the chunks were moved out, not copied");
+
+    println!("\n  flush has &mut self, so it can't move the chunks out of self.state: `let old =");
+    println!("  self.state;` is error[E0507]. mem::replace(&mut self.state, State::Sent(0)) puts");
+    println!("  a placeholder in and hands back the old state, chunks and all, so the upload is");
+    println!("  never without a state. mem::take would need State to have a Default, which it");
+    println!("  doesn't (error[E0277]); replace works with any value you choose");
+}
+
+// An upload either buffering chunks or done, having sent some bytes
+enum State {
+    Buffering(Vec<usize>),
+    Sent(usize),
+}
+
+impl State {
+    fn describe(&self) -> String {
+        match self {
+            State::Buffering(chunks) => format!("buffering {} chunks", chunks.len()),
+            State::Sent(bytes) => format!("sent ({} bytes)", bytes),
+        }
+    }
+}
+
+struct Upload {
+    name: &'static str,
+    state: State,
+}
+
+impl Upload {
+    // Sends whatever is buffered and returns how many bytes that was
+    fn flush(&mut self) -> usize {
+        let old = mem::replace(&mut self.state, State::Sent(0));
+        let bytes = match old {
+            State::Buffering(chunks) => chunks.iter().sum(),
+            State::Sent(_) => 0,
+        };
+        self.state = State::Sent(bytes);
+        bytes
+    }
+}
+
+fn take() {
+    let mut n = Narration::new();
+
+    let mut message = Message { to: "ana", body: String::from("Lunch at noon?") };
+    n.fact("to", message.to).fact("body", &message.body).address("buffer", message.body.as_ptr());
+    n.say("A message to {to} with body '{body}' (its text at {buffer})");
+
+    let (body, allocations) = instrument::measure(|| message.send());
+    n.fact("sent", &body).address("sent_buffer", body.as_ptr()).fact("allocations", allocations.count);
+    n.fact("left", format!("{:?}", message.body));
+    n.say("send() returned '{sent}', the same text at {sent_buffer}, with {allocations} allocations.");
+    n.say("The message is still whole: its body is now {left}, ready for the next one");
+
+    println!("\n  mem::take(&mut self.body) is mem::replace with the type's Default, and an empty");
+    println!("  String doesn't allocate, so taking costs nothing. `let body = self.body;` would be");
+    println!("  error[E0507], and self.body.clone() would copy the text and keep a stale draft");
+}
+
+struct Message {
+    to: &'static str,
+    body: String,
+}
+
+impl Message {
+    // Hands the body over for sending and leaves an empty one for the next message
+    fn send(&mut self) -> String {
+        mem::take(&mut self.body)
+    }
+}
+
+fn option_take() {
+    let mut n = Narration::new();
+
+    let mut stack = Stack { head: None };
+    for value in ["first", "second", "third"] {
+        stack.push(String::from(value));
+    }
+    let top = stack.peek().unwrap_or("");
+    n.fact("top", top).address("top_text", top.as_ptr());
+    n.say("Pushed first, second and third. Each push did self.head.take() to move the old");
+    n.say("head into the new node's next, so the top is {top} (its text at {top_text})");
+
+    let mut popped = Vec::new();
+    while let Some(value) = stack.pop() {
+        popped.push(value);
+    }
+    n.fact("popped", popped.join(", ")).fact("empty", stack.head.is_none());
+    n.fact("last_in", &popped[0]).address("popped_text", popped[0].as_ptr());
+    n.say("pop() until it returns None gives {popped}, with {last_in} still at {popped_text};");
+    n.say("head is None again: {empty}");
+
+    println!("\n  pop has &mut self, and self.head is an Option<Box<Node>> it doesn't own, so");
+    println!("  `let old_head = self.head;` is error[E0507]. So is self.head.map(..): map takes the");
+    println!("  Option by value, even though it looks like it only reads it. take() moves the Box");
+    println!("  out and leaves None in its place, so the stack is valid at every step, and no node");
+    println!("  or String is copied: the Box that was the head is unpacked, its next becomes the");
+    println!("  new head, and its value is returned");
+}
+
+// A singly linked stack: each node owns the rest of the list
+struct Stack {
+    head: Option<Box<Node>>,
+}
+
+struct Node {
+    value: String,
+    next: Option<Box<Node>>,
+}
+
+impl Stack {
+    fn push(&mut self, value: String) {
+        let node = Box::new(Node { value, next: self.head.take() });
+        self.head = Some(node);
+    }
+
+    fn pop(&mut self) -> Option<String> {
+        let node = self.head.take()?;
+        self.head = node.next;
+        Some(node.value)
+    }
+
+    fn peek(&self) -> Option<&str> {
+        self.head.as_ref().map(|node| node.value.as_str())
+    }
+}
+
+const SWAP_CODE: &str = r#"use std::mem;
+
+fn swap_pages(first: &mut String, second: &mut String) {
+    // Moving out would leave *first empty while its owner still has it
+    // let held = *first;
+    mem::swap(first, second);
+}
+
+fn main() {
+    let mut front = String::from("front page draft");
+    let mut back = String::from("back page draft");
+    swap_pages(&mut front, &mut back);
+    println!("front = {}, back = {}", front, back);
+
+    // Two &mut into the same Vec at once aren't allowed
+    let mut pages = vec![String::from("one"), String::from("two"), String::from("three")];
+    // mem::swap(&mut pages[0], &mut pages[1]);
+    pages.swap(0, 2);
+    println!("{:?}", pages);
+}
+"#;
+
+const REPLACE_CODE: &str = r#"use std::mem;
+
+enum State {
+    Buffering(Vec<usize>),
+    Sent(usize),
+}
+
+struct Upload {
+    state: State,
+}
+
+impl Upload {
+    fn flush(&mut self) -> usize {
+        // Can't move out of a field behind &mut self
+        // let old = self.state;
+        // take needs State: Default
+        // let old = mem::take(&mut self.state);
+        let old = mem::replace(&mut self.state, State::Sent(0));
+        let bytes = match old {
+            State::Buffering(chunks) => chunks.iter().sum(),
+            State::Sent(_) => 0,
+        };
+        self.state = State::Sent(bytes);
+        bytes
+    }
+}
+
+fn main() {
+    let mut upload = Upload { state: State::Buffering(vec![4096, 4096, 1200]) };
+    println!("sent {} bytes", upload.flush());
+    println!("then {} bytes", upload.flush());
+    if let State::Sent(bytes) = upload.state {
+        println!("state: sent {}", bytes);
+    }
+}
+"#;
+
+const TAKE_CODE: &str = r#"use std::mem;
+
+struct Message {
+    to: String,
+    body: String,
+}
+
+impl Message {
+    fn send(&mut self) -> String {
+        // Can't move out of a field behind &mut self
+        // let body = self.body;
+        mem::take(&mut self.body) // leaves String::new(), which doesn't allocate
+    }
+}
+
+fn main() {
+    let mut message = Message { to: String::from("ana"), body: String::from("Lunch at noon?") };
+    let body = message.send();
+    println!("sent '{}' to {}", body, message.to);
+    println!("body is now {:?}", message.body);
+}
+"#;
+
+const OPTION_TAKE_CODE: &str = r#"struct Node {
+    value: String,
+    next: Option<Box<Node>>,
+}
+
+struct Stack {
+    head: Option<Box<Node>>,
+}
+
+impl Stack {
+    fn push(&mut self, value: String) {
+        // take() moves the old head out and leaves None behind
+        let node = Box::new(Node { value, next: self.head.take() });
+        self.head = Some(node);
+    }
+
+    fn pop(&mut self) -> Option<String> {
+        // Can't move the head out from behind &mut self
+        // let old_head = self.head;
+        // map takes the Option by value, so this moves it too
+        // return self.head.map(|node| node.value);
+        let node = self.head.take()?;
+        self.head = node.next;
+        Some(node.value)
+    }
+}
+
+fn main() {
+    let mut stack = Stack { head: None };
+    for value in ["first", "second", "third"] {
+        stack.push(String::from(value));
+    }
+    while let Some(value) = stack.pop() {
+        println!("popped {}", value);
+    }
+    println!("empty: {}", stack.head.is_none());
+}
+"#;
@@ -0,0 +1,323 @@
+use std::any;
+use std::rc::Rc;
+
+use super::{BrokenLine, Example, Section, Stop};
+use crate::narration::Narration;
+
+pub const SECTION: Section = Section {
+    name: "methods",
+    title: "METHOD CALLS: AUTO-REF AND AUTO-DEREF",
+    examples: &[
+        Example {
+            name: "auto_ref",
+            title: "value.method(): the &, &mut or * the compiler adds for you",
+            run: auto_ref,
+            code: AUTO_REF_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: r#"shared.add("Encore");"#,
+                    error: "E0596",
+                },
+                BrokenLine {
+                    line: "let songs = shared.into_songs();",
+                    error: "E0507",
+                },
+                BrokenLine {
+                    line: r#"println!("{}", boxed.count());"#,
+                    error: "E0382",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: r#"list.add("Intro");"#,
+                    say: "list.add is Playlist::add(&mut list, ..): a mutable borrow",
+                },
+                Stop {
+                    line: r#"println!("{} song", list.count());"#,
+                    say: "list.count is Playlist::count(&list): a shared borrow",
+                },
+                Stop {
+                    line: r#"boxed.add("Outro");"#,
+                    say: "Through a Box the call derefs first: add(&mut *boxed, ..)",
+                },
+                Stop {
+                    line: "let shared = Rc::new",
+                    say: "An Rc derefs to &Playlist, never to &mut Playlist",
+                },
+                Stop {
+                    line: r#"// shared.add("Encore");"#,
+                    say: "so calling a &mut self method is E0596",
+                },
+                Stop {
+                    line: "// let songs = shared.into_songs();",
+                    say: "and a self method can't move it out of the Rc: E0507",
+                },
+                Stop {
+                    line: "let songs = boxed.into_songs();",
+                    say: "A Box owns its Playlist, so into_songs moves it out",
+                },
+                Stop {
+                    line: r#"// println!("{}", boxed.count());"#,
+                    say: "The Playlist inside boxed is gone: E0382",
+                },
+            ],
+        },
+        Example {
+            name: "method_surprises",
+            title: "When the method found isn't the one you meant: clone and into_iter",
+            run: method_surprises,
+            code: METHOD_SURPRISES_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: "let text: String = shared.clone();",
+                    error: "E0308",
+                },
+                BrokenLine {
+                    line: "let owned: Ticket = borrowed.clone();",
+                    error: "E0308",
+                },
+                BrokenLine {
+                    line: r#"println!("{:?}", names);"#,
+                    error: "E0382",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let another = shared.clone();",
+                    say: "Rc's own clone is found first: another owner, not a copy",
+                },
+                Stop {
+                    line: "// let text: String = shared.clone();",
+                    say: "It returns Rc<String>, not String: E0308",
+                },
+                Stop {
+                    line: "let text = String::clone(&shared);",
+                    say: "Naming the type picks String's clone, which copies the text",
+                },
+                Stop {
+                    line: "let copied = borrowed.clone();",
+                    say: "Ticket isn't Clone, but &Ticket is: this copies the reference",
+                },
+                Stop {
+                    line: "// let owned: Ticket = borrowed.clone();",
+                    say: "No Ticket was cloned: E0308",
+                },
+                Stop {
+                    line: "for name in view.into_iter()",
+                    say: "Through a reference, into_iter only borrows the Vec",
+                },
+                Stop {
+                    line: "let owned: Vec<String> = names.into_iter().collect();",
+                    say: "On the Vec itself, into_iter moves it",
+                },
+                Stop {
+                    line: r#"// println!("{:?}", names);"#,
+                    say: "names went into the iterator: E0382",
+                },
+            ],
+        },
+    ],
+};
+
+fn auto_ref() {
+    let mut n = Narration::new();
+
+    let mut list = Playlist { songs: Vec::new() };
+    list.add("Intro");
+    Playlist::add(&mut list, "Interlude");
+    n.fact("count", list.count()).fact("same", Playlist::count(&list));
+    n.say("list.add(\"Intro\") is Playlist::add(&mut list, \"Intro\"): add takes &mut self, so the");
+    n.say("call borrows list mutably for its duration, and the written-out form does the same.");
+    n.say("list.count() is Playlist::count(&list) = {count} (spelled out: {same}). Neither moved list");
+
+    let mut boxed = Box::new(list);
+    boxed.add("Outro");
+    let inside: &Playlist = &boxed;
+    n.address("playlist", inside as *const Playlist).address("songs", boxed.songs.as_ptr());
+    n.fact("count", boxed.count());
+    println!();
+    n.say("Box::new(list) moved the Playlist to the heap, at {playlist}. Box has no add or count,");
+    n.say("so the call derefs: boxed.add(..) is Playlist::add(&mut *boxed, ..), and boxed.count()");
+    n.say("= {count}. The songs themselves, at {songs}, didn't move when the Playlist did");
+
+    let shared = Rc::new(Playlist { songs: vec![String::from("Theme")] });
+    n.fact("count", shared.count());
+    println!();
+    n.say("An Rc derefs too, but only to &Playlist: shared.count() = {count} works, while");
+    n.say("shared.add(\"Encore\") is error[E0596], since an Rc may not be the only owner. And");
+    n.say("shared.into_songs() is error[E0507]: a self method would move the Playlist out of an Rc");
+
+    let songs = boxed.into_songs();
+    n.fact("songs", format!("{:?}", songs)).address("songs_now", songs.as_ptr());
+    println!();
+    n.say("boxed.into_songs() is allowed: the Box owns its Playlist, so *boxed can be moved out,");
+    n.say("and into_songs(self) consumed it. songs = {songs}, still at {songs_now}, and");
+    n.say("boxed.count() afterwards is error[E0382]");
+
+    println!("\n  For value.method(), the compiler tries value's own type, then &value, then");
+    println!("  &mut value, and if none has the method, derefs (through &, Box, Rc, String, Vec..)");
+    println!("  and tries again. So the call site never says whether the value is borrowed, mutably");
+    println!("  borrowed or moved: the method's self parameter does. Look at that to see why a call");
+    println!("  did or didn't move something");
+}
+
+struct Playlist {
+    songs: Vec<String>,
+}
+
+impl Playlist {
+    fn count(&self) -> usize {
+        self.songs.len()
+    }
+
+    fn add(&mut self, song: &str) {
+        self.songs.push(song.to_string());
+    }
+
+    fn into_songs(self) -> Vec<String> {
+        self.songs
+    }
+}
+
+fn method_surprises() {
+    let mut n = Narration::new();
+
+    let shared = Rc::new(String::from("opening night"));
+    let another = shared.clone();
+    let text = String::clone(&shared);
+    n.address("shared_text", shared.as_ptr()).address("another_text", another.as_ptr());
+    n.address("text", text.as_ptr()).fact("kind", type_of(&another)).fact("owners", Rc::strong_count(&shared));
+    n.say("shared.clone() found Rc's clone before String's: Rc<String> is the receiver's own");
+    n.say("type, so it's tried before deref. It returned an {kind}, text at {another_text}, the same");
+    n.say("as shared's {shared_text}, now with {owners} owners, so `let text: String = shared.clone();`");
+    n.say("is error[E0308]. String::clone(&shared) names the method: a new String at {text}");
+
+    let ticket = Ticket { seat: String::from("B12") };
+    let borrowed = &ticket;
+    #[allow(noop_method_call)] // the surprise this example is about
+    let copied = borrowed.clone();
+    n.fact("kind", type_of(&copied)).fact("seat", &copied.seat).address("ticket", &ticket as *const Ticket).address("copied", copied as *const Ticket);
+    println!();
+    n.say("Ticket doesn't implement Clone, but every &T is Copy, so borrowed.clone() found Clone");
+    n.say("for &Ticket and copied the reference: a {kind} to seat {seat} at {copied}, the same Ticket as {ticket}.");
+    n.say("`let owned: Ticket = borrowed.clone();` is error[E0308], and rustc warns about the call");
+
+    let names = vec![String::from("ana"), String::from("bo")];
+    let view = &names;
+    #[allow(clippy::into_iter_on_ref)] // the call this example is about
+    let seen: Vec<&String> = view.into_iter().collect();
+    n.fact("seen", format!("{:?}", seen)).fact("kind", type_of(&seen[0])).fact("left", names.len());
+    println!();
+    n.say("view.into_iter(), with view = &names, found IntoIterator for &Vec<String>: it gave {seen}");
+    n.say("as {kind}s, and names still has its {left} entries. That call only borrowed");
+
+    n.address("buffer", names.as_ptr());
+    let owned: Vec<String> = names.into_iter().collect();
+    n.address("owned", owned.as_ptr());
+    n.say("names.into_iter() found IntoIterator for Vec<String>, which takes self: it moved names,");
+    n.say("so printing names is error[E0382], and collect() reused its buffer ({buffer} -> {owned})");
+
+    println!("\n  When a call doesn't do what you expected, find which type the method was found on:");
+    println!("  the receiver's own type is tried before any auto-ref, and both before any deref");
+}
+
+struct Ticket {
+    seat: String,
+}
+
+// T's name without the module paths, e.g. "Rc<String>"
+fn type_of<T>(_: &T) -> String {
+    any::type_name::<T>().replace("alloc::rc::", "").replace("alloc::string::", "").replace("rust_ownership_demo::lessons::methods::", "")
+}
+
+const AUTO_REF_CODE: &str = r#"use std::rc::Rc;
+
+struct Playlist {
+    songs: Vec<String>,
+}
+
+impl Playlist {
+    fn count(&self) -> usize {
+        self.songs.len()
+    }
+
+    fn add(&mut self, song: &str) {
+        self.songs.push(song.to_string());
+    }
+
+    fn into_songs(self) -> Vec<String> {
+        self.songs
+    }
+}
+
+fn main() {
+    let mut list = Playlist { songs: Vec::new() };
+    // list.add(..) is Playlist::add(&mut list, ..): the call borrows list mutably
+    list.add("Intro");
+    // list.count() is Playlist::count(&list): a shared borrow
+    println!("{} song", list.count());
+
+    // Through a Box the call derefs first: Playlist::add(&mut *boxed, ..)
+    let mut boxed = Box::new(list);
+    boxed.add("Outro");
+    println!("{} songs in the box", boxed.count());
+
+    // An Rc derefs to &Playlist, never to &mut Playlist
+    let shared = Rc::new(Playlist { songs: vec![String::from("Theme")] });
+    println!("{} shared song", shared.count());
+    // shared.add("Encore");
+    // and into_songs can't move the Playlist out of an Rc others may share
+    // let songs = shared.into_songs();
+
+    // A Box owns its Playlist, so a self method moves it out of the box
+    let songs = boxed.into_songs();
+    // into_songs consumed the Playlist inside boxed
+    // println!("{}", boxed.count());
+    println!("{:?}", songs);
+}
+"#;
+
+const METHOD_SURPRISES_CODE: &str = r#"use std::rc::Rc;
+
+struct Ticket {
+    seat: String,
+}
+
+fn main() {
+    // Rc<String> has a clone method of its own, and the call finds it before String's
+    let shared = Rc::new(String::from("opening night"));
+    let another = shared.clone();
+    // so it returns an Rc<String>, not a String
+    // let text: String = shared.clone();
+    // Naming the type picks String's clone, which copies the text
+    let text = String::clone(&shared);
+    println!("{} owners of '{}', and a copy: '{}'", Rc::strong_count(&shared), another, text);
+
+    // Ticket isn't Clone, but &Ticket is, so clone() copies the reference
+    let ticket = Ticket { seat: String::from("B12") };
+    let borrowed = &ticket;
+    #[allow(noop_method_call)] // the surprise this example is about
+    let copied = borrowed.clone();
+    // no Ticket was cloned
+    // let owned: Ticket = borrowed.clone();
+    println!("seat {} and seat {}: the same Ticket", borrowed.seat, copied.seat);
+
+    // Through a reference, into_iter() finds IntoIterator for &Vec, which only borrows
+    let names = vec![String::from("ana"), String::from("bo")];
+    let view = &names;
+    #[allow(clippy::into_iter_on_ref)] // the call this example is about
+    for name in view.into_iter() {
+        println!("borrowed {}", name);
+    }
+    println!("names is still here: {:?}", names);
+    // On the Vec itself it's IntoIterator for Vec, which moves it
+    let owned: Vec<String> = names.into_iter().collect();
+    // names was moved into the iterator
+    // println!("{:?}", names);
+    println!("{:?}", owned);
+}
+"#;
@@ -0,0 +1,161 @@
+// The lesson registry: every section of the demo and the examples it contains.
+// The demo plays the sections in this order (unless a curriculum says
+// otherwise, see curriculum/mod.rs), and commands that work on a
+// single example (like `sandbox new`) look examples up here by id.
+
+mod api_design;
+mod borrowing;
+mod closures;
+mod collections;
+mod drop;
+mod fields;
+mod interior_mutability;
+mod io;
+mod lifetimes;
+mod mem;
+mod methods;
+mod ownership;
+mod patterns;
+mod practical;
+mod slices;
+mod smart_pointers;
+mod threads;
+
+use crate::aliases;
+use crate::instrument::{self, Allocations};
+use crate::toolchain::Feature;
+
+// A single narrated example together with a standalone version of its code
+pub struct Example {
+    // Short name, unique within its section (e.g. "mutable_refs")
+    pub name: &'static str,
+    // Heading printed before the example runs
+    pub title: &'static str,
+    // Prints the narrated walkthrough of the example
+    pub run: fn(),
+    // A complete program showing the same idea. Lines that would not compile
+    // are kept as comments so learners can uncomment them and see the error.
+    pub code: &'static str,
+    // The commented-out lines in `code` and the error each one causes
+    pub broken_lines: &'static [BrokenLine],
+    // Language features `code` needs beyond edition 2021, so older
+    // toolchains get a clear message instead of a compile error
+    pub requires: &'static [Feature],
+    // Crates `code` uses besides std, as Cargo.toml dependency lines. Plain
+    // rustc can't link them, so only a cargo project (like a sandbox) can
+    // build such code
+    pub crates: &'static [&'static str],
+    // The walkthrough `present` steps through, in order: each narration
+    // bullet with the code line it's about. Without any, it stops on every
+    // line worth pointing at instead
+    pub stops: &'static [Stop],
+}
+
+// What playing an example cost on the heap, shown as a footer after it so
+// the examples can be compared by memory as well as by what they print
+pub struct ExampleReport {
+    pub allocations: Allocations,
+    // Bytes still allocated when it finished that weren't before it started,
+    // e.g. a static it initialized or something it leaked on purpose
+    pub held: usize,
+}
+
+// A line of example code that is commented out because it doesn't compile
+pub struct BrokenLine {
+    // The line as it appears after the `// `, without indentation
+    pub line: &'static str,
+    // The error code rustc reports when the line is uncommented, e.g. "E0382"
+    pub error: &'static str,
+}
+
+// One step of a presented walkthrough: a narration bullet and its code line
+pub struct Stop {
+    // The start of the code line, without indentation. Each stop's line is
+    // looked for from the previous stop's, so a repeated line can be told apart
+    pub line: &'static str,
+    // What to say about it, shown under the code
+    pub say: &'static str,
+}
+
+// A group of related examples, printed under a single "SECTION" heading
+pub struct Section {
+    // Short name used as the prefix of example ids (e.g. "borrowing")
+    pub name: &'static str,
+    pub title: &'static str,
+    pub examples: &'static [Example],
+}
+
+// Old example ids and where they went, e.g. ("ownership_moves", "ownership_transfer").
+// Add a pair whenever an example is renamed or moved to another section
+pub const ALIASES: &[(&str, &str)] = &[];
+
+pub const SECTIONS: &[Section] = &[
+    ownership::SECTION,
+    borrowing::SECTION,
+    fields::SECTION,
+    mem::SECTION,
+    slices::SECTION,
+    lifetimes::SECTION,
+    practical::SECTION,
+    api_design::SECTION,
+    collections::SECTION,
+    smart_pointers::SECTION,
+    methods::SECTION,
+    interior_mutability::SECTION,
+    closures::SECTION,
+    drop::SECTION,
+    io::SECTION,
+    patterns::SECTION,
+    threads::SECTION,
+];
+
+impl Example {
+    // The id used on the command line, e.g. "borrowing_mutable_refs"
+    pub fn id(&self, section: &Section) -> String {
+        format!("{}_{}", section.name, self.name)
+    }
+
+    // Names of the crates in `crates`, e.g. ["serde", "serde_json"]
+    pub fn crate_names(&self) -> Vec<&'static str> {
+        self.crates.iter().filter_map(|line| line.split_whitespace().next()).collect()
+    }
+
+    // Plays the example, measuring its heap use. The narration's own strings
+    // count too, which is a small share next to what an example is about
+    pub fn run_measured(&self) -> ExampleReport {
+        let before = instrument::live_bytes();
+        let ((), allocations) = instrument::measure(self.run);
+        let held = instrument::live_bytes().saturating_sub(before);
+        ExampleReport { allocations, held }
+    }
+}
+
+impl ExampleReport {
+    // e.g. "[heap: peak 4.1 MB, 18 allocations of 6.2 MB in all, 0 B still held]"
+    pub fn footer(&self) -> String {
+        let count = match self.allocations.count {
+            1 => "1 allocation".to_string(),
+            n => format!("{} allocations", n),
+        };
+        format!(
+            "[heap: peak {}, {} of {} in all, {} still held]",
+            instrument::human_bytes(self.allocations.peak),
+            count,
+            instrument::human_bytes(self.allocations.bytes),
+            instrument::human_bytes(self.held)
+        )
+    }
+}
+
+// Every example in the order the demo plays them, paired with its section
+pub fn examples() -> impl Iterator<Item = (&'static Section, &'static Example)> {
+    SECTIONS
+        .iter()
+        .flat_map(|section| section.examples.iter().map(move |example| (section, example)))
+}
+
+// Looks up an example by its id, or an id it used to have
+pub fn find(id: &str) -> Option<(&'static Section, &'static Example)> {
+    let id = aliases::resolve(ALIASES, id);
+    examples().find(|(section, example)| example.id(section) == id)
+}
@@ -0,0 +1,622 @@
+use super::{BrokenLine, Example, Section, Stop};
+use crate::instrument;
+use crate::narration::Narration;
+
+pub const SECTION: Section = Section {
+    name: "ownership",
+    title: "BASIC OWNERSHIP",
+    examples: &[
+        Example {
+            name: "transfer",
+            title: "Transfer of ownership",
+            run: transfer,
+            code: TRANSFER_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: r#"println!("s1 = {}", s1);"#,
+                    error: "E0382",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let s1",
+                    say: "String::from puts 5 bytes of text on the heap; s1 owns them",
+                },
+                Stop {
+                    line: "let s2 = s1;",
+                    say: "Ownership moves to s2: the same heap text, and nothing is copied",
+                },
+                Stop {
+                    line: "// println!",
+                    say: "s1 owns nothing any more, so using it is error[E0382]",
+                },
+                Stop {
+                    line: "}",
+                    say: "s2 goes out of scope, so the String is freed, exactly once",
+                },
+            ],
+        },
+        Example {
+            name: "functions",
+            title: "Ownership with functions",
+            run: functions,
+            code: FUNCTIONS_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: r#"println!("s = {}", s);"#,
+                    error: "E0382",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let s",
+                    say: "main owns a String, s",
+                },
+                Stop {
+                    line: "takes_ownership(s);",
+                    say: "Passing s by value moves it into the function, just like let s2 = s",
+                },
+                Stop {
+                    line: "// println!",
+                    say: "so main can't use s afterwards: error[E0382]",
+                },
+                Stop {
+                    line: "makes_copy(x);",
+                    say: "An i32 is Copy: the function gets a copy, and x stays usable",
+                },
+                Stop {
+                    line: "} // some_string is dropped here",
+                    say: "The String is freed when takes_ownership returns, since its parameter owned it",
+                },
+            ],
+        },
+        Example {
+            name: "returning",
+            title: "Returning ownership",
+            run: returning,
+            code: RETURNING_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: r#"println!("s2 = {}", s2);"#,
+                    error: "E0382",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let s1 = gives_ownership();",
+                    say: "gives_ownership moves its String out to s1",
+                },
+                Stop {
+                    line: "let s3 = takes_and_gives_back(s2);",
+                    say: "s2 moves into the function, and the result moves out to s3",
+                },
+                Stop {
+                    line: r#"// println!("s2 = {}", s2);"#,
+                    say: "s2 gave its String away, so using it is E0382",
+                },
+                Stop {
+                    line: r#"String::from("yours")"#,
+                    say: "The returned String isn't dropped: its owner is now the caller",
+                },
+                Stop {
+                    line: "a_string // ownership moves back",
+                    say: "Returning a parameter hands ownership straight back",
+                },
+            ],
+        },
+        Example {
+            name: "loops",
+            title: "Moving values inside loop bodies",
+            run: loops,
+            code: LOOPS_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: "cards.push(Card { to: guest.clone(), signature });",
+                    error: "E0382",
+                },
+                BrokenLine {
+                    line: r#"println!("guests = {:?}", guests);"#,
+                    error: "E0382",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "for guest in &guests {",
+                    say: "&guests borrows the Vec, so each guest is a &String",
+                },
+                Stop {
+                    line: "// cards.push(Card { to: guest.clone(), signature });",
+                    say: "signature would move in the first pass, leaving none: E0382",
+                },
+                Stop {
+                    line: "cards.push(Card { to: guest.clone(), signature: signature.clone() });",
+                    say: "Fix 1: clone it on every iteration",
+                },
+                Stop {
+                    line: "let signature = format!",
+                    say: "Fix 2: each iteration makes a fresh value to move",
+                },
+                Stop {
+                    line: "borrowed.push(BorrowedCard",
+                    say: "Fix 3: the cards borrow the signature instead",
+                },
+                Stop {
+                    line: "for guest in guests {",
+                    say: "Without & the loop takes the Vec, so its Strings can move out",
+                },
+                Stop {
+                    line: r#"// println!("guests = {:?}", guests);"#,
+                    say: "guests was moved into the loop: E0382",
+                },
+            ],
+        },
+        Example {
+            name: "copy_vs_clone",
+            title: "Copy vs Clone: implicit bit copies and explicit deep copies",
+            run: copy_vs_clone,
+            code: COPY_VS_CLONE_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: r#"println!("{}", label.text);"#,
+                    error: "E0382",
+                },
+                BrokenLine {
+                    line: "#[derive(Clone, Copy)]",
+                    error: "E0204",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "#[derive(Debug, Clone, Copy)]",
+                    say: "Every field of Point is Copy, so Point can be",
+                },
+                Stop {
+                    line: "#[derive(Debug, Clone)]",
+                    say: "Label owns a String: it can be cloned, not copied",
+                },
+                Stop {
+                    line: "// #[derive(Clone, Copy)]",
+                    say: "A bitwise copy would give the buffer two owners: E0204",
+                },
+                Stop {
+                    line: "let p2 = p1;",
+                    say: "Assigning a Copy value copies it, and both stay usable",
+                },
+                Stop {
+                    line: "let moved = label;",
+                    say: "Assigning a Label moves it",
+                },
+                Stop {
+                    line: r#"// println!("{}", label.text);"#,
+                    say: "so label can't be used any more: E0382",
+                },
+                Stop {
+                    line: "let copy = moved.clone();",
+                    say: "A deep copy has to be asked for",
+                },
+                Stop {
+                    line: "print_label(copy);",
+                    say: "and print_label takes ownership of it, then drops it",
+                },
+            ],
+        },
+    ],
+};
+
+fn transfer() {
+    let mut n = Narration::new();
+
+    // When we assign a variable to another variable, the ownership is transferred
+    {
+        let s1 = String::from("hello"); // s1 is the owner of this String
+        n.fact("s1", &s1).fact("len", s1.len()).address("s1_buffer", s1.as_ptr());
+        n.say("Created s1: {s1} ({len} bytes on the heap at {s1_buffer})");
+
+        let s2 = s1; // ownership moves from s1 to s2
+        n.fact("s2", &s2).address("s2_buffer", s2.as_ptr());
+        n.say("Ownership transferred to s2: {s2} (still the heap data at {s2_buffer}, nothing was copied)");
+
+        // This would cause a compile error because s1 no longer owns the String
+        // println!("  Trying to use s1: {}", s1);
+        println!("  Note: We can't use s1 anymore as it no longer owns the String");
+    }
+    println!("  Variables s1 and s2 are now out of scope, memory is automatically freed");
+}
+
+fn functions() {
+    let mut n = Narration::new();
+
+    let s = String::from("hello world");
+    n.fact("s", &s);
+    n.say("Created string s: {s}");
+
+    // When we pass s to the function, ownership is transferred
+    takes_ownership(s);
+
+    // This would cause a compile error
+    // println!("  Trying to use s: {}", s);
+    println!("  Note: We can't use s anymore as its ownership was transferred to the function\n");
+
+    let x = 5;
+    n.fact("x", x).fact("x_size", std::mem::size_of_val(&x));
+    n.say("Created integer x: {x}");
+
+    // Integers are Copy types, so a copy is made instead of transferring ownership
+    makes_copy(x);
+
+    n.say("We can still use x after passing it to a function: {x}");
+    n.say("Copying x only meant copying its {x_size} bytes, there is no heap data to share");
+    println!("  Note: This is because primitive types like integers implement the Copy trait");
+}
+
+fn returning() {
+    let mut n = Narration::new();
+
+    {
+        let s1 = gives_ownership();
+        n.fact("s1", &s1);
+        n.say("Received ownership of string: {s1}");
+
+        let s2 = String::from("hello");
+        n.fact("s2", &s2).address("s2_buffer", s2.as_ptr());
+        n.say("Created s2: {s2} (heap data at {s2_buffer})");
+
+        let s3 = takes_and_gives_back(s2);
+        n.fact("s3", &s3).address("s3_buffer", s3.as_ptr());
+        n.say("Transferred s2 to function and received it back as s3: {s3} (heap data at {s3_buffer})");
+
+        // s2 is no longer valid here
+        println!("  Note: s2 is no longer valid as ownership was transferred");
+    }
+    println!("  s1 and s3 go out of scope and are dropped, freeing memory");
+}
+
+// This function takes ownership of the String passed to it
+fn takes_ownership(some_string: String) {
+    println!("  Function received ownership of: {}", some_string);
+} // some_string goes out of scope and `drop` is called, freeing memory
+
+// This function takes a copy of the value passed to it
+fn makes_copy(some_integer: i32) {
+    println!("  Function received a copy of: {}", some_integer);
+} // some_integer goes out of scope but nothing special happens
+
+// This function creates and returns a String, transferring ownership to the caller
+fn gives_ownership() -> String {
+    let some_string = String::from("yours");
+    println!("  Function created a string: {}", some_string);
+    some_string // Return and transfer ownership
+}
+
+// This function takes and returns ownership of a String
+fn takes_and_gives_back(a_string: String) -> String {
+    println!("  Function received ownership of: {}", a_string);
+    a_string // Return and transfer ownership back
+}
+
+fn loops() {
+    let mut n = Narration::new();
+
+    let guests = vec![String::from("Ada"), String::from("Grace"), String::from("Alan")];
+    let signature = String::from("the host");
+    n.fact("guests", format!("{:?}", guests)).fact("signature", &signature);
+    n.say("Writing a card for each of {guests}, signed '{signature}'");
+
+    // A loop body runs many times, but a move can only happen once
+    println!("  Putting signature itself into each card doesn't compile:");
+    println!("    for guest in &guests {{ cards.push(Card {{ to: guest.clone(), signature }}); }}");
+    println!("    error[E0382]: use of moved value: `signature`");
+    println!("    note: value moved here, in previous iteration of loop");
+    println!("  The first iteration moves signature into a Card; the second has nothing left to");
+    println!("  move. The compiler checks the body as if it ran again, so it rejects it even");
+    println!("  when the loop would happen to run only once");
+
+    // Fix 1: every iteration gets its own copy
+    let (cards, clone_allocs) = instrument::measure(|| {
+        let mut cards = Vec::new();
+        for guest in &guests {
+            cards.push(Card { to: guest.clone(), signature: signature.clone() });
+        }
+        cards
+    });
+    n.fact("clone_allocs", clone_allocs.count).fact("last", cards[2].describe());
+    n.say("Fix 1, clone per iteration: {clone_allocs} allocations (the Vec, two Strings per card)");
+    n.say("  signature is still ours. {last}");
+
+    // Fix 2: create the value inside the loop, so each iteration moves its own
+    let (cards, inside_allocs) = instrument::measure(|| {
+        let mut cards = Vec::new();
+        for guest in &guests {
+            let signature = format!("the host, for {}", guest);
+            cards.push(Card { to: guest.clone(), signature });
+        }
+        cards
+    });
+    n.fact("inside_allocs", inside_allocs.count).fact("last", cards[2].describe());
+    n.say("Fix 2, create it inside the loop: {inside_allocs} allocations, but each card can differ");
+    n.say("  {last}");
+
+    // Fix 3: borrow, so nothing moves at all
+    let (cards, borrow_allocs) = instrument::measure(|| {
+        let mut cards = Vec::new();
+        for guest in &guests {
+            cards.push(BorrowedCard { to: guest, signature: &signature });
+        }
+        cards
+    });
+    n.fact("borrow_allocs", borrow_allocs.count).fact("last", cards[2].describe());
+    n.say("Fix 3, borrow instead: {borrow_allocs} allocation (just the Vec), the cards borrow from us");
+    n.say("  {last}");
+
+    println!("\n  The loop itself can move, too: `for guest in guests` calls guests.into_iter(),");
+    println!("  which takes the Vec, so each guest String is ours to move without cloning:");
+    let mut cards = Vec::new();
+    for guest in guests {
+        cards.push(Card { to: guest, signature: signature.clone() });
+    }
+    n.fact("count", cards.len());
+    n.say("Moved {count} guests into cards; after the loop, guests is gone:");
+    println!("    println!(\"guests = {{:?}}\", guests); // error[E0382]: borrow of moved value: `guests`");
+    println!("  Loop over &guests when you still need the Vec, and over guests when you don't");
+}
+
+fn copy_vs_clone() {
+    let mut n = Narration::new();
+
+    // Copy: assignment duplicates the bits, and both variables stay usable
+    let p1 = Point { x: 1, y: 2 };
+    let p2 = p1;
+    n.fact("p1", p1.describe()).fact("p2", p2.describe()).fact("size", size_of::<Point>());
+    n.address("p1_at", &p1 as *const Point).address("p2_at", &p2 as *const Point);
+    n.say("Point is #[derive(Clone, Copy)]. let p2 = p1; gives p1 = {p1} at {p1_at}");
+    n.say("and p2 = {p2} at {p2_at}: {size} bytes copied, and p1 is still ours");
+
+    let shifted = shift(p1, 10);
+    n.fact("p1", p1.describe()).fact("shifted", shifted.describe());
+    n.say("shift(p1, 10) got its own copy and returned {shifted}; p1 is unchanged: {p1}");
+
+    // Clone without Copy: assignment moves, and copying has to be asked for
+    println!();
+    let label = Label { text: String::from("origin"), size: 12 };
+    n.fact("text", &label.text).address("text_at", label.text.as_ptr());
+    n.say("Label is only #[derive(Clone)]; its text '{text}' is on the heap at {text_at}");
+    let moved = label;
+    n.address("moved_at", moved.text.as_ptr());
+    n.say("let moved = label; is a move: moved.text is still at {moved_at}, and label is gone");
+    println!("  (println!(\"{{}}\", label.text) now is error[E0382]: borrow of moved value)");
+
+    let (copy, clone_allocs) = instrument::measure(|| moved.clone());
+    n.fact("allocs", clone_allocs.count).fact("bytes", clone_allocs.bytes).address("copy_at", copy.text.as_ptr());
+    n.say("moved.clone() is explicit and deep: {allocs} allocation of {bytes} bytes, a new buffer at {copy_at}");
+
+    let (_, call_allocs) = instrument::measure(|| describe_label(copy));
+    n.fact("call_allocs", call_allocs.count);
+    n.say("describe_label(copy) moved the clone in, with {call_allocs} allocations; copy is gone too");
+    n.fact("size", moved.size);
+    n.say("moved.size ({size}) is a u32, which is Copy, so reading it never moves anything");
+
+    // A type can only be Copy if every field is, and String isn't
+    println!();
+    println!("  Why can't Label be Copy? A bitwise copy would duplicate the String's pointer,");
+    println!("  leaving two owners of one heap buffer, each of which would free it when dropped.");
+    println!("  So #[derive(Clone, Copy)] on a struct with a String is error[E0204]: the trait");
+    println!("  `Copy` cannot be implemented for this type. For the same reason a Copy type can't");
+    println!("  implement Drop: dropping is exactly what its copies can't each do");
+    println!("  Copy types are Clone too, and their clone() is the same bit copy; Clone is the");
+    println!("  general promise, Copy the cheap special case the compiler may use silently");
+}
+
+// Small and plain: copying its bits is a complete, independent copy
+#[derive(Clone, Copy)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl Point {
+    fn describe(&self) -> String {
+        format!("({}, {})", self.x, self.y)
+    }
+}
+
+// Owns heap memory, so it can be cloned but not implicitly copied
+#[derive(Clone)]
+struct Label {
+    text: String,
+    size: u32,
+}
+
+// This function gets its own Point, so moving it doesn't affect the caller's
+fn shift(mut point: Point, by: i32) -> Point {
+    point.x += by;
+    point.y += by;
+    point
+}
+
+// This function takes ownership of the Label and drops it at the end
+fn describe_label(label: Label) -> usize {
+    label.text.len()
+}
+
+// An owned card: it keeps its own copy of everything it mentions
+struct Card {
+    to: String,
+    signature: String,
+}
+
+// A card that only borrows, so it can't outlive the data it points to
+struct BorrowedCard<'a> {
+    to: &'a str,
+    signature: &'a str,
+}
+
+impl Card {
+    fn describe(&self) -> String {
+        format!("Last card: 'Dear {}, from {}'", self.to, self.signature)
+    }
+}
+
+impl BorrowedCard<'_> {
+    fn describe(&self) -> String {
+        format!("Last card: 'Dear {}, from {}'", self.to, self.signature)
+    }
+}
+
+const TRANSFER_CODE: &str = r#"fn main() {
+    let s1 = String::from("hello"); // s1 is the owner of this String
+    let s2 = s1; // ownership moves from s1 to s2
+    println!("s2 = {}", s2);
+
+    // s1 no longer owns the String, so using it is a compile error
+    // println!("s1 = {}", s1);
+} // s2 goes out of scope and the String is freed
+"#;
+
+const FUNCTIONS_CODE: &str = r#"fn main() {
+    let s = String::from("hello world");
+    takes_ownership(s); // s moves into the function
+
+    // s was moved into takes_ownership, so this is a compile error
+    // println!("s = {}", s);
+
+    let x = 5;
+    makes_copy(x); // i32 is Copy, so x is copied instead of moved
+    println!("x is still usable: {}", x);
+}
+
+fn takes_ownership(some_string: String) {
+    println!("took ownership of: {}", some_string);
+} // some_string is dropped here
+
+fn makes_copy(some_integer: i32) {
+    println!("got a copy of: {}", some_integer);
+}
+"#;
+
+const RETURNING_CODE: &str = r#"fn main() {
+    let s1 = gives_ownership();
+    println!("s1 = {}", s1);
+
+    let s2 = String::from("hello");
+    let s3 = takes_and_gives_back(s2);
+    println!("s3 = {}", s3);
+
+    // s2 was moved into takes_and_gives_back, so this is a compile error
+    // println!("s2 = {}", s2);
+}
+
+fn gives_ownership() -> String {
+    String::from("yours") // ownership moves out to the caller
+}
+
+fn takes_and_gives_back(a_string: String) -> String {
+    a_string // ownership moves back out to the caller
+}
+"#;
+
+const LOOPS_CODE: &str = r#"struct Card {
+    to: String,
+    signature: String,
+}
+
+struct BorrowedCard<'a> {
+    to: &'a str,
+    signature: &'a str,
+}
+
+fn main() {
+    let guests = vec![String::from("Ada"), String::from("Grace"), String::from("Alan")];
+    let signature = String::from("the host");
+    let mut cards = Vec::new();
+
+    for guest in &guests {
+        // signature would move into the first card, leaving nothing for the second iteration
+        // cards.push(Card { to: guest.clone(), signature });
+
+        // Fix 1: clone it on every iteration
+        cards.push(Card { to: guest.clone(), signature: signature.clone() });
+    }
+
+    // Fix 2: create the value inside the loop, so each iteration owns a fresh one
+    for guest in &guests {
+        let signature = format!("the host, for {}", guest);
+        cards.push(Card { to: guest.clone(), signature });
+    }
+
+    // Fix 3: borrow instead of moving
+    let mut borrowed = Vec::new();
+    for guest in &guests {
+        borrowed.push(BorrowedCard { to: guest, signature: &signature });
+    }
+    for card in &borrowed {
+        println!("Dear {}, from {}", card.to, card.signature);
+    }
+
+    // `for guest in guests` moves the Vec into the loop, so its Strings can move out
+    for guest in guests {
+        cards.push(Card { to: guest, signature: signature.clone() });
+    }
+    // println!("guests = {:?}", guests);
+
+    for card in &cards {
+        println!("Dear {}, from {}", card.to, card.signature);
+    }
+}
+"#;
+
+const COPY_VS_CLONE_CODE: &str = r#"// Every field is Copy, so the whole struct can be
+#[derive(Debug, Clone, Copy)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+// A String owns heap memory: cloning is fine, implicit copying isn't
+#[derive(Debug, Clone)]
+struct Label {
+    text: String,
+}
+
+// A bitwise copy of a String would give its buffer two owners
+// #[derive(Clone, Copy)]
+struct Tag {
+    name: String,
+}
+
+fn shift(mut point: Point) -> Point {
+    point.x += 10;
+    point
+}
+
+fn print_label(label: Label) {
+    println!("label: {}", label.text);
+} // label is dropped here
+
+fn main() {
+    let p1 = Point { x: 1, y: 2 };
+    let p2 = p1; // copied: both are usable
+    let shifted = shift(p1); // the function gets a copy too
+    println!("{:?} {:?} {:?}", p1, p2, shifted);
+
+    let label = Label { text: String::from("origin") };
+    let moved = label; // moved: label can't be used any more
+    // println!("{}", label.text);
+
+    let copy = moved.clone(); // a deep copy has to be asked for
+    print_label(copy); // and the function takes ownership of it
+    println!("still have {:?}", moved);
+
+    let tag = Tag { name: String::from("v1") };
+    println!("tag {}", tag.name);
+}
+"#;
@@ -0,0 +1,396 @@
+use super::{BrokenLine, Example, Section, Stop};
+use crate::narration::Narration;
+
+pub const SECTION: Section = Section {
+    name: "patterns",
+    title: "PATTERNS AND OWNERSHIP",
+    examples: &[
+        Example {
+            name: "match_guards",
+            title: "What a match guard may do with the values it sees",
+            run: match_guards,
+            code: MATCH_GUARDS_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: r#"Some(name) if register(name) => println!("registered"),"#,
+                    error: "E0507",
+                },
+                BrokenLine {
+                    line: "Some(mut name) if { name.push('!'); true } => println!(\"{}\", name),",
+                    error: "E0596",
+                },
+                BrokenLine {
+                    line: "Some(_) if { slot = None; true } => {}",
+                    error: "E0510",
+                },
+                BrokenLine {
+                    line: r#"whole @ Some(inner) => println!("{:?} holds {}", whole, inner),"#,
+                    error: "E0382",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "match name {",
+                    say: "The arm's bindings are moved only once an arm is chosen",
+                },
+                Stop {
+                    line: "// Some(name) if register(name)",
+                    say: "so a guard can't move them: E0507",
+                },
+                Stop {
+                    line: "// Some(mut name) if",
+                    say: "or change them: E0596",
+                },
+                Stop {
+                    line: "Some(name) if name.len() > 3",
+                    say: "Reading in the guard is fine, then the arm takes name",
+                },
+                Stop {
+                    line: "// Some(_) if { slot = None; true } => {}",
+                    say: "A guard can't change what's being matched: E0510",
+                },
+                Stop {
+                    line: "slot = None;",
+                    say: "Once the match is over, slot can change",
+                },
+                Stop {
+                    line: "// whole @ Some(inner)",
+                    say: "Moving both the whole and a part would move twice: E0382",
+                },
+                Stop {
+                    line: "ref whole @ Some(ref inner)",
+                    say: "Borrowing both is fine",
+                },
+            ],
+        },
+        Example {
+            name: "binding_modes",
+            title: "Matching on an owned value vs a reference: default binding modes",
+            run: binding_modes,
+            code: BINDING_MODES_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: "let owned: String = body;",
+                    error: "E0308",
+                },
+                BrokenLine {
+                    line: r#"println!("{:?}", reply);"#,
+                    error: "E0382",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "match &reply {",
+                    say: "Matching on a reference: bindings become references",
+                },
+                Stop {
+                    line: "// let owned: String = body;",
+                    say: "body is a &String, not a String: E0308",
+                },
+                Stop {
+                    line: "if let Ok(body) = &mut reply {",
+                    say: "Through &mut, body is a &mut String",
+                },
+                Stop {
+                    line: "let kept = match reply {",
+                    say: "Matching on the value itself moves the String out",
+                },
+                Stop {
+                    line: r#"// println!("{:?}", reply);"#,
+                    say: "reply has been moved from: E0382",
+                },
+            ],
+        },
+        Example {
+            name: "ref_bindings",
+            title: "ref and ref mut: borrowing in a pattern on an owned value",
+            run: ref_bindings,
+            code: REF_BINDINGS_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: r#"println!("{}", contact.name);"#,
+                    error: "E0382",
+                },
+                BrokenLine {
+                    line: r#"if let &Some(name) = &nickname { println!("{}", name); }"#,
+                    error: "E0507",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "if let Some(ref name) = nickname {",
+                    say: "ref borrows from the owned value instead of moving",
+                },
+                Stop {
+                    line: "if let Some(ref mut name) = nickname {",
+                    say: "ref mut borrows mutably",
+                },
+                Stop {
+                    line: "// if let &Some(name) = &nickname",
+                    say: "Writing the & out switches back to moving: E0507",
+                },
+                Stop {
+                    line: "let Contact { name, ref email } = contact;",
+                    say: "name moves out while email is only borrowed",
+                },
+                Stop {
+                    line: r#"println!("still readable: {}", contact.email);"#,
+                    say: "So email can still be read through contact",
+                },
+                Stop {
+                    line: r#"// println!("{}", contact.name);"#,
+                    say: "but name has moved out of it: E0382",
+                },
+            ],
+        },
+    ],
+};
+
+fn match_guards() {
+    let mut n = Narration::new();
+
+    println!("  A guard runs before the arm is chosen. If it returns false, matching moves on to");
+    println!("  the next arm, which must still find the value intact. So inside a guard, the");
+    println!("  bindings are only shared references, even when the arm itself takes them by value:");
+
+    for name in [Some(String::from("ferris")), Some(String::from("al")), None] {
+        let described = describe(name);
+        n.fact("described", described);
+        n.say("  {described}");
+    }
+
+    println!("\n  Which is why each of these guards is rejected:");
+    println!("    Some(name) if register(name) => ..");
+    println!("      error[E0507]: cannot move out of `name` in pattern guard");
+    println!("    Some(mut name) if {{ name.push('!'); true }} => ..");
+    println!("      error[E0596]: cannot borrow `name` as mutable, as it is immutable for the pattern guard");
+    println!("    Some(_) if {{ slot = None; true }} => ..");
+    println!("      error[E0510]: cannot assign `slot` in match guard");
+    println!("  Fix: keep guards to questions (len(), starts_with, comparisons) and do the moving or");
+    println!("  mutating in the arm body, where the value is yours");
+
+    // Moving in the arm body is fine: by then this arm has been chosen
+    let mut registered = Vec::new();
+    for name in [Some(String::from("ferris")), Some(String::from("al"))] {
+        match name {
+            Some(name) if name.len() > 3 => registered.push(name),
+            Some(name) => registered.push(format!("{} (short)", name)),
+            None => {}
+        }
+    }
+    n.fact("registered", format!("{:?}", registered));
+    n.say("Moved each name into registered inside the arm: {registered}");
+
+    println!("\n  @ bindings have the same rule: a value can only be moved once. With an owned");
+    println!("  scrutinee, `whole @ Some(inner)` would move the String into inner and the Option");
+    println!("  into whole (error[E0382]: use of partially moved value). Match on a reference");
+    println!("  and both bindings become references:");
+    let name = Some(String::from("ferris"));
+    if let whole @ Some(inner) = &name {
+        n.fact("whole", format!("{:?}", whole)).fact("inner", inner);
+        n.say("whole @ Some(inner) on &name: whole = {whole}, inner = {inner}, name is untouched");
+    }
+}
+
+// This function takes ownership of the name and consults a guard that only looks at it
+fn describe(name: Option<String>) -> String {
+    match name {
+        // In the guard, name is a &String; in the body, it's the String itself
+        Some(name) if name.len() > 3 => format!("Some({:?}): guard saw &String, arm took ownership", name),
+        Some(name) => format!("Some({:?}): guard said no, the next arm still got the String", name),
+        None => String::from("None: no guard ran"),
+    }
+}
+
+fn binding_modes() {
+    let mut n = Narration::new();
+
+    let mut reply: Result<String, String> = Ok(String::from("200 OK"));
+    if let Ok(body) = &reply {
+        n.address("buffer", body.as_ptr());
+    }
+    n.say("reply is an Ok(String) that owns its text at {buffer}");
+
+    match &reply {
+        Ok(body) => {
+            n.fact("body", body).fact("type", short_type(&body)).address("at", body.as_ptr());
+            n.say("match &reply {{ Ok(body) => .. }} puts a reference where the pattern expects a");
+            n.say("Result, so Rust matches through it and binds body by reference: body is a {type}");
+            n.say("to '{body}' at {at}. Nothing moved, and reply can be used after the match");
+        }
+        Err(error) => println!("  unexpected error: {}", error),
+    }
+
+    if let Ok(body) = &mut reply {
+        body.push_str(" (cached)");
+        n.fact("type", short_type(&body));
+    }
+    n.fact("reply", format!("{:?}", reply));
+    n.say("Through &mut reply the same pattern binds a {type}, so the arm can push onto the");
+    n.say("String in place: reply is now {reply}");
+
+    let kept = match reply {
+        Ok(body) => {
+            n.fact("type", short_type(&body)).address("at", body.as_ptr());
+            body
+        }
+        Err(error) => error,
+    };
+    println!();
+    n.say("match reply {{ Ok(body) => .. }} on the value itself binds a {type}, moving the String");
+    n.fact("kept", &kept);
+    n.say("out of reply: its text is still at {at} (moved, not copied), and kept = '{kept}'.");
+    n.say("reply is moved from now, so printing it is error[E0382]");
+
+    println!("\n  These are default binding modes: when a pattern like Ok(body) meets a reference,");
+    println!("  the compiler dereferences for you and binds by ref, or by ref mut under &mut. So the");
+    println!("  scrutinee decides whether a match moves: match on &value to look, on value to take");
+    println!("  it apart. Inside a match on &reply, `let owned: String = body;` is error[E0308]:");
+    println!("  body is a &String, and body.clone() or .to_string() would make the owned copy");
+}
+
+fn ref_bindings() {
+    let mut n = Narration::new();
+
+    let mut nickname = Some(String::from("ferris"));
+    if let Some(ref name) = nickname {
+        n.fact("name", name).fact("type", short_type(&name));
+        n.say("if let Some(ref name) = nickname: the scrutinee is owned, but ref binds name as a");
+        n.say("{type} to '{name}'. It's the same as matching on &nickname, spelled in the pattern");
+    }
+    if let Some(ref mut name) = nickname {
+        name.push_str("_the_crab");
+    }
+    n.fact("nickname", format!("{:?}", nickname));
+    n.say("ref mut borrows mutably instead, so the arm can change it in place: {nickname}");
+
+    let contact = Contact { name: String::from("Ferris"), email: String::from("ferris@example.org") };
+    n.address("email_buffer", contact.email.as_ptr());
+    let Contact { name, ref email } = contact;
+    n.fact("name", &name).fact("email", email).address("email_at", email.as_ptr());
+    n.fact("still", &contact.email);
+    println!();
+    n.say("What ref can do that & on the scrutinee can't: decide binding by binding.");
+    n.say("let Contact {{ name, ref email }} = contact; moves name out ({name}) and borrows email");
+    n.say("({email} at {email_at}, the field's own text at {email_buffer}). contact.email can");
+    n.say("still be read ({still}), but contact.name has moved: using it is error[E0382]");
+
+    println!("\n  Going the other way doesn't work: `if let &Some(name) = &nickname` spells out the");
+    println!("  reference, so the binding mode goes back to moving, and moving the String out from");
+    println!("  behind & is error[E0507]. Write Some(name) and let the default mode borrow, or");
+    println!("  &Some(ref name). (In edition 2024 ref is only allowed where the mode is still move,");
+    println!("  as in these owned matches; under a reference it's already implied)");
+}
+
+struct Contact {
+    name: String,
+    email: String,
+}
+
+// The type of a binding, without the module paths
+fn short_type<T>(_: &T) -> String {
+    std::any::type_name::<T>().replace("alloc::string::", "")
+}
+
+const MATCH_GUARDS_CODE: &str = r#"fn register(name: String) -> bool {
+    println!("registered {}", name);
+    true
+}
+
+fn main() {
+    let name = Some(String::from("ferris"));
+    match name {
+        // A guard may only look at the bindings, not move them...
+        // Some(name) if register(name) => println!("registered"),
+        // ...or mutate them
+        // Some(mut name) if { name.push('!'); true } => println!("{}", name),
+        Some(name) if name.len() > 3 => println!("long name, moved into the arm: {}", name),
+        Some(name) => println!("short name: {}", name),
+        None => {}
+    }
+
+    // A guard can't change the value being matched, either
+    let mut slot = Some(String::from("ferris"));
+    match slot {
+        // Some(_) if { slot = None; true } => {}
+        Some(ref name) if name.starts_with('f') => println!("starts with f: {}", name),
+        _ => {}
+    }
+    slot = None; // fine once the match is over
+    println!("{:?}", slot);
+
+    // Binding the whole value and a part of it needs a borrowed scrutinee
+    let name = Some(String::from("ferris"));
+    #[allow(clippy::single_match)] // the @ pattern is what's shown here
+    match name {
+        // whole @ Some(inner) => println!("{:?} holds {}", whole, inner),
+        ref whole @ Some(ref inner) => println!("{:?} holds {}", whole, inner),
+        None => {}
+    }
+}
+"#;
+
+const BINDING_MODES_CODE: &str = r#"fn main() {
+    let mut reply: Result<String, String> = Ok(String::from("200 OK"));
+
+    // Matching on a reference: body binds as &String, and nothing moves
+    match &reply {
+        Ok(body) => {
+            // body is a &String here, not a String
+            // let owned: String = body;
+            println!("borrowed: {}", body);
+        }
+        Err(error) => println!("error: {}", error),
+    }
+
+    // Through &mut, body is a &mut String
+    if let Ok(body) = &mut reply {
+        body.push_str(" (cached)");
+    }
+
+    // Matching on the value itself moves the String out of reply
+    let kept = match reply {
+        Ok(body) => body,
+        Err(error) => error,
+    };
+    // reply has been moved from
+    // println!("{:?}", reply);
+    println!("kept: {}", kept);
+}
+"#;
+
+const REF_BINDINGS_CODE: &str = r#"struct Contact {
+    name: String,
+    email: String,
+}
+
+fn main() {
+    let mut nickname = Some(String::from("ferris"));
+
+    // ref borrows from an owned scrutinee, like matching on &nickname
+    if let Some(ref name) = nickname {
+        println!("borrowed: {}", name);
+    }
+    // ref mut borrows mutably
+    if let Some(ref mut name) = nickname {
+        name.push_str("_the_crab");
+    }
+    // Spelling out the & switches back to moving, out of a reference
+    // if let &Some(name) = &nickname { println!("{}", name); }
+    println!("{:?}", nickname);
+
+    // Move one field out and borrow another in the same pattern
+    let contact = Contact { name: String::from("Ferris"), email: String::from("ferris@example.org") };
+    let Contact { name, ref email } = contact;
+    println!("moved {}, borrowed {}", name, email);
+    println!("still readable: {}", contact.email);
+    // but name has moved out of contact
+    // println!("{}", contact.name);
+}
+"#;
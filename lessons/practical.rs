@@ -0,0 +1,1106 @@
+use std::env;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::ops::Range;
+use std::path::Path;
+use std::process::{self, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::{BrokenLine, Example, Section, Stop};
+use crate::instrument;
+use crate::narration::Narration;
+use crate::rng;
+use crate::tracked::Tracked;
+use crate::utils::get_first_word;
+
+pub const SECTION: Section = Section {
+    name: "practical",
+    title: "PRACTICAL EXAMPLES",
+    examples: &[
+        Example {
+            name: "first_word",
+            title: "Borrowing the first word of a string",
+            run: first_word,
+            code: FIRST_WORD_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: "text.clear();",
+                    error: "E0502",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let word = get_first_word(&text);",
+                    say: "word is a slice that borrows part of text",
+                },
+                Stop {
+                    line: "// text.clear();",
+                    say: "Clearing text while word is in use is E0502",
+                },
+                Stop {
+                    line: r#"println!("first word: {}", word);"#,
+                    say: "This is word's last use",
+                },
+                Stop {
+                    line: "text.clear();",
+                    say: "so text can be changed again",
+                },
+                Stop {
+                    line: "return &s[0..i];",
+                    say: "The result is a slice of s, not a copy",
+                },
+            ],
+        },
+        Example {
+            name: "file_lines",
+            title: "Returning lines read from a file",
+            run: file_lines,
+            code: FILE_LINES_CODE,
+            broken_lines: &[BrokenLine {
+                line: "fn read_lines(path: &Path) -> Vec<&str> { let text = fs::read_to_string(path).unwrap(); text.lines().collect() }",
+                error: "E0515",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "// fn read_lines",
+                    say: "The String dies in the function, so its lines can't leave: E0515",
+                },
+                Stop {
+                    line: "struct Lines {",
+                    say: "Fix 1: return the buffer along with where the lines are in it",
+                },
+                Stop {
+                    line: "fn get(&self, index: usize) -> &str {",
+                    say: "Lines are lent out from the buffer Lines owns",
+                },
+                Stop {
+                    line: "fn read_lines_owned",
+                    say: "Fix 2: copy every line into a String of its own",
+                },
+                Stop {
+                    line: "fn split_lines(text: &str) -> Vec<&str> {",
+                    say: "Fix 3: the caller owns the text, and the lines borrow it",
+                },
+                Stop {
+                    line: r#"println!("borrowed: {:?}", split_lines(&text));"#,
+                    say: "Here text outlives the lines borrowed from it",
+                },
+            ],
+        },
+        Example {
+            name: "first_word_properties",
+            title: "Testing get_first_word with thousands of random strings",
+            run: first_word_properties,
+            code: FIRST_WORD_PROPERTIES_CODE,
+            broken_lines: &[],
+            requires: &[],
+            crates: &[r#"proptest = "1""#],
+            stops: &[
+                Stop {
+                    line: "return &s[0..i];",
+                    say: "Slicing at a space is safe: b' ' is never part of a wider character",
+                },
+                Stop {
+                    line: "fn preview",
+                    say: "Slicing at byte 5 can land inside a character and panic",
+                },
+                Stop {
+                    line: "fn first_word_is_a_borrowed_prefix",
+                    say: "The first word always starts where the text does",
+                },
+                Stop {
+                    line: "fn preview_never_panics",
+                    say: "proptest finds a string that makes preview panic",
+                },
+            ],
+        },
+        Example {
+            name: "clone_hotspots",
+            title: "Finding the clones that slow a pipeline down",
+            run: clone_hotspots,
+            code: CLONE_HOTSPOTS_CODE,
+            broken_lines: &[],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let fields: Vec<String>",
+                    say: "Every field becomes a String of its own",
+                },
+                Stop {
+                    line: "name: fields[0].clone(),",
+                    say: "and is then cloned again into the Record",
+                },
+                Stop {
+                    line: r#"records.iter().filter(|record| record.role == "admin").cloned()"#,
+                    say: "Filtering clones each record that's kept",
+                },
+                Stop {
+                    line: "struct RecordRef<'a>",
+                    say: "The borrowing version points into the input lines",
+                },
+                Stop {
+                    line: "let name = fields.next()",
+                    say: "Fields are slices of the line, not copies",
+                },
+                Stop {
+                    line: "fn admins_borrowed",
+                    say: "Filtering keeps references to the records",
+                },
+                Stop {
+                    line: "fn names_borrowed",
+                    say: "and the names are still slices of the input",
+                },
+            ],
+        },
+        Example {
+            name: "zero_copy_parsing",
+            title: "Zero-copy parsing at scale: owned tokens vs borrowed slices over megabytes",
+            run: zero_copy_parsing,
+            code: ZERO_COPY_PARSING_CODE,
+            broken_lines: &[BrokenLine {
+                line: "drop(log);",
+                error: "E0505",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let tokens: Vec<String>",
+                    say: "The owned parser copies every token into a String",
+                },
+                Stop {
+                    line: "entries.push(LogEntry { user: user.clone()",
+                    say: "and clones the ones it keeps once more",
+                },
+                Stop {
+                    line: "struct LogEntryRef<'a>",
+                    say: "The borrowed entries are slices of the log",
+                },
+                Stop {
+                    line: "let tokens: Vec<&str>",
+                    say: "Splitting gives slices, so no text is copied",
+                },
+                Stop {
+                    line: "let owned = parse_entries_owned(&log);",
+                    say: "Millions of small allocations",
+                },
+                Stop {
+                    line: "let borrowed = parse_entries_borrowed(&log);",
+                    say: "One Vec of entries, all pointing into log",
+                },
+                Stop {
+                    line: "// drop(log);",
+                    say: "The entries borrow log, so dropping it first is E0505",
+                },
+            ],
+        },
+        Example {
+            name: "child_process",
+            title: "Owning a child process's pipes: stdin closes when it's dropped",
+            run: child_process,
+            code: CHILD_PROCESS_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: "let stdout = child.stdout.unwrap();",
+                    error: "E0382",
+                },
+                BrokenLine {
+                    line: "stdin.write_all(b\"kiwi\\n\").unwrap();",
+                    error: "E0382",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: r#"let mut child = Command::new("sort")"#,
+                    say: "child owns the process and the ends of its pipes",
+                },
+                Stop {
+                    line: "let mut stdin = child.stdin.take()",
+                    say: "take() moves the handle out and leaves None in child",
+                },
+                Stop {
+                    line: "// let stdout = child.stdout.unwrap();",
+                    say: "unwrap would move stdout out of child for good: E0382 below",
+                },
+                Stop {
+                    line: "let reader = thread::spawn(move || {",
+                    say: "The reader thread owns stdout from here on",
+                },
+                Stop {
+                    line: r#"writeln!(stdin, "{}", name)"#,
+                    say: "The bytes are copied into the pipe, and name is dropped",
+                },
+                Stop {
+                    line: "drop(stdin);",
+                    say: "Dropping stdin closes the pipe, so sort sees its input end",
+                },
+                Stop {
+                    line: "// stdin.write_all",
+                    say: "stdin was moved into drop: E0382",
+                },
+                Stop {
+                    line: r#"println!("sort exited with"#,
+                    say: "child still owns the process, so it can wait for it",
+                },
+            ],
+        },
+    ],
+};
+
+fn first_word() {
+    let mut n = Narration::new();
+
+    let text = String::from("The quick brown fox jumps over the lazy dog");
+    n.fact("text", &text).address("text_buffer", text.as_ptr());
+    n.say("Original text: {text}");
+
+    let first_word = get_first_word(&text);
+    n.fact("first_word", first_word).address("word_start", first_word.as_ptr());
+    n.say("First word: {first_word} (a slice starting at {word_start}, the start of the text at {text_buffer})");
+
+    // Demonstrate how borrowing prevents modification
+    println!("\n  Demonstrating how borrowing prevents data races:");
+
+    let mut mutable_text = String::from("Hello world");
+    n.fact("mutable_text", &mutable_text);
+    n.say("Created mutable text: {mutable_text}");
+
+    // Get a reference to the first word
+    let word = get_first_word(&mutable_text);
+    n.fact("word", word);
+    n.say("First word reference: {word}");
+
+    // This would cause a compile error because we have an immutable reference
+    // while trying to make a mutable one
+    // mutable_text.clear();
+
+    println!("  Can't modify mutable_text while word reference exists");
+    println!("  This prevents a data race where word would be pointing to invalid memory");
+
+    n.say("Using word: {word}"); // Using word
+
+    // Now we can modify mutable_text because word is no longer used
+    mutable_text.clear();
+    n.fact("mutable_text", &mutable_text);
+    n.say("After word is no longer used, we can modify text: '{mutable_text}'");
+}
+
+#[cfg(feature = "proptest")]
+fn first_word_properties() {
+    use proptest::prelude::*;
+    use proptest::test_runner::{Config, RngAlgorithm, TestError, TestRng, TestRunner};
+    use std::cell::Cell;
+    use std::panic;
+
+    let mut n = Narration::new();
+
+    println!("  get_first_word slices by byte index, which panics if the index lands inside a");
+    println!("  multi-byte character. Examples can't cover every string, so state what must hold");
+    println!("  for all of them, and let proptest try to break it:");
+    println!("    - it never panics");
+    println!("    - the word is a prefix of the input, borrowed from it (same pointer), with no space\n");
+
+    // Unicode words joined by spaces, so there's something to find
+    let sentences = prop::collection::vec("\\PC{0,8}", 0..6).prop_map(|words| words.join(" "));
+    let (cases, multibyte) = (Cell::new(0), Cell::new(0));
+    // Failures would be saved next to the test's source file, which a lesson doesn't have
+    let config = Config { cases: 2000, failure_persistence: None, ..Config::default() };
+    // Seeded from the demo's seed (see rng.rs), so a run can be repeated exactly
+    let mut seeds = rng::stream("practical_first_word_properties");
+    let mut seed = || -> Vec<u8> { (0..4).flat_map(|_| seeds.next_u64().to_le_bytes()).collect() };
+    let mut runner = TestRunner::new_with_rng(config.clone(), TestRng::from_seed(RngAlgorithm::ChaCha, &seed()));
+    let result = runner.run(&sentences, |text| {
+        cases.set(cases.get() + 1);
+        if text.len() != text.chars().count() {
+            multibyte.set(multibyte.get() + 1);
+        }
+        let word = get_first_word(&text);
+        prop_assert_eq!(word.as_ptr(), text.as_ptr());
+        prop_assert!(text.starts_with(word) && !word.contains(' '));
+        Ok(())
+    });
+    n.fact("cases", cases.get()).fact("multibyte", multibyte.get());
+    n.fact("verdict", if result.is_ok() { "every property held" } else { "a property failed" });
+    n.say("{cases} random strings, {multibyte} of them with multi-byte characters: {verdict}");
+    println!("  It can't panic: b' ' is one byte, and UTF-8 never uses that byte inside a character\n");
+
+    // A helper that looks just as harmless, but cuts at a fixed byte count
+    fn preview(text: &str) -> &str {
+        &text[..text.len().min(5)]
+    }
+    println!("  Compare fn preview(text: &str) -> &str {{ &text[..text.len().min(5)] }}:");
+    // The failing cases panic on purpose; keep their messages out of the narration
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    // The same seed gives the same counterexample every time
+    let mut runner = TestRunner::new_with_rng(config, TestRng::from_seed(RngAlgorithm::ChaCha, &seed()));
+    let result = runner.run(&any::<String>(), |text| {
+        preview(&text);
+        Ok(())
+    });
+    panic::set_hook(hook);
+    match result {
+        Err(TestError::Fail(_, text)) => {
+            n.fact("input", format!("{:?}", text)).fact("bytes", text.len());
+            n.say("proptest found a panic and shrank it to {input} ({bytes} bytes): byte 5 is mid-character");
+        }
+        _ => println!("  proptest didn't find a failing input this time"),
+    }
+    println!("  Kept as #[test]s, properties like these guard a borrowing API against regressions");
+}
+
+#[cfg(not(feature = "proptest"))]
+fn first_word_properties() {
+    println!("  This example needs proptest, which the demo only builds with the proptest feature:");
+    println!("    cargo run --features proptest -- run practical_first_word_properties");
+}
+
+fn file_lines() {
+    let mut n = Narration::new();
+
+    let path = env::temp_dir().join(format!("ownership-demo-{}-lines.txt", process::id()));
+    if let Err(e) = fs::write(&path, "alpha\nbeta\ngamma\ndelta\n") {
+        println!("  Could not write a sample file to {}: {}", path.display(), e);
+        return;
+    }
+    n.say("Wrote a sample file with four lines");
+
+    println!("  The first idea is to read the file into a String and return its lines:");
+    println!("    fn read_lines(path: &Path) -> Vec<&str> {{");
+    println!("        let text = fs::read_to_string(path).unwrap();");
+    println!("        text.lines().collect()");
+    println!("    }} // error[E0515]: cannot return value referencing local variable `text`");
+    println!("  The &strs would point into text, which is dropped when the function returns.");
+    println!("  (Elision even ties them to path, which isn't what they borrow from.) Three fixes:");
+
+    // Fix 1: return the buffer along with the positions of the lines in it
+    let (lines, offset_allocs) = instrument::measure(|| Lines::read(&path));
+    let lines = lines.expect("the sample file was just written");
+    n.fact("second", lines.get(1)).fact("count", lines.len()).fact("offset_allocs", offset_allocs.count);
+    n.address("buffer", lines.buffer.as_ptr()).address("second_at", lines.get(1).as_ptr());
+    n.say("1. Return the buffer plus offsets: {count} lines, {offset_allocs} allocations (buffer and offsets),");
+    n.say("   lines.get(1) = '{second}' at {second_at}, inside the buffer at {buffer}");
+
+    // Fix 2: copy every line into its own String
+    let (owned, owned_allocs) = instrument::measure(|| read_lines_owned(&path));
+    let owned = owned.expect("the sample file was just written");
+    n.fact("owned", format!("{:?}", owned)).fact("owned_allocs", owned_allocs.count);
+    n.say("2. Return Vec<String>: {owned}, {owned_allocs} allocations, one more per line");
+
+    // Fix 3: let the caller own the buffer and only borrow from it
+    let text = fs::read_to_string(&path).expect("the sample file was just written");
+    let (borrowed, borrowed_allocs) = instrument::measure(|| split_lines(&text));
+    n.fact("borrowed", format!("{:?}", borrowed)).fact("borrowed_allocs", borrowed_allocs.count);
+    n.say("3. Take the buffer as a parameter: split_lines(&text) = {borrowed},");
+    n.say("   {borrowed_allocs} allocation for the Vec itself; the lines live as long as the caller's text");
+
+    let _ = fs::remove_file(&path);
+    println!("\n  Prefer 3 when the caller can keep the text around, 1 when one value has to carry");
+    println!("  everything (to store it or return it further up), and 2 when lines are few or short");
+}
+
+// How many CSV lines the clone_hotspots pipeline processes
+const RECORDS: usize = 2000;
+
+// Where clone_hotspots writes its allocation counts as folded stacks, when set
+const FOLDED_VARIABLE: &str = "RUST_OWNERSHIP_DEMO_FOLDED";
+
+fn clone_hotspots() {
+    let mut n = Narration::new();
+
+    let input: Vec<String> = (0..RECORDS)
+        .map(|i| format!("user{},{},{}", i, i * 7 % 100, if i % 4 == 0 { "admin" } else { "member" }))
+        .collect();
+    n.fact("records", input.len()).fact("sample", &input[0]);
+    n.say("A report over {records} CSV lines like '{sample}': parse them, keep the admins, list their names");
+
+    // The version that clones wherever the borrow checker complained
+    let mut cloning = Vec::new();
+    let records = stage(&mut cloning, "parse", || parse_owned(&input));
+    let admins = stage(&mut cloning, "filter", || admins_cloned(&records));
+    let cloned_names = stage(&mut cloning, "names", || names_cloned(&admins));
+
+    // The same pipeline, borrowing from the input all the way through
+    let mut borrowing = Vec::new();
+    let records = stage(&mut borrowing, "parse", || parse_borrowed(&input));
+    let admins = stage(&mut borrowing, "filter", || admins_borrowed(&records));
+    let borrowed_names = stage(&mut borrowing, "names", || names_borrowed(&admins));
+
+    let same = cloned_names.iter().map(|name| name.as_str()).eq(borrowed_names.iter().copied());
+    n.fact("names", borrowed_names.len()).fact("same", same);
+    n.say("Both versions report {names} admin names (identical: {same})");
+
+    println!("\n  stage    cloning: allocations  time      borrowing: allocations  time");
+    for (before, after) in cloning.iter().zip(&borrowing) {
+        println!(
+            "  {:<8} {:>21} {:>8.2?} {:>23} {:>8.2?}",
+            before.name, before.allocations, before.time, after.allocations, after.time
+        );
+    }
+
+    let total = |stages: &[Stage]| stages.iter().map(|stage| stage.allocations).sum::<usize>();
+    let time = |stages: &[Stage]| stages.iter().map(|stage| stage.time).sum::<Duration>();
+    let hotspot = cloning.iter().max_by_key(|stage| stage.allocations).expect("the pipeline has stages");
+    n.fact("hotspot", hotspot.name).fact("hot_allocs", hotspot.allocations).fact("before", total(&cloning));
+    n.fact("per_line", hotspot.allocations / input.len());
+    n.fact("after", total(&borrowing));
+    n.fact("before_time", format!("{:.2?}", time(&cloning))).fact("after_time", format!("{:.2?}", time(&borrowing)));
+    println!();
+    n.say("The hotspot is {hotspot}: {hot_allocs} of the {before} allocations, {per_line} per line.");
+    n.say("Borrowing &strs from the input instead: {before} -> {after} allocations, {before_time} -> {after_time}");
+    println!("  The allocation count points at the same stage every run; the times vary, so compare");
+    println!("  them on a release build before trusting small differences. To find the lines doing");
+    println!("  the cloning, as you would in a real codebase, run this with --trace-clones");
+
+    match env::var_os(FOLDED_VARIABLE) {
+        Some(path) => {
+            let mut folded = String::new();
+            for (version, stages) in [("cloning", &cloning), ("borrowing", &borrowing)] {
+                for stage in stages.iter() {
+                    folded.push_str(&format!("clone_hotspots;{};{} {}\n", version, stage.name, stage.allocations));
+                }
+            }
+            match fs::write(&path, folded) {
+                Ok(()) => println!("  Wrote the allocation counts as folded stacks to {}", Path::new(&path).display()),
+                Err(e) => println!("  Could not write {}: {}", Path::new(&path).display(), e),
+            }
+        }
+        None => {
+            println!("  Set {}=<file> to save these counts as folded stacks, which", FOLDED_VARIABLE);
+            println!("  flamegraph.pl or inferno-flamegraph draw as an allocation flame graph");
+        }
+    }
+}
+
+// One step of a pipeline: what it cost to run
+struct Stage {
+    name: &'static str,
+    allocations: usize,
+    time: Duration,
+}
+
+fn stage<T>(stages: &mut Vec<Stage>, name: &'static str, f: impl FnOnce() -> T) -> T {
+    let started = Instant::now();
+    let (result, allocations) = instrument::measure(f);
+    stages.push(Stage { name, allocations: allocations.count, time: started.elapsed() });
+    result
+}
+
+// The Strings are Tracked, so --trace-clones can show where they're cloned
+#[derive(Clone)]
+struct Record {
+    name: Tracked<String>,
+    score: u32,
+    role: Tracked<String>,
+}
+
+// Every field becomes a String, and then gets copied again into the Record
+fn parse_owned(lines: &[String]) -> Vec<Record> {
+    let mut records = Vec::new();
+    for line in lines {
+        let fields: Vec<Tracked<String>> = line.split(',').map(|field| Tracked(String::from(field))).collect();
+        records.push(Record {
+            name: fields[0].clone(),
+            score: fields[1].parse().unwrap_or(0),
+            role: fields[2].clone(),
+        });
+    }
+    records
+}
+
+fn admins_cloned(records: &[Record]) -> Vec<Record> {
+    records.iter().filter(|record| *record.role == "admin").cloned().collect()
+}
+
+fn names_cloned(records: &[Record]) -> Vec<Tracked<String>> {
+    records.iter().filter(|record| record.score > 0).map(|record| record.name.clone()).collect()
+}
+
+// The same fields, borrowed from the line they were parsed from
+struct RecordRef<'a> {
+    name: &'a str,
+    score: u32,
+    role: &'a str,
+}
+
+fn parse_borrowed(lines: &[String]) -> Vec<RecordRef<'_>> {
+    let mut records = Vec::with_capacity(lines.len());
+    for line in lines {
+        let mut fields = line.split(',');
+        let (name, score, role) = (fields.next().unwrap_or(""), fields.next().unwrap_or(""), fields.next().unwrap_or(""));
+        records.push(RecordRef { name, score: score.parse().unwrap_or(0), role });
+    }
+    records
+}
+
+fn admins_borrowed<'a, 'r>(records: &'r [RecordRef<'a>]) -> Vec<&'r RecordRef<'a>> {
+    records.iter().filter(|record| record.role == "admin").collect()
+}
+
+fn names_borrowed<'a>(records: &[&RecordRef<'a>]) -> Vec<&'a str> {
+    records.iter().filter(|record| record.score > 0).map(|record| record.name).collect()
+}
+
+// How much access log zero_copy_parsing generates
+const LOG_BYTES: usize = 4 << 20;
+const MB: f64 = (1 << 20) as f64;
+
+fn zero_copy_parsing() {
+    let mut n = Narration::new();
+
+    let log = generate_log(LOG_BYTES);
+    n.fact("mb", format!("{:.1}", log.len() as f64 / MB)).fact("lines", log.lines().count());
+    n.say("An access log of {mb} MB, {lines} lines like:");
+    println!("    {}", log.lines().next().unwrap_or_default());
+
+    // Both parsers read the same text; only where their fields live differs
+    let started = Instant::now();
+    let (owned, owned_allocs) = instrument::measure(|| parse_entries_owned(&log));
+    let owned_time = started.elapsed();
+    let started = Instant::now();
+    let (borrowed, borrowed_allocs) = instrument::measure(|| parse_entries_borrowed(&log));
+    let borrowed_time = started.elapsed();
+
+    let throughput = |time: Duration| format!("{:.0} MB/s", log.len() as f64 / MB / time.as_secs_f64());
+    let megabytes = |bytes: usize| format!("{:.1} MB", bytes as f64 / MB);
+    n.fact("owned_rate", throughput(owned_time)).fact("owned_allocs", owned_allocs.count);
+    n.fact("owned_peak", megabytes(owned_allocs.peak));
+    n.fact("borrowed_rate", throughput(borrowed_time)).fact("borrowed_allocs", borrowed_allocs.count);
+    n.fact("borrowed_peak", megabytes(borrowed_allocs.peak));
+    println!();
+    n.say("Vec<LogEntry>, a String per field:    {owned_rate}, peak {owned_peak}, allocations: {owned_allocs}");
+    n.say("Vec<LogEntryRef>, a &str per field:   {borrowed_rate}, peak {borrowed_peak}, allocations: {borrowed_allocs}");
+
+    let owned_errors = owned.iter().filter(|entry| entry.level == "ERROR" && entry.status >= 500).count();
+    let borrowed_errors = borrowed.iter().filter(|entry| entry.level == "ERROR" && entry.status >= 500).count();
+    let same = owned.len() == borrowed.len()
+        && owned.iter().zip(&borrowed).all(|(a, b)| a.time == b.time && a.user == b.user && a.path == b.path && a.status == b.status);
+    n.fact("errors", owned_errors).fact("same", same && owned_errors == borrowed_errors);
+    n.fact("time", borrowed[0].time).fact("path", borrowed[0].path).fact("user", borrowed[0].user);
+    n.address("user_at", borrowed[0].user.as_ptr()).address("log_at", log.as_ptr());
+    n.say("Both find {errors} server errors (identical entries: {same}).");
+    n.say("The first request, {path} at {time}, came from '{user}', whose text is at {user_at},");
+    n.say("inside the log at {log_at}: the borrowed parser copied nothing");
+    n.fact("times", format!("{:.1}", owned_allocs.peak as f64 / borrowed_allocs.peak.max(1) as f64));
+    n.fact("per_line", owned_allocs.count / owned.len().max(1));
+    n.fact("speedup", format!("{:.1}", owned_time.as_secs_f64() / borrowed_time.as_secs_f64()));
+    println!();
+    n.say("Owning every token costs {per_line} allocations per line, {times}x the memory at its peak,");
+    n.say("and a {speedup}x slower parse. At this size that's the difference between a log viewer");
+    println!("  that opens instantly and one that stalls, with the same code shape: only the field");
+    println!("  types changed. The price is the lifetime: the entries borrow log, so drop(log) while");
+    println!("  they're in use is error[E0505]. (Times vary run to run and are far better in a release");
+    println!("  build; the allocation counts and peaks are exact)");
+}
+
+// A deterministic access log of about `bytes` bytes, one request per line
+fn generate_log(bytes: usize) -> String {
+    const PATHS: [&str; 5] = ["/", "/api/items", "/api/items/42", "/login", "/static/app.js"];
+    let mut rng = rng::stream("practical_zero_copy_parsing");
+    let mut log = String::with_capacity(bytes + 128);
+    let mut second = 0;
+    while log.len() < bytes {
+        second += rng.below(3);
+        let status = match rng.below(100) {
+            0..=1 => 500,
+            2..=9 => 404,
+            _ => 200,
+        };
+        let level = if status == 500 { "ERROR" } else { "INFO" };
+        log.push_str(&format!(
+            "2026-10-15T{:02}:{:02}:{:02}Z {} user{} GET {} {} {}\n",
+            9 + second / 3600 % 12,
+            second / 60 % 60,
+            second % 60,
+            level,
+            rng.below(1000),
+            PATHS[rng.below(PATHS.len())],
+            status,
+            rng.below(20_000)
+        ));
+    }
+    log
+}
+
+// One request from the log, every field in its own String
+struct LogEntry {
+    time: String,
+    level: String,
+    user: String,
+    path: String,
+    status: u16,
+}
+
+// The same request, every field borrowed from the log text
+struct LogEntryRef<'a> {
+    time: &'a str,
+    level: &'a str,
+    user: &'a str,
+    path: &'a str,
+    status: u16,
+}
+
+// Splits each line into owned tokens, then keeps the ones it needs
+fn parse_entries_owned(log: &str) -> Vec<LogEntry> {
+    let mut entries = Vec::new();
+    for line in log.lines() {
+        let tokens: Vec<String> = line.split(' ').map(String::from).collect();
+        let [time, level, user, _method, path, status, _size] = &tokens[..] else { continue };
+        entries.push(LogEntry {
+            time: time.clone(),
+            level: level.clone(),
+            user: user.clone(),
+            path: path.clone(),
+            status: status.parse().unwrap_or(0),
+        });
+    }
+    entries
+}
+
+// Splits each line into slices of the log; nothing is copied but the Vec
+fn parse_entries_borrowed(log: &str) -> Vec<LogEntryRef<'_>> {
+    let mut entries = Vec::with_capacity(log.lines().count());
+    for line in log.lines() {
+        let mut tokens = line.split(' ');
+        let (Some(time), Some(level), Some(user), Some(_), Some(path), Some(status)) =
+            (tokens.next(), tokens.next(), tokens.next(), tokens.next(), tokens.next(), tokens.next())
+        else {
+            continue;
+        };
+        entries.push(LogEntryRef { time, level, user, path, status: status.parse().unwrap_or(0) });
+    }
+    entries
+}
+
+// The whole file plus where each line starts and ends in it
+struct Lines {
+    buffer: String,
+    offsets: Vec<Range<usize>>,
+}
+
+impl Lines {
+    // This function owns the buffer it reads, and remembers positions instead of &strs
+    fn read(path: &Path) -> io::Result<Lines> {
+        let buffer = fs::read_to_string(path)?;
+        let mut offsets = Vec::new();
+        let mut start = 0;
+        for line in buffer.split_inclusive('\n') {
+            offsets.push(start..start + line.trim_end_matches('\n').len());
+            start += line.len();
+        }
+        Ok(Lines { buffer, offsets })
+    }
+
+    // Borrowing from self is fine: the buffer lives as long as the Lines
+    fn get(&self, index: usize) -> &str {
+        &self.buffer[self.offsets[index].clone()]
+    }
+
+    fn len(&self) -> usize {
+        self.offsets.len()
+    }
+}
+
+// This function copies each line into a String the caller will own
+fn read_lines_owned(path: &Path) -> io::Result<Vec<String>> {
+    Ok(fs::read_to_string(path)?.lines().map(String::from).collect())
+}
+
+// This function only borrows, so its result is tied to the caller's buffer
+fn split_lines(text: &str) -> Vec<&str> {
+    text.lines().collect()
+}
+
+fn child_process() {
+    let mut n = Narration::new();
+
+    // LC_ALL=C so sort orders by bytes, the same on every machine
+    let spawned = Command::new("sort").env("LC_ALL", "C").stdin(Stdio::piped()).stdout(Stdio::piped()).spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) => {
+            println!("  Could not start sort, which this example talks to: {}", e);
+            return;
+        }
+    };
+    n.fact("pid", child.id());
+    n.say("Spawned sort as process {pid}, its stdin and stdout connected to pipes that child holds");
+
+    let mut stdin = child.stdin.take().expect("stdin was piped");
+    let mut stdout = child.stdout.take().expect("stdout was piped");
+    n.fact("taken", child.stdin.is_none() && child.stdout.is_none());
+    n.say("child.stdin.take() and child.stdout.take() moved both handles out of child (both are");
+    n.say("None now: {taken}), so each end of the conversation can have an owner of its own");
+
+    // The move closure takes stdout, so the thread owns it and reads until sort is done
+    let reader = thread::spawn(move || {
+        let mut sorted = String::new();
+        stdout.read_to_string(&mut sorted).map(|_| sorted)
+    });
+
+    let fruit = Vec::from(["pear", "apple", "fig", "banana"].map(String::from));
+    let (count, bytes) = (fruit.len(), fruit.iter().map(|name| name.len() + 1).sum::<usize>());
+    for name in fruit {
+        if let Err(e) = writeln!(stdin, "{}", name) {
+            println!("  Could not write to sort: {}", e);
+            return;
+        }
+    }
+    n.fact("lines", count).fact("bytes", bytes);
+    n.say("Wrote {lines} Strings ({bytes} bytes) into stdin, consuming them. Only their bytes went");
+    n.say("into the pipe; sort reads its own copy, so ownership stops at the process boundary");
+
+    thread::sleep(Duration::from_millis(300));
+    n.fact("finished", reader.is_finished());
+    n.say("300ms later, has the reader thread got sort's output? {finished}. sort can't sort until");
+    n.say("its input ends, and it doesn't end while our stdin handle is open");
+
+    drop(stdin);
+    let started = Instant::now();
+    let sorted = match reader.join() {
+        Ok(Ok(sorted)) => sorted,
+        _ => {
+            println!("  Could not read sort's output");
+            return;
+        }
+    };
+    n.fact("after", format!("{:.0?}", started.elapsed())).fact("sorted", sorted.lines().collect::<Vec<_>>().join(", "));
+    n.say("drop(stdin) closed the pipe, sort saw the end of its input, and {after} later the");
+    n.say("reader thread returned: {sorted}");
+    match child.wait() {
+        Ok(status) => n.fact("status", status),
+        Err(e) => n.fact("status", format!("not waited for ({})", e)),
+    };
+    n.say("child.wait() collected sort's exit: {status}");
+
+    println!("\n  A ChildStdin owns a file descriptor, and dropping it closes it, just as dropping a");
+    println!("  File does. So the end of the input is a question of ownership: while the handle is");
+    println!("  alive the child waits, and when its owner (a scope, a thread) ends, the pipe closes.");
+    println!("  After drop(stdin), `stdin.write_all(..)` is error[E0382]. So is taking child.stdout");
+    println!("  after `let stdout = child.stdout.unwrap();`, which moved it out of child for good;");
+    println!("  take() leaves None in its place");
+}
+
+const FIRST_WORD_CODE: &str = r#"fn main() {
+    let mut text = String::from("Hello world");
+
+    // word borrows part of text
+    let word = get_first_word(&text);
+
+    // text can't be modified while word is still in use
+    // text.clear();
+
+    println!("first word: {}", word);
+
+    // word is no longer used, so text can be modified again
+    text.clear();
+    println!("after clear: '{}'", text);
+}
+
+fn get_first_word(s: &str) -> &str {
+    let bytes = s.as_bytes();
+
+    for (i, &item) in bytes.iter().enumerate() {
+        if item == b' ' {
+            return &s[0..i];
+        }
+    }
+
+    s
+}
+"#;
+
+const FIRST_WORD_PROPERTIES_CODE: &str = r#"fn get_first_word(s: &str) -> &str {
+    let bytes = s.as_bytes();
+
+    for (i, &item) in bytes.iter().enumerate() {
+        if item == b' ' {
+            return &s[0..i];
+        }
+    }
+
+    s
+}
+
+// Looks as harmless, but slicing at byte 5 can land inside a character
+fn preview(text: &str) -> &str {
+    &text[..text.len().min(5)]
+}
+
+fn main() {
+    println!("{}", get_first_word("héllo wörld"));
+    println!("{}", preview("hello world"));
+    println!("run `cargo test --example practical_first_word_properties --features proptest`");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        // Unicode words joined by spaces
+        #[test]
+        fn first_word_is_a_borrowed_prefix(words in prop::collection::vec("\\PC{0,8}", 0..6)) {
+            let text = words.join(" ");
+            let word = get_first_word(&text);
+            prop_assert_eq!(word.as_ptr(), text.as_ptr());
+            prop_assert!(text.starts_with(word) && !word.contains(' '));
+        }
+
+        // Fails: proptest shrinks it to a short string with a multi-byte character
+        #[test]
+        #[should_panic]
+        fn preview_never_panics(text in any::<String>()) {
+            preview(&text);
+        }
+    }
+}
+"#;
+
+const FILE_LINES_CODE: &str = r#"use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+// The String is dropped at the end of the function, so its lines can't be returned
+// fn read_lines(path: &Path) -> Vec<&str> { let text = fs::read_to_string(path).unwrap(); text.lines().collect() }
+
+// Fix 1: return the buffer together with the positions of the lines in it
+struct Lines {
+    buffer: String,
+    offsets: Vec<Range<usize>>,
+}
+
+impl Lines {
+    fn read(path: &Path) -> Lines {
+        let buffer = fs::read_to_string(path).unwrap();
+        let mut offsets = Vec::new();
+        let mut start = 0;
+        for line in buffer.split_inclusive('\n') {
+            offsets.push(start..start + line.trim_end_matches('\n').len());
+            start += line.len();
+        }
+        Lines { buffer, offsets }
+    }
+
+    fn get(&self, index: usize) -> &str {
+        &self.buffer[self.offsets[index].clone()]
+    }
+}
+
+// Fix 2: return owned Strings
+fn read_lines_owned(path: &Path) -> Vec<String> {
+    fs::read_to_string(path).unwrap().lines().map(String::from).collect()
+}
+
+// Fix 3: let the caller own the buffer
+fn split_lines(text: &str) -> Vec<&str> {
+    text.lines().collect()
+}
+
+fn main() {
+    let path = std::env::temp_dir().join("lines.txt");
+    fs::write(&path, "alpha\nbeta\ngamma\n").unwrap();
+
+    let lines = Lines::read(&path);
+    println!("second line: {}", lines.get(1));
+
+    println!("owned: {:?}", read_lines_owned(&path));
+
+    let text = fs::read_to_string(&path).unwrap();
+    println!("borrowed: {:?}", split_lines(&text));
+}
+"#;
+
+const CLONE_HOTSPOTS_CODE: &str = r#"use std::time::Instant;
+
+#[derive(Clone)]
+struct Record {
+    name: String,
+    score: u32,
+    role: String,
+}
+
+// Clones wherever the borrow checker complained: every field is copied, twice
+fn parse_owned(lines: &[String]) -> Vec<Record> {
+    let mut records = Vec::new();
+    for line in lines {
+        let fields: Vec<String> = line.split(',').map(String::from).collect();
+        records.push(Record {
+            name: fields[0].clone(),
+            score: fields[1].parse().unwrap_or(0),
+            role: fields[2].clone(),
+        });
+    }
+    records
+}
+
+fn admins_cloned(records: &[Record]) -> Vec<Record> {
+    records.iter().filter(|record| record.role == "admin").cloned().collect()
+}
+
+fn names_cloned(records: &[Record]) -> Vec<String> {
+    records.iter().filter(|record| record.score > 0).map(|record| record.name.clone()).collect()
+}
+
+// The same pipeline, borrowing from the input all the way through
+struct RecordRef<'a> {
+    name: &'a str,
+    score: u32,
+    role: &'a str,
+}
+
+fn parse_borrowed(lines: &[String]) -> Vec<RecordRef<'_>> {
+    let mut records = Vec::with_capacity(lines.len());
+    for line in lines {
+        let mut fields = line.split(',');
+        let name = fields.next().unwrap_or("");
+        let score = fields.next().unwrap_or("").parse().unwrap_or(0);
+        let role = fields.next().unwrap_or("");
+        records.push(RecordRef { name, score, role });
+    }
+    records
+}
+
+fn admins_borrowed<'a, 'r>(records: &'r [RecordRef<'a>]) -> Vec<&'r RecordRef<'a>> {
+    records.iter().filter(|record| record.role == "admin").collect()
+}
+
+fn names_borrowed<'a>(records: &[&RecordRef<'a>]) -> Vec<&'a str> {
+    records.iter().filter(|record| record.score > 0).map(|record| record.name).collect()
+}
+
+fn main() {
+    let input: Vec<String> = (0..100_000)
+        .map(|i| format!("user{},{},{}", i, i * 7 % 100, if i % 4 == 0 { "admin" } else { "member" }))
+        .collect();
+
+    let started = Instant::now();
+    let cloned = names_cloned(&admins_cloned(&parse_owned(&input)));
+    println!("cloning:   {} names in {:?}", cloned.len(), started.elapsed());
+
+    let started = Instant::now();
+    let records = parse_borrowed(&input);
+    let borrowed = names_borrowed(&admins_borrowed(&records));
+    println!("borrowing: {} names in {:?}", borrowed.len(), started.elapsed());
+
+    assert_eq!(cloned, borrowed);
+}
+"#;
+
+const ZERO_COPY_PARSING_CODE: &str = r#"use std::time::Instant;
+
+// Every field in its own String: one allocation per token, and the text copied
+struct LogEntry {
+    user: String,
+    path: String,
+    status: u16,
+}
+
+fn parse_entries_owned(log: &str) -> Vec<LogEntry> {
+    let mut entries = Vec::new();
+    for line in log.lines() {
+        let tokens: Vec<String> = line.split(' ').map(String::from).collect();
+        if let [_time, _level, user, _method, path, status, _size] = &tokens[..] {
+            entries.push(LogEntry { user: user.clone(), path: path.clone(), status: status.parse().unwrap_or(0) });
+        }
+    }
+    entries
+}
+
+// Every field a slice of the log: no text is copied, but the entries can't outlive it
+struct LogEntryRef<'a> {
+    user: &'a str,
+    path: &'a str,
+    status: u16,
+}
+
+fn parse_entries_borrowed(log: &str) -> Vec<LogEntryRef<'_>> {
+    let mut entries = Vec::with_capacity(log.lines().count());
+    for line in log.lines() {
+        let tokens: Vec<&str> = line.splitn(7, ' ').collect();
+        if let [_time, _level, user, _method, path, status, _size] = tokens[..] {
+            entries.push(LogEntryRef { user, path, status: status.parse().unwrap_or(0) });
+        }
+    }
+    entries
+}
+
+fn main() {
+    // About 4 MB of access log
+    let paths = ["/", "/api/items", "/api/items/42", "/login", "/static/app.js"];
+    let mut log = String::new();
+    let mut i: usize = 0;
+    while log.len() < 4 << 20 {
+        let status = match i % 50 {
+            0 => 500,
+            _ => 200,
+        };
+        let line = format!("2026-10-15T09:{:02}:{:02}Z INFO user{} GET {} {} {}\n", i / 60 % 60, i % 60, i * 7 % 1000, paths[i % 5], status, i % 20_000);
+        log.push_str(&line);
+        i += 1;
+    }
+
+    let started = Instant::now();
+    let owned = parse_entries_owned(&log);
+    let errors = owned.iter().filter(|entry| entry.status >= 500).count();
+    println!("owned:    {} entries, {} errors in {:?}", owned.len(), errors, started.elapsed());
+    println!("first: {} {}", owned[0].user, owned[0].path);
+
+    let started = Instant::now();
+    let borrowed = parse_entries_borrowed(&log);
+    let errors = borrowed.iter().filter(|entry| entry.status >= 500).count();
+    println!("borrowed: {} entries, {} errors in {:?}", borrowed.len(), errors, started.elapsed());
+
+    // The entries point into log, so log has to outlive them
+    // drop(log);
+    println!("first: {} {}", borrowed[0].user, borrowed[0].path);
+}
+"#;
+
+const CHILD_PROCESS_CODE: &str = r#"use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::thread;
+
+fn main() {
+    let mut child = Command::new("sort")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("could not start sort");
+
+    // take() moves each handle out of child, leaving None behind
+    let mut stdin = child.stdin.take().expect("stdin was piped");
+    // Moves stdout out of child for good, so it can't be taken below
+    // let stdout = child.stdout.unwrap();
+    let mut stdout = child.stdout.take().expect("stdout was piped");
+
+    // The reader thread owns stdout; sort prints nothing until its input ends
+    let reader = thread::spawn(move || {
+        let mut sorted = String::new();
+        stdout.read_to_string(&mut sorted).expect("could not read from sort");
+        sorted
+    });
+
+    let fruit = vec![String::from("pear"), String::from("apple"), String::from("fig")];
+    for name in fruit {
+        // The bytes are copied into the pipe, and name is dropped after the write
+        writeln!(stdin, "{}", name).expect("could not write to sort");
+    }
+    // Dropping stdin closes the pipe: that's how sort learns its input has ended
+    drop(stdin);
+    // The handle is gone
+    // stdin.write_all(b"kiwi\n").unwrap();
+
+    print!("{}", reader.join().expect("the reader thread panicked"));
+    println!("sort exited with {}", child.wait().expect("sort wasn't running"));
+}
+"#;
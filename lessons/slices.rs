@@ -0,0 +1,80 @@
+use super::{BrokenLine, Example, Section, Stop};
+use crate::narration::Narration;
+
+pub const SECTION: Section = Section {
+    name: "slices",
+    title: "SLICES",
+    examples: &[Example {
+        name: "string_slices",
+        title: "String slices",
+        run: string_slices,
+        code: STRING_SLICES_CODE,
+        broken_lines: &[
+            BrokenLine {
+                line: "s.clear();",
+                error: "E0502",
+            },
+        ],
+        requires: &[],
+        crates: &[],
+        stops: &[
+                Stop {
+                    line: "let hello = &s[0..5];",
+                    say: "hello borrows the first five bytes of s",
+                },
+                Stop {
+                    line: "let world = &s[6..11];",
+                    say: "world borrows the last five: no text is copied",
+                },
+                Stop {
+                    line: "// s.clear();",
+                    say: "Clearing s while the slices are in use is E0502",
+                },
+                Stop {
+                    line: r#"println!("'{}' and '{}'", hello, world);"#,
+                    say: "This is the last use of the slices",
+                },
+                Stop {
+                    line: "s.clear();",
+                    say: "so s can be changed again",
+                },
+            ],
+    }],
+};
+
+fn string_slices() {
+    let mut n = Narration::new();
+
+    let s = String::from("hello world");
+    n.fact("s", &s).address("s_buffer", s.as_ptr());
+    n.say("Created string s: {s} (heap data at {s_buffer})");
+
+    let hello = &s[0..5];
+    let world = &s[6..11];
+
+    n.fact("hello", hello).address("hello_start", hello.as_ptr());
+    n.fact("world", world).fact("world_offset", world.as_ptr() as usize - s.as_ptr() as usize);
+    n.fact("slice_size", std::mem::size_of::<&str>());
+    n.say("Created slices: '{hello}' and '{world}'");
+    n.say("'{hello}' starts at {hello_start} and '{world}' starts {world_offset} bytes further into the same data");
+    n.say("Each slice is just a {slice_size}-byte pointer and length");
+    println!("  Note: Slices are references to a portion of the String");
+    println!("  This means they don't take ownership of the data");
+}
+
+const STRING_SLICES_CODE: &str = r#"fn main() {
+    let mut s = String::from("hello world");
+
+    let hello = &s[0..5];
+    let world = &s[6..11];
+
+    // The slices borrow s, so it can't be modified while they are in use
+    // s.clear();
+
+    println!("'{}' and '{}'", hello, world);
+
+    // Once the slices are no longer used, s can be modified again
+    s.clear();
+    println!("after clear: '{}'", s);
+}
+"#;
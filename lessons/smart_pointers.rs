@@ -0,0 +1,1179 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::mem::size_of;
+use std::rc::{Rc, Weak};
+use std::sync::Arc;
+use std::thread;
+
+use super::{BrokenLine, Example, Section, Stop};
+use crate::instrument;
+use crate::narration::Narration;
+use crate::toolchain::ARC_INTO_INNER;
+
+pub const SECTION: Section = Section {
+    name: "smart_pointers",
+    title: "SMART POINTERS AND SHARED OWNERSHIP",
+    examples: &[
+        Example {
+            name: "boxed_values",
+            title: "Box<T>: one owner, data on the heap",
+            run: boxed_values,
+            code: BOXED_VALUES_CODE,
+            broken_lines: &[BrokenLine {
+                line: r#"println!("{}", boxed.sensor);"#,
+                error: "E0382",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let boxed = Box::new",
+                    say: "The Reading moves to the heap; boxed points at it",
+                },
+                Stop {
+                    line: "let moved = boxed;",
+                    say: "Moving the Box copies the pointer; the Reading stays put",
+                },
+                Stop {
+                    line: r#"// println!("{}", boxed.sensor);"#,
+                    say: "boxed gave its Reading away: E0382",
+                },
+                Stop {
+                    line: r#"println!("{} {}", moved.celsius"#,
+                    say: "Deref lets fields and methods reach through the Box",
+                },
+                Stop {
+                    line: "let unboxed = *moved;",
+                    say: "*moved moves the Reading out and frees the heap block",
+                },
+                Stop {
+                    line: "} // temporary goes out of scope",
+                    say: "A Box going out of scope frees what it points to",
+                },
+            ],
+        },
+        Example {
+            name: "recursive_types",
+            title: "Recursive types need a Box",
+            run: recursive_types,
+            code: RECURSIVE_TYPES_CODE,
+            broken_lines: &[BrokenLine {
+                line: "enum Unboxed { Cons(i32, Unboxed), Nil }",
+                error: "E0072",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "// enum Unboxed",
+                    say: "A type that contains itself would have no size: E0072",
+                },
+                Stop {
+                    line: "Cons(i32, Box<List>),",
+                    say: "A Box is a pointer, so List has a fixed size",
+                },
+                Stop {
+                    line: "let list = Cons(1, Box::new",
+                    say: "Each Cons owns the Box that holds the rest",
+                },
+                Stop {
+                    line: "let mut node = &list;",
+                    say: "Walking the list only borrows it",
+                },
+                Stop {
+                    line: "} // list is dropped",
+                    say: "Dropping list drops each Box in turn, down the chain",
+                },
+            ],
+        },
+        Example {
+            name: "shared_list",
+            title: "Rc<T>: several owners of one list",
+            run: shared_list,
+            code: SHARED_LIST_CODE,
+            broken_lines: &[BrokenLine {
+                line: r#"println!("{}", Rc::strong_count(&a));"#,
+                error: "E0382",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "Cons(i32, Rc<List>),",
+                    say: "Each list shares its tail through an Rc",
+                },
+                Stop {
+                    line: "let a = Rc::new",
+                    say: "a is the only owner so far: the count is 1",
+                },
+                Stop {
+                    line: "let b = Cons(3, Rc::clone(&a));",
+                    say: "Rc::clone adds an owner; the list isn't copied",
+                },
+                Stop {
+                    line: "} // c is dropped",
+                    say: "When c goes, the count goes down by one",
+                },
+                Stop {
+                    line: "drop(b);",
+                    say: "and again when b is dropped",
+                },
+                Stop {
+                    line: "let _d = Cons(2, a);",
+                    say: "The Rc itself still has one owner: this moves a",
+                },
+                Stop {
+                    line: r#"// println!("{}", Rc::strong_count(&a));"#,
+                    say: "so a can't be used any more: E0382",
+                },
+                Stop {
+                    line: "} // d is dropped",
+                    say: "The count reaches 0, and only then is the list freed",
+                },
+            ],
+        },
+        Example {
+            name: "reference_cycle",
+            title: "Rc cycles leak; Weak breaks them",
+            run: reference_cycle,
+            code: REFERENCE_CYCLE_CODE,
+            broken_lines: &[BrokenLine {
+                line: r#"println!("{}", leaf.parent.borrow().name);"#,
+                error: "E0609",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "*a.other.borrow_mut() = Some(Rc::clone(&b));",
+                    say: "a holds a strong reference to b",
+                },
+                Stop {
+                    line: "*b.other.borrow_mut() = Some(Rc::clone(&a));",
+                    say: "and b one to a: a cycle",
+                },
+                Stop {
+                    line: "} // Each count drops to 1",
+                    say: "Each count only drops to 1, so neither is freed: a leak",
+                },
+                Stop {
+                    line: "children: RefCell::new(vec![Rc::clone(&leaf)]),",
+                    say: "A parent owns its children with strong references",
+                },
+                Stop {
+                    line: "*leaf.parent.borrow_mut() = Rc::downgrade(&branch);",
+                    say: "The child points back with a Weak, which owns nothing",
+                },
+                Stop {
+                    line: r#"// println!("{}", leaf.parent.borrow().name);"#,
+                    say: "A Weak has no fields to reach: E0609",
+                },
+                Stop {
+                    line: r#"println!("parent = {:?}", leaf.parent.borrow().upgrade()"#,
+                    say: "upgrade gives an Rc if the parent still exists",
+                },
+                Stop {
+                    line: "} // branch dropped",
+                    say: "branch's strong count was 1, so it's freed here",
+                },
+            ],
+        },
+        Example {
+            name: "any_downcast",
+            title: "Owning values of unknown type with Box<dyn Any>",
+            run: any_downcast,
+            code: ANY_DOWNCAST_CODE,
+            broken_lines: &[BrokenLine {
+                line: "let text: String = *boxed;",
+                error: "E0308",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let values: Vec<Box<dyn Any>>",
+                    say: "Each Box owns a value whose type has been erased",
+                },
+                Stop {
+                    line: "// let text: String = *boxed;",
+                    say: "*boxed is a dyn Any, not a String: E0308",
+                },
+                Stop {
+                    line: "match boxed.downcast::<String>() {",
+                    say: "downcast takes the Box, checking the type at runtime",
+                },
+                Stop {
+                    line: "let text: String = *text;",
+                    say: "On success the String can be moved out of its Box",
+                },
+                Stop {
+                    line: "Err(boxed) => match",
+                    say: "On failure the Box comes back, so nothing is lost",
+                },
+                Stop {
+                    line: "Err(_) => println!",
+                    say: "A box nobody wants is dropped here",
+                },
+            ],
+        },
+        Example {
+            name: "weak_upgrade",
+            title: "Weak::upgrade and observers that go away",
+            run: weak_upgrade,
+            code: WEAK_UPGRADE_CODE,
+            broken_lines: &[BrokenLine {
+                line: r#"println!("{}", watcher.name);"#,
+                error: "E0609",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let logger = Rc::new",
+                    say: "logger is the observer's only strong owner",
+                },
+                Stop {
+                    line: "let watcher: Weak<Observer> = Rc::downgrade(&logger);",
+                    say: "A Weak doesn't own it; it can only ask",
+                },
+                Stop {
+                    line: r#"// println!("{}", watcher.name);"#,
+                    say: "A Weak has no fields to reach: E0609",
+                },
+                Stop {
+                    line: "if let Some(observer) = watcher.upgrade() {",
+                    say: "upgrade gives a strong Rc while the value is alive",
+                },
+                Stop {
+                    line: "drop(logger);",
+                    say: "The last strong owner goes, and so does the observer",
+                },
+                Stop {
+                    line: "None => println!",
+                    say: "Now upgrade returns None",
+                },
+            ],
+        },
+        Example {
+            name: "make_mut",
+            title: "Clone-on-write with Arc::make_mut",
+            run: make_mut,
+            code: MAKE_MUT_CODE,
+            broken_lines: &[BrokenLine {
+                line: "config.push(4);",
+                error: "E0596",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let snapshot = Arc::clone(&config);",
+                    say: "Cloning the Arc shares the Vec; it doesn't copy it",
+                },
+                Stop {
+                    line: "// config.push(4);",
+                    say: "Data behind a shared Arc can't be changed: E0596",
+                },
+                Stop {
+                    line: r#"println!("get_mut while shared"#,
+                    say: "get_mut only works for the sole owner",
+                },
+                Stop {
+                    line: "Arc::make_mut(&mut config).push(4);",
+                    say: "make_mut clones the Vec, since snapshot shares it",
+                },
+                Stop {
+                    line: "Arc::make_mut(&mut config)[0] = 42;",
+                    say: "config now owns its copy, so nothing is cloned",
+                },
+            ],
+        },
+        Example {
+            name: "try_unwrap",
+            title: "Reclaiming unique ownership with Rc::try_unwrap and Arc::into_inner",
+            run: try_unwrap,
+            code: TRY_UNWRAP_CODE,
+            broken_lines: &[BrokenLine {
+                line: "let entries: Vec<String> = *log;",
+                error: "E0507",
+            }],
+            requires: &[ARC_INTO_INNER],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let worker = Rc::clone(&log);",
+                    say: "Two owners of one Vec",
+                },
+                Stop {
+                    line: "// let entries: Vec<String> = *log;",
+                    say: "A shared Vec can't just be moved out: E0507",
+                },
+                Stop {
+                    line: "let log = match Rc::try_unwrap(log) {",
+                    say: "try_unwrap fails while another owner exists",
+                },
+                Stop {
+                    line: "Err(log) => log,",
+                    say: "and gives the Rc back, so nothing is lost",
+                },
+                Stop {
+                    line: "drop(worker);",
+                    say: "Once the other owner is gone",
+                },
+                Stop {
+                    line: "let entries: Vec<String> = Rc::try_unwrap(log).unwrap();",
+                    say: "the last one reclaims the Vec without cloning",
+                },
+                Stop {
+                    line: "thread::spawn(move || Arc::into_inner(results))",
+                    say: "into_inner gives the value to exactly one owner",
+                },
+            ],
+        },
+        Example {
+            name: "shared_strings",
+            title: "Box<str> and Arc<str>: smaller and shared strings",
+            run: shared_strings,
+            code: SHARED_STRINGS_CODE,
+            broken_lines: &[BrokenLine {
+                line: r#"name.push_str("!");"#,
+                error: "E0599",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: r#"println!("String: {} bytes"#,
+                    say: "A String is pointer, length and capacity; Box<str> drops the capacity",
+                },
+                Stop {
+                    line: "let mut name = String::with_capacity(64);",
+                    say: "A String built piece by piece usually has room to spare",
+                },
+                Stop {
+                    line: "let name: Box<str> = name.into_boxed_str();",
+                    say: "into_boxed_str gives the spare room back",
+                },
+                Stop {
+                    line: r#"// name.push_str("!");"#,
+                    say: "A Box<str> has no room to grow into: E0599",
+                },
+                Stop {
+                    line: "let endpoint: Arc<str>",
+                    say: "Arc<str> shares one copy of text that never changes",
+                },
+                Stop {
+                    line: "let handlers",
+                    say: "Cloning it only adds to the count",
+                },
+                Stop {
+                    line: "let mut name = String::from(name);",
+                    say: "Back to a String reuses the same buffer",
+                },
+                Stop {
+                    line: "name.push('!');",
+                    say: "and push grows it again",
+                },
+            ],
+        },
+    ],
+};
+
+fn any_downcast() {
+    let mut n = Narration::new();
+
+    // The Vec owns three boxes, each owning a value of a different type
+    let values: Vec<Box<dyn Any>> =
+        vec![Box::new(String::from("hello")), Box::new(42_i32), Box::new(vec![1.5_f64, 2.5])];
+    n.fact("count", values.len());
+    n.say("Stored {count} values of different types in a Vec<Box<dyn Any>>");
+    println!("  The Vec owns the boxes and each box owns its value, but the type is erased:");
+    println!("  let text: String = *boxed; doesn't compile (error[E0308]: expected String, found dyn Any)");
+
+    // downcast_ref only borrows, so we can peek without giving anything up
+    if let Some(text) = values[0].downcast_ref::<String>() {
+        n.fact("text", text);
+        n.say("downcast_ref::<String>() borrowed the first value: {text}");
+    }
+
+    println!("\n  downcast::<T>() takes the box by value and either gives back Box<T>, or hands");
+    println!("  the original Box<dyn Any> back in the Err so ownership is never lost:");
+    for (i, boxed) in values.into_iter().enumerate() {
+        n.fact("i", i).address("boxed_at", &*boxed as *const dyn Any);
+        match take_ownership_of(boxed) {
+            Recovered::Text(text) => {
+                n.fact("value", text);
+                n.say("value {i} (at {boxed_at}): recovered an owned String: {value}");
+            }
+            Recovered::Number(number) => {
+                n.fact("value", number);
+                n.say("value {i} (at {boxed_at}): not a String, got the box back, then recovered i32 {value}");
+            }
+            Recovered::Unknown(boxed) => {
+                n.address("returned_at", &*boxed as *const dyn Any);
+                n.say("value {i} (at {boxed_at}): neither type matched; we still own the box, now at {returned_at}");
+            } // the box, and the Vec<f64> inside it, is dropped here
+        }
+    }
+    println!("  Every value ended up owned by someone: nothing leaked and nothing was dropped early");
+}
+
+// What we managed to recover from a Box<dyn Any>
+enum Recovered {
+    Text(String),
+    Number(i32),
+    Unknown(Box<dyn Any>),
+}
+
+// This function takes ownership of the box and tries each type in turn.
+// A failed downcast returns the box in Err, so we can try the next type.
+fn take_ownership_of(boxed: Box<dyn Any>) -> Recovered {
+    let boxed = match boxed.downcast::<String>() {
+        Ok(text) => return Recovered::Text(*text), // move the String out of its box
+        Err(boxed) => boxed,
+    };
+    match boxed.downcast::<i32>() {
+        Ok(number) => Recovered::Number(*number),
+        Err(boxed) => Recovered::Unknown(boxed),
+    }
+}
+
+fn weak_upgrade() {
+    let mut n = Narration::new();
+
+    // The subject only keeps Weak handles; whoever created an observer owns it
+    let mut subject = Subject::default();
+    let logger = Rc::new(Observer { name: String::from("logger") });
+    let metrics = Rc::new(Observer { name: String::from("metrics") });
+    subject.watch(&logger);
+    subject.watch(&metrics);
+    n.fact("strong", Rc::strong_count(&logger)).fact("weak", Rc::weak_count(&logger));
+    n.say("logger is watched: strong = {strong}, weak = {weak}");
+    println!("  Rc::downgrade added a weak count but no strong count, so the subject doesn't keep");
+    println!("  logger alive. A Weak can't be used directly, since its value might be gone:");
+    println!("  println!(\"{{}}\", watcher.name); doesn't compile (error[E0609]: no field on Weak)");
+
+    let delivered = subject.notify("started");
+    n.fact("delivered", format!("{:?}", delivered));
+    n.say("notify(\"started\") upgraded every Weak and reached {delivered}");
+
+    // upgrade hands out a temporary strong reference while we use the value
+    let upgraded = subject.watchers[0].upgrade();
+    n.fact("strong", Rc::strong_count(&logger));
+    n.say("While an upgraded Rc is held, logger's strong count is {strong}");
+    drop(upgraded);
+    n.fact("strong", Rc::strong_count(&logger));
+    n.say("After dropping it, the strong count is back to {strong}");
+
+    println!("\n  Now the owner of logger drops it:");
+    let watcher = Rc::downgrade(&logger);
+    drop(logger);
+    n.fact("strong", watcher.strong_count()).fact("weak", watcher.weak_count());
+    n.fact("upgrade", format!("{:?}", watcher.upgrade().map(|observer| observer.name.clone())));
+    n.say("strong = {strong}: the Observer was dropped, and upgrade() = {upgrade}");
+    n.say("weak_count() reports {weak} once no strong owner is left, even though Weaks remain");
+    println!("  The allocation stays until the last Weak goes (so the counts can still be read),");
+    println!("  but the value inside is gone; upgrade returns None instead of a dangling pointer");
+
+    let delivered = subject.notify("stopping");
+    n.fact("delivered", format!("{:?}", delivered)).fact("watchers", subject.watchers.len());
+    n.say("notify(\"stopping\") reached {delivered} and pruned the dead Weak: {watchers} left");
+    n.fact("strong", Rc::strong_count(&metrics)).fact("weak", Rc::weak_count(&metrics));
+    n.say("metrics is unaffected: strong = {strong}, weak = {weak}");
+
+    // Nothing owns anything in a loop, so dropping the owners frees everything
+    let (_, cleanup) = instrument::measure(|| {
+        let mut subject = Subject::default();
+        let observer = Rc::new(Observer { name: String::from("audit") });
+        subject.watch(&observer);
+        subject.notify("started");
+    });
+    n.fact("count", cleanup.count).fact("unfreed", cleanup.unfreed);
+    n.say("A subject and observer built and dropped inside measure: {count} allocations, {unfreed} left");
+    println!("  behind. Had the observer held an Rc back to the subject, neither count could reach 0");
+}
+
+fn make_mut() {
+    let mut n = Narration::new();
+
+    let mut config = Arc::new((0..10_000).collect::<Vec<u32>>());
+    n.fact("len", config.len()).address("data", config.as_ptr());
+    n.say("config owns a Vec of {len} numbers at {data}");
+
+    // Cloning an Arc copies a pointer and bumps a count; the Vec isn't touched
+    let (snapshot, clone_allocs) = instrument::measure(|| Arc::clone(&config));
+    n.fact("strong", Arc::strong_count(&config)).fact("clone_allocs", clone_allocs.count);
+    n.address("snapshot", snapshot.as_ptr());
+    n.say("Arc::clone made snapshot: strong = {strong}, {clone_allocs} allocations, data still at {snapshot}");
+    println!("  Shared data is read-only, so config.push(4); doesn't compile");
+    println!("  (error[E0596]: cannot borrow data in an Arc as mutable)");
+
+    // get_mut only succeeds for the sole owner, so it can never surprise anyone
+    n.fact("get_mut", Arc::get_mut(&mut config).is_some());
+    n.say("Arc::get_mut(&mut config).is_some() = {get_mut}: someone else can still see the Vec");
+
+    println!("\n  Arc::make_mut gives &mut anyway, copying the data first if it is shared:");
+    let (_, copy_allocs) = instrument::measure(|| Arc::make_mut(&mut config).push(10_000));
+    n.fact("copy_allocs", copy_allocs.count).fact("copy_bytes", copy_allocs.bytes);
+    n.fact("strong", Arc::strong_count(&config)).fact("len", config.len()).fact("old_len", snapshot.len());
+    n.address("data", config.as_ptr());
+    n.say("deep copy: {copy_allocs} allocations ({copy_bytes} bytes), config's data moved to {data}");
+    n.say("config now has {len} numbers and strong = {strong}; snapshot still sees {old_len} at {snapshot}");
+    println!("  (a new Arc, a copy of the Vec, and growing that copy for the push)");
+
+    // Now config is the only owner of its copy, so make_mut mutates in place
+    let (_, in_place_allocs) = instrument::measure(|| Arc::make_mut(&mut config)[0] = 42);
+    n.fact("in_place_allocs", in_place_allocs.count).fact("first", config[0]);
+    n.say("make_mut again: {in_place_allocs} allocations, first = {first}, data still at {data}");
+    n.fact("get_mut", Arc::get_mut(&mut config).is_some());
+    n.say("and Arc::get_mut(&mut config).is_some() = {get_mut} now that config is unique");
+
+    println!("\n  Readers share one copy for free; a writer pays for a copy only while the data is");
+    println!("  shared, and only once: after that it owns its own Vec and mutates it in place");
+}
+
+fn try_unwrap() {
+    let mut n = Narration::new();
+
+    // Several parts of a program append to one shared log while they run
+    let log = Rc::new(vec![String::from("started")]);
+    let worker = Rc::clone(&log);
+    n.fact("strong", Rc::strong_count(&log)).address("data", log.as_ptr());
+    n.say("log and worker share one Vec at {data}: strong = {strong}");
+    println!("  To take the Vec out for good, moving out of the Rc doesn't compile:");
+    println!("  let entries: Vec<String> = *log; (error[E0507]: cannot move out of an Rc)");
+
+    // While worker exists, try_unwrap fails and hands the Rc straight back
+    let log = Rc::try_unwrap(log).expect_err("worker still owns the log");
+    n.fact("strong", Rc::strong_count(&log));
+    n.say("Rc::try_unwrap(log) = Err(log): another owner exists, we keep our Rc (strong = {strong})");
+
+    // Teardown: once every other owner is gone, the last one can reclaim the value
+    drop(worker);
+    let entries = Rc::try_unwrap(log).expect("worker was dropped, so log is the only owner");
+    n.fact("entries", format!("{:?}", entries)).address("entries_at", entries.as_ptr());
+    n.say("After drop(worker), try_unwrap returned Ok: an owned Vec {entries}, still at {entries_at}");
+    println!("  No clone happened: the Vec moved out of the Rc allocation, which was then freed");
+
+    println!("\n  With threads, try_unwrap has a race: two threads can both see strong = 2,");
+    println!("  both fail, and the value is dropped with nobody getting it. Arc::into_inner");
+    println!("  returns Some to exactly one caller, the last one to let go:");
+    let results = Arc::new(vec![1, 2, 3]);
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let results = Arc::clone(&results);
+            thread::spawn(move || Arc::into_inner(results))
+        })
+        .collect();
+    let mut winners: Vec<Vec<i32>> = Vec::new();
+    winners.extend(Arc::into_inner(results));
+    for handle in handles {
+        winners.extend(handle.join().expect("thread panicked"));
+    }
+    n.fact("winners", winners.len()).fact("values", format!("{:?}", winners[0]));
+    n.say("5 owners called Arc::into_inner, {winners} got the Vec back: {values}");
+    println!("  Which one wins depends on timing; that exactly one wins does not");
+}
+
+const HANDLERS: usize = 1000;
+
+fn boxed_values() {
+    let mut n = Narration::new();
+
+    // Box::new moves the value to the heap; the Box itself is just a pointer
+    let (boxed, allocs) = instrument::measure(|| Box::new(Reading { celsius: 21.5, sensor: 7 }));
+    n.fact("allocs", allocs.count).fact("bytes", allocs.bytes).address("heap", &*boxed as *const Reading);
+    n.fact("box_size", size_of::<Box<Reading>>()).fact("value_size", size_of::<Reading>());
+    n.say("Box::new(Reading {{ .. }}): {allocs} allocation of {bytes} bytes, the Reading now at {heap}");
+    n.say("The Box on the stack is {box_size} bytes, a pointer to those {value_size} bytes");
+
+    // Moving a Box moves the pointer, not the value it points to
+    let moved = boxed;
+    n.address("moved_heap", &*moved as *const Reading);
+    n.say("let moved = boxed; moved points at {moved_heap}: {box_size} bytes copied, the Reading stayed");
+    println!("  boxed has given up ownership, so using it is error[E0382]: borrow of moved value");
+
+    // Deref: *moved is the Reading, and field access and methods go through the Box
+    n.fact("celsius", moved.celsius).fact("fahrenheit", moved.fahrenheit());
+    n.say("moved.celsius = {celsius} and moved.fahrenheit() = {fahrenheit} reach through the Box");
+    let reading: &Reading = &moved;
+    n.address("deref_at", reading as *const Reading);
+    n.say("&moved coerces to &Reading pointing at {deref_at}: Box<T> implements Deref<Target = T>");
+
+    // *box moves the value back out, which only Box allows
+    let (unboxed, unbox_allocs) = instrument::measure(|| *moved);
+    n.fact("sensor", unboxed.sensor).fact("unbox_allocs", unbox_allocs.count);
+    n.say("let unboxed = *moved; moved the Reading (sensor {sensor}) back onto the stack with");
+    n.say("{unbox_allocs} allocations, and freed the heap block. Rc and Arc can't: they may have other owners");
+
+    // Dropping the Box frees the heap block; nothing is left behind
+    let ((big, big_moved), dropped) = instrument::measure(|| {
+        let temporary = Box::new([0u8; 4096]);
+        let big = temporary.as_ptr();
+        let moved_again = temporary;
+        (big, moved_again.as_ptr())
+    });
+    n.address("big", big).address("big_moved", big_moved);
+    n.fact("big_allocs", dropped.count).fact("unfreed", dropped.unfreed);
+    n.say("A Box<[u8; 4096]> moved from one variable to another stayed at {big} = {big_moved}, and");
+    n.say("going out of scope freed it: {big_allocs} allocation, {unfreed} left behind");
+}
+
+// A value worth putting behind a pointer
+struct Reading {
+    celsius: f64,
+    sensor: u32,
+}
+
+impl Reading {
+    fn fahrenheit(&self) -> f64 {
+        self.celsius * 9.0 / 5.0 + 32.0
+    }
+}
+
+fn recursive_types() {
+    let mut n = Narration::new();
+
+    println!("  enum List {{ Cons(i32, List), Nil }} would contain itself: its size would be");
+    println!("  i32 + List = i32 + i32 + List = ..., and rustc rejects it (error[E0072]: recursive");
+    println!("  type has infinite size). A Box has a fixed size, whatever it points to:\n");
+    n.fact("list", size_of::<List>()).fact("boxed", size_of::<Box<List>>());
+    n.say("enum List {{ Cons(i32, Box<List>), Nil }} is {list} bytes however long the list is: an i32");
+    n.say("and a {boxed}-byte Box (Nil is told apart by the Box's pointer, which is never null)");
+
+    let (list, allocs) = instrument::measure(|| {
+        List::Cons(1, Box::new(List::Cons(2, Box::new(List::Cons(3, Box::new(List::Nil))))))
+    });
+    n.fact("allocs", allocs.count).fact("bytes", allocs.bytes);
+    n.say("Cons(1, Cons(2, Cons(3, Nil))): {allocs} allocations, {bytes} bytes: one per Box");
+
+    let mut node = &list;
+    let mut position = 0;
+    while let List::Cons(value, next) = node {
+        n.fact("value", value).address("next", &**next as *const List);
+        n.say("  Cons({value}, ..) owns the next node, at {next}");
+        node = next;
+        position += 1;
+    }
+    n.fact("length", position);
+    n.say("{length} values; the first node lives on the stack, every later one on the heap");
+
+    let ((), dropped) = instrument::measure(|| drop(list));
+    n.fact("unfreed", dropped.unfreed);
+    println!("  Each Cons owns its Box, so dropping the head drops the whole chain in turn:");
+    n.say("drop(list) left {unfreed} allocations behind");
+}
+
+enum List {
+    Cons(i32, Box<List>),
+    Nil,
+}
+
+fn shared_list() {
+    use SharedList::{Cons, Nil};
+    let mut n = Narration::new();
+
+    println!("  In ownership_transfer, let s2 = s1 moved the String: one value, one owner at a time.");
+    println!("  With a Box, a list could be the tail of b or of c, but not both. Rc<T> counts its");
+    println!("  owners instead, and frees the value when the last one goes:\n");
+    let (a, allocs) = instrument::measure(|| Rc::new(Cons(5, Rc::new(Cons(10, Rc::new(Nil))))));
+    n.fact("allocs", allocs.count).fact("count", Rc::strong_count(&a)).address("list", Rc::as_ptr(&a));
+    n.say("a = Rc::new(Cons(5, Cons(10, Nil))): {allocs} allocations, the list at {list}");
+    n.say("Rc::strong_count(&a) = {count}");
+
+    let (b, clone_allocs) = instrument::measure(|| Cons(3, Rc::clone(&a)));
+    n.fact("clone_allocs", clone_allocs.count).fact("count", Rc::strong_count(&a));
+    if let Cons(_, tail) = &b {
+        n.address("tail", Rc::as_ptr(tail));
+    }
+    n.say("b = Cons(3, Rc::clone(&a)): {clone_allocs} allocations, strong_count = {count}");
+    n.say("b's tail is at {tail}: the same list, not a copy of it");
+
+    {
+        let _c = Cons(4, Rc::clone(&a));
+        n.fact("count", Rc::strong_count(&a));
+        n.say("c = Cons(4, Rc::clone(&a)) in an inner scope: strong_count = {count}");
+        println!("  The inner scope ends, and c is dropped:");
+    }
+    n.fact("count", Rc::strong_count(&a));
+    n.say("strong_count = {count}: only c's own node went, the shared list is still there");
+
+    println!("  drop(b):");
+    drop(b);
+    n.fact("count", Rc::strong_count(&a));
+    n.say("strong_count = {count}: a is the last owner");
+
+    println!("  An Rc is still a value with one owner, so let d = Cons(2, a) moves a, and using a");
+    println!("  afterwards is error[E0382]. When d goes, the count reaches 0 and the list goes with it:");
+    let d = Cons(2, a);
+    drop(d);
+}
+
+// A list whose tail can be shared, announcing each node as it's freed
+enum SharedList {
+    Cons(i32, Rc<SharedList>),
+    Nil,
+}
+
+impl Drop for SharedList {
+    fn drop(&mut self) {
+        if let SharedList::Cons(value, _) = self {
+            println!("    Cons({}, ..) dropped", value);
+        }
+    }
+}
+
+fn reference_cycle() {
+    let mut n = Narration::new();
+
+    println!("  Two nodes that each hold an Rc to the other, through a RefCell so the link can be");
+    println!("  set after both exist:");
+    let (counts, leaked) = instrument::measure(|| {
+        let a = Rc::new(Peer { name: "a", other: RefCell::new(None) });
+        let b = Rc::new(Peer { name: "b", other: RefCell::new(None) });
+        *a.other.borrow_mut() = Some(Rc::clone(&b));
+        *b.other.borrow_mut() = Some(Rc::clone(&a));
+        (Rc::strong_count(&a), Rc::strong_count(&b))
+    });
+    n.fact("a", counts.0).fact("b", counts.1).fact("count", leaked.count).fact("unfreed", leaked.unfreed);
+    n.say("a.other = b and b.other = a: strong counts a = {a}, b = {b}");
+    n.say("a and b went out of scope, and no \"dropped\" line appeared: {unfreed} of {count} allocations leaked");
+    println!("  Each local's drop only took its count from 2 to 1. The other node still owns it,");
+    println!("  and neither can go first, so both stay on the heap until the process exits.");
+    println!("  This isn't unsafe (nothing dangles), so the compiler allows it\n");
+
+    println!("  The fix is to decide which direction owns. A parent owns its children with Rc;");
+    println!("  a child only refers back to its parent with a Weak, which doesn't count:");
+    let leaf = Rc::new(TreeNode::new("leaf"));
+    n.fact("strong", Rc::strong_count(&leaf)).fact("weak", Rc::weak_count(&leaf));
+    n.say("leaf: strong = {strong}, weak = {weak}");
+    {
+        let branch = Rc::new(TreeNode::new("branch"));
+        branch.children.borrow_mut().push(Rc::clone(&leaf));
+        *leaf.parent.borrow_mut() = Rc::downgrade(&branch);
+        n.fact("strong", Rc::strong_count(&branch)).fact("weak", Rc::weak_count(&branch));
+        n.fact("leaf_strong", Rc::strong_count(&leaf)).fact("leaf_weak", Rc::weak_count(&leaf));
+        n.say("branch owns leaf, leaf.parent = Rc::downgrade(&branch):");
+        n.say("  branch: strong = {strong}, weak = {weak}; leaf: strong = {leaf_strong}, weak = {leaf_weak}");
+        let parent = leaf.parent.borrow().upgrade().map(|parent| parent.name);
+        n.fact("parent", format!("{:?}", parent));
+        n.say("leaf.parent.borrow().upgrade() = {parent}");
+        println!("  branch goes out of scope:");
+    }
+    let parent = leaf.parent.borrow().upgrade().map(|parent| parent.name);
+    n.fact("parent", format!("{:?}", parent)).fact("strong", Rc::strong_count(&leaf));
+    n.say("leaf.parent.borrow().upgrade() = {parent}, and leaf: strong = {strong}");
+    println!("  drop(leaf):");
+    drop(leaf);
+    println!("  Both nodes were freed: the Weak didn't hold branch's count up, so it could reach 0");
+    println!("  first, and dropping branch released its Rc to leaf");
+}
+
+// Two of these pointing at each other keep each other alive
+struct Peer {
+    name: &'static str,
+    other: RefCell<Option<Rc<Peer>>>,
+}
+
+impl Drop for Peer {
+    fn drop(&mut self) {
+        println!("    {} dropped", self.name);
+    }
+}
+
+// Children are owned; the parent is only referred to
+struct TreeNode {
+    name: &'static str,
+    parent: RefCell<Weak<TreeNode>>,
+    children: RefCell<Vec<Rc<TreeNode>>>,
+}
+
+impl TreeNode {
+    fn new(name: &'static str) -> TreeNode {
+        TreeNode { name, parent: RefCell::new(Weak::new()), children: RefCell::new(Vec::new()) }
+    }
+}
+
+impl Drop for TreeNode {
+    fn drop(&mut self) {
+        println!("    {} dropped", self.name);
+    }
+}
+
+fn shared_strings() {
+    let mut n = Narration::new();
+
+    n.fact("string", size_of::<String>()).fact("boxed", size_of::<Box<str>>());
+    n.fact("arc", size_of::<Arc<str>>()).fact("slice", size_of::<&str>());
+    n.say("On the stack: String is {string} bytes (pointer, length, capacity), Box<str> is {boxed},");
+    n.say("Arc<str> is {arc} (pointer and length, like a &str at {slice}): neither has a capacity");
+
+    // A String built up piece by piece usually ends up with spare capacity
+    let mut name = String::with_capacity(64);
+    name.push_str("ferris");
+    n.fact("len", name.len()).fact("capacity", name.capacity()).address("before", name.as_ptr());
+    println!();
+    n.say("name: String has len {len} but capacity {capacity} on the heap at {before}");
+    let (name, shrink_allocs) = instrument::measure(|| name.into_boxed_str());
+    n.fact("len", name.len()).fact("shrink_allocs", shrink_allocs.count).address("after", name.as_ptr());
+    n.say("into_boxed_str(): exactly {len} bytes at {after}, {shrink_allocs} reallocation to shrink it");
+    println!("  A Box<str> can't grow, so it has no methods that would need to:");
+    println!("    name.push_str(\"!\"); // error[E0599]: no method named `push_str` found for Box<str>");
+    println!("  That's the point: for text that is built once and then only read, it is a String");
+    println!("  without the unused capacity and without the capacity field");
+
+    println!("\n  Config-style data is read by many owners. Giving each of {} handlers the endpoint:", HANDLERS);
+    let endpoint = String::from("https://example.com/api/v1/orders");
+    let (copies, string_allocs) = instrument::measure(|| vec![endpoint.clone(); HANDLERS]);
+    n.fact("string_allocs", string_allocs.count).fact("string_bytes", string_allocs.bytes);
+    n.say("Vec<String>:   {string_allocs} allocations, {string_bytes} heap bytes, one copy per handler");
+
+    let shared: Arc<str> = Arc::from(endpoint.as_str());
+    let (handles, arc_allocs) = instrument::measure(|| vec![Arc::clone(&shared); HANDLERS]);
+    n.fact("arc_allocs", arc_allocs.count).fact("arc_bytes", arc_allocs.bytes);
+    n.fact("strong", Arc::strong_count(&shared));
+    n.say("Vec<Arc<str>>: {arc_allocs} allocation, {arc_bytes} heap bytes (the Vec), strong = {strong}");
+    n.address("first", handles[0].as_ptr()).address("last", handles[HANDLERS - 1].as_ptr());
+    n.address("copy_first", copies[0].as_ptr()).address("copy_last", copies[HANDLERS - 1].as_ptr());
+    n.say("Every Arc points at the same text ({first} and {last}), the Strings don't ({copy_first}, {copy_last})");
+
+    println!("\n  Use String while text is being built or edited, Box<str> for many small strings");
+    println!("  that never change, and Arc<str> (or Rc<str> on one thread) when the same immutable");
+    println!("  text is handed to many owners. Arc<str> is one allocation: counts and text together,");
+    println!("  unlike Arc<String>, which points to a String that points to the text");
+}
+
+// Something that wants to hear about events, owned by whoever created it
+struct Observer {
+    name: String,
+}
+
+// Holds observers without owning them, so they can go away at any time
+#[derive(Default)]
+struct Subject {
+    watchers: Vec<Weak<Observer>>,
+}
+
+impl Subject {
+    // This function borrows the Rc just long enough to make a Weak from it
+    fn watch(&mut self, observer: &Rc<Observer>) {
+        self.watchers.push(Rc::downgrade(observer));
+    }
+
+    // This function delivers an event to every observer that is still alive,
+    // forgets the ones whose owner dropped them, and reports who got the event
+    fn notify(&mut self, event: &str) -> Vec<String> {
+        let mut delivered = Vec::new();
+        self.watchers.retain(|watcher| match watcher.upgrade() {
+            Some(observer) => {
+                delivered.push(format!("{} got {}", observer.name, event));
+                true
+            } // the upgraded Rc is dropped here, giving back the strong count
+            None => false,
+        });
+        delivered
+    }
+}
+
+const ANY_DOWNCAST_CODE: &str = r#"use std::any::Any;
+
+fn main() {
+    let values: Vec<Box<dyn Any>> = vec![Box::new(String::from("hello")), Box::new(42_i32)];
+
+    for boxed in values {
+        // The type is erased, so we can't just move the value out
+        // let text: String = *boxed;
+
+        // downcast takes ownership of the box; on failure it gives the box back
+        match boxed.downcast::<String>() {
+            Ok(text) => {
+                let text: String = *text; // move the String out of its Box
+                println!("recovered a String: {}", text);
+            }
+            Err(boxed) => match boxed.downcast::<i32>() {
+                Ok(number) => println!("recovered an i32: {}", number),
+                Err(_) => println!("unknown type, the box is dropped here"),
+            },
+        }
+    }
+}
+"#;
+
+const WEAK_UPGRADE_CODE: &str = r#"use std::rc::{Rc, Weak};
+
+struct Observer {
+    name: String,
+}
+
+fn main() {
+    let logger = Rc::new(Observer { name: String::from("logger") });
+
+    // The watcher doesn't own the observer, it can only ask whether it's still there
+    let watcher: Weak<Observer> = Rc::downgrade(&logger);
+    println!("strong = {}, weak = {}", Rc::strong_count(&logger), Rc::weak_count(&logger));
+
+    // A Weak has to be upgraded before the value can be used
+    // println!("{}", watcher.name);
+    if let Some(observer) = watcher.upgrade() {
+        println!("{} is alive, strong = {}", observer.name, Rc::strong_count(&observer));
+    }
+
+    // Once the last strong owner is gone, upgrade returns None
+    drop(logger);
+    println!("strong = {}, weak = {}", watcher.strong_count(), watcher.weak_count());
+    match watcher.upgrade() {
+        Some(observer) => println!("still alive: {}", observer.name),
+        None => println!("the observer was dropped"),
+    }
+}
+"#;
+
+const MAKE_MUT_CODE: &str = r#"use std::sync::Arc;
+
+fn main() {
+    let mut config = Arc::new(vec![1, 2, 3]);
+
+    // Cloning the Arc shares the Vec, it doesn't copy it
+    let snapshot = Arc::clone(&config);
+    println!("strong count: {}", Arc::strong_count(&config));
+
+    // Data behind a shared Arc can't be mutated directly
+    // config.push(4);
+
+    // get_mut only works for the sole owner
+    println!("get_mut while shared: {:?}", Arc::get_mut(&mut config).is_some());
+
+    // make_mut clones the Vec because snapshot still shares it...
+    Arc::make_mut(&mut config).push(4);
+    println!("config = {:?}, snapshot = {:?}", config, snapshot);
+
+    // ...and after that config owns its copy, so no further clone happens
+    Arc::make_mut(&mut config)[0] = 42;
+    println!("same data: {}", Arc::ptr_eq(&config, &snapshot));
+    println!("config = {:?}, strong count: {}", config, Arc::strong_count(&config));
+}
+"#;
+
+const TRY_UNWRAP_CODE: &str = r#"use std::rc::Rc;
+use std::sync::Arc;
+use std::thread;
+
+fn main() {
+    let log = Rc::new(vec![String::from("started")]);
+    let worker = Rc::clone(&log);
+
+    // The Vec is shared, so we can't just move it out
+    // let entries: Vec<String> = *log;
+
+    // try_unwrap fails while another owner exists, and gives the Rc back
+    let log = match Rc::try_unwrap(log) {
+        Ok(entries) => panic!("worker still owns the log, got {:?}", entries),
+        Err(log) => log,
+    };
+    println!("still shared, strong count: {}", Rc::strong_count(&log));
+
+    // Once the other owner is gone, the last one reclaims the Vec without cloning
+    drop(worker);
+    let entries: Vec<String> = Rc::try_unwrap(log).unwrap();
+    println!("reclaimed: {:?}", entries);
+
+    // Across threads, Arc::into_inner gives the value to exactly one owner
+    let results = Arc::new(vec![1, 2, 3]);
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let results = Arc::clone(&results);
+            thread::spawn(move || Arc::into_inner(results))
+        })
+        .collect();
+    let mut winners = vec![Arc::into_inner(results)];
+    for handle in handles {
+        winners.push(handle.join().unwrap());
+    }
+    println!("{:?}", winners.into_iter().flatten().collect::<Vec<_>>());
+}
+"#;
+
+const SHARED_STRINGS_CODE: &str = r#"use std::mem::size_of;
+use std::sync::Arc;
+
+fn main() {
+    println!("String: {} bytes, Box<str>: {} bytes, Arc<str>: {} bytes",
+        size_of::<String>(), size_of::<Box<str>>(), size_of::<Arc<str>>());
+
+    // A String built up piece by piece usually has spare capacity
+    let mut name = String::with_capacity(64);
+    name.push_str("ferris");
+    println!("len {}, capacity {}", name.len(), name.capacity());
+
+    // into_boxed_str drops the spare capacity and the capacity field with it
+    let name: Box<str> = name.into_boxed_str();
+    println!("boxed: {} ({} bytes)", name, name.len());
+
+    // Box<str> is immutable in size: there is no capacity to grow into
+    // name.push_str("!");
+
+    // Arc<str> shares one immutable copy; cloning only bumps a count
+    let endpoint: Arc<str> = Arc::from("https://example.com/api");
+    let handlers: Vec<Arc<str>> = (0..3).map(|_| Arc::clone(&endpoint)).collect();
+    println!("{} handlers share {}, strong count {}", handlers.len(), endpoint, Arc::strong_count(&endpoint));
+
+    // Going back to String reuses the same buffer; push then grows it
+    let mut name = String::from(name);
+    name.push('!');
+    println!("{}", name);
+}
+"#;
+
+const BOXED_VALUES_CODE: &str = r#"struct Reading {
+    celsius: f64,
+    sensor: u32,
+}
+
+impl Reading {
+    fn fahrenheit(&self) -> f64 {
+        self.celsius * 9.0 / 5.0 + 32.0
+    }
+}
+
+fn main() {
+    // The Reading moves to the heap; boxed is a pointer to it
+    let boxed = Box::new(Reading { celsius: 21.5, sensor: 7 });
+
+    // Moving the Box copies the pointer; the Reading stays where it is
+    let moved = boxed;
+    // println!("{}", boxed.sensor);
+
+    // Deref lets fields and methods reach through the Box
+    println!("{} {}", moved.celsius, moved.fahrenheit());
+    let reading: &Reading = &moved;
+    println!("{}", reading.sensor);
+
+    // *moved moves the Reading back out and frees the heap block
+    let unboxed = *moved;
+    println!("{}", unboxed.sensor);
+
+    {
+        let temporary = Box::new([0u8; 4096]);
+        println!("{}", temporary.len());
+    } // temporary goes out of scope, and its 4096 bytes are freed
+}
+"#;
+
+const SHARED_LIST_CODE: &str = r#"use std::rc::Rc;
+
+// Each list shares its tail through an Rc instead of owning it outright
+enum List {
+    Cons(i32, Rc<List>),
+    Nil,
+}
+
+use List::{Cons, Nil};
+
+impl Drop for List {
+    fn drop(&mut self) {
+        if let Cons(value, _) = self {
+            println!("dropping Cons({}, ..)", value);
+        }
+    }
+}
+
+fn main() {
+    let a = Rc::new(Cons(5, Rc::new(Cons(10, Rc::new(Nil)))));
+    println!("after creating a: {}", Rc::strong_count(&a));
+
+    // Rc::clone copies the pointer and adds one to the count; the list isn't copied
+    let b = Cons(3, Rc::clone(&a));
+    println!("after creating b: {}", Rc::strong_count(&a));
+    {
+        let _c = Cons(4, Rc::clone(&a));
+        println!("after creating c: {}", Rc::strong_count(&a));
+    } // c is dropped, and the count goes down by one
+    println!("after c goes out of scope: {}", Rc::strong_count(&a));
+
+    drop(b);
+    println!("after dropping b: {}", Rc::strong_count(&a));
+
+    // Each Rc is still a value with one owner: this moves a
+    let _d = Cons(2, a);
+    // println!("{}", Rc::strong_count(&a));
+} // d is dropped, the count reaches 0, and the list is freed
+"#;
+
+const REFERENCE_CYCLE_CODE: &str = r#"use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+struct Peer {
+    name: &'static str,
+    other: RefCell<Option<Rc<Peer>>>,
+}
+
+struct TreeNode {
+    name: &'static str,
+    parent: RefCell<Weak<TreeNode>>,
+    children: RefCell<Vec<Rc<TreeNode>>>,
+}
+
+// Each node says when it's freed, so a missing line means a leak
+impl Drop for Peer {
+    fn drop(&mut self) {
+        println!("{} dropped", self.name);
+    }
+}
+
+impl Drop for TreeNode {
+    fn drop(&mut self) {
+        println!("{} dropped", self.name);
+    }
+}
+
+fn main() {
+    {
+        let a = Rc::new(Peer { name: "a", other: RefCell::new(None) });
+        let b = Rc::new(Peer { name: "b", other: RefCell::new(None) });
+        *a.other.borrow_mut() = Some(Rc::clone(&b));
+        *b.other.borrow_mut() = Some(Rc::clone(&a));
+        println!("a strong = {}, b strong = {}", Rc::strong_count(&a), Rc::strong_count(&b));
+    } // Each count drops to 1, never 0: neither "dropped" line is printed
+
+    let leaf = Rc::new(TreeNode { name: "leaf", parent: RefCell::new(Weak::new()), children: RefCell::new(vec![]) });
+    {
+        let branch = Rc::new(TreeNode {
+            name: "branch",
+            parent: RefCell::new(Weak::new()),
+            children: RefCell::new(vec![Rc::clone(&leaf)]),
+        });
+        // The child refers back with a Weak, which doesn't keep branch alive
+        *leaf.parent.borrow_mut() = Rc::downgrade(&branch);
+        println!("branch strong = {}, weak = {}", Rc::strong_count(&branch), Rc::weak_count(&branch));
+        println!("leaf strong = {}, weak = {}", Rc::strong_count(&leaf), Rc::weak_count(&leaf));
+
+        // A Weak has to be upgraded to reach the value, since it may be gone
+        // println!("{}", leaf.parent.borrow().name);
+        println!("parent = {:?}", leaf.parent.borrow().upgrade().map(|parent| parent.name));
+        println!("children = {}", branch.children.borrow().len());
+    } // branch dropped: its strong count was 1
+    println!("parent = {:?}", leaf.parent.borrow().upgrade().map(|parent| parent.name));
+} // leaf dropped
+"#;
+
+const RECURSIVE_TYPES_CODE: &str = r#"// Without indirection the type would contain itself, and have no size
+// enum Unboxed { Cons(i32, Unboxed), Nil }
+
+// A Box is a pointer, so List has a fixed size however long the list is
+enum List {
+    Cons(i32, Box<List>),
+    Nil,
+}
+
+use List::{Cons, Nil};
+
+fn main() {
+    let list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
+
+    let mut node = &list;
+    while let Cons(value, next) = node {
+        println!("{}", value);
+        node = next;
+    }
+} // list is dropped, and each Cons drops the Box it owns, down the chain
+"#;
@@ -0,0 +1,1112 @@
+use std::cell::{Cell, RefCell};
+use std::marker::PhantomData;
+use std::panic;
+use std::rc::Rc;
+use std::sync::{Arc, Barrier, Mutex, MutexGuard, RwLock};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, TrySendError};
+use std::thread;
+use std::time::Duration;
+
+use super::{BrokenLine, Example, Section, Stop};
+use crate::instrument;
+use crate::narration::Narration;
+
+pub const SECTION: Section = Section {
+    name: "threads",
+    title: "OWNERSHIP ACROSS THREADS",
+    examples: &[
+        Example {
+            name: "join_handle",
+            title: "Moving data into a thread and getting it back from join",
+            run: join_handle,
+            code: JOIN_HANDLE_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: "let borrowed = thread::spawn(|| numbers.iter().sum::<i32>());",
+                    error: "E0373",
+                },
+                BrokenLine {
+                    line: r#"println!("{:?}", numbers);"#,
+                    error: "E0382",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let numbers = vec!",
+                    say: "main owns the Vec",
+                },
+                Stop {
+                    line: "// let borrowed = thread::spawn",
+                    say: "The thread may outlive main's frame, so borrowing is E0373",
+                },
+                Stop {
+                    line: "let handle = thread::spawn(move || {",
+                    say: "move gives the Vec to the thread",
+                },
+                Stop {
+                    line: "(sum, numbers)",
+                    say: "and the thread hands it back with the result",
+                },
+                Stop {
+                    line: r#"// println!("{:?}", numbers);"#,
+                    say: "Until then it belongs to the thread: E0382",
+                },
+                Stop {
+                    line: "let (sum, numbers) = handle.join().unwrap();",
+                    say: "join returns what the thread returned, ownership and all",
+                },
+                Stop {
+                    line: "Err(payload) => match payload.downcast::<&str>() {",
+                    say: "A panic's payload is an owned Box handed to whoever joins",
+                },
+            ],
+        },
+        Example {
+            name: "move_closures",
+            title: "move closures: what a spawned thread takes, and what main has left",
+            run: move_closures,
+            code: MOVE_CLOSURES_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: r#"let handle = thread::spawn(|| println!("{}", greeting));"#,
+                    error: "E0373",
+                },
+                BrokenLine {
+                    line: r#"println!("{}", greeting);"#,
+                    error: "E0382",
+                },
+                BrokenLine {
+                    line: r#"thread::spawn(move || println!("{}", name));"#,
+                    error: "E0597",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "// let handle = thread::spawn(|| println!",
+                    say: "Borrowing greeting from a thread is E0373",
+                },
+                Stop {
+                    line: "let kept = greeting.clone();",
+                    say: "A clone made beforehand stays with main",
+                },
+                Stop {
+                    line: "let handle = thread::spawn(move || {",
+                    say: "move makes the closure, and so the thread, own greeting",
+                },
+                Stop {
+                    line: r#"// println!("{}", greeting);"#,
+                    say: "greeting belongs to the thread now: E0382",
+                },
+                Stop {
+                    line: r#"println!("main: {} (retries: {})""#,
+                    say: "retries is Copy: the thread got a copy, main keeps its own",
+                },
+                Stop {
+                    line: "let name = &label;",
+                    say: "name is a reference to main's local",
+                },
+                Stop {
+                    line: r#"// thread::spawn(move || println!("{}", name));"#,
+                    say: "move copies the reference, which still borrows label: E0597",
+                },
+                Stop {
+                    line: "let owned = name.clone();",
+                    say: "Cloning gives the thread text it owns",
+                },
+            ],
+        },
+        Example {
+            name: "shared_arc",
+            title: "Arc<T>: several threads owning the same Vec",
+            run: shared_arc,
+            code: SHARED_ARC_CODE,
+            broken_lines: &[BrokenLine {
+                line: r#"thread::spawn(move || println!("{}", local.len()));"#,
+                error: "E0277",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let local = Rc::new",
+                    say: "Rc's count isn't updated atomically",
+                },
+                Stop {
+                    line: r#"// thread::spawn(move || println!("{}", local.len()));"#,
+                    say: "so an Rc can't be sent to another thread: E0277",
+                },
+                Stop {
+                    line: "let numbers: Arc<Vec<i32>>",
+                    say: "Arc's count is atomic, so it can",
+                },
+                Stop {
+                    line: "let numbers = Arc::clone(&numbers);",
+                    say: "Each thread gets a clone: a new owner of the same Vec",
+                },
+                Stop {
+                    line: "workers.push(thread::spawn(move ||",
+                    say: "The clone moves into the thread",
+                },
+                Stop {
+                    line: "let total: i32 = workers.into_iter()",
+                    say: "As each thread ends its clone is dropped, and the count falls",
+                },
+            ],
+        },
+        Example {
+            name: "shared_mutex",
+            title: "Arc<Mutex<T>>: threads taking turns to change one counter",
+            run: shared_mutex,
+            code: SHARED_MUTEX_CODE,
+            broken_lines: &[BrokenLine {
+                line: "drop(guard);",
+                error: "E0505",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let counter = Arc::new(Mutex::new(0));",
+                    say: "Arc lets every thread own the Mutex; the Mutex guards the i32",
+                },
+                Stop {
+                    line: "let mut guard = counter.lock().unwrap();",
+                    say: "lock returns a guard, a borrow that derefs to the i32",
+                },
+                Stop {
+                    line: "} // the guard drops",
+                    say: "Dropping the guard unlocks the Mutex",
+                },
+                Stop {
+                    line: "let guard = counter.lock().unwrap();",
+                    say: "While a guard is alive nobody else gets the lock",
+                },
+                Stop {
+                    line: "drop(guard);",
+                    say: "After dropping it, the lock is free again",
+                },
+                Stop {
+                    line: "let inside: &mut i32 = &mut guard;",
+                    say: "A reference into the Mutex borrows the guard it came through",
+                },
+                Stop {
+                    line: "// drop(guard);",
+                    say: "so unlocking while it's in use is E0505",
+                },
+            ],
+        },
+        Example {
+            name: "rwlock_readers",
+            title: "RwLock<T>: many readers or one writer, like &T and &mut T",
+            run: rwlock_readers,
+            code: RWLOCK_READERS_CODE,
+            broken_lines: &[BrokenLine {
+                line: r#"settings.push(String::from("dark mode"));"#,
+                error: "E0596",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let settings = Arc::new(RwLock::new",
+                    say: "An RwLock works like many &T or one &mut T",
+                },
+                Stop {
+                    line: "let settings = settings.read().unwrap();",
+                    say: "Every reader can hold a read guard at the same time",
+                },
+                Stop {
+                    line: r#"// settings.push(String::from("dark mode"));"#,
+                    say: "A read guard derefs to &Vec, so it can't change it: E0596",
+                },
+                Stop {
+                    line: "let mut writer = settings.write().unwrap();",
+                    say: "The writer waits for the readers, then excludes them all",
+                },
+                Stop {
+                    line: "} // the write guard drops here",
+                    say: "When the write guard drops, readers can get in again",
+                },
+            ],
+        },
+        Example {
+            name: "channel_moves",
+            title: "mpsc::channel: send moves a String to another thread",
+            run: channel_moves,
+            code: CHANNEL_MOVES_CODE,
+            broken_lines: &[BrokenLine {
+                line: r#"println!("worker still has {}", greeting);"#,
+                error: "E0382",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "sender.send(greeting).unwrap();",
+                    say: "send takes the String by value, like takes_ownership(s)",
+                },
+                Stop {
+                    line: r#"// println!("worker still has {}", greeting);"#,
+                    say: "greeting now belongs to the receiver: E0382",
+                },
+                Stop {
+                    line: "let greeting: String = receiver.recv().unwrap();",
+                    say: "recv hands ownership to this thread",
+                },
+                Stop {
+                    line: "let sender = sender.clone();",
+                    say: "Every clone of the Sender feeds the same channel",
+                },
+                Stop {
+                    line: "drop(sender);",
+                    say: "The loop ends when the last Sender is dropped, so drop ours too",
+                },
+                Stop {
+                    line: "for message in receiver {",
+                    say: "Each message arrives owned by main",
+                },
+            ],
+        },
+        Example {
+            name: "send_sync",
+            title: "Send and Sync: which types may move to, or be shared with, another thread",
+            run: send_sync,
+            code: SEND_SYNC_CODE,
+            broken_lines: &[
+                BrokenLine {
+                    line: "assert_sync::<Cell<i32>>();",
+                    error: "E0277",
+                },
+                BrokenLine {
+                    line: "assert_send::<Rc<String>>();",
+                    error: "E0277",
+                },
+                BrokenLine {
+                    line: "thread::spawn(move || shared.set(1));",
+                    error: "E0277",
+                },
+            ],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "fn assert_send<T: Send>() {}",
+                    say: "These only compile for types that are Send, or Sync",
+                },
+                Stop {
+                    line: "assert_send::<Cell<i32>>();",
+                    say: "A Cell can move to another thread",
+                },
+                Stop {
+                    line: "// assert_sync::<Cell<i32>>();",
+                    say: "but not be shared, since two threads could set it: E0277",
+                },
+                Stop {
+                    line: "// assert_send::<Rc<String>>();",
+                    say: "Rc's count isn't atomic, so it can't even move: E0277",
+                },
+                Stop {
+                    line: "let shared = Arc::new(Cell::new(0));",
+                    say: "Arc<T> is Send only if T is Sync",
+                },
+                Stop {
+                    line: "// thread::spawn(move || shared.set(1));",
+                    say: "so this Arc can't go to another thread: E0277",
+                },
+                Stop {
+                    line: "let counter = Arc::new(Mutex::new(0));",
+                    say: "With a Mutex inside, the Arc can go anywhere",
+                },
+            ],
+        },
+        Example {
+            name: "bounded_channel",
+            title: "sync_channel: handing over ownership through a bounded buffer",
+            run: bounded_channel,
+            code: BOUNDED_CHANNEL_CODE,
+            broken_lines: &[BrokenLine {
+                line: r#"println!("{}", first);"#,
+                error: "E0382",
+            }],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {
+                    line: "let (sender, receiver) = mpsc::sync_channel::<String>(2);",
+                    say: "A channel with room for two messages",
+                },
+                Stop {
+                    line: "sender.send(first).unwrap();",
+                    say: "Sending moves the String into the channel's buffer",
+                },
+                Stop {
+                    line: r#"// println!("{}", first);"#,
+                    say: "It's the receiver's to take now: E0382",
+                },
+                Stop {
+                    line: "match sender.try_send(third) {",
+                    say: "The buffer is full, so try_send fails",
+                },
+                Stop {
+                    line: "Err(TrySendError::Full(third))",
+                    say: "and gives the String back instead of blocking",
+                },
+                Stop {
+                    line: "let producer = thread::spawn(move || {",
+                    say: "A producer blocks in send until the consumer makes room",
+                },
+                Stop {
+                    line: r#"let error = sender.send(String::from("late")).unwrap_err();"#,
+                    say: "With no receiver, send returns the value inside the error",
+                },
+            ],
+        },
+    ],
+};
+
+fn join_handle() {
+    let mut n = Narration::new();
+
+    let numbers = vec![1, 2, 3, 4];
+    n.fact("numbers", format!("{:?}", numbers)).address("buffer", numbers.as_ptr());
+    n.say("numbers = {numbers}, on the heap at {buffer}");
+    println!("  thread::spawn needs a 'static closure: the thread may outlive this function, so");
+    println!("  it can't borrow our locals (error[E0373]: closure may outlive the current function)");
+
+    // move hands the Vec to the thread; the thread hands it back, with a result, through join
+    let handle = thread::spawn(move || {
+        let sum: i32 = numbers.iter().sum();
+        (sum, numbers)
+    });
+    println!("  After `move`, numbers belongs to the thread (using it here is error[E0382])");
+
+    let (sum, numbers) = handle.join().expect("the summing thread doesn't panic");
+    n.fact("sum", sum).address("returned", numbers.as_ptr());
+    n.say("join() returned Ok((sum, numbers)): sum = {sum}, and the Vec is back at {returned}");
+    println!("  JoinHandle<T> owns the thread's eventual T; join consumes the handle and moves");
+    println!("  the T out to us. The Vec made a round trip without being copied");
+
+    // A panic ends the thread, and its payload becomes an owned value for the joiner
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let handle = thread::spawn(|| -> i32 { panic::panic_any(ParseError { line: 3 }) });
+    let result = handle.join();
+    panic::set_hook(previous);
+
+    let payload = result.expect_err("the thread panicked");
+    println!("\n  When the thread panics, join returns Err(Box<dyn Any + Send>): the panic");
+    println!("  payload, now owned by us. Downcasting recovers it by value:");
+    match payload.downcast::<ParseError>() {
+        Ok(error) => {
+            n.fact("line", error.line);
+            n.say("payload.downcast::<ParseError>() = Ok: parse error on line {line}");
+        }
+        Err(_) => n.say("the payload was not a ParseError"),
+    }
+    println!("  panic!(\"..\") payloads are &'static str or String; panic_any can send any");
+    println!("  Send + 'static value, which is why the payload has to be a type-erased Box");
+}
+
+fn move_closures() {
+    let mut n = Narration::new();
+
+    let greeting = String::from("hello from main");
+    let retries = 3;
+    n.fact("greeting", &greeting).address("buffer", greeting.as_ptr());
+    n.say("main owns greeting = '{greeting}', its text at {buffer}, and retries = 3, an i32");
+    println!("  thread::spawn(|| println!(\"{{}}\", greeting)) is error[E0373]: without move the");
+    println!("  closure only borrows greeting, and spawn needs F: 'static, because nothing stops");
+    println!("  the thread from running after main's stack frame, and greeting, are gone");
+
+    let kept = greeting.clone();
+    let handle = thread::spawn(move || (greeting.to_uppercase(), greeting.as_ptr() as usize, retries));
+    let (shouted, inside, copied) = handle.join().expect("the thread doesn't panic");
+    n.fact("shouted", shouted).address("buffer", inside as *const u8).fact("copied", copied);
+    println!();
+    n.say("With move, the closure is a struct that owns greeting itself, and spawn gives that");
+    n.say("struct to the new thread. Inside it, greeting is still at {buffer}: the String was");
+    n.say("moved, not copied, and the thread made '{shouted}' from it");
+    println!("  Back in main, `println!(\"{{}}\", greeting);` is error[E0382]. greeting was moved into");
+    println!("  the closure, so main has nothing left to read; the thread owns it, and drops it");
+    println!("  when the closure finishes, whenever that is");
+
+    n.fact("kept", &kept).address("kept_buffer", kept.as_ptr()).fact("retries", retries);
+    println!();
+    n.say("main keeps what it didn't give away: kept = '{kept}', a clone made before the spawn,");
+    n.say("with its own text at {kept_buffer}; and retries = {retries}, since an i32 is Copy and move");
+    n.say("copied it into the closure (the thread saw {copied})");
+
+    let label = String::from("worker");
+    let name = &label;
+    let owned = name.clone();
+    let handle = thread::spawn(move || owned.len());
+    n.fact("len", handle.join().expect("the thread doesn't panic"));
+    println!();
+    n.say("move doesn't turn a borrow into ownership: with name = &label, thread::spawn(move ||");
+    n.say("println!(\"{{}}\", name)) moves a copy of the reference, which still borrows label, so");
+    n.say("it's error[E0597]: label does not live long enough. Cloning name into an owned String");
+    n.say("first gives the thread something of its own (it counted {len} bytes)");
+
+    println!("\n  To let threads borrow main's locals instead, thread::scope (see rwlock_readers)");
+    println!("  guarantees they're joined before the scope ends, so its closures need no move");
+}
+
+const WORKERS: usize = 4;
+
+fn shared_arc() {
+    let mut n = Narration::new();
+
+    println!("  Moving a Vec into one thread works (see join_handle), but several threads can't");
+    println!("  all own it, and a borrow can't cross into a thread. Rc would give it several owners,");
+    println!("  but its count is a plain integer: two threads cloning at once could both read 1,");
+    println!("  both write 2, and free the Vec while one of them still uses it. So Rc isn't Send,");
+    println!("  and thread::spawn(move || .. local ..) is error[E0277]: `Rc<Vec<i32>>` cannot be");
+    println!("  sent between threads safely\n");
+    let local = Rc::new(vec![1, 2, 3]);
+    n.fact("count", Rc::strong_count(&local));
+    n.say("An Rc<Vec<i32>> is fine on one thread (strong_count = {count}), just not across them");
+
+    // Arc updates its count atomically, so clones on different threads can't race
+    let numbers: Arc<Vec<i32>> = Arc::new((1..=100).collect());
+    let mut handles = Vec::with_capacity(WORKERS);
+    let ((), allocs) = instrument::measure(|| handles.extend((0..WORKERS).map(|_| Arc::clone(&numbers))));
+    n.fact("workers", WORKERS).fact("count", Arc::strong_count(&numbers));
+    n.fact("allocs", allocs.count).address("numbers", numbers.as_ptr());
+    n.say("numbers = Arc::new((1..=100).collect()) at {numbers}, then {workers} Arc::clones:");
+    n.say("strong_count = {count}, and {allocs} allocations for the clones themselves");
+    println!("  Arc<T> is Send and Sync when T is both: each clone can move into its own thread,");
+    println!("  and all of them read the same Vec at once. Vec<i32> is, so the threads can start:");
+
+    let chunk = numbers.len() / WORKERS;
+    let workers: Vec<_> = handles
+        .into_iter()
+        .enumerate()
+        .map(|(i, numbers)| {
+            thread::spawn(move || {
+                let part = &numbers[i * chunk..(i + 1) * chunk];
+                // A raw pointer isn't Send, so the address travels back as a number
+                (part.iter().sum::<i32>(), numbers.as_ptr() as usize)
+            })
+        })
+        .collect();
+    let mut total = 0;
+    for (i, worker) in workers.into_iter().enumerate() {
+        let (sum, seen) = worker.join().expect("the summing threads don't panic");
+        n.fact("i", i).fact("from", i * chunk + 1).fact("to", (i + 1) * chunk).fact("sum", sum).address("seen", seen as *const i32);
+        n.say("  thread {i} summed {from}..={to} = {sum}, reading the Vec at {seen}");
+        total += sum;
+    }
+    n.fact("total", total).fact("count", Arc::strong_count(&numbers));
+    n.say("Together: {total}. Each thread dropped its clone when it finished: strong_count = {count}");
+    println!("  Arc only shares; the Vec is read-only while it's shared. Changing it from several");
+    println!("  threads needs a Mutex or RwLock inside the Arc");
+}
+
+const INCREMENTS: usize = 1000;
+
+fn shared_mutex() {
+    let mut n = Narration::new();
+
+    // The Arc shares ownership of the Mutex; the Mutex hands out &mut i32 one holder at a time
+    let counter = Arc::new(Mutex::new(0));
+    n.address("counter", Arc::as_ptr(&counter));
+    n.say("counter = Arc::new(Mutex::new(0)): one Mutex<i32> on the heap at {counter}");
+    println!("  Arc alone only shares reads. Mutex<i32> adds a lock, so `&Mutex<i32>` (all a");
+    println!("  shared Arc gives) is enough to change the i32: whoever holds the lock gets &mut");
+
+    let workers: Vec<_> = (0..WORKERS)
+        .map(|_| {
+            let counter = Arc::clone(&counter);
+            thread::spawn(move || {
+                for _ in 0..INCREMENTS {
+                    let mut guard = counter.lock().expect("no thread panics while holding the lock");
+                    *guard += 1;
+                } // the guard drops here, every time round, and the Mutex unlocks
+            })
+        })
+        .collect();
+    for worker in workers {
+        worker.join().expect("the counting threads don't panic");
+    }
+    n.fact("workers", WORKERS).fact("increments", INCREMENTS);
+    n.fact("count", *counter.lock().expect("not poisoned")).fact("owners", Arc::strong_count(&counter));
+    n.say("{workers} threads each did {increments} `*counter.lock().unwrap() += 1`: count = {count}");
+    n.say("No increment was lost, and with the threads done the Arc has {owners} owner again");
+
+    // The guard is a borrow of the Mutex: it points into it, and unlocks when it drops
+    println!();
+    let mut guard: MutexGuard<i32> = counter.lock().expect("not poisoned");
+    *guard += 1;
+    n.address("inside", &*guard as *const i32).fact("guard_size", size_of::<MutexGuard<i32>>());
+    n.say("let mut guard = counter.lock().unwrap(); derefs to the i32 inside it, at {inside}");
+    n.say("MutexGuard<'_, i32> is {guard_size} bytes: a reference to the Mutex and a panic flag");
+    n.fact("locked", counter.try_lock().is_err());
+    n.say("While guard is alive, try_lock() fails even on this thread: {locked}");
+    drop(guard);
+    n.fact("free", counter.try_lock().is_ok());
+    n.say("After drop(guard), try_lock() succeeds: {free}");
+    println!("  There's no unlock(): the guard's Drop is the unlock, the same RAII as a Box freeing");
+    println!("  its memory. And a reference taken through the guard borrows the guard, so");
+    println!("  drop(guard) while that reference is still in use is error[E0505]: no unlocking early");
+
+    // Drop runs during a panic too, so a panicking holder can't leave the Mutex locked
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let holder = Arc::clone(&counter);
+    let result = thread::spawn(move || {
+        let mut guard = holder.lock().expect("not poisoned");
+        *guard = -1;
+        panic!("gave up halfway");
+    })
+    .join();
+    panic::set_hook(previous);
+
+    println!();
+    n.fact("panicked", result.is_err());
+    n.say("A thread that panics while holding the guard (panicked: {panicked}) still drops it:");
+    match counter.lock() {
+        Ok(_) => n.say("  the lock is free and untouched"),
+        Err(poisoned) => {
+            n.fact("value", *poisoned.into_inner());
+            n.say("  lock() returns Err(PoisonError): unlocked, but marked as maybe half-changed");
+            n.say("  into_inner() still hands over the guard, and the value it left: {value}");
+        }
+    }
+}
+
+fn rwlock_readers() {
+    let mut n = Narration::new();
+
+    println!("  Section 2's rule: any number of &T, or exactly one &mut T, never both. The compiler");
+    println!("  checks it for borrows of a local. RwLock<T> applies the same rule to data shared");
+    println!("  between threads, at runtime: read() hands out shared guards, write() an exclusive one\n");
+
+    let settings = Arc::new(RwLock::new(vec![String::from("large font")]));
+    let reading = AtomicUsize::new(0);
+    let most = AtomicUsize::new(0);
+    // Every reader waits at the barrier while holding its guard, so all of them hold one at once
+    let barrier = Barrier::new(WORKERS + 1);
+    thread::scope(|scope| {
+        for _ in 0..WORKERS {
+            scope.spawn(|| {
+                let guard = settings.read().expect("no writer panicked");
+                let now = reading.fetch_add(1, Ordering::SeqCst) + 1;
+                most.fetch_max(now, Ordering::SeqCst);
+                barrier.wait(); // all readers are in here together
+                barrier.wait(); // and stay until the main thread has looked
+                reading.fetch_sub(1, Ordering::SeqCst);
+                drop(guard);
+            });
+        }
+        barrier.wait();
+        n.fact("workers", WORKERS).fact("most", most.load(Ordering::SeqCst));
+        n.fact("blocked", settings.try_write().is_err());
+        n.say("{workers} reader threads each took settings.read(): {most} guards alive at once, like {most} &T");
+        n.say("Meanwhile try_write() fails (blocked: {blocked}): no &mut T while a &T is alive");
+        barrier.wait();
+    });
+
+    // With the readers gone the writer gets in, and then it's the readers who wait
+    let mut writer = settings.write().expect("no reader panicked");
+    writer.push(String::from("dark mode"));
+    n.fact("locked_out", settings.try_read().is_err());
+    n.say("After every read guard dropped, write() succeeded; while it's held, try_read() fails: {locked_out}");
+    drop(writer);
+    n.fact("settings", format!("{:?}", *settings.read().expect("no writer panicked")));
+    n.say("drop(writer) lets readers back in: settings = {settings}");
+
+    println!("  A read guard only derefs to &T, so pushing through one doesn't compile:");
+    println!("  error[E0596]: cannot borrow data in dereference of `RwLockReadGuard` as mutable.");
+    println!("  The difference from &T/&mut T is when the rule is checked: the compiler rejects a");
+    println!("  conflicting borrow, a lock makes the conflicting thread wait. Prefer RwLock over");
+    println!("  Mutex when reads are common and long; for short ones a Mutex is usually as fast");
+}
+
+fn channel_moves() {
+    let mut n = Narration::new();
+
+    println!("  takes_ownership(s) moved a String into a function, and s was unusable after it.");
+    println!("  sender.send(s) is the same move, only the new owner is on another thread\n");
+
+    let (sender, receiver) = mpsc::channel::<String>();
+    let worker = thread::spawn(move || {
+        let greeting = String::from("hello from the worker");
+        // The address travels as a number; the String itself goes through the channel
+        let sent_from = greeting.as_ptr() as usize;
+        sender.send(greeting).expect("the main thread is receiving");
+        sent_from
+    });
+    let greeting = receiver.recv().expect("the worker sends one greeting");
+    let sent_from = worker.join().expect("the worker doesn't panic");
+    n.fact("greeting", &greeting).address("sent_from", sent_from as *const u8).address("arrived", greeting.as_ptr());
+    n.say("The worker made \"{greeting}\" with its text at {sent_from}, and sent it");
+    n.say("recv() here returned a String whose text is at {arrived}: moved, not copied");
+    println!("  send(greeting) takes the String by value, so the worker can't touch it afterwards:");
+    println!("  using greeting after the send is error[E0382]: borrow of moved value");
+
+    // Several producers: each Sender clone is one more way into the same channel
+    println!();
+    let (sender, receiver) = mpsc::channel::<String>();
+    let producers: Vec<_> = (0..3)
+        .map(|i| {
+            let sender = sender.clone();
+            thread::spawn(move || {
+                for j in 0..2 {
+                    sender.send(format!("worker {} job {}", i, j)).expect("the main thread is receiving");
+                }
+            }) // this clone of the Sender drops here
+        })
+        .collect();
+    drop(sender);
+    for producer in producers {
+        producer.join().expect("the producers don't panic");
+    }
+    let mut received: Vec<String> = receiver.iter().collect();
+    received.sort();
+    n.fact("count", received.len()).fact("first", &received[0]).fact("last", &received[received.len() - 1]);
+    n.say("3 threads, each with a sender.clone(), sent {count} Strings: \"{first}\" .. \"{last}\"");
+    println!("  `for message in receiver` ends once every Sender is dropped. That's why the original");
+    println!("  sender is dropped by hand: while any Sender lives, the receiver waits for more");
+}
+
+// Whether a type is Send and Sync, found out at the call site: the inherent
+// methods only exist when the bound holds, and otherwise method lookup falls
+// back to the trait's, which answer false. Only works with concrete types
+struct Probe<T: ?Sized>(PhantomData<T>);
+
+trait Fallback {
+    fn is_send(&self) -> bool {
+        false
+    }
+    fn is_sync(&self) -> bool {
+        false
+    }
+}
+
+impl<T: ?Sized> Fallback for Probe<T> {}
+
+impl<T: ?Sized + Send> Probe<T> {
+    fn is_send(&self) -> bool {
+        true
+    }
+}
+
+impl<T: ?Sized + Sync> Probe<T> {
+    fn is_sync(&self) -> bool {
+        true
+    }
+}
+
+// (type name, Send, Sync) for each type
+macro_rules! auto_traits {
+    ($($ty:ty),* $(,)?) => {
+        vec![$((stringify!($ty), Probe::<$ty>(PhantomData).is_send(), Probe::<$ty>(PhantomData).is_sync())),*]
+    };
+}
+
+fn send_sync() {
+    let mut n = Narration::new();
+
+    println!("  Two marker traits decide what may cross a thread boundary:");
+    println!("    Send: ownership of a T can move to another thread (thread::spawn(move || ..))");
+    println!("    Sync: a &T can be shared between threads, i.e. T is Sync exactly when &T is Send");
+    println!("  They're auto traits: a struct is Send or Sync when all its fields are, with no impl.");
+    println!("  These are the types this demo uses, checked by the compiler as it built it:\n");
+
+    let table = auto_traits![
+        i32,
+        String,
+        &str,
+        Vec<String>,
+        Box<String>,
+        Rc<String>,
+        Arc<String>,
+        Cell<i32>,
+        RefCell<String>,
+        Arc<Cell<i32>>,
+        Mutex<String>,
+        MutexGuard<'static, i32>,
+        RwLock<String>,
+        Arc<Mutex<String>>,
+        mpsc::Sender<String>,
+        mpsc::Receiver<String>,
+        *const i32,
+    ];
+    let yes_no = |flag: bool| if flag { "yes" } else { "no" };
+    println!("    {:<26} {:<5} Sync", "type", "Send");
+    for (name, send, sync) in &table {
+        println!("    {:<26} {:<5} {}", name, yes_no(*send), yes_no(*sync));
+    }
+
+    let neither: Vec<&str> = table.iter().filter(|(_, send, sync)| !send && !sync).map(|(name, ..)| *name).collect();
+    n.fact("neither", neither.join(", "));
+    println!();
+    n.say("Neither Send nor Sync: {neither}.");
+    println!("  Rc's count is a plain integer, so two threads touching one Rc could corrupt it.");
+    println!("  Arc<Cell<i32>> inherits the problem: Arc<T> is only Send if T is Sync, because every");
+    println!("  clone shares the T. So thread::spawn(move || shared.set(1)) is error[E0277]");
+    println!("  Cell and RefCell are Send but not Sync: one thread may own one, but sharing &Cell");
+    println!("  would let two threads set it at once. Mutex and RwLock make their T Sync with a lock");
+    println!("  MutexGuard is Sync but not Send: some platforms require the locking thread to unlock");
+
+    // What the compiler allows, it allows without any runtime cost
+    let counter = Arc::new(Mutex::new(0));
+    let worker = {
+        let counter = Arc::clone(&counter);
+        thread::spawn(move || *counter.lock().expect("not poisoned") += 1)
+    };
+    worker.join().expect("the worker doesn't panic");
+    n.fact("count", *counter.lock().expect("not poisoned"));
+    println!();
+    n.say("Arc<Mutex<i32>> is Send and Sync, so a worker thread could take a clone: count = {count}");
+    println!("  The checks are all compile-time: Send and Sync have no methods and cost nothing at");
+    println!("  runtime. A raw pointer opts out of both, and a type built on one has to promise");
+    println!("  safety itself with `unsafe impl Send`, which is how Arc and Mutex are written");
+}
+
+const CAPACITY: usize = 2;
+
+fn bounded_channel() {
+    let mut n = Narration::new();
+
+    // send moves each value into the channel; the buffer holds at most CAPACITY of them
+    let (sender, receiver) = mpsc::sync_channel::<String>(CAPACITY);
+    n.fact("capacity", CAPACITY);
+    n.say("sync_channel({capacity}): the channel can own {capacity} values that nobody has received yet");
+    let first = String::from("first");
+    sender.send(first).expect("the receiver is alive");
+    sender.send(String::from("second")).expect("the receiver is alive");
+    println!("  Sent two Strings. Each send moved its value into the channel, so first is gone");
+    println!("  from this scope (println!(\"{{}}\", first) is error[E0382])");
+
+    // A full buffer won't take ownership; try_send hands the value straight back
+    match sender.try_send(String::from("third")) {
+        Ok(()) => n.say("try_send(third) found room"),
+        Err(TrySendError::Full(third)) | Err(TrySendError::Disconnected(third)) => {
+            n.fact("third", third);
+            n.say("try_send(\"{third}\") = Err(Full(..)): the buffer is full and we still own {third}");
+        }
+    }
+    n.fact("first", receiver.recv().expect("first was sent"));
+    n.fact("second", receiver.recv().expect("second was sent"));
+    n.say("Received {first} and {second}: ownership moved out of the channel to the receiver");
+
+    // send blocks instead: the producer can only get CAPACITY values ahead of the consumer
+    static SENT: AtomicUsize = AtomicUsize::new(0);
+    let producer = thread::spawn(move || {
+        for i in 0..5 {
+            sender.send(format!("job {}", i)).expect("the receiver is alive");
+            SENT.fetch_add(1, Ordering::SeqCst);
+        }
+        sender
+    });
+    thread::sleep(Duration::from_millis(100));
+    n.fact("sent", SENT.load(Ordering::SeqCst));
+    println!();
+    n.say("A producer thread sending 5 jobs had finished {sent} sends after 100ms: the rest wait");
+    println!("  That's backpressure: a slow consumer automatically slows the producer down, and");
+    println!("  memory stays bounded by {} values no matter how far behind it falls", CAPACITY);
+    let jobs: Vec<String> = receiver.iter().take(5).collect();
+    let sender = producer.join().expect("the producer doesn't panic");
+    n.fact("jobs", format!("{:?}", jobs));
+    n.say("Receiving let the producer finish: {jobs}");
+
+    // With nobody left to receive, send fails and gives the value back
+    drop(receiver);
+    let error = sender.send(String::from("late")).expect_err("the receiver was dropped");
+    n.fact("late", error.0);
+    println!();
+    n.say("After drop(receiver), send(\"late\") = Err(SendError(\"{late}\")): the value came back to us");
+    println!("  Values still in the buffer when the receiver goes are dropped with the channel,");
+    println!("  but send never takes a value it can't deliver: each one is either owned by the");
+    println!("  channel or handed back to the sender");
+}
+
+// An owned error value a thread can panic with
+struct ParseError {
+    line: usize,
+}
+
+const JOIN_HANDLE_CODE: &str = r#"use std::thread;
+
+fn main() {
+    let numbers = vec![1, 2, 3, 4];
+
+    // Without move the closure would borrow numbers, but the thread may outlive main's stack frame
+    // let borrowed = thread::spawn(|| numbers.iter().sum::<i32>());
+
+    // move gives the Vec to the thread; the result, and the Vec itself, come back through join
+    let handle = thread::spawn(move || {
+        let sum: i32 = numbers.iter().sum();
+        (sum, numbers)
+    });
+    // numbers now belongs to the thread
+    // println!("{:?}", numbers);
+
+    let (sum, numbers) = handle.join().unwrap();
+    println!("sum of {:?} is {}", numbers, sum);
+
+    // A panic's payload is an owned Box<dyn Any + Send> handed to whoever joins
+    let handle = thread::spawn(|| -> i32 { panic!("bad input") });
+    match handle.join() {
+        Ok(value) => println!("got {}", value),
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => println!("thread panicked: {}", message),
+            Err(_) => println!("thread panicked with something else"),
+        },
+    }
+}
+"#;
+
+const MOVE_CLOSURES_CODE: &str = r#"use std::thread;
+
+fn main() {
+    let greeting = String::from("hello from main");
+    let retries = 3;
+
+    // Without move the closure only borrows greeting, but the thread may outlive main's frame
+    // let handle = thread::spawn(|| println!("{}", greeting));
+
+    // A clone made beforehand stays with main
+    let kept = greeting.clone();
+    // move makes the closure own greeting, and spawn gives the closure to the new thread
+    let handle = thread::spawn(move || {
+        println!("thread: {} (retries: {})", greeting.to_uppercase(), retries);
+        greeting.len()
+    });
+    // greeting belongs to the thread now
+    // println!("{}", greeting);
+    // retries is a Copy i32: the closure took a copy, and main still has its own
+    println!("main: {} (retries: {})", kept, retries);
+    println!("the thread's greeting was {} bytes", handle.join().unwrap());
+
+    // move puts a copy of the reference in the closure, but it still borrows main's local
+    let label = String::from("worker");
+    let name = &label;
+    // thread::spawn(move || println!("{}", name));
+    let owned = name.clone();
+    thread::spawn(move || println!("{}", owned)).join().unwrap();
+}
+"#;
+
+const SHARED_ARC_CODE: &str = r#"use std::rc::Rc;
+use std::sync::Arc;
+use std::thread;
+
+fn main() {
+    // Rc's count isn't updated atomically, so an Rc can't be sent to another thread
+    let local = Rc::new(vec![1, 2, 3]);
+    println!("{}", Rc::strong_count(&local));
+    // thread::spawn(move || println!("{}", local.len()));
+
+    // Arc's count is atomic: Arc<Vec<i32>> is Send + Sync because Vec<i32> is
+    let numbers: Arc<Vec<i32>> = Arc::new((1..=100).collect());
+    let mut workers = Vec::new();
+    for i in 0..4 {
+        // Each thread gets its own clone: a new owner of the same Vec
+        let numbers = Arc::clone(&numbers);
+        workers.push(thread::spawn(move || numbers[i * 25..(i + 1) * 25].iter().sum::<i32>()));
+    }
+    println!("owners while the threads run: up to {}", Arc::strong_count(&numbers));
+
+    let total: i32 = workers.into_iter().map(|worker| worker.join().unwrap()).sum();
+    println!("total {}, owners now {}", total, Arc::strong_count(&numbers));
+}
+"#;
+
+const SHARED_MUTEX_CODE: &str = r#"use std::sync::{Arc, Mutex};
+use std::thread;
+
+fn main() {
+    // Arc: every thread owns the Mutex. Mutex: one of them at a time changes the i32
+    let counter = Arc::new(Mutex::new(0));
+    let mut workers = Vec::new();
+    for _ in 0..4 {
+        let counter = Arc::clone(&counter);
+        workers.push(thread::spawn(move || {
+            for _ in 0..1000 {
+                // lock() returns a MutexGuard, a borrow of the Mutex that derefs to the i32
+                let mut guard = counter.lock().unwrap();
+                *guard += 1;
+            } // the guard drops at the end of each pass, and dropping it unlocks the Mutex
+        }));
+    }
+    for worker in workers {
+        worker.join().unwrap();
+    }
+    println!("count = {}", *counter.lock().unwrap());
+
+    // While a guard is alive nobody else gets the lock, not even this thread
+    let guard = counter.lock().unwrap();
+    println!("try_lock while locked fails: {}", counter.try_lock().is_err());
+    drop(guard);
+    println!("try_lock after drop(guard) works: {}", counter.try_lock().is_ok());
+
+    // A reference into the Mutex lives only as long as the guard it came through
+    let mut guard = counter.lock().unwrap();
+    let inside: &mut i32 = &mut guard;
+    // Unlocking while that reference is still in use
+    // drop(guard);
+    *inside += 1;
+    println!("count = {}", inside);
+}
+"#;
+
+const RWLOCK_READERS_CODE: &str = r#"use std::sync::{Arc, RwLock};
+use std::thread;
+
+fn main() {
+    let settings = Arc::new(RwLock::new(vec![String::from("large font")]));
+
+    // Like many &T: every reader can hold a read guard at the same time
+    let readers: Vec<_> = (0..4)
+        .map(|i| {
+            let settings = Arc::clone(&settings);
+            thread::spawn(move || {
+                let settings = settings.read().unwrap();
+                // A read guard derefs to &Vec<String>, so it can't change the Vec
+                // settings.push(String::from("dark mode"));
+                println!("reader {} sees {:?}", i, *settings);
+            })
+        })
+        .collect();
+    for reader in readers {
+        reader.join().unwrap();
+    }
+
+    // Like one &mut T: the writer waits until no reader holds a guard, then excludes them all
+    {
+        let mut writer = settings.write().unwrap();
+        println!("try_read while writing fails: {}", settings.try_read().is_err());
+        writer.push(String::from("dark mode"));
+    } // the write guard drops here, and readers can get in again
+
+    println!("now: {:?}", *settings.read().unwrap());
+}
+"#;
+
+const CHANNEL_MOVES_CODE: &str = r#"use std::sync::mpsc;
+use std::thread;
+
+fn main() {
+    let (sender, receiver) = mpsc::channel::<String>();
+
+    thread::spawn(move || {
+        let greeting = String::from("hello from the worker");
+        // send takes the String by value, like takes_ownership(s) does
+        sender.send(greeting).unwrap();
+        // greeting now belongs to whoever receives it
+        // println!("worker still has {}", greeting);
+    });
+
+    // recv hands ownership to this thread
+    let greeting: String = receiver.recv().unwrap();
+    println!("main got {:?}", greeting);
+
+    // Several producers, one receiver: every clone of the Sender feeds the same channel
+    let (sender, receiver) = mpsc::channel::<String>();
+    for i in 0..3 {
+        let sender = sender.clone();
+        thread::spawn(move || {
+            for j in 0..2 {
+                sender.send(format!("worker {} job {}", i, j)).unwrap();
+            }
+        });
+    }
+    // The loop below ends when the last Sender is dropped, so drop ours too
+    drop(sender);
+    for message in receiver {
+        println!("received {}", message);
+    }
+}
+"#;
+
+const SEND_SYNC_CODE: &str = r#"use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+// These compile only for types that are Send, or Sync
+fn assert_send<T: Send>() {}
+fn assert_sync<T: Sync>() {}
+
+fn main() {
+    assert_send::<String>();
+    assert_sync::<String>();
+    assert_send::<Arc<Mutex<i32>>>();
+    assert_sync::<Mutex<i32>>();
+
+    // A Cell can move to another thread, but not be shared: two threads could set it at once
+    assert_send::<Cell<i32>>();
+    // assert_sync::<Cell<i32>>();
+
+    // Rc's count isn't atomic, so an Rc can neither move to nor be shared with another thread
+    // assert_send::<Rc<String>>();
+    let local = Rc::new(String::from("stays on this thread"));
+    println!("{}", local);
+
+    // Arc<T> is Send only if T is Sync: every clone shares the same T
+    #[allow(clippy::arc_with_non_send_sync)] // the point: this Arc can't go to another thread
+    let shared = Arc::new(Cell::new(0));
+    // thread::spawn(move || shared.set(1));
+    shared.set(1);
+    println!("shared = {}", shared.get());
+
+    // With a Mutex inside, the Arc can go to any thread
+    let counter = Arc::new(Mutex::new(0));
+    let worker = {
+        let counter = Arc::clone(&counter);
+        thread::spawn(move || *counter.lock().unwrap() += 1)
+    };
+    worker.join().unwrap();
+    println!("count = {}", *counter.lock().unwrap());
+}
+"#;
+
+const BOUNDED_CHANNEL_CODE: &str = r#"use std::sync::mpsc::{self, TrySendError};
+use std::thread;
+use std::time::Duration;
+
+fn main() {
+    // Room for two messages: sending transfers ownership into the channel's buffer
+    let (sender, receiver) = mpsc::sync_channel::<String>(2);
+    let first = String::from("first");
+    sender.send(first).unwrap();
+    // first moved into the channel, it's the receiver's to take now
+    // println!("{}", first);
+    sender.send(String::from("second")).unwrap();
+
+    // The buffer is full: try_send fails and gives the String back instead of blocking
+    let third = String::from("third");
+    // sender.send(third.clone()) would block here until someone receives
+    match sender.try_send(third) {
+        Ok(()) => println!("sent"),
+        Err(TrySendError::Full(third)) => println!("full, still own {:?}", third),
+        Err(TrySendError::Disconnected(third)) => println!("no receiver, still own {:?}", third),
+    }
+
+    // A producer thread blocks in send until the consumer makes room
+    let producer = thread::spawn(move || {
+        for i in 0..4 {
+            sender.send(format!("job {}", i)).unwrap();
+        }
+        sender
+    });
+    thread::sleep(Duration::from_millis(50));
+    for _ in 0..4 {
+        println!("received {}", receiver.recv().unwrap());
+    }
+    let sender = producer.join().unwrap();
+
+    // Once the receiver is gone, send returns the value inside the error
+    drop(receiver);
+    let error = sender.send(String::from("late")).unwrap_err();
+    let late: String = error.0;
+    println!("nobody will receive {:?}, so it came back to us", late);
+}
+"#;
@@ -0,0 +1,91 @@
+// Addresses that point into the demo from outside it (slides, handouts, a
+// course page), all accepted wherever a command takes an example:
+//
+//   ownership-demo://ownership/transfer#2   step 2 of an example
+//   ownership/transfer#2                    the same, without the scheme
+//   ownership_transfer                      a plain example id
+//
+// A step is one of the lines `present` stops on, counted from 1. When an
+// address no longer matches anything, the error suggests where the example
+// probably went, since old links outlive lesson reshuffles.
+
+use crate::lessons::{self, Example, Section};
+use crate::present;
+
+pub const SCHEME: &str = "ownership-demo://";
+
+pub struct Target {
+    pub section: &'static Section,
+    pub example: &'static Example,
+    pub step: Option<usize>,
+}
+
+pub fn resolve(address: &str) -> Result<Target, String> {
+    let rest = address.strip_prefix(SCHEME).unwrap_or(address);
+    let (path, step) = match rest.split_once('#') {
+        Some((path, step)) => (path, Some(step)),
+        None => (rest, None),
+    };
+    let path = path.trim_end_matches('/');
+    let id = match path.split_once('/') {
+        Some((section, name)) => format!("{}_{}", section, name),
+        None => path.to_string(),
+    };
+    let (section, example) = lessons::find(&id).ok_or_else(|| not_found(address, &id))?;
+
+    let step = match step {
+        None => None,
+        Some(step) => {
+            let number: usize = step.parse().map_err(|_| format!("'{}' in {} isn't a step number", step, address))?;
+            let steps = present::steps(example).len();
+            if number == 0 || number > steps {
+                return Err(format!("{} has steps 1 to {}, so there's no step {}", example.id(section), steps, number));
+            }
+            Some(number)
+        }
+    };
+    Ok(Target { section, example, step })
+}
+
+// The deep link for an example, or one of its steps
+pub fn link(section: &Section, example: &Example, step: Option<usize>) -> String {
+    match step {
+        Some(step) => format!("{}{}/{}#{}", SCHEME, section.name, example.name, step),
+        None => format!("{}{}/{}", SCHEME, section.name, example.name),
+    }
+}
+
+fn not_found(address: &str, id: &str) -> String {
+    // An example that moved to another section keeps its name; one that was
+    // renamed is usually only a few letters off
+    let moved = lessons::examples().find(|(_, example)| id.ends_with(&format!("_{}", example.name)));
+    let closest = lessons::examples()
+        .map(|(section, example)| (distance(id, &example.id(section)), section, example))
+        .filter(|(distance, _, _)| *distance <= 3)
+        .min_by_key(|(distance, _, _)| *distance)
+        .map(|(_, section, example)| (section, example));
+    match moved.or(closest) {
+        Some((section, example)) => format!(
+            "no example at '{}'; did you mean {} ({})? `list` shows every example",
+            address,
+            example.id(section),
+            link(section, example, None)
+        ),
+        None => format!("no example at '{}' (see `list`)", address),
+    }
+}
+
+// Edit distance between two ids
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitute = previous[j] + usize::from(ca != *cb);
+            current.push(substitute.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
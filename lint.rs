@@ -0,0 +1,286 @@
+// `lint-content`: checks the lesson data for problems a reviewer would
+// otherwise have to spot by hand. It exits with an error when anything is
+// found, so it can gate CI.
+//
+//  - narration lines wider than MAX_WIDTH (they wrap badly when projected)
+//  - examples still holding the TODO placeholders `new-lesson` wrote
+//  - examples with no broken line to quiz on, unless QUIZ_EXEMPT says why
+//  - examples with no presentation stops, or with a stop for a code line
+//    that isn't in the example's code
+//  - example code that doesn't compile as shipped
+//  - commented-out broken lines that can't be found in the example's code
+//  - broken lines whose claimed error code doesn't match what rustc reports
+//  - generated files in examples/ that have drifted from the lessons
+//  - a diagnostics bundle that's missing some broken line
+//  - exercise starters that don't fail the way they claim, and solutions
+//    that don't pass their exercise's tests
+//  - aliases for old ids that lead nowhere or shadow a current id
+//  - curricula in curricula/ that don't load
+//  - sample lesson packs in lesson-packs/ that `pack install` would refuse
+//  - translations of nothing, or that dropped a placeholder (untranslated
+//    text is only counted, since it falls back to English)
+//
+// The checks that don't need rustc (content_problems) also run as a test,
+// so `cargo test` catches them without a separate lint-content run.
+
+use std::env;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use crate::aliases;
+use crate::codegen;
+use crate::curriculum;
+use crate::diagnostics;
+use crate::exercises;
+use crate::harness;
+use crate::i18n;
+use crate::lessons;
+use crate::packs;
+use crate::pool;
+use crate::present;
+use crate::refactor;
+use crate::render;
+use crate::scaffold;
+use crate::toolchain;
+use crate::xapi;
+
+const MAX_WIDTH: usize = 100;
+
+// Examples the quiz (and so the classroom) has no question about, and why.
+// Questions are made from broken lines, so every other example needs one
+const QUIZ_EXEMPT: &[(&str, &str)] = &[
+    ("borrowing_immutable_refs", "the Book's calculate_length, which compiles; borrowing_mutable_refs has the errors"),
+    ("drop_early_return", "it's about when drops run, which nothing stops from compiling"),
+    ("practical_first_word_properties", "a property test of code that compiles, checked at run time"),
+    ("practical_clone_hotspots", "a profiling walkthrough in which every version compiles"),
+];
+
+pub fn command() -> Result<(), String> {
+    let mut problems = content_problems(narration)?;
+    let mut skipped = Vec::new();
+
+    if toolchain::installed().is_none() {
+        eprintln!("warning: rustc was not found on the PATH, skipping compile checks");
+    }
+
+    for (section, example) in lessons::examples() {
+        let id = example.id(section);
+        if toolchain::installed().is_none() {
+            continue;
+        }
+        if !example.crates.is_empty() {
+            let names = example.crate_names().join(", ");
+            skipped.push(format!("{}: needs {} from crates.io, which plain rustc can't link", id, names));
+            continue;
+        }
+        if let Err(reason) = harness::ready_for(example) {
+            skipped.push(format!("{}: {}", id, reason));
+            continue;
+        }
+        let outcome = harness::compile(example.code)?;
+        if !outcome.success {
+            problems.push(format!("{}: example code doesn't compile:\n{}", id, outcome.diagnostics));
+        }
+
+        for broken in example.broken_lines {
+            let Some(code) = harness::with_line_enabled(example, broken) else {
+                problems.push(format!("{}: no commented-out line '// {}' in the example code", id, broken.line));
+                continue;
+            };
+            let outcome = harness::compile(&code)?;
+            if !outcome.error_codes.iter().any(|code| code == broken.error) {
+                let reported = if outcome.success {
+                    "it compiles".to_string()
+                } else {
+                    format!("rustc reports {}", outcome.error_codes.join(", "))
+                };
+                problems.push(format!("{}: '{}' is claimed to cause {}, but {}", id, broken.line, broken.error, reported));
+            }
+        }
+    }
+
+    if toolchain::installed().is_some() {
+        // Each exercise takes two rustc runs, so they're checked in parallel
+        // Only the built-in packs: installed ones were checked when they were installed
+        let all: Vec<_> = exercises::PACKS
+            .iter()
+            .flat_map(|pack| pack.exercises.iter().map(move |exercise| (exercise.id(pack), exercise)))
+            .collect();
+        pool::run(
+            &all,
+            |(id, exercise)| {
+                let mut found = Vec::new();
+                check_exercise(id, exercise, &mut found).map(|()| found)
+            },
+            |(id, _), result| match result {
+                Ok(found) => problems.extend(found),
+                Err(e) => problems.push(format!("{}: could not check: {}", id, e)),
+            },
+        );
+    }
+
+    for (path, _) in codegen::stale_files(Path::new("examples"))? {
+        problems.push(format!("{} is out of date, run `gen-examples`", path.display()));
+    }
+
+    if let Some(reason) = diagnostics::stale_bundle()? {
+        problems.push(format!("diagnostics.txt {}, run `bundle-diagnostics`", reason));
+    }
+
+    problems.extend(packs::problems());
+    let (_, untranslated) = i18n::check();
+
+    for reason in &skipped {
+        println!("skipped compile checks for {}", reason);
+    }
+    for (lang, count) in untranslated {
+        if count > 0 {
+            println!("{} translation: {} text(s) still shown in English", lang, count);
+        }
+    }
+    for problem in &problems {
+        println!("{}", problem);
+    }
+    match problems.len() {
+        0 => {
+            println!("Lesson content looks good");
+            Ok(())
+        }
+        n => Err(format!("found {} problem(s) in the lesson content", n)),
+    }
+}
+
+// The checks that need no rustc: each example's placeholders, narration
+// (played by `play`, which returns what it printed), stops and quiz coverage, then
+// the alias tables, refactorings, curricula and translations
+fn content_problems(play: impl Fn(&str) -> Result<Output, String>) -> Result<Vec<String>, String> {
+    let mut problems = Vec::new();
+
+    for (section, example) in lessons::examples() {
+        let id = example.id(section);
+
+        let says = example.stops.iter().map(|stop| stop.say);
+        if [section.title, example.title, example.code].into_iter().chain(says).any(|text| text.contains(scaffold::PLACEHOLDER)) {
+            problems.push(format!("{}: still has {} placeholders from new-lesson to fill in", id, scaffold::PLACEHOLDER));
+        }
+
+        // An example that panics partway would otherwise only have the
+        // narration it got through checked
+        let played = play(&id)?;
+        if !played.status.success() {
+            let stderr = String::from_utf8_lossy(&played.stderr);
+            problems.push(format!("{}: playing it failed ({}): {}", id, played.status, stderr.trim()));
+        }
+        for line in String::from_utf8_lossy(&played.stdout).lines() {
+            let width = render::width(line);
+            if width > MAX_WIDTH {
+                problems.push(format!("{}: narration line is {} characters (limit {}): {}", id, width, MAX_WIDTH, line.trim()));
+            }
+        }
+
+        if example.stops.is_empty() {
+            problems.push(format!("{}: has no stops, so `present` can only step through it line by line", id));
+        }
+        for stop in present::misplaced(example) {
+            problems.push(format!("{}: the stop '{}' is for a line that isn't in the code after the stop before", id, stop.line));
+        }
+
+        let exempt = QUIZ_EXEMPT.iter().any(|(exempt, _)| *exempt == id);
+        if example.broken_lines.is_empty() && !exempt {
+            problems.push(format!("{}: has no broken line to quiz on; add one, or say why not in QUIZ_EXEMPT", id));
+        }
+        if !example.broken_lines.is_empty() && exempt {
+            problems.push(format!("{}: is in QUIZ_EXEMPT but has broken lines now; take it out", id));
+        }
+    }
+    for (id, _) in QUIZ_EXEMPT {
+        if !lessons::examples().any(|(section, example)| example.id(section) == *id) {
+            problems.push(format!("QUIZ_EXEMPT names {}, which isn't an example", id));
+        }
+    }
+
+    let example_exists = |id: &str| lessons::examples().any(|(section, example)| example.id(section) == id);
+    problems.extend(aliases::problems(lessons::ALIASES, example_exists, "example"));
+    let exercise_exists = |id: &str| exercises::exercises().any(|(pack, exercise)| exercise.id(pack) == id);
+    problems.extend(aliases::problems(exercises::ALIASES, exercise_exists, "exercise"));
+
+    problems.extend(refactor::problems());
+    problems.extend(curriculum::problems());
+    problems.extend(i18n::check().0);
+    Ok(problems)
+}
+
+// The starter has to fail the way the exercise says (with its error, or by
+// failing the tests), and the solution has to build and pass the tests.
+// `pack install` holds installed packs to the same standard
+pub fn check_exercise(id: &str, exercise: &exercises::Exercise, problems: &mut Vec<String>) -> Result<(), String> {
+    let starter = harness::test(&exercise.with_tests(exercise.starter))?;
+    match exercise.error {
+        Some(error) if !starter.build.error_codes.iter().any(|code| code == error) => {
+            let reported = if starter.build.success {
+                "it compiles".to_string()
+            } else {
+                format!("rustc reports {}", starter.build.error_codes.join(", "))
+            };
+            problems.push(format!("{}: starter is claimed to cause {}, but {}", id, error, reported));
+        }
+        None if !starter.build.success => {
+            problems.push(format!("{}: starter is meant to compile, but doesn't:\n{}", id, starter.build.diagnostics));
+        }
+        None if starter.passed => problems.push(format!("{}: starter already passes the tests", id)),
+        _ => {}
+    }
+
+    let solution = harness::test(&exercise.with_tests(exercise.solution))?;
+    if !solution.build.success {
+        problems.push(format!("{}: solution doesn't compile:\n{}", id, solution.build.diagnostics));
+    } else if let Some(reason) = &solution.stopped {
+        problems.push(format!("{}: solution's tests were stopped because {}", id, reason));
+    } else if !solution.passed {
+        problems.push(format!("{}: solution fails the tests:\n{}", id, solution.output));
+    }
+    Ok(())
+}
+
+// Plays an example in a child process and captures what it prints
+fn narration(id: &str) -> Result<Output, String> {
+    let exe = env::current_exe().map_err(|e| format!("could not find the demo executable: {}", e))?;
+    Command::new(exe)
+        .args(["run", id])
+        // Playing every example isn't the learner completing them
+        .env_remove(xapi::TARGET_VAR)
+        .output()
+        .map_err(|e| format!("could not run example {}: {}", id, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Set for the child process that plays one example for the narration check
+    const PLAY_VAR: &str = "RUST_OWNERSHIP_DEMO_LINT_PLAY";
+
+    // The test binary can't run `rust_ownership_demo run <id>`, so it runs
+    // itself with just play_example selected, which plays the example in PLAY_VAR
+    fn play_in_child(id: &str) -> Result<Output, String> {
+        let exe = env::current_exe().map_err(|e| format!("could not find the test executable: {}", e))?;
+        Command::new(exe)
+            .args(["--exact", "lint::tests::play_example", "--nocapture", "--quiet"])
+            .env(PLAY_VAR, id)
+            .output()
+            .map_err(|e| format!("could not play example {}: {}", id, e))
+    }
+
+    #[test]
+    fn play_example() {
+        let Ok(id) = env::var(PLAY_VAR) else { return };
+        let (_, example) = lessons::examples().find(|(section, example)| example.id(section) == id).expect("an example id");
+        (example.run)();
+    }
+
+    #[test]
+    fn content_has_no_problems() {
+        let problems = content_problems(play_in_child).expect("the checks run");
+        assert!(problems.is_empty(), "lint-content would report:\n{}", problems.join("\n"));
+    }
+}
@@ -0,0 +1,96 @@
+// Narration templates filled in from the running example.
+//
+// Instead of writing values into the narration by hand, examples record the
+// facts they want to talk about (values, lengths, sizes, where a buffer lives)
+// and narrate with `{name}` placeholders. That keeps every sentence true even
+// when the example's data changes.
+//
+// Heap addresses differ on every run, so they are shown as labels (@A, @B,
+// ...) handed out in the order they are first seen: two facts with the same
+// label really do point at the same memory.
+
+use std::fmt::Display;
+
+#[derive(Default)]
+pub struct Narration {
+    facts: Vec<(&'static str, String)>,
+    addresses: Vec<usize>,
+}
+
+impl Narration {
+    pub fn new() -> Narration {
+        Narration::default()
+    }
+
+    // Records a value under `name`, replacing any earlier fact with that name
+    pub fn fact(&mut self, name: &'static str, value: impl Display) -> &mut Narration {
+        let value = value.to_string();
+        match self.facts.iter_mut().find(|(n, _)| *n == name) {
+            Some(fact) => fact.1 = value,
+            None => self.facts.push((name, value)),
+        }
+        self
+    }
+
+    // Records where some data lives, as a label like "@A"
+    pub fn address<T: ?Sized>(&mut self, name: &'static str, ptr: *const T) -> &mut Narration {
+        let address = ptr as *const u8 as usize;
+        let index = match self.addresses.iter().position(|&a| a == address) {
+            Some(index) => index,
+            None => {
+                self.addresses.push(address);
+                self.addresses.len() - 1
+            }
+        };
+        self.fact(name, label(index))
+    }
+
+    // Prints a line of narration, indented like the rest of the demo
+    pub fn say(&self, template: &str) {
+        println!("  {}", self.render(template));
+    }
+
+    // Fills in the `{name}` placeholders; `{{` and `}}` stand for literal braces
+    pub fn render(&self, template: &str) -> String {
+        let mut out = String::with_capacity(template.len());
+        let mut rest = template;
+
+        while let Some(i) = rest.find(['{', '}']) {
+            out.push_str(&rest[..i]);
+            let brace = &rest[i..i + 1];
+            rest = &rest[i + 1..];
+
+            if rest.starts_with(brace) {
+                out.push_str(brace);
+                rest = &rest[1..];
+            } else if brace == "{" {
+                let end = rest
+                    .find('}')
+                    .unwrap_or_else(|| panic!("unclosed placeholder in narration: {:?}", template));
+                out.push_str(self.value(&rest[..end], template));
+                rest = &rest[end + 1..];
+            } else {
+                panic!("unmatched '}}' in narration: {:?}", template);
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+
+    fn value(&self, name: &str, template: &str) -> &str {
+        match self.facts.iter().find(|(n, _)| *n == name) {
+            Some((_, value)) => value,
+            None => panic!("narration refers to unknown fact '{}': {:?}", name, template),
+        }
+    }
+}
+
+// @A, @B, ..., @Z, @AA, @AB, ...
+fn label(index: usize) -> String {
+    let letter = (b'A' + (index % 26) as u8) as char;
+    if index < 26 {
+        format!("@{}", letter)
+    } else {
+        format!("{}{}", label(index / 26 - 1), letter)
+    }
+}
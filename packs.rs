@@ -0,0 +1,466 @@
+// Lesson packs: exercises written outside the demo, installed with `pack
+// install` and then listed, started and checked like the built-in ones.
+//
+// A pack is a directory, or a zip of one, with a pack.toml (the TOML subset
+// of toml.rs):
+//
+//   name = "loops"                      # the prefix of its exercise ids
+//   title = "Moves in loops"
+//   version = "1.0.0"
+//   author = "Ada <ada@example.org>"
+//
+//   [[exercise]]
+//   name = "signature"                  # so the id is loops_signature
+//   title = "A signature on every card"
+//   level = 2                           # 1 (warm-up) to 3
+//   task = "Make cards() sign every card without giving up the signature"
+//   error = "E0382"                     # what the starter fails with; leave it
+//                                       # out if it compiles and fails the tests
+//   starter = "signature/starter.rs"    # files, relative to pack.toml
+//   tests = "signature/tests.rs"
+//   solution = "signature/solution.rs"
+//   hints = ["The loop body runs once per guest", "Clone it, or borrow it"]
+//
+// The fields mean what they do in exercises/mod.rs, and lesson-packs/mail is
+// a complete pack to start from. Installing checks a pack
+// the way lint-content checks the built-in ones: its content first (names,
+// levels, error codes, texts, files), then every exercise with the compile
+// harness, so the starter has to fail as claimed and the solution has to pass
+// the tests. That runs the pack's code, confined like a learner's (see
+// confine.rs), so before any of it runs the pack's checksums and signature
+// are checked and it has to be trusted, as trust.rs describes. Only a pack
+// that passes is copied, its manifest and the files it names, into the packs
+// directory. `pack seal` writes the SHA256SUMS an author ships with a pack.
+
+use std::env;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+use crate::exercises::{self, Exercise, Pack};
+use crate::lint;
+use crate::pool;
+use crate::render;
+use crate::scratch;
+use crate::toml::{self, Value};
+use crate::toolchain;
+use crate::trust::{self, Policy};
+
+const MANIFEST: &str = "pack.toml";
+
+// Where the sample packs shipped with the demo live
+pub const SAMPLES_DIR: &str = "lesson-packs";
+
+// An installed pack, or one being installed
+struct Loaded {
+    pack: &'static Pack,
+    version: String,
+    author: String,
+    // The files it's made of, pack.toml first, relative to its directory
+    files: Vec<PathBuf>,
+    fingerprint: String,
+}
+
+pub fn command(args: &[String]) -> Result<(), String> {
+    match (args.first().map(String::as_str), args.get(1)) {
+        (None | Some("list"), _) => list(),
+        (Some("install"), Some(source)) => install(Path::new(source), trust::policy(&args[2..])?),
+        (Some("seal"), Some(dir)) => seal(Path::new(dir)),
+        (Some("remove"), Some(name)) => remove(name),
+        _ => Err(
+            "usage: pack [list] | pack install <directory or .zip> [--trust <policy>] | pack seal <directory> | pack remove <pack>"
+                .to_string(),
+        ),
+    }
+}
+
+// $XDG_DATA_HOME or ~/.local/share, falling back to the temp dir
+fn packs_dir() -> PathBuf {
+    let base = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share")))
+        .unwrap_or_else(env::temp_dir);
+    base.join("rust_ownership_demo").join("packs")
+}
+
+// The fingerprints of the packs someone chose to trust (see trust.rs)
+fn trust_store() -> PathBuf {
+    packs_dir().join(".trusted")
+}
+
+// Every installed pack that loads, by name. One that doesn't, or whose files
+// aren't the ones that were trusted, is skipped with a warning, so a damaged
+// pack can't keep the built-in exercises from working
+pub fn installed() -> &'static [&'static Pack] {
+    static INSTALLED: OnceLock<Vec<&'static Pack>> = OnceLock::new();
+    INSTALLED.get_or_init(|| load_installed().into_iter().map(|loaded| loaded.pack).collect())
+}
+
+fn load_installed() -> Vec<Loaded> {
+    let Ok(entries) = fs::read_dir(packs_dir()) else { return Vec::new() };
+    let mut dirs: Vec<PathBuf> = entries.filter_map(Result::ok).map(|entry| entry.path()).collect();
+    dirs.retain(|dir| dir.join(MANIFEST).exists());
+    dirs.sort();
+    let store = trust_store();
+    dirs.iter()
+        .filter_map(|dir| match load(dir) {
+            Ok(loaded) if trust::is_trusted(&store, &loaded.fingerprint) => Some(loaded),
+            Ok(_) => {
+                eprintln!(
+                    "warning: skipping the lesson pack in {}: its files aren't the ones that were trusted; \
+                     `pack install` it again to check it",
+                    dir.display()
+                );
+                None
+            }
+            Err(e) => {
+                eprintln!("warning: skipping the lesson pack in {}: {}", dir.display(), e);
+                None
+            }
+        })
+        .collect()
+}
+
+fn list() -> Result<(), String> {
+    println!("Built in:");
+    for pack in exercises::PACKS {
+        println!("  {} {} ({})", render::pad(pack.name, 16), pack.title, count(pack));
+    }
+    let installed = load_installed();
+    println!("\nInstalled in {}:", packs_dir().display());
+    if installed.is_empty() {
+        println!("  none yet; `pack install <directory or .zip>` adds one");
+    }
+    for loaded in installed {
+        let pack = loaded.pack;
+        println!("  {} {} {} by {} ({})", render::pad(pack.name, 16), pack.title, loaded.version, loaded.author, count(pack));
+        let seal = trust::verify(&packs_dir().join(pack.name), &loaded.files).map_or_else(|e| e, |seal| seal.describe());
+        println!("  {} {}", render::pad("", 16), seal);
+    }
+    Ok(())
+}
+
+fn count(pack: &Pack) -> String {
+    match pack.exercises.len() {
+        1 => "1 exercise".to_string(),
+        n => format!("{} exercises", n),
+    }
+}
+
+fn install(source: &Path, policy: Policy) -> Result<(), String> {
+    if source.is_dir() {
+        return install_from(source, &policy);
+    }
+    if source.extension().is_none_or(|extension| extension != "zip") {
+        return Err(format!("{} is neither a directory nor a .zip", source.display()));
+    }
+    // Only a directory this run made is removed afterwards
+    let scratch = scratch::private_dir("pack")?;
+    let result = unpack(source, &scratch).and_then(|dir| install_from(&dir, &policy));
+    let _ = fs::remove_dir_all(&scratch);
+    result
+}
+
+// The directory holding the zip's pack.toml, once it's unpacked into
+// `scratch`: at the top, or in the one directory the zip holds
+fn unpack(source: &Path, scratch: &Path) -> Result<PathBuf, String> {
+    let status = Command::new("unzip")
+        .arg("-q")
+        .arg(source)
+        .arg("-d")
+        .arg(scratch)
+        .status()
+        .map_err(|e| format!("could not run unzip (is it installed?): {}", e))?;
+    if !status.success() {
+        return Err(format!("unzip could not unpack {}", source.display()));
+    }
+    if scratch.join(MANIFEST).exists() {
+        return Ok(scratch.to_path_buf());
+    }
+    let entries = fs::read_dir(scratch).map_err(|e| format!("could not read {}: {}", scratch.display(), e))?;
+    let dirs: Vec<PathBuf> = entries.filter_map(Result::ok).map(|entry| entry.path()).filter(|path| path.is_dir()).collect();
+    match dirs.as_slice() {
+        [dir] if dir.join(MANIFEST).exists() => Ok(dir.clone()),
+        _ => Err(format!("{} has no {} at its top", source.display(), MANIFEST)),
+    }
+}
+
+fn install_from(dir: &Path, policy: &Policy) -> Result<(), String> {
+    let loaded = load(dir)?;
+    let pack = loaded.pack;
+
+    // Nothing of the pack's runs until it's trusted
+    let seal = trust::verify(dir, &loaded.files).map_err(|e| format!("{} wasn't installed: {}", pack.name, e))?;
+    let about = format!("{} {} by {}", pack.title, loaded.version, loaded.author);
+    let known = trust::is_trusted(&trust_store(), &loaded.fingerprint);
+    trust::decide(policy, &about, &seal, known).map_err(|e| format!("{} wasn't installed: {}", pack.name, e))?;
+
+    toolchain::require("checking a pack's exercises before installing it")?;
+    println!("Checking {} with rustc...", count(pack));
+    let problems = check(pack);
+    if !problems.is_empty() {
+        return Err(format!("{} wasn't installed:\n  {}", pack.name, problems.join("\n  ")));
+    }
+
+    // Copied next to its final place and then moved there, so a failed copy
+    // leaves any earlier version as it was
+    let packs = packs_dir();
+    let target = packs.join(pack.name);
+    let staging = packs.join(format!(".{}.installing", pack.name));
+    let _ = fs::remove_dir_all(&staging);
+    let seal_files = [trust::CHECKSUMS, trust::SIGNATURE].map(Path::new).into_iter().filter(|file| dir.join(file).exists());
+    for file in loaded.files.iter().map(PathBuf::as_path).chain(seal_files) {
+        let to = staging.join(file);
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("could not create {}: {}", parent.display(), e))?;
+        }
+        fs::copy(dir.join(file), &to).map_err(|e| format!("could not copy {}: {}", file.display(), e))?;
+    }
+    let previous = load(&target).ok();
+    if target.exists() {
+        fs::remove_dir_all(&target).map_err(|e| format!("could not remove the old {}: {}", target.display(), e))?;
+    }
+    fs::rename(&staging, &target).map_err(|e| format!("could not install into {}: {}", target.display(), e))?;
+    trust::record(&trust_store(), pack.name, &loaded.fingerprint)?;
+
+    match previous {
+        Some(previous) => println!("Replaced {} {} with {}", pack.title, previous.version, loaded.version),
+        None => println!("Installed {} ({})", about, seal.describe()),
+    }
+    let ids: Vec<String> = pack.exercises.iter().map(|exercise| exercise.id(pack)).collect();
+    println!("Its exercises are {}; `exercise start <exercise>` begins one", ids.join(", "));
+    Ok(())
+}
+
+fn remove(name: &str) -> Result<(), String> {
+    if exercises::PACKS.iter().any(|pack| pack.name == name) {
+        return Err(format!("{} is built in, so it can't be removed", name));
+    }
+    // The name is joined onto the packs directory, so it mustn't be able to leave it
+    if !valid_name(name) {
+        return Err(format!("'{}' isn't a pack name; they're lowercase words joined by _", name));
+    }
+    let dir = packs_dir().join(name);
+    if !dir.join(MANIFEST).exists() {
+        return Err(format!("no pack called '{}' is installed (see `pack list`)", name));
+    }
+    fs::remove_dir_all(&dir).map_err(|e| format!("could not remove {}: {}", dir.display(), e))?;
+    trust::forget(&trust_store(), name)?;
+    println!("Removed {}. Progress on its exercises is kept, in case it's installed again", name);
+    Ok(())
+}
+
+// Writes the SHA256SUMS for the pack in `dir`, for its author to ship (and sign)
+fn seal(dir: &Path) -> Result<(), String> {
+    let loaded = load(dir)?;
+    let sums = trust::checksums(dir, &loaded.files)?;
+    let path = dir.join(trust::CHECKSUMS);
+    fs::write(&path, sums).map_err(|e| format!("could not write {}: {}", path.display(), e))?;
+    println!("Wrote {}, covering {} files", path.display(), loaded.files.len());
+    if dir.join(trust::SIGNATURE).exists() {
+        println!("{} signed the old checksums, so it no longer matches; sign them again:", trust::SIGNATURE);
+    } else {
+        println!("To sign it with your SSH key, so packs can be trusted by who made them:");
+    }
+    println!("  ssh-keygen -Y sign -f ~/.ssh/id_ed25519 -n {} {}", trust::NAMESPACE, path.display());
+    Ok(())
+}
+
+// Everything that would keep a pack from being installed: the problems a
+// reader could spot, and when there are none, what rustc finds
+fn check(pack: &'static Pack) -> Vec<String> {
+    let mut problems = content_problems(pack);
+    if problems.is_empty() && toolchain::installed().is_some() {
+        pool::run(
+            pack.exercises,
+            |exercise| {
+                let mut found = Vec::new();
+                lint::check_exercise(&exercise.id(pack), exercise, &mut found).map(|()| found)
+            },
+            |exercise, result| match result {
+                Ok(found) => problems.extend(found),
+                Err(e) => problems.push(format!("{}: could not check: {}", exercise.id(pack), e)),
+            },
+        );
+    }
+    problems
+}
+
+// What `pack install` would refuse the sample packs in SAMPLES_DIR for, for
+// lint-content
+pub fn problems() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(SAMPLES_DIR) else { return Vec::new() };
+    let mut dirs: Vec<PathBuf> = entries.filter_map(Result::ok).map(|entry| entry.path()).collect();
+    dirs.sort();
+    let mut problems = Vec::new();
+    for dir in dirs.iter().filter(|dir| dir.join(MANIFEST).exists()) {
+        match load(dir) {
+            Ok(loaded) => {
+                if let Err(e) = trust::verify(dir, &loaded.files) {
+                    problems.push(format!("{}: {}, run `pack seal {}`", dir.display(), e, dir.display()));
+                }
+                problems.extend(check(loaded.pack).into_iter().map(|problem| format!("{}: {}", dir.display(), problem)));
+            }
+            Err(e) => problems.push(e),
+        }
+    }
+    problems
+}
+
+// Lowercase words joined by _, which also keeps a pack's name a plain directory name
+fn valid_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+// Problems a reader could spot without compiling anything
+fn content_problems(pack: &Pack) -> Vec<String> {
+    let mut problems = Vec::new();
+    if !valid_name(pack.name) {
+        problems.push(format!("the pack's name '{}' should be lowercase words joined by _", pack.name));
+    }
+    if exercises::PACKS.iter().any(|built_in| built_in.name == pack.name) {
+        problems.push(format!("'{}' is the name of a built-in pack", pack.name));
+    }
+    if pack.exercises.is_empty() {
+        problems.push("it has no [[exercise]] tables".to_string());
+    }
+    for (i, exercise) in pack.exercises.iter().enumerate() {
+        let id = exercise.id(pack);
+        if !valid_name(exercise.name) {
+            problems.push(format!("{}: the name should be lowercase words joined by _", id));
+        }
+        if pack.exercises[..i].iter().any(|earlier| earlier.name == exercise.name) {
+            problems.push(format!("{}: two exercises have this name", id));
+        }
+        // Another pack's exercise could have the same id, e.g. borrowck + use_after_move
+        let taken = exercises::PACKS.iter().flat_map(|other| other.exercises.iter().map(move |other_exercise| other_exercise.id(other)));
+        if taken.chain(other_installed_ids(pack.name)).any(|other| other == id) {
+            problems.push(format!("{}: another pack already has an exercise with this id", id));
+        }
+        if exercise.title.trim().is_empty() || exercise.task.trim().is_empty() {
+            problems.push(format!("{}: needs a title and a task", id));
+        }
+        if !(1..=3).contains(&exercise.level) {
+            problems.push(format!("{}: level should be 1, 2 or 3, not {}", id, exercise.level));
+        }
+        let is_code = |error: &str| error.len() == 5 && error.starts_with('E') && error[1..].chars().all(|c| c.is_ascii_digit());
+        if let Some(error) = exercise.error.filter(|error| !is_code(error)) {
+            problems.push(format!("{}: error should be a rustc error code like E0382, not '{}'", id, error));
+        }
+        if exercise.hints.is_empty() {
+            problems.push(format!("{}: needs at least one hint", id));
+        }
+        if exercise.tests.trim().is_empty() {
+            problems.push(format!("{}: its tests are empty, so any fix would pass", id));
+        }
+    }
+    problems
+}
+
+// The exercise ids of installed packs other than `name` (which is being replaced)
+fn other_installed_ids(name: &str) -> Vec<String> {
+    let others = installed().iter().filter(|pack| pack.name != name);
+    others.flat_map(|pack| pack.exercises.iter().map(|exercise| exercise.id(pack))).collect()
+}
+
+// Reads the pack in `dir`. Its strings live as long as the program, like the
+// built-in packs' do
+fn load(dir: &Path) -> Result<Loaded, String> {
+    let path = dir.join(MANIFEST);
+    let text = fs::read_to_string(&path).map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+    let document = toml::parse(&text, "exercise").map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    let (mut name, mut title, mut version, mut author) = (None, None, None, None);
+    for entry in document.top {
+        let target = match entry.key.as_str() {
+            "name" => &mut name,
+            "title" => &mut title,
+            "version" => &mut version,
+            "author" => &mut author,
+            key => return Err(format!("{}: line {}: unknown setting '{}'", path.display(), entry.line, key)),
+        };
+        match entry.value {
+            Value::Text(text) => *target = Some(text),
+            _ => return Err(format!("{}: line {}: {} should be a string", path.display(), entry.line, entry.key)),
+        }
+    }
+    let missing = |key: &str| format!("{} needs a {}", path.display(), key);
+    let name = name.ok_or_else(|| missing("name"))?;
+
+    let mut files = vec![PathBuf::from(MANIFEST)];
+    let mut pack_exercises = Vec::new();
+    for (number, table) in document.tables {
+        let exercise = exercise(table, dir, &mut files)
+            .map_err(|e| format!("{}: the [[exercise]] on line {}: {}", path.display(), number, e))?;
+        pack_exercises.push(exercise);
+    }
+    let pack = Pack {
+        name: leak(name),
+        title: leak(title.ok_or_else(|| missing("title"))?),
+        exercises: Box::leak(pack_exercises.into_boxed_slice()),
+    };
+    let fingerprint = trust::fingerprint(dir, &files)?;
+    Ok(Loaded {
+        pack: Box::leak(Box::new(pack)),
+        version: version.ok_or_else(|| missing("version"))?,
+        author: author.ok_or_else(|| missing("author"))?,
+        files,
+        fingerprint,
+    })
+}
+
+fn exercise(table: Vec<toml::Entry>, dir: &Path, files: &mut Vec<PathBuf>) -> Result<Exercise, String> {
+    let (mut name, mut title, mut task, mut error) = (None, None, None, None);
+    let (mut starter, mut tests, mut solution) = (None, None, None);
+    let (mut level, mut hints) = (None, Vec::new());
+    for entry in table {
+        match (entry.key.as_str(), entry.value) {
+            ("name", Value::Text(text)) => name = Some(text),
+            ("title", Value::Text(text)) => title = Some(text),
+            ("task", Value::Text(text)) => task = Some(text),
+            ("error", Value::Text(text)) => error = Some(leak(text)),
+            ("starter", Value::Text(file)) => starter = Some(read_file(dir, &file, files)?),
+            ("tests", Value::Text(file)) => tests = Some(read_file(dir, &file, files)?),
+            ("solution", Value::Text(file)) => solution = Some(read_file(dir, &file, files)?),
+            ("level", Value::Number(number)) => level = Some(u8::try_from(number).unwrap_or(u8::MAX)),
+            ("hints", Value::List(list)) => hints = list,
+            ("level", _) => return Err("level should be a number".to_string()),
+            ("hints", _) => return Err("hints should be an array of strings".to_string()),
+            (key @ ("name" | "title" | "task" | "error" | "starter" | "tests" | "solution"), _) => {
+                return Err(format!("{} should be a string", key));
+            }
+            (key, _) => return Err(format!("unknown key '{}'", key)),
+        }
+    }
+    let missing = |key: &str| format!("needs a {}", key);
+    let hints: Vec<&'static str> = hints.into_iter().map(leak).collect();
+    Ok(Exercise {
+        name: leak(name.ok_or_else(|| missing("name"))?),
+        title: leak(title.ok_or_else(|| missing("title"))?),
+        level: level.ok_or_else(|| missing("level"))?,
+        task: leak(task.ok_or_else(|| missing("task"))?),
+        error,
+        starter: starter.ok_or_else(|| missing("starter"))?,
+        tests: tests.ok_or_else(|| missing("tests"))?,
+        hints: Box::leak(hints.into_boxed_slice()),
+        solution: solution.ok_or_else(|| missing("solution"))?,
+    })
+}
+
+// A file the manifest names. It has to be inside the pack, so installing
+// copies everything the pack needs and nothing else
+fn read_file(dir: &Path, file: &str, files: &mut Vec<PathBuf>) -> Result<&'static str, String> {
+    let relative = PathBuf::from(file);
+    if !relative.components().all(|component| matches!(component, Component::Normal(_))) {
+        return Err(format!("{} should be a path inside the pack, like signature/starter.rs", file));
+    }
+    let text = fs::read_to_string(dir.join(&relative)).map_err(|e| format!("could not read {}: {}", file, e))?;
+    files.push(relative);
+    Ok(leak(text))
+}
+
+fn leak(text: String) -> &'static str {
+    Box::leak(text.into_boxed_str())
+}
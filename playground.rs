@@ -0,0 +1,25 @@
+// Links that open an example's code in the Rust Playground (play.rust-lang.org).
+// The code travels in the `code` query parameter, so no gist or network
+// access is needed to build the link.
+
+const PLAYGROUND_URL: &str = "https://play.rust-lang.org/";
+
+pub fn link(code: &str) -> String {
+    format!("{}?version=stable&mode=debug&edition=2021&code={}", PLAYGROUND_URL, percent_encode(code))
+}
+
+// Percent-encodes everything that isn't safe inside a query parameter value.
+// Common punctuation is left as-is to keep the link (and its QR code) short.
+fn percent_encode(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() * 2);
+    for &byte in text.as_bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' => out.push(byte as char),
+            b'-' | b'.' | b'_' | b'~' | b'!' | b'$' | b'\'' | b'(' | b')' | b'*' | b',' | b';' | b':' | b'@' | b'/' | b'?' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
@@ -0,0 +1,34 @@
+// A small job pool for slow, independent work like compiling exercises:
+// jobs run on one thread per CPU, and each result is handed back on the
+// calling thread as soon as it's ready, so progress can be printed (and
+// shared state updated) without locks.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+// Runs `work` on every job and calls `finished` with each result, in the
+// order they complete
+pub fn run<T: Sync, R: Send>(jobs: &[T], work: impl Fn(&T) -> R + Sync, mut finished: impl FnMut(&T, R)) {
+    let workers = thread::available_parallelism().map_or(1, |n| n.get()).min(jobs.len());
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let (sender, next, work) = (sender.clone(), &next, &work);
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(job) = jobs.get(index) else { break };
+                if sender.send((index, work(job))).is_err() {
+                    break;
+                }
+            });
+        }
+        // Only the workers' senders are left, so the loop ends when they do
+        drop(sender);
+        for (index, result) in receiver {
+            finished(&jobs[index], result);
+        }
+    });
+}
@@ -0,0 +1,286 @@
+// `exercise`: the kata runner. `exercise start` copies an exercise's starter
+// code into practice/<id>.rs for the learner to fix, `exercise check` builds
+// their version with the hidden tests and runs them, and `hint` and
+// `solution` help when they're stuck. Progress is kept in
+// practice/progress.txt unless configured otherwise (see progress/mod.rs).
+// What the learner reads is shown in their language where there's a
+// translation (see i18n).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::aliases;
+use crate::exercises::{self, Exercise, Pack};
+use crate::fingerprint;
+use crate::harness;
+use crate::i18n;
+use crate::pool;
+use crate::progress::{self, Progress};
+use crate::render;
+use crate::toolchain;
+use crate::xapi;
+
+const PRACTICE_DIR: &str = "practice";
+
+pub fn command(args: &[String]) -> Result<(), String> {
+    // --force checks code again even when it passed before and hasn't changed
+    let force = args.iter().any(|arg| arg == "--force");
+    let id = args.iter().skip(1).map(String::as_str).find(|arg| *arg != "--force");
+    match (args.first().map(String::as_str), id) {
+        (None | Some("list"), _) => list(),
+        (Some("start"), Some(id)) => start(id),
+        (Some("check"), Some("--all")) => check_all(force),
+        (Some("check"), Some(id)) => check(id, force),
+        (Some("hint"), Some(id)) => hint(id),
+        (Some("solution"), Some(id)) => solution(id),
+        _ => Err("usage: exercise [list] | exercise start|check|hint|solution <exercise> | exercise check --all [--force]".to_string()),
+    }
+}
+
+fn list() -> Result<(), String> {
+    let progress = load_progress()?;
+    for pack in exercises::all_packs() {
+        let solved = pack.exercises.iter().filter(|e| progress.entry(&e.id(pack)).solved).count();
+        let title = i18n::text(&format!("{}.title", pack.name), pack.title);
+        println!("{} ({}/{} solved)", title, solved, pack.exercises.len());
+        for exercise in pack.exercises {
+            let id = exercise.id(pack);
+            let status = if progress.entry(&id).solved {
+                "solved"
+            } else if working_file(&id).exists() {
+                "started"
+            } else {
+                ""
+            };
+            let title = translated(&id, "title", exercise.title);
+            let line = format!("  {:<36} {:<3} {} {}", id, "*".repeat(exercise.level.into()), render::pad(title, 44), status);
+            println!("{}", line.trim_end());
+        }
+    }
+    Ok(())
+}
+
+fn start(id: &str) -> Result<(), String> {
+    let (pack, exercise) = find_exercise(id)?;
+    let id = exercise.id(pack);
+    let path = working_file(&id);
+    let (title, task) = (translated(&id, "title", exercise.title), translated(&id, "task", exercise.task));
+
+    println!("{}: {}", id, title);
+    println!("  {}", task);
+    if path.exists() {
+        println!("\nYou already started this one, so {} was left as it is.", path.display());
+        println!("Delete it to start over.");
+    } else {
+        let problem = match exercise.error {
+            Some(error) => i18n::ui("start.compile_error", &[("error", error)]),
+            None => i18n::ui("start.test_failure", &[]),
+        };
+        let header = format!(
+            "// {}\n\
+             // {}\n\
+             // {}. {}\n\
+             //   rust_ownership_demo exercise check {}\n\
+             // {}\n\n",
+            title,
+            task,
+            problem,
+            i18n::ui("start.fix_it", &[]),
+            id,
+            i18n::ui("start.stuck", &[("id", &id)])
+        );
+        fs::create_dir_all(PRACTICE_DIR).map_err(|e| format!("could not create {}: {}", PRACTICE_DIR, e))?;
+        fs::write(&path, header + exercise.starter).map_err(|e| format!("could not write {}: {}", path.display(), e))?;
+        println!("\nEdit {} and run `exercise check {}` when you're done.", path.display(), id);
+        if let Err(reason) = toolchain::require("checking your fix") {
+            println!("Note: {}. Hints and the solution work without it.", reason);
+        }
+    }
+    Ok(())
+}
+
+fn check(id: &str, force: bool) -> Result<(), String> {
+    let (pack, exercise) = find_exercise(id)?;
+    let id = exercise.id(pack);
+    let path = working_file(&id);
+    let code = fs::read_to_string(&path)
+        .map_err(|_| format!("{} doesn't exist yet, run `exercise start {}` first", path.display(), id))?;
+
+    // The fingerprint covers the hidden tests too, so a changed pack is checked again
+    let source = exercise.with_tests(&code);
+    let fingerprint = fingerprint::of(&source);
+    let mut progress = load_progress()?;
+    if !force && progress.entry(&id).passed.as_ref() == Some(&fingerprint) {
+        println!("{} hasn't changed since it passed, so it wasn't built again (--force checks it anyway)", id);
+        return Ok(());
+    }
+    toolchain::require("checking exercises")?;
+
+    let run = harness::test(&source)?;
+    if !run.build.success {
+        eprint!("{}", run.build.diagnostics);
+        return Err(format!("{} doesn't compile yet", path.display()));
+    }
+    if let Some(reason) = &run.stopped {
+        // Only the end: a runaway program's output is mostly the same line
+        let lines: Vec<&str> = run.output.lines().collect();
+        for line in &lines[lines.len().saturating_sub(20)..] {
+            println!("{}", line);
+        }
+        return Err(format!("the tests were stopped because {}", reason));
+    }
+    if !run.passed {
+        print!("{}", run.output);
+        return Err(format!("{} compiles, but the tests fail; the fix has to keep the behaviour", path.display()));
+    }
+
+    let entry = progress.update(&id, |entry| {
+        entry.solved = true;
+        entry.passed = Some(fingerprint);
+    })?;
+    println!("Solved {}: it compiles and passes the tests.", id);
+    xapi::passed_exercise(&id, exercise.title);
+    if entry.saw_solution {
+        println!("(You had seen the solution, so try the next one on your own.)");
+    } else {
+        println!("Compare with the canonical fix: `exercise solution {}`", id);
+    }
+    Ok(())
+}
+
+// Checks every exercise that has a working file, several at a time, and
+// reports each one as soon as it's done. Files that passed before and
+// haven't changed since are skipped unless `force` is set
+fn check_all(force: bool) -> Result<(), String> {
+    let mut progress = load_progress()?;
+    let mut to_check = Vec::new();
+    let mut unchanged = Vec::new();
+    for (pack, exercise) in exercises::exercises() {
+        let id = exercise.id(pack);
+        let Ok(code) = fs::read_to_string(working_file(&id)) else { continue };
+        let source = exercise.with_tests(&code);
+        let fingerprint = fingerprint::of(&source);
+        if !force && progress.entry(&id).passed.as_ref() == Some(&fingerprint) {
+            unchanged.push(id);
+        } else {
+            to_check.push((id, source, fingerprint));
+        }
+    }
+    let total = to_check.len() + unchanged.len();
+    if total == 0 {
+        println!("No exercises started yet; pick one from `exercise list` and `exercise start` it");
+        return Ok(());
+    }
+    for id in &unchanged {
+        println!("  solved   {} (unchanged since it passed)", id);
+    }
+    if !to_check.is_empty() {
+        toolchain::require("checking exercises")?;
+        println!("Checking {} exercise(s)...", to_check.len());
+    }
+
+    let mut unsolved = 0;
+    let mut failure = None;
+    pool::run(
+        &to_check,
+        |(_, source, _)| harness::test(source),
+        |(id, _, fingerprint), run| match run.map(|run| problem(&run)) {
+            Ok(None) => {
+                println!("  solved   {}", id);
+                if let Some((_, exercise)) = exercises::find(id) {
+                    xapi::passed_exercise(id, exercise.title);
+                }
+                let passed = Some(fingerprint.clone());
+                if let Err(e) = progress.update(id, |entry| {
+                    entry.solved = true;
+                    entry.passed = passed;
+                }) {
+                    failure.get_or_insert(e);
+                }
+            }
+            Ok(Some(problem)) => {
+                unsolved += 1;
+                println!("  not yet  {}: {}", id, problem);
+            }
+            Err(e) => {
+                unsolved += 1;
+                println!("  error    {}: {}", id, e);
+            }
+        },
+    );
+    if let Some(e) = failure {
+        return Err(e);
+    }
+
+    println!("\n{} of {} solved", total - unsolved, total);
+    match unsolved {
+        0 => Ok(()),
+        n => Err(format!("{} exercise(s) not solved yet; `exercise check <exercise>` shows the details", n)),
+    }
+}
+
+// What's still wrong with a checked exercise, in one line, or None if it's solved
+fn problem(run: &harness::TestRun) -> Option<String> {
+    if !run.build.success {
+        Some(format!("doesn't compile ({})", run.build.error_codes.join(", ")))
+    } else if let Some(reason) = &run.stopped {
+        Some(format!("the tests were stopped because {}", reason))
+    } else if !run.passed {
+        Some("compiles, but the tests fail".to_string())
+    } else {
+        None
+    }
+}
+
+fn hint(id: &str) -> Result<(), String> {
+    let (pack, exercise) = find_exercise(id)?;
+    let id = exercise.id(pack);
+    let mut progress = load_progress()?;
+
+    let shown = progress.entry(&id).hints;
+    let entry = progress.update(&id, |entry| entry.hints = (shown + 1).min(exercise.hints.len()))?;
+    for (i, hint) in exercise.hints.iter().take(entry.hints).enumerate() {
+        let hint = translated(&id, &format!("hint{}", i + 1), hint);
+        println!("Hint {}/{}: {}", i + 1, exercise.hints.len(), hint);
+    }
+    if shown >= exercise.hints.len() {
+        println!("\nThat's every hint. `exercise solution {}` shows a fix.", id);
+    }
+    Ok(())
+}
+
+fn solution(id: &str) -> Result<(), String> {
+    let (pack, exercise) = find_exercise(id)?;
+    let id = exercise.id(pack);
+    load_progress()?.update(&id, |entry| entry.saw_solution = true)?;
+
+    println!("A fix for {} ({}):\n", id, translated(&id, "title", exercise.title));
+    print!("{}", exercise.solution);
+    Ok(())
+}
+
+// An exercise's title, task or hint (e.g. "hint2") in the learner's language
+fn translated(id: &str, field: &str, english: &'static str) -> &'static str {
+    i18n::text(&format!("{}.{}", id, field), english)
+}
+
+fn find_exercise(id: &str) -> Result<(&'static Pack, &'static Exercise), String> {
+    exercises::find(id).ok_or_else(|| format!("no exercise named '{}' (see `exercise list`)", id))
+}
+
+fn working_file(id: &str) -> PathBuf {
+    Path::new(PRACTICE_DIR).join(format!("{}.rs", id))
+}
+
+// Loads progress, first moving the working files of renamed exercises to
+// their new names (progress/mod.rs migrates the entries)
+fn load_progress() -> Result<Progress, String> {
+    for (old, _) in exercises::ALIASES {
+        let (from, to) = (working_file(old), working_file(aliases::resolve(exercises::ALIASES, old)));
+        if from.exists() && !to.exists() {
+            fs::rename(&from, &to).map_err(|e| format!("could not rename {}: {}", from.display(), e))?;
+        }
+    }
+    let storage = progress::open(&Path::new(PRACTICE_DIR).join("progress.txt"));
+    Progress::load(storage, &progress::learner(), exercises::ALIASES)
+}
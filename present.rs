@@ -0,0 +1,126 @@
+// `present <example>`: walks through an example's code for a projector, one
+// step per Enter. The current line is highlighted, the ones already covered
+// stay visible, and the rest are dimmed until they're reached, so an
+// instructor can talk through exactly the line everyone is looking at.
+//
+// An example that declares stops (see Stop in lessons/mod.rs) is walked
+// through by its narration: each step reveals a bullet under the code,
+// alongside the line it's about, and the bullets so far stay up. Every
+// lesson should (lint-content reports any that don't); one without stops
+// yet stops on every line, skipping blank lines, comments and lines that
+// are only braces, since there's nothing on them to point at.
+//
+// A deep link like ownership-demo://ownership/transfer#3 starts at the third
+// step, and each step shows its own link for bookmarking (see links.rs).
+
+use std::io::{self, BufRead, Write};
+
+use crate::lessons::{Example, Stop};
+use crate::links;
+
+const CLEAR: &str = "\x1b[2J\x1b[H";
+const HIGHLIGHT: &str = "\x1b[7m";
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+pub fn command(args: &[String]) -> Result<(), String> {
+    let address = args.first().ok_or("present needs an example id (see `list`)")?;
+    let target = links::resolve(address)?;
+    let example = target.example;
+    let lines: Vec<&str> = example.code.lines().collect();
+    let stops = steps(example);
+    let first = target.step.map_or(0, |step| step - 1);
+
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    for step in first..stops.len() {
+        draw(example.title, &lines, &stops, step);
+        let link = links::link(target.section, example, Some(step + 1));
+        print!("\n  [{}/{}] {}  Enter: next step, q: stop ", step + 1, stops.len(), link);
+        io::stdout().flush().map_err(|e| format!("could not write to the terminal: {}", e))?;
+
+        let mut answer = String::new();
+        let read = input.read_line(&mut answer).map_err(|e| format!("could not read the keyboard: {}", e))?;
+        if read == 0 || answer.trim() == "q" {
+            break;
+        }
+    }
+    println!();
+    Ok(())
+}
+
+// A step of a presentation: a line of the code, by index, and the narration
+// bullet that goes with it, if the example declares stops
+pub struct Step {
+    pub line: usize,
+    pub say: Option<&'static str>,
+}
+
+// The steps presenting an example goes through
+pub fn steps(example: &Example) -> Vec<Step> {
+    let lines: Vec<&str> = example.code.lines().collect();
+    if example.stops.is_empty() {
+        return (0..lines.len()).filter(|&i| worth_a_stop(lines[i])).map(|line| Step { line, say: None }).collect();
+    }
+    place(example, &lines).0
+}
+
+// The example's stops whose line can't be found in its code, in order after
+// the stop before (lint-content reports them; presenting skips them)
+pub fn misplaced(example: &Example) -> Vec<&'static Stop> {
+    let lines: Vec<&str> = example.code.lines().collect();
+    place(example, &lines).1
+}
+
+fn place(example: &Example, lines: &[&str]) -> (Vec<Step>, Vec<&'static Stop>) {
+    let mut steps = Vec::new();
+    let mut missing = Vec::new();
+    let mut from = 0;
+    for stop in example.stops {
+        // From the previous stop's line, not after it, so one line can have two bullets
+        match (from..lines.len()).find(|&i| lines[i].trim_start().starts_with(stop.line)) {
+            Some(line) => {
+                steps.push(Step { line, say: Some(stop.say) });
+                from = line;
+            }
+            None => missing.push(stop),
+        }
+    }
+    (steps, missing)
+}
+
+// Clears the screen and shows the code at step `step`: its line highlighted,
+// and under the code, the narration so far with the current bullet highlighted
+pub fn draw(title: &str, lines: &[&str], steps: &[Step], step: usize) {
+    let current = steps[step].line;
+    print!("{}", CLEAR);
+    println!("{}\n", title);
+    for (i, line) in lines.iter().enumerate() {
+        let number = format!("{:>3} ", i + 1);
+        if i == current {
+            println!("{}{}{}{}", HIGHLIGHT, number, line, RESET);
+        } else if i < current {
+            println!("{}{}", number, line);
+        } else {
+            println!("{}{}{}{}", DIM, number, line, RESET);
+        }
+    }
+
+    let said: Vec<&str> = steps[..=step].iter().filter_map(|step| step.say).collect();
+    if let Some((last, earlier)) = said.split_last() {
+        println!();
+        for bullet in earlier {
+            println!("  - {}", bullet);
+        }
+        if steps[step].say.is_some() {
+            println!("{}  - {}{}", HIGHLIGHT, last, RESET);
+        } else {
+            println!("  - {}", last);
+        }
+    }
+}
+
+fn worth_a_stop(line: &str) -> bool {
+    let line = line.trim();
+    !line.is_empty() && !line.starts_with("//") && !line.chars().all(|c| "{}();,".contains(c))
+}
@@ -0,0 +1,231 @@
+// Progress files in the format described in mod.rs.
+//
+// Saving writes a temporary file and renames it over the old one, so a crash
+// or a full disk leaves either the old progress or the new, never a mix. The
+// version being replaced is kept next to it as <file>.bak. A file that's
+// damaged anyway (a bad disk, an editor accident) isn't thrown away: loading
+// it offers to restore the backup or to keep the entries that can still be
+// read, and moves the damaged file aside rather than deleting it.
+
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{self, BufRead, ErrorKind, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+use super::{Entry, Storage};
+
+pub struct Files {
+    location: Location,
+}
+
+enum Location {
+    // One file, whoever the learner is
+    Single(PathBuf),
+    // A directory with a file per learner
+    PerLearner(PathBuf),
+}
+
+impl Files {
+    pub fn single(path: &Path) -> Files {
+        Files { location: Location::Single(path.to_path_buf()) }
+    }
+
+    pub fn per_learner(dir: &Path) -> Files {
+        Files { location: Location::PerLearner(dir.to_path_buf()) }
+    }
+
+    fn path(&self, learner: &str) -> PathBuf {
+        match &self.location {
+            Location::Single(path) => path.clone(),
+            Location::PerLearner(dir) => {
+                // Learner names come from the environment, so keep them to one path component
+                let name: String = learner
+                    .chars()
+                    .map(|c| if c.is_alphanumeric() || "@._-".contains(c) { c } else { '_' })
+                    .collect();
+                dir.join(format!("{}.txt", name.trim_start_matches('.')))
+            }
+        }
+    }
+}
+
+impl Storage for Files {
+    fn load(&self, learner: &str) -> Result<BTreeMap<String, Entry>, String> {
+        let path = self.path(learner);
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(BTreeMap::new()),
+            Err(e) => return Err(format!("could not read {}: {}", path.display(), e)),
+        };
+        parse(&bytes).or_else(|damage| recover(&path, damage))
+    }
+
+    fn save(&self, learner: &str, entries: &BTreeMap<String, Entry>) -> Result<(), String> {
+        let path = self.path(learner);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("could not create {}: {}", dir.display(), e))?;
+        }
+        // A damaged file never replaces a good backup
+        if let Some(previous) = fs::read(&path).ok().filter(|previous| parse(previous).is_ok()) {
+            write_atomically(&sibling(&path, ".bak"), &previous)?;
+        }
+        write_atomically(&path, format(entries).as_bytes())
+    }
+}
+
+// What's wrong with a progress file, and what could still be read from it
+struct Damage {
+    problem: String,
+    readable: BTreeMap<String, Entry>,
+}
+
+fn parse(bytes: &[u8]) -> Result<BTreeMap<String, Entry>, Damage> {
+    let Ok(text) = std::str::from_utf8(bytes) else {
+        let text = String::from_utf8_lossy(bytes);
+        let readable = text.lines().filter_map(parse_line).collect();
+        return Err(Damage { problem: "it isn't plain text any more".to_string(), readable });
+    };
+    let mut entries = BTreeMap::new();
+    let mut bad = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match parse_line(line) {
+            Some((id, entry)) => {
+                entries.insert(id, entry);
+            }
+            None => bad.push(number + 1),
+        }
+    }
+    match bad[..] {
+        [] => Ok(entries),
+        [line] => Err(Damage { problem: format!("line {} isn't a progress entry", line), readable: entries }),
+        [first, ..] => Err(Damage {
+            problem: format!("{} lines, from line {}, aren't progress entries", bad.len(), first),
+            readable: entries,
+        }),
+    }
+}
+
+// One line, or None if it isn't one this or a newer build could have written
+fn parse_line(line: &str) -> Option<(String, Entry)> {
+    let mut fields = line.split_whitespace();
+    let id = fields.next()?;
+    if !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+    let mut entry = Entry::default();
+    for field in fields {
+        let (name, value) = field.split_once('=')?;
+        match name {
+            "solved" => entry.solved = yes_no(value)?,
+            "hints" => entry.hints = value.parse().ok()?,
+            "solution" => entry.saw_solution = yes_no(value)?,
+            "passed" if !value.is_empty() && value.chars().all(|c| c.is_ascii_hexdigit()) => entry.passed = Some(value.to_string()),
+            // Unknown fields are ignored, so older builds can read newer files
+            _ if !name.is_empty() && name.chars().all(|c| c.is_ascii_lowercase() || c == '_') => {}
+            _ => return None,
+        }
+    }
+    Some((id.to_string(), entry))
+}
+
+fn yes_no(value: &str) -> Option<bool> {
+    match value {
+        "yes" => Some(true),
+        "no" => Some(false),
+        _ => None,
+    }
+}
+
+fn format(entries: &BTreeMap<String, Entry>) -> String {
+    let yes_no = |flag: bool| if flag { "yes" } else { "no" };
+    entries
+        .iter()
+        .map(|(id, entry)| {
+            let mut line = format!(
+                "{} solved={} hints={} solution={}",
+                id,
+                yes_no(entry.solved),
+                entry.hints,
+                yes_no(entry.saw_solution)
+            );
+            if let Some(passed) = &entry.passed {
+                line.push_str(&format!(" passed={}", passed));
+            }
+            line + "\n"
+        })
+        .collect()
+}
+
+// Asks what to do about a damaged file. Without a terminal to ask on,
+// nothing is touched and the error says how to recover by hand
+fn recover(path: &Path, damage: Damage) -> Result<BTreeMap<String, Entry>, String> {
+    let backup = sibling(path, ".bak");
+    let restorable = fs::read(&backup).ok().and_then(|bytes| parse(&bytes).ok());
+    if !io::stdin().is_terminal() {
+        let by_hand = match restorable {
+            Some(_) => format!(", or copy {} over it", backup.display()),
+            None => String::new(),
+        };
+        return Err(format!(
+            "{} is damaged: {}. Nothing has been changed; run this again in a terminal to recover it{}",
+            path.display(),
+            damage.problem,
+            by_hand
+        ));
+    }
+
+    let summary = |entries: &BTreeMap<String, Entry>| {
+        let solved = entries.values().filter(|entry| entry.solved).count();
+        format!("{} started, {} solved", entries.len(), solved)
+    };
+    eprintln!("{} is damaged: {}.", path.display(), damage.problem);
+    let mut choices = Vec::new();
+    if let Some(entries) = restorable {
+        choices.push((format!("Restore the backup from the save before ({})", summary(&entries)), entries));
+    }
+    choices.push((format!("Keep what can still be read ({})", summary(&damage.readable)), damage.readable));
+    for (i, (description, _)) in choices.iter().enumerate() {
+        println!("  {}. {}", i + 1, description);
+    }
+    println!("  {}. Stop, and leave the file as it is", choices.len() + 1);
+    print!("Choose 1-{}: ", choices.len() + 1);
+    io::stdout().flush().map_err(|e| format!("could not write to the terminal: {}", e))?;
+
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer).map_err(|e| format!("could not read the keyboard: {}", e))?;
+    let Some(index) = answer.trim().parse::<usize>().ok().filter(|n| (1..=choices.len()).contains(n)) else {
+        return Err(format!("left {} as it was", path.display()));
+    };
+    let (_, entries) = choices.swap_remove(index - 1);
+
+    let damaged = sibling(path, ".damaged");
+    fs::rename(path, &damaged).map_err(|e| format!("could not move {} aside: {}", path.display(), e))?;
+    write_atomically(path, format(&entries).as_bytes())?;
+    println!("Recovered {}; the damaged file is kept as {}", path.display(), damaged.display());
+    Ok(entries)
+}
+
+// Writes `bytes` to a temporary file next to `path`, flushes it to disk and
+// renames it into place, so `path` is only ever the old file or the new one
+fn write_atomically(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    let temporary = sibling(path, &format!(".{}.tmp", process::id()));
+    let written = File::create(&temporary)
+        .and_then(|mut file| file.write_all(bytes).and_then(|()| file.sync_all()))
+        .and_then(|()| fs::rename(&temporary, path));
+    written.map_err(|e| {
+        let _ = fs::remove_file(&temporary);
+        format!("could not write {}: {}", path.display(), e)
+    })
+}
+
+// `path` with `suffix` added to its file name, e.g. progress.txt.bak
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(suffix);
+    PathBuf::from(name)
+}
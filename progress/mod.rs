@@ -0,0 +1,130 @@
+// What a learner has done with each exercise, so it survives between runs.
+// By default it's kept in practice/progress.txt, which has one line per
+// exercise, e.g.
+//
+//   borrowck_use_after_move solved=yes hints=1 solution=no passed=af63bd4c8601b7df
+//
+// and is small and plain enough to edit (or delete) by hand (see files.rs
+// for how it's kept safe from crashes). Entries for exercises that were
+// renamed are moved to the new id as they're loaded.
+//
+// Where progress is kept is up to a Storage backend, so a classroom server
+// or a web deployment can keep many learners' progress in one place.
+// RUST_OWNERSHIP_DEMO_PROGRESS picks it:
+//
+//   (unset)            practice/progress.txt, for whoever is at this machine
+//   sqlite:<file>      one table in a SQLite database, keyed by learner
+//   <directory>        a progress file per learner, <directory>/<learner>.txt
+//
+// Anything else that can store and return a learner's entries can be added
+// by implementing Storage and giving it a form of the variable in `open`.
+
+mod files;
+mod sqlite;
+
+use std::collections::BTreeMap;
+use std::env;
+use std::path::Path;
+
+use crate::aliases;
+
+use files::Files;
+use sqlite::Sqlite;
+
+const STORAGE_VAR: &str = "RUST_OWNERSHIP_DEMO_PROGRESS";
+const LEARNER_VAR: &str = "RUST_OWNERSHIP_DEMO_LEARNER";
+
+#[derive(Clone, Default)]
+pub struct Entry {
+    // Whether a check of the learner's code has passed
+    pub solved: bool,
+    // How many hints have been revealed
+    pub hints: usize,
+    // Whether the canonical solution has been shown
+    pub saw_solution: bool,
+    // Fingerprint of the code (with its tests) that last passed a check, so
+    // an unchanged file doesn't have to be built again
+    pub passed: Option<String>,
+}
+
+impl Entry {
+    // Combines the progress recorded under an old id with this one, keeping
+    // whatever is furthest along
+    fn merge(&mut self, other: Entry) {
+        self.solved |= other.solved;
+        self.hints = self.hints.max(other.hints);
+        self.saw_solution |= other.saw_solution;
+        if self.passed.is_none() {
+            self.passed = other.passed;
+        }
+    }
+}
+
+// Where learners' progress is kept. Every call names the learner; a backend
+// that only ever holds one learner's progress may ignore it
+pub trait Storage {
+    // The learner's entries by exercise id, empty if they have none yet
+    fn load(&self, learner: &str) -> Result<BTreeMap<String, Entry>, String>;
+    // Replaces everything stored for the learner with `entries`
+    fn save(&self, learner: &str, entries: &BTreeMap<String, Entry>) -> Result<(), String>;
+}
+
+// The backend RUST_OWNERSHIP_DEMO_PROGRESS asks for; `local` is the file
+// used when it's unset
+pub fn open(local: &Path) -> Box<dyn Storage> {
+    match env::var(STORAGE_VAR) {
+        Ok(value) if value.starts_with("sqlite:") => Box::new(Sqlite::new(&value["sqlite:".len()..])),
+        Ok(value) if !value.is_empty() => Box::new(Files::per_learner(Path::new(&value))),
+        _ => Box::new(Files::single(local)),
+    }
+}
+
+// Who is learning: RUST_OWNERSHIP_DEMO_LEARNER, or else the login name
+pub fn learner() -> String {
+    env::var(LEARNER_VAR)
+        .or_else(|_| env::var("USER"))
+        .or_else(|_| env::var("USERNAME"))
+        .unwrap_or_else(|_| "learner".to_string())
+}
+
+pub struct Progress {
+    storage: Box<dyn Storage>,
+    learner: String,
+    entries: BTreeMap<String, Entry>,
+}
+
+impl Progress {
+    // Reads the learner's progress, or starts empty if there isn't any yet.
+    // Ids in `renamed` are migrated, and saved back if any were found
+    pub fn load(storage: Box<dyn Storage>, learner: &str, renamed: &[(&str, &str)]) -> Result<Progress, String> {
+        let entries = storage.load(learner)?;
+        let mut progress = Progress { storage, learner: learner.to_string(), entries };
+        let old: Vec<String> = progress.entries.keys().filter(|id| aliases::resolve(renamed, id) != *id).cloned().collect();
+        for id in &old {
+            let entry = progress.entries.remove(id).unwrap_or_default();
+            let current = progress.entries.entry(aliases::resolve(renamed, id).to_string()).or_default();
+            current.merge(entry);
+        }
+        if !old.is_empty() {
+            progress.save()?;
+        }
+        Ok(progress)
+    }
+
+    pub fn entry(&self, id: &str) -> Entry {
+        self.entries.get(id).cloned().unwrap_or_default()
+    }
+
+    // Changes the entry for `id` and saves the learner's progress
+    pub fn update(&mut self, id: &str, change: impl FnOnce(&mut Entry)) -> Result<Entry, String> {
+        let entry = self.entries.entry(id.to_string()).or_default();
+        change(entry);
+        let entry = entry.clone();
+        self.save()?;
+        Ok(entry)
+    }
+
+    fn save(&self) -> Result<(), String> {
+        self.storage.save(&self.learner, &self.entries)
+    }
+}
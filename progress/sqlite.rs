@@ -0,0 +1,102 @@
+// Progress in a SQLite database, one row per learner and exercise, for
+// servers that keep everyone's progress together. It goes through the
+// sqlite3 command-line shell rather than a library, so the program itself
+// stays free of dependencies; the database needs sqlite3 on the PATH.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use super::{Entry, Storage};
+
+const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS progress (
+    learner TEXT NOT NULL,
+    exercise TEXT NOT NULL,
+    solved INTEGER NOT NULL,
+    hints INTEGER NOT NULL,
+    solution INTEGER NOT NULL,
+    passed TEXT,
+    PRIMARY KEY (learner, exercise)
+);";
+
+// How long to wait for another process's write to finish, so learners
+// checking exercises at the same moment don't fail each other
+const BUSY_TIMEOUT_MS: u32 = 5000;
+
+pub struct Sqlite {
+    path: PathBuf,
+}
+
+impl Sqlite {
+    pub fn new(path: &str) -> Sqlite {
+        Sqlite { path: PathBuf::from(path) }
+    }
+
+    // Runs a script in the sqlite3 shell and returns what it printed
+    fn run(&self, script: &str) -> Result<String, String> {
+        let mut shell = Command::new("sqlite3")
+            .args(["-batch", "-bail", "-noheader", "-separator", "\t"])
+            .arg(&self.path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|_| "keeping progress in SQLite needs sqlite3 on the PATH".to_string())?;
+        let input = format!(".timeout {}\n{}\n{}\n", BUSY_TIMEOUT_MS, SCHEMA, script);
+        if let Some(mut stdin) = shell.stdin.take() {
+            stdin.write_all(input.as_bytes()).map_err(|e| format!("could not talk to sqlite3: {}", e))?;
+        }
+        let output = shell.wait_with_output().map_err(|e| format!("could not talk to sqlite3: {}", e))?;
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("sqlite3 failed on {}: {}", self.path.display(), error.trim()));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+impl Storage for Sqlite {
+    fn load(&self, learner: &str) -> Result<BTreeMap<String, Entry>, String> {
+        let rows = self.run(&format!(
+            "SELECT exercise, solved, hints, solution, ifnull(passed, '') FROM progress WHERE learner = {};",
+            quote(learner)
+        ))?;
+        let mut entries = BTreeMap::new();
+        for row in rows.lines() {
+            let fields: Vec<&str> = row.split('\t').collect();
+            let [exercise, solved, hints, solution, passed] = fields[..] else { continue };
+            let entry = Entry {
+                solved: solved == "1",
+                hints: hints.parse().unwrap_or(0),
+                saw_solution: solution == "1",
+                passed: Some(passed.to_string()).filter(|passed| !passed.is_empty()),
+            };
+            entries.insert(exercise.to_string(), entry);
+        }
+        Ok(entries)
+    }
+
+    fn save(&self, learner: &str, entries: &BTreeMap<String, Entry>) -> Result<(), String> {
+        // One transaction, so the learner's rows are never seen half replaced
+        let mut script = format!("BEGIN IMMEDIATE;\nDELETE FROM progress WHERE learner = {};\n", quote(learner));
+        for (exercise, entry) in entries {
+            script.push_str(&format!(
+                "INSERT INTO progress VALUES ({}, {}, {}, {}, {}, {});\n",
+                quote(learner),
+                quote(exercise),
+                u8::from(entry.solved),
+                entry.hints,
+                u8::from(entry.saw_solution),
+                entry.passed.as_deref().map_or("NULL".to_string(), quote)
+            ));
+        }
+        script.push_str("COMMIT;");
+        self.run(&script).map(|_| ())
+    }
+}
+
+// A SQL string literal: single quotes doubled
+fn quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
@@ -0,0 +1,521 @@
+// A small QR Code encoder, just enough to show a Playground link on screen.
+//
+// It only supports byte mode at error correction level L (the highest
+// capacity, up to 2953 bytes in a version 40 symbol), which is all a URL
+// needs. The construction follows the QR Code specification (ISO/IEC 18004):
+// pick the smallest version that fits, add Reed-Solomon error correction,
+// draw the fixed patterns, lay the codewords out in the zigzag order and
+// apply the mask with the lowest penalty score.
+
+// Error correction codewords per block at level L, indexed by version
+const ECC_CODEWORDS_PER_BLOCK: [usize; 41] = [
+    0, 7, 10, 15, 20, 26, 18, 20, 24, 30, 18, 20, 24, 26, 30, 22, 24, 28, 30, 28, 28, 28, 28, 30,
+    30, 26, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+];
+
+// Number of error correction blocks at level L, indexed by version
+const NUM_BLOCKS: [usize; 41] = [
+    0, 1, 1, 1, 1, 1, 2, 2, 2, 2, 4, 4, 4, 4, 4, 6, 6, 6, 6, 7, 8, 8, 9, 9, 10, 12, 12, 12, 13, 14,
+    15, 16, 17, 18, 19, 19, 20, 21, 22, 24, 25,
+];
+
+// The two format bits that identify error correction level L
+const LEVEL_L_FORMAT_BITS: u32 = 1;
+
+pub struct QrCode {
+    size: usize,
+    // Row-major: modules[y * size + x] is true for a dark module
+    modules: Vec<bool>,
+    // Marks the finder, timing, alignment, format and version patterns,
+    // which are never overwritten by data or flipped by the mask
+    is_function: Vec<bool>,
+}
+
+impl QrCode {
+    // Encodes the bytes in the smallest symbol that can hold them
+    pub fn encode(data: &[u8]) -> Result<QrCode, String> {
+        let version = (1..=40)
+            .find(|&v| data_bits_needed(v, data.len()) <= num_data_codewords(v) * 8)
+            .ok_or_else(|| format!("{} bytes is too much data for a QR code (the limit is 2953)", data.len()))?;
+
+        let codewords = add_ecc_and_interleave(version, &data_codewords(version, data));
+
+        let size = version * 4 + 17;
+        let mut qr = QrCode {
+            size,
+            modules: vec![false; size * size],
+            is_function: vec![false; size * size],
+        };
+        qr.draw_function_patterns(version);
+        qr.draw_codewords(&codewords);
+
+        // Try every mask and keep the one that scores best
+        let mut best = (u32::MAX, 0);
+        for mask in 0..8 {
+            qr.apply_mask(mask);
+            qr.draw_format_bits(mask);
+            let penalty = qr.penalty_score();
+            if penalty < best.0 {
+                best = (penalty, mask);
+            }
+            qr.apply_mask(mask); // masking is an XOR, so this undoes it
+        }
+        qr.apply_mask(best.1);
+        qr.draw_format_bits(best.1);
+
+        Ok(qr)
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    // Whether the module at column x, row y is dark
+    pub fn module(&self, x: usize, y: usize) -> bool {
+        self.modules[y * self.size + x]
+    }
+
+    // Renders the code with half-block characters so each line of text holds
+    // two rows of modules, surrounded by the quiet zone scanners need. The
+    // colors are set explicitly so it scans on light and dark terminal themes.
+    pub fn to_terminal_string(&self) -> String {
+        const QUIET_ZONE: usize = 4;
+
+        let size = self.size() as isize;
+        let border = QUIET_ZONE as isize;
+        let dark = |x: isize, y: isize| (0..size).contains(&x) && (0..size).contains(&y) && self.module(x as usize, y as usize);
+
+        let mut out = String::new();
+        for y in (-border..size + border).step_by(2) {
+            out.push_str("\x1b[30;107m");
+            for x in -border..size + border {
+                out.push(match (dark(x, y), dark(x, y + 1)) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                });
+            }
+            out.push_str("\x1b[0m\n");
+        }
+        out
+    }
+
+    fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+        self.modules[y * self.size + x] = dark;
+        self.is_function[y * self.size + x] = true;
+    }
+
+    fn draw_function_patterns(&mut self, version: usize) {
+        let size = self.size;
+
+        // Timing patterns
+        for i in 0..size {
+            self.set_function(6, i, i % 2 == 0);
+            self.set_function(i, 6, i % 2 == 0);
+        }
+
+        // Finder patterns (and their separators) in three corners
+        for (cx, cy) in [(3, 3), (size - 4, 3), (3, size - 4)] {
+            for dy in -4isize..=4 {
+                for dx in -4isize..=4 {
+                    let (x, y) = (cx as isize + dx, cy as isize + dy);
+                    if (0..size as isize).contains(&x) && (0..size as isize).contains(&y) {
+                        let distance = dx.abs().max(dy.abs());
+                        self.set_function(x as usize, y as usize, distance != 2 && distance != 4);
+                    }
+                }
+            }
+        }
+
+        // Alignment patterns, except where they would overlap the finders
+        let positions = alignment_positions(version);
+        let last = positions.len().saturating_sub(1);
+        let at_edge = |k: usize| k == 0 || k == last;
+        for (i, &cx) in positions.iter().enumerate() {
+            for (j, &cy) in positions.iter().enumerate() {
+                if (i == 0 && at_edge(j)) || (j == 0 && at_edge(i)) {
+                    continue;
+                }
+                for dy in -2isize..=2 {
+                    for dx in -2isize..=2 {
+                        let distance = dx.abs().max(dy.abs());
+                        self.set_function((cx as isize + dx) as usize, (cy as isize + dy) as usize, distance != 1);
+                    }
+                }
+            }
+        }
+
+        // Reserve the format areas now; the real bits are drawn once the mask is chosen
+        self.draw_format_bits(0);
+
+        // Version information, only present from version 7 up
+        if version >= 7 {
+            let mut remainder = version as u32;
+            for _ in 0..12 {
+                remainder = (remainder << 1) ^ ((remainder >> 11) * 0x1F25);
+            }
+            let bits = (version as u32) << 12 | remainder;
+            for i in 0..18 {
+                let dark = (bits >> i) & 1 != 0;
+                let a = size - 11 + i % 3;
+                let b = i / 3;
+                self.set_function(a, b, dark);
+                self.set_function(b, a, dark);
+            }
+        }
+    }
+
+    fn draw_format_bits(&mut self, mask: u32) {
+        let data = LEVEL_L_FORMAT_BITS << 3 | mask;
+        let mut remainder = data;
+        for _ in 0..10 {
+            remainder = (remainder << 1) ^ ((remainder >> 9) * 0x537);
+        }
+        let bits = (data << 10 | remainder) ^ 0x5412;
+        let bit = |i: usize| (bits >> i) & 1 != 0;
+        let size = self.size;
+
+        // First copy, around the top-left finder
+        for i in 0..6 {
+            self.set_function(8, i, bit(i));
+        }
+        self.set_function(8, 7, bit(6));
+        self.set_function(8, 8, bit(7));
+        self.set_function(7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function(14 - i, 8, bit(i));
+        }
+
+        // Second copy, split between the other two finders
+        for i in 0..8 {
+            self.set_function(size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function(8, size - 15 + i, bit(i));
+        }
+        self.set_function(8, size - 8, true); // always dark
+    }
+
+    // Places the codewords in the two-column zigzag, skipping function modules
+    fn draw_codewords(&mut self, codewords: &[u8]) {
+        let size = self.size;
+        let total_bits = codewords.len() * 8;
+        let mut i = 0;
+
+        let mut right = size - 1;
+        loop {
+            if right == 6 {
+                right = 5; // the vertical timing pattern takes a whole column
+            }
+            let upward = (right + 1) & 2 == 0;
+            for vertical in 0..size {
+                let y = if upward { size - 1 - vertical } else { vertical };
+                for x in [right, right - 1] {
+                    if !self.is_function[y * size + x] && i < total_bits {
+                        self.modules[y * size + x] = (codewords[i / 8] >> (7 - i % 8)) & 1 != 0;
+                        i += 1;
+                    }
+                    // Any remainder bits stay light, as the specification requires
+                }
+            }
+            if right < 2 {
+                break;
+            }
+            right -= 2;
+        }
+    }
+
+    fn apply_mask(&mut self, mask: u32) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let flip = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (x / 3 + y / 2) % 2 == 0,
+                    5 => x * y % 2 + x * y % 3 == 0,
+                    6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+                };
+                if flip && !self.is_function[y * self.size + x] {
+                    self.modules[y * self.size + x] ^= true;
+                }
+            }
+        }
+    }
+
+    // The specification's four penalty rules; lower scores scan more reliably
+    fn penalty_score(&self) -> u32 {
+        let size = self.size;
+        let mut score = 0;
+
+        // Rule 1: runs of five or more same-colored modules in a row or column
+        // Rule 3: patterns that look like a finder (1:1:3:1:1 with light space)
+        const FINDER_LIKE: [bool; 11] = [true, false, true, true, true, false, true, false, false, false, false];
+        for horizontal in [true, false] {
+            for a in 0..size {
+                let line: Vec<bool> = (0..size)
+                    .map(|b| if horizontal { self.module(b, a) } else { self.module(a, b) })
+                    .collect();
+
+                let mut run = 1;
+                for b in 1..=size {
+                    if b < size && line[b] == line[b - 1] {
+                        run += 1;
+                    } else {
+                        if run >= 5 {
+                            score += 3 + (run - 5) as u32;
+                        }
+                        run = 1;
+                    }
+                }
+
+                for window in line.windows(FINDER_LIKE.len()) {
+                    if window.iter().eq(FINDER_LIKE.iter()) || window.iter().eq(FINDER_LIKE.iter().rev()) {
+                        score += 40;
+                    }
+                }
+            }
+        }
+
+        // Rule 2: 2x2 blocks of the same color
+        for y in 0..size - 1 {
+            for x in 0..size - 1 {
+                let color = self.module(x, y);
+                if color == self.module(x + 1, y) && color == self.module(x, y + 1) && color == self.module(x + 1, y + 1) {
+                    score += 3;
+                }
+            }
+        }
+
+        // Rule 4: how far the proportion of dark modules strays from 50%
+        let dark = self.modules.iter().filter(|&&m| m).count();
+        let total = size * size;
+        let deviation = (dark * 20).abs_diff(total * 10).div_ceil(total);
+        score += (deviation.saturating_sub(1) * 10) as u32;
+
+        score
+    }
+}
+
+// Modules available for data and error correction once every function
+// pattern has been drawn (including the unused remainder bits)
+fn num_raw_data_modules(version: usize) -> usize {
+    let mut result = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let num_align = version / 7 + 2;
+        result -= (25 * num_align - 10) * num_align - 55;
+        if version >= 7 {
+            result -= 36;
+        }
+    }
+    result
+}
+
+fn num_data_codewords(version: usize) -> usize {
+    num_raw_data_modules(version) / 8 - ECC_CODEWORDS_PER_BLOCK[version] * NUM_BLOCKS[version]
+}
+
+// Mode indicator, character count and the data itself, in bits
+fn data_bits_needed(version: usize, len: usize) -> usize {
+    let count_bits = if version <= 9 { 8 } else { 16 };
+    if len >= 1 << count_bits {
+        return usize::MAX;
+    }
+    4 + count_bits + len * 8
+}
+
+// The byte-mode segment, terminated and padded to fill the data capacity
+fn data_codewords(version: usize, data: &[u8]) -> Vec<u8> {
+    let capacity_bits = num_data_codewords(version) * 8;
+    let count_bits = if version <= 9 { 8 } else { 16 };
+
+    let mut bits: Vec<bool> = Vec::with_capacity(capacity_bits);
+    let mut push = |value: u32, len: usize| {
+        for i in (0..len).rev() {
+            bits.push((value >> i) & 1 != 0);
+        }
+    };
+    push(0b0100, 4); // byte mode
+    push(data.len() as u32, count_bits);
+    for &byte in data {
+        push(u32::from(byte), 8);
+    }
+
+    // Terminator of up to four zero bits, then zero bits up to a byte boundary
+    let terminator = (capacity_bits - bits.len()).min(4);
+    bits.extend(std::iter::repeat_n(false, terminator));
+    bits.extend(std::iter::repeat_n(false, (8 - bits.len() % 8) % 8));
+
+    let mut codewords: Vec<u8> = bits
+        .chunks(8)
+        .map(|byte| byte.iter().fold(0, |acc, &bit| acc << 1 | u8::from(bit)))
+        .collect();
+
+    // Alternate pad bytes fill whatever capacity is left
+    for pad in [0xEC, 0x11].into_iter().cycle() {
+        if codewords.len() * 8 >= capacity_bits {
+            break;
+        }
+        codewords.push(pad);
+    }
+    codewords
+}
+
+// Splits the data into blocks, appends each block's error correction
+// codewords and interleaves the blocks as the specification prescribes
+fn add_ecc_and_interleave(version: usize, data: &[u8]) -> Vec<u8> {
+    let num_blocks = NUM_BLOCKS[version];
+    let ecc_len = ECC_CODEWORDS_PER_BLOCK[version];
+    let raw_codewords = num_raw_data_modules(version) / 8;
+    let num_short_blocks = num_blocks - raw_codewords % num_blocks;
+    let short_block_len = raw_codewords / num_blocks;
+
+    let divisor = reed_solomon_divisor(ecc_len);
+    let mut blocks = Vec::with_capacity(num_blocks);
+    let mut start = 0;
+    for i in 0..num_blocks {
+        let data_len = short_block_len - ecc_len + usize::from(i >= num_short_blocks);
+        let block_data = &data[start..start + data_len];
+        start += data_len;
+
+        // Short blocks get a placeholder byte so every block has the same layout
+        let mut block = block_data.to_vec();
+        block.resize(short_block_len + 1 - ecc_len, 0);
+        block.extend(reed_solomon_remainder(block_data, &divisor));
+        blocks.push(block);
+    }
+
+    let mut result = Vec::with_capacity(raw_codewords);
+    for i in 0..=short_block_len {
+        for (j, block) in blocks.iter().enumerate() {
+            // Skip the placeholder byte of the short blocks
+            if i != short_block_len - ecc_len || j >= num_short_blocks {
+                result.push(block[i]);
+            }
+        }
+    }
+    result
+}
+
+// Coefficients of the generator polynomial for `degree` error correction codewords
+fn reed_solomon_divisor(degree: usize) -> Vec<u8> {
+    let mut result = vec![0; degree];
+    result[degree - 1] = 1;
+
+    let mut root = 1;
+    for _ in 0..degree {
+        for j in 0..degree {
+            result[j] = gf_multiply(result[j], root);
+            if j + 1 < degree {
+                result[j] ^= result[j + 1];
+            }
+        }
+        root = gf_multiply(root, 0x02);
+    }
+    result
+}
+
+fn reed_solomon_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+    let mut result = vec![0; divisor.len()];
+    for &byte in data {
+        let factor = byte ^ result.remove(0);
+        result.push(0);
+        for (r, &d) in result.iter_mut().zip(divisor) {
+            *r ^= gf_multiply(d, factor);
+        }
+    }
+    result
+}
+
+// Multiplication in GF(2^8) modulo the QR polynomial x^8 + x^4 + x^3 + x^2 + 1
+fn gf_multiply(x: u8, y: u8) -> u8 {
+    let mut z: u32 = 0;
+    for i in (0..8).rev() {
+        z = (z << 1) ^ ((z >> 7) * 0x11D);
+        z ^= ((u32::from(y) >> i) & 1) * u32::from(x);
+    }
+    z as u8
+}
+
+// Centers of the alignment patterns along each axis
+fn alignment_positions(version: usize) -> Vec<usize> {
+    if version == 1 {
+        return Vec::new();
+    }
+    let num_align = version / 7 + 2;
+    let size = version * 4 + 17;
+    let step = if version == 32 {
+        26
+    } else {
+        (version * 4 + num_align * 2 + 1) / (num_align * 2 - 2) * 2
+    };
+
+    let mut positions = vec![6];
+    positions.extend((0..num_align - 1).map(|i| size - 7 - i * step).rev());
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_bits_match_the_specification_table() {
+        // Level L rows of the format information table, masks 0 to 7
+        const LEVEL_L: [u32; 8] = [
+            0b111011111000100,
+            0b111001011110011,
+            0b111110110101010,
+            0b111100010011101,
+            0b110011000101111,
+            0b110001100011000,
+            0b110110001000001,
+            0b110100101110110,
+        ];
+        let mut qr = QrCode::encode(b"x").unwrap();
+        for (mask, expected) in LEVEL_L.into_iter().enumerate() {
+            qr.draw_format_bits(mask as u32);
+            let size = qr.size();
+            let first = [(8, 0), (8, 1), (8, 2), (8, 3), (8, 4), (8, 5), (8, 7), (8, 8), (7, 8), (5, 8), (4, 8), (3, 8), (2, 8), (1, 8), (0, 8)];
+            let second: Vec<_> = (0..8).map(|i| (size - 1 - i, 8)).chain((8..15).map(|i| (8, size - 15 + i))).collect();
+            // Bit i of the format information sits at positions[i]
+            let read = |positions: &[(usize, usize)]| {
+                positions.iter().enumerate().fold(0, |bits, (i, &(x, y))| bits | u32::from(qr.module(x, y)) << i)
+            };
+            assert_eq!(read(&first), expected, "first copy, mask {}", mask);
+            assert_eq!(read(&second), expected, "second copy, mask {}", mask);
+        }
+    }
+
+    #[test]
+    fn error_correction_matches_the_specification_example() {
+        // "01234567" at version 1-M, from the worked example in ISO/IEC 18004
+        let data = [0x10, 0x20, 0x0C, 0x56, 0x61, 0x80, 0xEC, 0x11, 0xEC, 0x11, 0xEC, 0x11, 0xEC, 0x11, 0xEC, 0x11];
+        let ecc = reed_solomon_remainder(&data, &reed_solomon_divisor(10));
+        assert_eq!(ecc, [0xA5, 0x24, 0xD4, 0xC1, 0xED, 0x36, 0xC7, 0x87, 0x2C, 0x55]);
+    }
+
+    #[test]
+    fn byte_mode_data_is_terminated_and_padded() {
+        // 0100 (byte mode), 00000101 (length 5), "hello", 0000 (terminator)
+        let expected = [0x40, 0x56, 0x86, 0x56, 0xC6, 0xC6, 0xF0, 0xEC, 0x11, 0xEC, 0x11, 0xEC, 0x11, 0xEC, 0x11, 0xEC, 0x11, 0xEC, 0x11];
+        assert_eq!(data_codewords(1, b"hello"), expected);
+    }
+
+    #[test]
+    fn picks_the_smallest_version_that_fits() {
+        // Version 1-L holds 17 bytes; one more needs version 2
+        assert_eq!(QrCode::encode(&[b'a'; 17]).unwrap().size(), 21);
+        assert_eq!(QrCode::encode(&[b'a'; 18]).unwrap().size(), 25);
+        assert_eq!(QrCode::encode(&[b'a'; 2953]).unwrap().size(), 177);
+    }
+
+    #[test]
+    fn too_much_data_is_an_error() {
+        let error = QrCode::encode(&[b'a'; 2954]).err().unwrap();
+        assert_eq!(error, "2954 bytes is too much data for a QR code (the limit is 2953)");
+    }
+}
@@ -0,0 +1,184 @@
+// `export-quiz`: the lessons' broken lines as a question pool for an LMS.
+// Every commented-out line already records which error rustc gives for it,
+// so each one becomes a multiple-choice question ("which error does this
+// line cause?"), with rustc's own message from the diagnostics bundle as the
+// feedback. GIFT imports into Moodle; CSV suits most other systems and
+// spreadsheets.
+//
+// Questions are generated, not stored, so the pool follows the lessons as
+// they change. With a curriculum (see curriculum/mod.rs) only its lessons are
+// quizzed, in its order. None of them reference images, so there's nothing
+// to export alongside.
+
+use std::collections::BTreeMap;
+
+use crate::curriculum::{self, Curriculum};
+use crate::diagnostics;
+use crate::lessons;
+use crate::rng;
+
+// How many wrong answers each question offers
+const DISTRACTORS: usize = 3;
+
+pub struct Question {
+    // Section name, used as the question's category
+    pub section: &'static str,
+    // The example id and the broken line's position in it, e.g. "ownership_transfer_1"
+    pub name: String,
+    // The example id alone, e.g. "ownership_transfer"
+    pub example: String,
+    pub example_title: &'static str,
+    pub line: &'static str,
+    pub answer: &'static str,
+    pub wrong: Vec<&'static str>,
+    // rustc's message from the first line of its output, when it's bundled
+    pub feedback: Option<String>,
+}
+
+impl Question {
+    pub fn prompt(&self) -> String {
+        format!(
+            "In \"{}\", which error does rustc report when this line is uncommented? `{}`",
+            self.example_title, self.line
+        )
+    }
+
+    // Every answer, the right one included, in an order that doesn't give it
+    // away. The order comes from the seed (see rng.rs), so it's the same on
+    // every screen in a classroom and in every run with the same --seed
+    pub fn choices(&self) -> Vec<&'static str> {
+        let mut choices = self.wrong.clone();
+        choices.push(self.answer);
+        choices.sort();
+        rng::stream(&self.name).shuffle(&mut choices);
+        choices
+    }
+}
+
+pub fn command(args: &[String]) -> Result<(), String> {
+    let questions = questions();
+    match args.first().map(String::as_str) {
+        None | Some("gift") => print!("{}", gift(&questions)),
+        Some("csv") => print!("{}", csv(&questions)),
+        Some(other) => return Err(format!("unknown quiz format '{}', expected gift or csv", other)),
+    }
+    Ok(())
+}
+
+// The questions for the active curriculum's lessons, in its order
+pub fn questions() -> Vec<Question> {
+    questions_for(curriculum::active())
+}
+
+// The questions for a curriculum's quizzed lessons, in its order
+pub fn questions_for(curriculum: &Curriculum) -> Vec<Question> {
+    let mut wanted: Vec<String> = Vec::new();
+    for lesson in curriculum.lessons.iter().filter(|lesson| lesson.quiz) {
+        for (section, example) in &lesson.examples {
+            let id = example.id(section);
+            if !wanted.contains(&id) {
+                wanted.push(id);
+            }
+        }
+    }
+    let mut questions: Vec<Question> = pool().into_iter().filter(|question| wanted.contains(&question.example)).collect();
+    // Stable, so an example's questions keep the order of its broken lines
+    questions.sort_by_key(|question| wanted.iter().position(|id| *id == question.example));
+    questions
+}
+
+// Every question the lessons have, in the order of the sections. Names and
+// choices don't depend on the curriculum, so two copies of the same release
+// agree on them whatever curriculum each was given (see classroom.rs)
+pub fn pool() -> Vec<Question> {
+    // Wrong answers come from the codes the lessons use most, so they're
+    // errors a learner has met, not obscure ones
+    let mut counts = BTreeMap::new();
+    for (_, example) in lessons::examples() {
+        for broken in example.broken_lines {
+            *counts.entry(broken.error).or_insert(0) += 1;
+        }
+    }
+    let mut common: Vec<&str> = counts.keys().copied().collect();
+    common.sort_by_key(|code| std::cmp::Reverse(counts[code]));
+    common.truncate(8);
+
+    let mut questions = Vec::new();
+    for (section, example) in lessons::examples() {
+        for (i, broken) in example.broken_lines.iter().enumerate() {
+            let others: Vec<&str> = common.iter().copied().filter(|code| *code != broken.error).collect();
+            // Rotating through the candidates keeps the same three from always showing up
+            let wrong = (0..DISTRACTORS).map(|k| others[(questions.len() + k) % others.len()]).collect();
+            // "error[E0382]: borrow of moved value: `s1`" becomes "rustc says: borrow of moved value: `s1`"
+            let feedback = diagnostics::quote(example, broken).and_then(|captured| {
+                let (_, message) = captured.text.lines().next()?.split_once("]: ")?;
+                Some(format!("rustc says: {}", message))
+            });
+            questions.push(Question {
+                section: section.name,
+                name: format!("{}_{}", example.id(section), i + 1),
+                example: example.id(section),
+                example_title: example.title,
+                line: broken.line,
+                answer: broken.error,
+                wrong,
+                feedback,
+            });
+        }
+    }
+    questions
+}
+
+// Moodle's GIFT format: one category per section, one question per broken line
+fn gift(questions: &[Question]) -> String {
+    let mut out = String::new();
+    let mut category = "";
+    for question in questions {
+        if question.section != category {
+            category = question.section;
+            out.push_str(&format!("$CATEGORY: ownership-demo/{}\n\n", category));
+        }
+        out.push_str(&format!("::{}::[markdown]{} {{\n", question.name, gift_escape(&question.prompt())));
+        let feedback = question.feedback.as_ref().map(|text| format!("#{}", gift_escape(text))).unwrap_or_default();
+        out.push_str(&format!("  ={}{}\n", question.answer, feedback));
+        for wrong in &question.wrong {
+            out.push_str(&format!("  ~{}\n", wrong));
+        }
+        out.push_str("}\n\n");
+    }
+    out
+}
+
+// GIFT gives ~ = # { } : a meaning, so in text they need a backslash
+fn gift_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "~=#{}:\\".contains(c) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn csv(questions: &[Question]) -> String {
+    let mut out = String::from("category,name,question,answer,wrong_1,wrong_2,wrong_3,feedback\n");
+    for question in questions {
+        let mut fields = vec![question.section.to_string(), question.name.clone(), question.prompt(), question.answer.to_string()];
+        fields.extend(question.wrong.iter().map(|wrong| wrong.to_string()));
+        fields.push(question.feedback.clone().unwrap_or_default());
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+// Quotes a field when it has to be, doubling any quotes inside (RFC 4180)
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,131 @@
+// `--record <file>`: records what the demo prints, with its timing, as an
+// asciinema v2 cast, so an instructor can publish a walkthrough of any lesson
+// that plays back exactly as it ran (asciinema play, or the web player). It
+// works with any command: `--record transfer.cast present ownership_transfer`
+// records a presentation at the pace it was given.
+//
+// The demo runs itself again as a child with its output piped back through
+// here, so everything is shown as usual and copied into the cast as it
+// arrives. Keyboard input still goes straight to the child; what's typed
+// isn't part of the output, so it isn't recorded, only what it led to.
+//
+// A cast is a JSON header line, then one [seconds, "o", text] line per chunk
+// of output.
+
+use std::env;
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use crate::xapi;
+
+// Used when the terminal's size can't be found: wide enough for the narration
+const DEFAULT_SIZE: (u16, u16) = (100, 30);
+
+// Removes `--record <file>` (or `--record=<file>`) from the arguments and
+// returns the file
+pub fn take_flag(args: &mut Vec<String>) -> Result<Option<PathBuf>, String> {
+    let Some(position) = args.iter().position(|arg| arg == "--record" || arg.starts_with("--record=")) else {
+        return Ok(None);
+    };
+    let flag = args.remove(position);
+    match flag.strip_prefix("--record=") {
+        Some(path) => Ok(Some(PathBuf::from(path))),
+        None if position < args.len() => Ok(Some(PathBuf::from(args.remove(position)))),
+        None => Err("--record needs a file to write the cast to".to_string()),
+    }
+}
+
+// Runs the demo with `args` and records its output into `path`. Returns the
+// demo's exit code
+pub fn run(path: &Path, args: &[String]) -> Result<i32, String> {
+    let file = File::create(path).map_err(|e| format!("could not create {}: {}", path.display(), e))?;
+    let mut cast = BufWriter::new(file);
+    let (width, height) = terminal_size().unwrap_or(DEFAULT_SIZE);
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let title = format!("rust_ownership_demo {}", args.join(" "));
+    let terminal = env::var("TERM").unwrap_or_else(|_| "xterm-256color".to_string());
+    let header = format!(
+        "{{\"version\": 2, \"width\": {}, \"height\": {}, \"timestamp\": {}, \"title\": \"{}\", \"env\": {{\"TERM\": \"{}\"}}}}\n",
+        width,
+        height,
+        timestamp,
+        xapi::escape(title.trim()),
+        xapi::escape(&terminal)
+    );
+    let failed = |e: io::Error| format!("could not write {}: {}", path.display(), e);
+    cast.write_all(header.as_bytes()).map_err(failed)?;
+
+    let program = env::current_exe().map_err(|e| format!("could not find the demo's own program: {}", e))?;
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("could not start the demo to record it: {}", e))?;
+
+    // Both streams are shown as they come and sent here, stamped, for the cast
+    let started = Instant::now();
+    let (sender, chunks) = mpsc::channel();
+    let stdout = child.stdout.take().map(|pipe| tee(pipe, io::stdout(), started, sender.clone()));
+    let stderr = child.stderr.take().map(|pipe| tee(pipe, io::stderr(), started, sender));
+    for (seconds, text) in chunks {
+        // Output is written for a terminal, which would turn \n into \r\n itself
+        let text = text.replace("\r\n", "\n").replace('\n', "\r\n");
+        writeln!(cast, "[{:.6}, \"o\", \"{}\"]", seconds, xapi::escape(&text)).map_err(failed)?;
+    }
+    for reader in stdout.into_iter().chain(stderr) {
+        let _ = reader.join();
+    }
+    cast.flush().map_err(failed)?;
+
+    let status = child.wait().map_err(|e| format!("could not wait for the demo: {}", e))?;
+    eprintln!("Recorded {} (play it with `asciinema play {}`)", path.display(), path.display());
+    Ok(status.code().unwrap_or(1))
+}
+
+// Copies `pipe` to `shown` as it arrives, and sends each chunk's text with the
+// seconds since `started`. A chunk that ends partway through a character keeps
+// the rest of it for the next one, so the cast is always valid UTF-8
+fn tee(
+    mut pipe: impl Read + Send + 'static,
+    mut shown: impl Write + Send + 'static,
+    started: Instant,
+    chunks: mpsc::Sender<(f64, String)>,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        let mut buffer = [0; 4096];
+        let mut pending = Vec::new();
+        while let Ok(read) = pipe.read(&mut buffer) {
+            if read == 0 {
+                break;
+            }
+            let _ = shown.write_all(&buffer[..read]).and_then(|()| shown.flush());
+            pending.extend_from_slice(&buffer[..read]);
+            let complete = match std::str::from_utf8(&pending) {
+                Ok(_) => pending.len(),
+                Err(e) if e.error_len().is_none() => e.valid_up_to(),
+                // Not UTF-8 at all: record it as best we can rather than stall
+                Err(_) => pending.len(),
+            };
+            let text = String::from_utf8_lossy(&pending[..complete]).into_owned();
+            pending.drain(..complete);
+            if !text.is_empty() && chunks.send((started.elapsed().as_secs_f64(), text)).is_err() {
+                break;
+            }
+        }
+    })
+}
+
+// Columns and rows of the terminal, from `stty size` (which prints "rows columns")
+fn terminal_size() -> Option<(u16, u16)> {
+    let output = Command::new("stty").arg("size").stdin(Stdio::inherit()).stderr(Stdio::null()).output().ok()?;
+    let text = String::from_utf8(output.stdout).ok()?;
+    let (rows, columns) = text.trim().split_once(' ')?;
+    Some((columns.parse().ok()?, rows.parse().ok()?))
+}
@@ -0,0 +1,230 @@
+// `refactor`: a guided "choose the signature" walkthrough. The learner gets a
+// small program with the usual first-draft habits (a &String parameter, a
+// clone to get past the borrow checker, an owned String returned where a
+// slice would do) and a menu of refactorings. Each choice is applied to the
+// code, the program is built again through the harness, and the heap
+// allocations its report function makes are counted, so the effect of every
+// signature change is measured rather than claimed.
+//
+// Choices can be undone, and applied in any order: every combination
+// compiles, and prints the same result.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+use crate::exercises;
+use crate::harness;
+use crate::render;
+use crate::toolchain;
+
+// One change the learner can make to PROGRAM
+struct Refactoring {
+    title: &'static str,
+    // (before, after) pairs, each replaced once
+    edits: &'static [(&'static str, &'static str)],
+    // Why it helps, shown once it's applied
+    why: &'static str,
+}
+
+const REFACTORINGS: &[Refactoring] = &[
+    Refactoring {
+        title: "count_vowels takes &str instead of &String",
+        edits: &[
+            ("fn count_vowels(word: &String)", "fn count_vowels(word: &str)"),
+            (r#"count_vowels(&"rhythm and blues".to_string())"#, r#"count_vowels("rhythm and blues")"#),
+        ],
+        why: "A literal is already a &str, so it no longer has to be copied into a String just \
+              to be lent out. A &String argument still works: it derefs to &str.",
+    },
+    Refactoring {
+        title: "Pass each word without .clone()",
+        edits: &[("count_vowels(&word.clone())", "count_vowels(word)")],
+        why: "count_vowels only reads the word, so lending the one in the Vec is enough. The \
+              clone made a String per word and dropped it straight after the call.",
+    },
+    Refactoring {
+        title: "longest_word returns &str instead of String",
+        edits: &[
+            ("fn longest_word(text: &str) -> String", "fn longest_word(text: &str) -> &str"),
+            ("    longest.to_string()\n", "    longest\n"),
+        ],
+        why: "The longest word is already a slice of text, and elision ties the result to text, \
+              so it can be handed back as it is. Callers that need to keep it can call \
+              .to_string() themselves.",
+    },
+];
+
+const PROGRAM: &str = r#"// A word report: how many vowels the words have, plus the length of the longest one
+
+fn count_vowels(word: &String) -> usize {
+    word.chars().filter(|c| "aeiou".contains(*c)).count()
+}
+
+fn longest_word(text: &str) -> String {
+    let mut longest = "";
+    for word in text.split(' ') {
+        if word.len() > longest.len() {
+            longest = word;
+        }
+    }
+    longest.to_string()
+}
+
+fn report(text: &str, words: &[String]) -> usize {
+    let mut vowels = 0;
+    for word in words {
+        vowels += count_vowels(&word.clone());
+    }
+    vowels += count_vowels(&"rhythm and blues".to_string());
+    vowels + longest_word(text).len()
+}
+
+fn main() {
+    let text = "the quick brown fox jumps over the lazy dog";
+    let words: Vec<String> = text.split(' ').map(String::from).collect();
+    println!("{}", report(text, &words));
+}
+"#;
+
+// Measures report() alone: building the inputs isn't part of what's being refactored
+const MEASURE: &str = r#"    #[test]
+    fn measure_report() {
+        let text = "the quick brown fox jumps over the lazy dog";
+        let words: Vec<String> = text.split(' ').map(String::from).collect();
+        let (result, allocations) = instrument::measure(|| report(text, &words));
+        println!("measured {} {} {}", result, allocations.count, allocations.bytes);
+    }
+"#;
+
+// What one version of the program did
+#[derive(Clone, Copy)]
+struct Measurement {
+    result: usize,
+    allocations: usize,
+    bytes: usize,
+}
+
+pub fn command() -> Result<(), String> {
+    toolchain::require("the refactoring walkthrough")?;
+    let mut applied = vec![false; REFACTORINGS.len()];
+    let mut measured = HashMap::new();
+
+    println!("Choose the signature: a refactoring walkthrough\n");
+    println!("This program works, but it allocates more than it needs to:\n");
+    for line in PROGRAM.lines() {
+        println!("    {}", line);
+    }
+    let original = measure(PROGRAM, &mut measured)?;
+    println!("\nreport() returned {} after {} allocations ({} bytes).", original.result, original.allocations, original.bytes);
+
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    loop {
+        println!("\nRefactorings:");
+        for (i, refactoring) in REFACTORINGS.iter().enumerate() {
+            let mark = if applied[i] { "x" } else { " " };
+            println!("  {}. [{}] {}", i + 1, mark, refactoring.title);
+        }
+        print!("Choose 1-{} to apply or undo one, or q to stop: ", REFACTORINGS.len());
+        io::stdout().flush().map_err(|e| format!("could not write to the terminal: {}", e))?;
+
+        let mut answer = String::new();
+        let read = input.read_line(&mut answer).map_err(|e| format!("could not read the keyboard: {}", e))?;
+        let answer = answer.trim();
+        if read == 0 || answer == "q" {
+            break;
+        }
+        let Some(choice) = answer.parse::<usize>().ok().filter(|n| (1..=REFACTORINGS.len()).contains(n)) else {
+            println!("'{}' isn't one of the choices", answer);
+            continue;
+        };
+
+        let before = program(&applied);
+        let before_measurement = measure(&before, &mut measured)?;
+        applied[choice - 1] = !applied[choice - 1];
+        let after = program(&applied);
+        let after_measurement = measure(&after, &mut measured)?;
+
+        let refactoring = &REFACTORINGS[choice - 1];
+        let verb = if applied[choice - 1] { "Applied" } else { "Undid" };
+        println!("\n{}: {}\n", verb, refactoring.title);
+        print_changes(&before, &after);
+        println!(
+            "\nallocations: {} -> {} ({} -> {} bytes)",
+            before_measurement.allocations, after_measurement.allocations, before_measurement.bytes, after_measurement.bytes
+        );
+        if after_measurement.result == original.result {
+            println!("report() still returns {}", after_measurement.result);
+        } else {
+            println!("report() now returns {} instead of {}", after_measurement.result, original.result);
+        }
+        if applied[choice - 1] {
+            for line in render::wrap(refactoring.why, 80) {
+                println!("{}", line);
+            }
+        }
+        if applied.iter().all(|&a| a) {
+            println!("\nThat's all of them: report() now only borrows, and allocates nothing.");
+        }
+    }
+    Ok(())
+}
+
+// Edits that no longer match PROGRAM, for lint-content: the walkthrough
+// would silently leave those refactorings out
+pub fn problems() -> Vec<String> {
+    REFACTORINGS
+        .iter()
+        .flat_map(|refactoring| refactoring.edits.iter().map(move |edit| (refactoring, edit)))
+        .filter(|(_, (before, _))| !PROGRAM.contains(before))
+        .map(|(refactoring, (before, _))| format!("refactor: '{}' edits '{}', which isn't in the program", refactoring.title, before.trim()))
+        .collect()
+}
+
+// PROGRAM with the chosen refactorings applied
+fn program(applied: &[bool]) -> String {
+    let mut code = PROGRAM.to_string();
+    for (refactoring, _) in REFACTORINGS.iter().zip(applied).filter(|(_, on)| **on) {
+        for (before, after) in refactoring.edits {
+            code = code.replacen(before, after, 1);
+        }
+    }
+    code
+}
+
+// Builds the program with the measuring test and runs it, once per version
+fn measure(code: &str, measured: &mut HashMap<String, Measurement>) -> Result<Measurement, String> {
+    if let Some(measurement) = measured.get(code) {
+        return Ok(*measurement);
+    }
+    print!("(building...)\r");
+    io::stdout().flush().map_err(|e| format!("could not write to the terminal: {}", e))?;
+    let run = harness::test(&exercises::with_hidden_tests(code, MEASURE));
+    print!("{:13}\r", "");
+    let run = run?;
+    if !run.build.success {
+        eprint!("{}", run.build.diagnostics);
+        return Err("this version of the program doesn't compile".to_string());
+    }
+    let numbers: Vec<usize> = run
+        .output
+        .split("measured ")
+        .nth(1)
+        .map(|rest| rest.split_whitespace().take(3).filter_map(|n| n.parse().ok()).collect())
+        .unwrap_or_default();
+    let [result, allocations, bytes] = numbers[..] else {
+        return Err(format!("the program ran, but didn't report its allocations:\n{}", run.output));
+    };
+    let measurement = Measurement { result, allocations, bytes };
+    measured.insert(code.to_string(), measurement);
+    Ok(measurement)
+}
+
+// The lines that differ between two versions of the program, which always
+// have the same number of lines
+fn print_changes(before: &str, after: &str) {
+    for (old, new) in before.lines().zip(after.lines()).filter(|(old, new)| old != new) {
+        println!("  - {}", old.trim());
+        println!("  + {}", new.trim());
+    }
+}
@@ -0,0 +1,158 @@
+// Helpers for laying out longer text in the terminal.
+//
+// Widths are measured in terminal columns, not chars: CJK and most emoji take
+// two columns, combining accents and joiners take none, and a character with
+// its accents (a grapheme cluster) is never split across lines. Paragraphs
+// that start in a right-to-left script (Hebrew, Arabic) are right-aligned; the
+// terminal still does the reordering within each line.
+
+// Breaks text into lines of at most `width` columns, keeping words whole.
+// Blank lines in the input separate paragraphs and are kept. CJK text, which
+// is written without spaces, is broken between characters where it has to be.
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for paragraph in text.split("\n\n") {
+        let first = lines.len();
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            for piece in split_to_fit(word, width) {
+                if !line.is_empty() && self::width(&line) + 1 + self::width(piece) > width {
+                    lines.push(std::mem::take(&mut line));
+                }
+                // Pieces of one long word continue without a space
+                if !line.is_empty() && piece.as_ptr() == word.as_ptr() {
+                    line.push(' ');
+                }
+                line.push_str(piece);
+            }
+        }
+        lines.push(line);
+        if is_rtl(paragraph) {
+            for line in &mut lines[first..] {
+                *line = format!("{}{}", " ".repeat(width.saturating_sub(self::width(line))), line);
+            }
+        }
+        lines.push(String::new());
+    }
+
+    lines.pop(); // no blank line after the last paragraph
+    lines
+}
+
+// A heading underlined to its own width
+pub fn heading(title: &str) -> String {
+    format!("{}\n{}", title, "-".repeat(width(title)))
+}
+
+// `text` followed by enough spaces to fill `columns`, for lining up tables.
+// format!'s `{:<N}` counts chars, which misaligns wide and accented text
+pub fn pad(text: &str, columns: usize) -> String {
+    format!("{}{}", text, " ".repeat(columns.saturating_sub(width(text))))
+}
+
+// How many terminal columns `text` takes
+pub fn width(text: &str) -> usize {
+    clusters(text).map(cluster_width).sum()
+}
+
+// The grapheme clusters of `text`: each base character with the accents,
+// variation selectors and skin tones that follow it, and anything joined on
+// with a zero-width joiner. Close enough to Unicode's rules for lesson text
+fn clusters(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        let mut chars = rest.char_indices();
+        let (_, first) = chars.next()?;
+        let mut joined = first == '\u{200d}';
+        let mut end = rest.len();
+        for (i, c) in chars {
+            if joined || is_extending(c) {
+                joined = c == '\u{200d}';
+            } else {
+                end = i;
+                break;
+            }
+        }
+        let (cluster, remaining) = rest.split_at(end);
+        rest = remaining;
+        Some(cluster)
+    })
+}
+
+// The width of a cluster is the width of its first character
+fn cluster_width(cluster: &str) -> usize {
+    match cluster.chars().next() {
+        Some(c) if c.is_control() || is_extending(c) => 0,
+        Some(c) if is_wide(c) => 2,
+        Some(_) => 1,
+        None => 0,
+    }
+}
+
+// Characters that attach to the one before them and take no columns
+fn is_extending(c: char) -> bool {
+    matches!(c,
+        '\u{0300}'..='\u{036f}'     // combining accents
+        | '\u{0483}'..='\u{0489}'
+        | '\u{0591}'..='\u{05bd}'   // Hebrew points
+        | '\u{0610}'..='\u{061a}'   // Arabic marks
+        | '\u{064b}'..='\u{065f}'
+        | '\u{1ab0}'..='\u{1aff}'
+        | '\u{1dc0}'..='\u{1dff}'
+        | '\u{200b}'..='\u{200f}'   // zero-width space, joiners and direction marks
+        | '\u{20d0}'..='\u{20ff}'
+        | '\u{fe00}'..='\u{fe0f}'   // variation selectors
+        | '\u{fe20}'..='\u{fe2f}'
+        | '\u{1f3fb}'..='\u{1f3ff}' // skin tones
+        | '\u{e0100}'..='\u{e01ef}')
+}
+
+// East Asian wide and fullwidth characters, and emoji, which take two columns
+fn is_wide(c: char) -> bool {
+    matches!(c,
+        '\u{1100}'..='\u{115f}'     // Hangul Jamo
+        | '\u{2e80}'..='\u{303e}'   // CJK radicals and punctuation
+        | '\u{3041}'..='\u{33ff}'   // kana, CJK symbols
+        | '\u{3400}'..='\u{4dbf}'
+        | '\u{4e00}'..='\u{9fff}'   // CJK ideographs
+        | '\u{a000}'..='\u{a4cf}'
+        | '\u{ac00}'..='\u{d7a3}'   // Hangul syllables
+        | '\u{f900}'..='\u{faff}'
+        | '\u{fe30}'..='\u{fe4f}'
+        | '\u{ff00}'..='\u{ff60}'   // fullwidth forms
+        | '\u{ffe0}'..='\u{ffe6}'
+        | '\u{1f300}'..='\u{1f64f}' // emoji
+        | '\u{1f900}'..='\u{1f9ff}'
+        | '\u{20000}'..='\u{3fffd}')
+}
+
+// Whether a paragraph's first letter is from a right-to-left script
+fn is_rtl(paragraph: &str) -> bool {
+    paragraph
+        .chars()
+        .find(|c| c.is_alphabetic())
+        .is_some_and(|c| matches!(c, '\u{0590}'..='\u{08ff}' | '\u{fb1d}'..='\u{fdff}' | '\u{fe70}'..='\u{fefc}'))
+}
+
+// A word as pieces that each fit in `width` columns, split between clusters.
+// Only CJK words are split; a long URL is better left whole
+fn split_to_fit(word: &str, width: usize) -> Vec<&str> {
+    if !word.chars().any(is_wide) {
+        return vec![word];
+    }
+    let mut pieces = Vec::new();
+    let (mut start, mut used) = (0, 0);
+    let mut offset = 0;
+    for cluster in clusters(word) {
+        let columns = cluster_width(cluster);
+        if used + columns > width && used > 0 {
+            pieces.push(&word[start..offset]);
+            (start, used) = (offset, 0);
+        }
+        used += columns;
+        offset += cluster.len();
+    }
+    pieces.push(&word[start..]);
+    pieces
+}
@@ -0,0 +1,82 @@
+// Randomness that can be repeated. Anything in the demo that picks at random
+// (the order of a question's choices, the strings a property test tries)
+// asks for an Rng here, named for what it's for, instead of reaching for an
+// unseeded source. Every Rng is derived from one seed, which `--seed <n>`
+// sets, so two runs with the same seed and the same release pick the same
+// things, and a classroom's students use their host's seed so everyone sees
+// the same screen. Without --seed the seed is DEFAULT_SEED, so a plain run
+// is repeatable too.
+//
+// Each purpose gets its own stream, so adding randomness to one lesson
+// doesn't change what another one draws. The generator is SplitMix64: small,
+// fast, and good enough for teaching, not for anything secret.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::fingerprint;
+
+pub const DEFAULT_SEED: u64 = 2015;
+
+static SEED: AtomicU64 = AtomicU64::new(DEFAULT_SEED);
+
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    // The stream for `purpose` (an example id, a question name, ..) under `seed`
+    pub fn new(seed: u64, purpose: &str) -> Rng {
+        let purpose = u64::from_str_radix(&fingerprint::of(purpose), 16).unwrap_or(0);
+        Rng { state: seed ^ purpose }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // A number in 0..n (n must not be 0)
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    // Fisher-Yates, so every order is equally likely
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+// The stream for `purpose` under the current seed
+pub fn stream(purpose: &str) -> Rng {
+    Rng::new(seed(), purpose)
+}
+
+pub fn seed() -> u64 {
+    SEED.load(Ordering::Relaxed)
+}
+
+// Replaces the seed, e.g. with a classroom host's
+pub fn set_seed(seed: u64) {
+    SEED.store(seed, Ordering::Relaxed);
+}
+
+// Removes `--seed <n>` (or `--seed=<n>`) from the arguments and uses it
+pub fn take_flag(args: &mut Vec<String>) -> Result<(), String> {
+    let Some(position) = args.iter().position(|arg| arg == "--seed" || arg.starts_with("--seed=")) else {
+        return Ok(());
+    };
+    let flag = args.remove(position);
+    let value = match flag.strip_prefix("--seed=") {
+        Some(value) => value.to_string(),
+        None if position < args.len() => args.remove(position),
+        None => return Err("--seed needs a number".to_string()),
+    };
+    let seed = value.parse().map_err(|_| format!("--seed needs a number, not '{}'", value))?;
+    set_seed(seed);
+    Ok(())
+}
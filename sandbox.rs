@@ -0,0 +1,128 @@
+// Numbered experiments for learners who want to break things themselves.
+// `sandbox new` copies an example's code (including the commented-out lines
+// that don't compile) into sandbox/NNN_<example>/ as a standalone cargo
+// project, and `sandbox run` builds and runs one of them.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::cli;
+use crate::harness;
+use crate::lessons;
+use crate::toolchain;
+
+const SANDBOX_DIR: &str = "sandbox";
+
+pub fn command(args: &[String]) -> Result<(), String> {
+    match args.first().map(String::as_str) {
+        Some("new") => {
+            let id = args.get(1).ok_or("sandbox new needs an example id (see `list`)")?;
+            new(id)
+        }
+        Some("run") => {
+            let id = args.get(1).ok_or("sandbox run needs an experiment number or name")?;
+            run(id)
+        }
+        _ => Err("usage: sandbox new <example> | sandbox run <id>".to_string()),
+    }
+}
+
+// Creates the next numbered experiment from the example's code
+fn new(example_id: &str) -> Result<(), String> {
+    let (section, example) = cli::find_example(example_id)?;
+    let number = next_number(Path::new(SANDBOX_DIR))?;
+    let dir = Path::new(SANDBOX_DIR).join(format!("{:03}_{}", number, example.id(section)));
+
+    let mut manifest = format!(
+        "[package]\nname = \"experiment_{:03}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n\
+         # Keep the experiment independent of any surrounding workspace\n[workspace]\n",
+        number
+    );
+    if !example.crates.is_empty() {
+        manifest.push_str(&format!("\n[dependencies]\n{}\n", example.crates.join("\n")));
+    }
+    let main = format!(
+        "// Experiment {:03}: {}\n\
+         // Try uncommenting the lines that don't compile and read the errors, then\n\
+         // change the code until it builds again. Run it with:\n\
+         //   rust_ownership_demo sandbox run {:03}\n\n{}",
+        number, example.title, number, example.code
+    );
+
+    write_file(&dir.join("Cargo.toml"), &manifest)?;
+    write_file(&dir.join("src").join("main.rs"), &main)?;
+
+    println!("Created experiment {:03} in {}", number, dir.display());
+    if let Err(reason) = harness::ready_for(example) {
+        println!("Note: this experiment won't build yet: {}", reason);
+    }
+    println!("Edit {} and run it with `sandbox run {:03}`", dir.join("src").join("main.rs").display(), number);
+    Ok(())
+}
+
+// Builds and runs an experiment with cargo, streaming its output
+fn run(id: &str) -> Result<(), String> {
+    let dir = find_experiment(Path::new(SANDBOX_DIR), id)?;
+
+    // Experiments are named NNN_<example>, so we know which example they started from
+    let example_id = dir.file_name().and_then(|name| name.to_str()).and_then(|name| name.split_once('_'));
+    if let Some((_, example)) = example_id.and_then(|(_, example_id)| lessons::find(example_id)) {
+        harness::ready_for(example).map_err(|reason| format!("can't build {}: {}", dir.display(), reason))?;
+    }
+
+    toolchain::require_cargo("sandbox run")?;
+    let status = Command::new("cargo")
+        .args(["run", "--quiet"])
+        .current_dir(&dir)
+        .status()
+        .map_err(|e| format!("could not run cargo: {}", e))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("experiment {} did not build or run successfully", dir.display()))
+    }
+}
+
+// One past the highest experiment number in the sandbox directory
+fn next_number(root: &Path) -> Result<u32, String> {
+    let highest = experiments(root)?.into_iter().map(|(number, _)| number).max();
+    Ok(highest.map_or(1, |n| n + 1))
+}
+
+// Finds an experiment by number ("7" or "007") or by its full directory name
+fn find_experiment(root: &Path, id: &str) -> Result<PathBuf, String> {
+    let number: Option<u32> = id.parse().ok();
+
+    experiments(root)?
+        .into_iter()
+        .find(|(n, path)| Some(*n) == number || path.file_name().is_some_and(|name| name == id))
+        .map(|(_, path)| path)
+        .ok_or_else(|| format!("no experiment '{}' in {}/", id, root.display()))
+}
+
+// Every NNN_* directory in the sandbox, paired with its number
+fn experiments(root: &Path) -> Result<Vec<(u32, PathBuf)>, String> {
+    if !root.exists() {
+        return Ok(Vec::new());
+    }
+
+    let entries = fs::read_dir(root).map_err(|e| format!("could not read {}: {}", root.display(), e))?;
+    let mut found = Vec::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let number = name.split('_').next().and_then(|prefix| prefix.parse().ok());
+        if let Some(number) = number {
+            found.push((number, entry.path()));
+        }
+    }
+    Ok(found)
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("could not create {}: {}", parent.display(), e))?;
+    }
+    fs::write(path, contents).map_err(|e| format!("could not write {}: {}", path.display(), e))
+}
@@ -0,0 +1,238 @@
+// `new-lesson <section> <name>`: writes the skeleton of a new example into the
+// lesson sources, so adding one starts from something that builds and passes
+// `lint-content` rather than from reading how the others are put together.
+//
+// The example goes into lessons/<section>.rs: an entry in its SECTION, a
+// narrated run function, the stops `present` walks through, and the
+// standalone code with one commented-out line that doesn't compile (which
+// also becomes its quiz question). A new
+// section gets its own file, registered in lessons/mod.rs after the others.
+// Everything the author still has to write says TODO, and `lint-content`
+// reports examples that still do.
+//
+// The registry is compiled into the program, so the generated files that come
+// from it (examples/<id>.rs and the bundled diagnostics) can only be written
+// once the program has been built with the new example; the command says how.
+
+use std::fs;
+use std::path::Path;
+
+const LESSONS_DIR: &str = "lessons";
+
+// What lint-content looks for to find a skeleton that hasn't been filled in
+pub const PLACEHOLDER: &str = "TODO";
+
+pub fn command(args: &[String]) -> Result<(), String> {
+    let [section, name] = args else {
+        return Err("new-lesson needs a section and an example name, e.g. `new-lesson drop guards`".to_string());
+    };
+    for word in [section, name] {
+        let valid = word.starts_with(|c: char| c.is_ascii_lowercase())
+            && word.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        if !valid {
+            return Err(format!("'{}' should be lowercase words joined by _, like early_drop", word));
+        }
+    }
+    let registry = Path::new(LESSONS_DIR).join("mod.rs");
+    if !registry.exists() {
+        return Err("new-lesson edits the lesson sources; run it in the demo's source directory".to_string());
+    }
+
+    let path = Path::new(LESSONS_DIR).join(format!("{}.rs", section));
+    let id = format!("{}_{}", section, name);
+    let mut changed = Vec::new();
+    if path.exists() {
+        let source = read(&path)?;
+        if source.contains(&format!("name: \"{}\",", name)) {
+            return Err(format!("{} already has an example called {}", path.display(), name));
+        }
+        write(&path, &add_example(&source, name)?)?;
+    } else {
+        write(&path, &new_section(section, name))?;
+        write(&registry, &register(&read(&registry)?, section)?)?;
+        changed.push(registry.display().to_string());
+    }
+    changed.insert(0, path.display().to_string());
+
+    println!("Added {} to {}", id, changed.join(" and "));
+    println!("\nNext:");
+    println!("  1. Fill in everything marked {} in {}: the title, the narration,", PLACEHOLDER, path.display());
+    println!("     and the code with its broken line and the error rustc gives for it");
+    println!("  2. cargo build, then `rust_ownership_demo run {}` to watch it", id);
+    println!("  3. `gen-examples` writes examples/{}.rs, and `bundle-diagnostics` captures", id);
+    println!("     rustc's output for the broken line (the quiz's feedback)");
+    println!("  4. `lint-content` checks the narration, the code and the error code");
+    if changed.len() > 1 {
+        println!("  A new section also wants a Rust Book chapter in book.rs (the `sections` list)");
+    }
+    Ok(())
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path.display(), e))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("could not write {}: {}", path.display(), e))
+}
+
+// The entry in SECTION's examples for a new example
+fn entry(name: &str) -> String {
+    format!(
+        "        Example {{
+            name: \"{name}\",
+            title: \"{todo}: what this example shows\",
+            run: {name},
+            code: {constant},
+            broken_lines: &[BrokenLine {{
+                line: \"println!(\\\"{{}}\\\", value);\",
+                error: \"E0382\",
+            }}],
+            requires: &[],
+            crates: &[],
+            stops: &[
+                Stop {{
+                    line: \"let value\",
+                    say: \"{todo}: what this line does, for `present`\",
+                }},
+                Stop {{
+                    line: \"// println!\",
+                    say: \"{todo}: why this line doesn't compile\",
+                }},
+            ],
+        }},
+",
+        todo = PLACEHOLDER,
+        constant = constant(name)
+    )
+}
+
+// The narrated walkthrough, which runs the idea for real and prints what happened
+fn run_function(name: &str) -> String {
+    format!(
+        "fn {name}() {{
+    let mut n = Narration::new();
+
+    // {todo}: show the idea with real values. n.fact names a value and n.say
+    // prints a line with {{names}} filled in; fixed text can use println!
+    let value = String::from(\"hello\");
+    let moved = value;
+    n.fact(\"moved\", &moved);
+    n.say(\"value moved into moved, which now holds '{{moved}}'\");
+    println!(\"  Using value after the move is error[E0382]\");
+}}
+
+",
+        todo = PLACEHOLDER
+    )
+}
+
+// The standalone program, with the line that doesn't compile commented out
+fn code(name: &str) -> String {
+    format!(
+        "const {constant}: &str = r#\"fn main() {{
+    // {todo}: the same idea as a complete program
+    let value = String::from(\"hello\");
+    let moved = value;
+    // println!(\"{{}}\", value);
+    println!(\"{{}}\", moved);
+}}
+\"#;
+",
+        constant = constant(name),
+        todo = PLACEHOLDER
+    )
+}
+
+fn constant(name: &str) -> String {
+    format!("{}_CODE", name.to_uppercase())
+}
+
+// A file for a new section, holding just the new example
+fn new_section(section: &str, name: &str) -> String {
+    format!(
+        "use super::{{BrokenLine, Example, Section, Stop}};
+use crate::narration::Narration;
+
+pub const SECTION: Section = Section {{
+    name: \"{section}\",
+    title: \"{todo}: {title}\",
+    examples: &[
+{entry}    ],
+}};
+
+{run}{code}",
+        todo = PLACEHOLDER,
+        title = section.replace('_', " ").to_uppercase(),
+        entry = entry(name),
+        run = run_function(name),
+        code = code(name)
+    )
+}
+
+// Adds the example to an existing section's source: its entry at the end of
+// SECTION, its run function before the code constants, and its code last
+fn add_example(source: &str, name: &str) -> Result<String, String> {
+    let unexpected = || "its SECTION isn't laid out like the other lessons'; add the example by hand".to_string();
+    let start = source.find("pub const SECTION: Section").ok_or_else(unexpected)?;
+    let end = start + source[start..].find("\n};\n").ok_or_else(unexpected)?;
+    let section = &source[start..end];
+
+    // A section with one example writes it as `examples: &[Example { .. }],`
+    let examples = if let Some(single) = section.strip_suffix("    }],") {
+        let (before, example) = single.split_once("examples: &[Example {").ok_or_else(unexpected)?;
+        let indented: Vec<String> = example.lines().map(|line| if line.is_empty() { String::new() } else { format!("    {}", line) }).collect();
+        format!("{}examples: &[\n        Example {{{}\n        }},\n{}    ],", before, indented.join("\n"), entry(name))
+    } else {
+        let listed = section.strip_suffix("    ],").ok_or_else(unexpected)?;
+        format!("{}{}    ],", listed, entry(name))
+    };
+
+    let mut rest = source[end..].to_string();
+    // Before the first code constant and the comments above it
+    let mut functions_end = rest.find("_CODE: &str = ").map_or(rest.len(), |position| rest[..position].rfind('\n').map_or(0, |line| line + 1));
+    while let Some(previous) = rest[..functions_end.saturating_sub(1)].rfind('\n') {
+        if !rest[previous + 1..functions_end].starts_with("//") {
+            break;
+        }
+        functions_end = previous + 1;
+    }
+    rest.insert_str(functions_end, &run_function(name));
+    if !rest.ends_with('\n') {
+        rest.push('\n');
+    }
+    rest.push('\n');
+    rest.push_str(&code(name));
+
+    let mut updated = format!("{}{}{}", &source[..start], examples, rest);
+    // The entry and the run function need these
+    updated = updated.replacen("use super::{Example, Section};", "use super::{BrokenLine, Example, Section, Stop};", 1);
+    updated = updated.replacen("use super::{BrokenLine, Example, Section};", "use super::{BrokenLine, Example, Section, Stop};", 1);
+    if !updated.contains("use crate::narration::Narration;") {
+        let import = updated.find("use super::").ok_or_else(unexpected)?;
+        let line_end = import + updated[import..].find('\n').ok_or_else(unexpected)? + 1;
+        updated.insert_str(line_end, "use crate::narration::Narration;\n");
+    }
+    Ok(updated)
+}
+
+// Declares a new section's module in lessons/mod.rs and plays it last
+fn register(registry: &str, section: &str) -> Result<String, String> {
+    let unexpected = || "lessons/mod.rs isn't laid out as expected; register the section by hand".to_string();
+    let declaration = format!("mod {};\n", section);
+    // The declarations are kept in alphabetical order
+    let mut position = registry.find("mod ").ok_or_else(unexpected)?;
+    while let Some(line) = registry[position..].lines().next().filter(|line| line.starts_with("mod ")) {
+        if line > declaration.trim_end() {
+            break;
+        }
+        position += line.len() + 1;
+    }
+    let mut updated = registry.to_string();
+    updated.insert_str(position, &declaration);
+
+    let list = updated.find("pub const SECTIONS: &[Section] = &[").ok_or_else(unexpected)?;
+    let list_end = list + updated[list..].find("\n];").ok_or_else(unexpected)? + 1;
+    updated.insert_str(list_end, &format!("    {}::SECTION,\n", section));
+    Ok(updated)
+}
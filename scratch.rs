@@ -0,0 +1,70 @@
+// Private scratch directories for downloads and unpacked archives. Each is
+// made fresh in the system's temp directory, readable only by us, and never
+// reused: a directory of the same name that someone else made first (to
+// swap files in while we check them) makes us pick another name rather
+// than write into theirs. The caller removes it when done.
+
+use std::env;
+use std::fs::DirBuilder;
+use std::io::ErrorKind;
+#[cfg(unix)]
+use std::os::unix::fs::DirBuilderExt;
+use std::path::PathBuf;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// How many names to try before giving up on the temp directory
+const ATTEMPTS: usize = 16;
+
+// A new, empty directory named for `purpose`, e.g. "update"
+pub fn private_dir(purpose: &str) -> Result<PathBuf, String> {
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+    for _ in 0..ATTEMPTS {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.subsec_nanos());
+        let dir = env::temp_dir().join(format!(
+            "rust_ownership_demo_{}_{}_{}_{:08x}",
+            purpose,
+            process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            nanos
+        ));
+        let mut builder = DirBuilder::new();
+        #[cfg(unix)]
+        builder.mode(0o700);
+        // create, not create_all: it fails if the directory is already there
+        match builder.create(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(format!("could not create {}: {}", dir.display(), e)),
+        }
+    }
+    Err(format!("could not find an unused name for a directory in {}", env::temp_dir().display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn each_directory_is_new_and_empty() {
+        let (first, second) = (private_dir("test").unwrap(), private_dir("test").unwrap());
+        assert_ne!(first, second);
+        assert_eq!(fs::read_dir(&first).unwrap().count(), 0);
+        fs::remove_dir(&first).unwrap();
+        fs::remove_dir(&second).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn only_we_can_use_it() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = private_dir("test").unwrap();
+        let mode = fs::metadata(&dir).unwrap().permissions().mode();
+        fs::remove_dir(&dir).unwrap();
+        assert_eq!(mode & 0o777, 0o700);
+    }
+}
@@ -0,0 +1,201 @@
+// The small subset of TOML the demo's own files are written in: `key = value`
+// lines with strings, whole numbers, true/false and arrays of strings (which
+// may run over several lines), # comments, and one kind of [[table]] that
+// can repeat. Curricula use [[lesson]] tables, lesson packs [[exercise]]
+// ones. Anything else is an error that names its line.
+
+// A value on the right of `=`
+pub enum Value {
+    Text(String),
+    Number(u64),
+    Flag(bool),
+    List(Vec<String>),
+}
+
+// One `key = value` line, and the line number it's on
+pub struct Entry {
+    pub line: usize,
+    pub key: String,
+    pub value: Value,
+}
+
+pub struct Document {
+    // The settings above the first table
+    pub top: Vec<Entry>,
+    // Each table as (line it starts on, its entries)
+    pub tables: Vec<(usize, Vec<Entry>)>,
+}
+
+// Parses `text`, whose only tables are [[`table`]]
+pub fn parse(text: &str, table: &str) -> Result<Document, String> {
+    let header = format!("[[{}]]", table);
+    let mut document = Document { top: Vec::new(), tables: Vec::new() };
+
+    let mut lines = text.lines().enumerate().map(|(i, line)| (i + 1, line));
+    while let Some((number, line)) = lines.next() {
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line == header {
+            document.tables.push((number, Vec::new()));
+            continue;
+        }
+        if line.starts_with('[') {
+            return Err(format!("line {}: the only table is {}, not {}", number, header, line));
+        }
+        let (key, value) = line.split_once('=').ok_or_else(|| format!("line {}: expected key = value", number))?;
+        let (key, mut value) = (key.trim().to_string(), value.trim().to_string());
+        // An array can go on over several lines, up to its closing bracket
+        while value.starts_with('[') && !value.ends_with(']') {
+            let (_, more) = lines.next().ok_or_else(|| format!("line {}: the array is never closed", number))?;
+            value.push(' ');
+            value.push_str(strip_comment(more).trim());
+        }
+        let value = parse_value(&value).map_err(|e| format!("line {}: {}", number, e))?;
+        let entry = Entry { line: number, key, value };
+        match document.tables.last_mut() {
+            Some((_, entries)) => entries.push(entry),
+            None => document.top.push(entry),
+        }
+    }
+    Ok(document)
+}
+
+// The line without a trailing # comment (a # inside a string is kept)
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn parse_value(value: &str) -> Result<Value, String> {
+    match value {
+        "true" => return Ok(Value::Flag(true)),
+        "false" => return Ok(Value::Flag(false)),
+        _ => {}
+    }
+    if let Ok(number) = value.parse() {
+        return Ok(Value::Number(number));
+    }
+    if let Some(inner) = value.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+        let mut items = Vec::new();
+        let mut rest = inner.trim();
+        while !rest.is_empty() {
+            let (item, after) = parse_string(rest)?;
+            items.push(item);
+            rest = after.trim_start();
+            rest = rest.strip_prefix(',').map_or(rest, str::trim_start);
+        }
+        return Ok(Value::List(items));
+    }
+    match parse_string(value)? {
+        (text, "") => Ok(Value::Text(text)),
+        (_, extra) => Err(format!("unexpected '{}' after the string", extra.trim())),
+    }
+}
+
+// A "quoted" string at the start of `text`, and what follows it
+fn parse_string(text: &str) -> Result<(String, &str), String> {
+    let body = text
+        .strip_prefix('"')
+        .ok_or_else(|| format!("expected a \"quoted\" string, a number, true or false, not {}", text))?;
+    let mut out = String::new();
+    let mut chars = body.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((out, &body[i + 1..])),
+            '\\' => match chars.next().map(|(_, c)| c) {
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some(c @ ('"' | '\\')) => out.push(c),
+                _ => return Err("unknown escape in a string".to_string()),
+            },
+            c => out.push(c),
+        }
+    }
+    Err("a string is never closed".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(value: &Value) -> &str {
+        match value {
+            Value::Text(text) => text,
+            _ => panic!("not a string"),
+        }
+    }
+
+    fn list(value: &Value) -> &[String] {
+        match value {
+            Value::List(items) => items,
+            _ => panic!("not an array"),
+        }
+    }
+
+    #[test]
+    fn strings_keep_escapes_and_hashes() {
+        let document = parse(r#"say = "a \"quote\", a \\ and a # "  # then a comment"#, "lesson").unwrap();
+        assert_eq!(text(&document.top[0].value), "a \"quote\", a \\ and a # ");
+        let document = parse(r#"say = "one\ttwo\nthree""#, "lesson").unwrap();
+        assert_eq!(text(&document.top[0].value), "one\ttwo\nthree");
+    }
+
+    #[test]
+    fn numbers_and_flags() {
+        let document = parse("count = 42\nhidden = true\nquiz = false\n", "lesson").unwrap();
+        assert!(matches!(document.top[0].value, Value::Number(42)));
+        assert!(matches!(document.top[1].value, Value::Flag(true)));
+        assert!(matches!(document.top[2].value, Value::Flag(false)));
+    }
+
+    #[test]
+    fn arrays_on_one_line_or_several() {
+        let document = parse("ids = [\"a\", \"b, c\"]\nempty = []\n", "lesson").unwrap();
+        assert_eq!(list(&document.top[0].value), ["a", "b, c"]);
+        assert!(list(&document.top[1].value).is_empty());
+
+        let text = "ids = [\n    \"a\",  # the first\n    \"b\",\n]\nafter = \"x\"\n";
+        let document = parse(text, "lesson").unwrap();
+        assert_eq!(list(&document.top[0].value), ["a", "b"]);
+        assert_eq!((document.top[1].line, document.top[1].key.as_str()), (5, "after"));
+    }
+
+    #[test]
+    fn tables_collect_the_entries_below_them() {
+        let text = "# a curriculum\ntitle = \"t\"\n\n[[lesson]]\nsection = \"a\"\n[[lesson]]\nsection = \"b\"\nquiz = false\n";
+        let document = parse(text, "lesson").unwrap();
+        assert_eq!(document.top.len(), 1);
+        let tables: Vec<_> = document.tables.iter().map(|(line, entries)| (*line, entries.len())).collect();
+        assert_eq!(tables, [(4, 1), (6, 2)]);
+        assert_eq!(document.tables[1].1[1].line, 8);
+    }
+
+    #[test]
+    fn errors_name_their_line() {
+        let cases = [
+            ("title = \"t\"\n[[exercise]]\n", "line 2: the only table is [[lesson]], not [[exercise]]"),
+            ("\n\njust words\n", "line 3: expected key = value"),
+            ("ids = [\n\"a\",\n", "line 1: the array is never closed"),
+            ("say = \"a \\q\"\n", "line 1: unknown escape in a string"),
+            ("title = \"t\"\nsay = \"open\n", "line 2: a string is never closed"),
+            ("say = \"a\" \"b\"\n", "line 1: unexpected '\"b\"' after the string"),
+            ("say = bare\n", "line 1: expected a \"quoted\" string, a number, true or false, not bare"),
+            ("ids = [\"a\", 1]\n", "line 1: expected a \"quoted\" string, a number, true or false, not 1"),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(parse(text, "lesson").err().unwrap(), expected, "{:?}", text);
+        }
+    }
+}
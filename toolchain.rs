@@ -0,0 +1,122 @@
+// Detects the installed Rust toolchain, so examples that need newer language
+// features can say "requires Rust 1.XX" up front instead of failing to compile
+// with a confusing error on an older compiler.
+
+use std::fmt;
+use std::process::Command;
+use std::sync::OnceLock;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+// A language or library feature some examples rely on
+pub struct Feature {
+    pub name: &'static str,
+    pub since: Version,
+}
+
+const fn rust(minor: u32) -> Version {
+    Version { major: 1, minor, patch: 0 }
+}
+
+pub const EDITION_2021: Feature = Feature { name: "edition 2021", since: rust(56) };
+pub const SCOPED_THREADS: Feature = Feature { name: "scoped threads", since: rust(63) };
+pub const LET_ELSE: Feature = Feature { name: "let-else", since: rust(65) };
+pub const ARC_INTO_INNER: Feature = Feature { name: "Arc::into_inner", since: rust(70) };
+pub const LAZY_LOCK: Feature = Feature { name: "LazyLock", since: rust(80) };
+
+pub const FEATURES: &[&Feature] = &[&EDITION_2021, &SCOPED_THREADS, &LET_ELSE, &ARC_INTO_INNER, &LAZY_LOCK];
+
+pub struct Toolchain {
+    pub rustc: Version,
+    // First line of `rustc --version`, e.g. "rustc 1.80.0 (051478957 2024-07-21)"
+    pub rustc_description: String,
+    // First line of `cargo --version`, if cargo is installed
+    pub cargo_description: Option<String>,
+}
+
+impl Toolchain {
+    pub fn supports(&self, feature: &Feature) -> bool {
+        self.rustc >= feature.since
+    }
+
+    // Why the given features can't be used, or None if they all can
+    pub fn check(&self, features: &[Feature]) -> Option<String> {
+        let missing: Vec<&Feature> = features.iter().filter(|f| !self.supports(f)).collect();
+        let newest = missing.iter().map(|f| f.since).max()?;
+        let names: Vec<&str> = missing.iter().map(|f| f.name).collect();
+        Some(format!(
+            "requires Rust {}.{} ({}), but the installed rustc is {}",
+            newest.major,
+            newest.minor,
+            names.join(", "),
+            self.rustc
+        ))
+    }
+}
+
+// The installed toolchain, detected once on first use
+pub fn installed() -> Option<&'static Toolchain> {
+    static TOOLCHAIN: OnceLock<Option<Toolchain>> = OnceLock::new();
+    TOOLCHAIN.get_or_init(detect).as_ref()
+}
+
+// Where to send learners who haven't set up Rust yet
+const INSTALL: &str = "install it from https://rustup.rs, and `doctor` will show what works";
+
+// The installed toolchain, or an error saying that `what` needs rustc and how
+// to get it. Everything that compiles goes through here (or ready_for), so a
+// missing toolchain is a clear message instead of a failure halfway through
+pub fn require(what: &str) -> Result<&'static Toolchain, String> {
+    installed().ok_or_else(|| format!("{} needs rustc, which isn't installed; {}", what, INSTALL))
+}
+
+// Like require, for the things that build with cargo
+pub fn require_cargo(what: &str) -> Result<&'static Toolchain, String> {
+    let installed = require(what)?;
+    match installed.cargo_description {
+        Some(_) => Ok(installed),
+        None => Err(format!("{} needs cargo, which isn't installed; {}", what, INSTALL)),
+    }
+}
+
+fn detect() -> Option<Toolchain> {
+    let rustc_description = version_line("rustc")?;
+    let rustc = parse_version(&rustc_description)?;
+    Some(Toolchain {
+        rustc,
+        rustc_description,
+        cargo_description: version_line("cargo"),
+    })
+}
+
+fn version_line(program: &str) -> Option<String> {
+    let output = Command::new(program).arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout.lines().next().map(str::to_string)
+}
+
+// Reads "1.80.0" out of "rustc 1.80.0 (051478957 2024-07-21)" or "rustc 1.82.0-nightly (...)"
+fn parse_version(description: &str) -> Option<Version> {
+    let number = description.split_whitespace().nth(1)?;
+    let number = number.split('-').next()?;
+    let mut parts = number.split('.').map(|part| part.parse::<u32>().ok());
+    Some(Version {
+        major: parts.next()??,
+        minor: parts.next()??,
+        patch: parts.next().flatten().unwrap_or(0),
+    })
+}
@@ -0,0 +1,135 @@
+// `--trace-clones`: where did all these clones come from? In a real codebase a
+// profiler points at a hot allocation, and finding the .clone() behind it
+// means walking up the stack to your own code. The lessons' values that are
+// worth tracing are wrapped in Tracked; with the flag, every clone of one
+// captures a backtrace, and when the command finishes a report lists the
+// places in the demo's own code that cloned the most.
+//
+// Without the flag a clone is the inner clone plus one atomic load. With it,
+// capturing backtraces costs far more than the clones themselves (and
+// allocates), so the lessons' timings and allocation counts are off for that
+// run. Backtraces name functions in any build, and files and lines when the
+// program has debug info (as a debug build does).
+
+use std::backtrace::Backtrace;
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// How many clone sites the report lists
+const TOP_SITES: usize = 10;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static CLONES: Mutex<Vec<Backtrace>> = Mutex::new(Vec::new());
+
+// A value whose clones are recorded under --trace-clones. It derefs to the
+// value, so it reads like one
+pub struct Tracked<T>(pub T);
+
+impl<T: Clone> Clone for Tracked<T> {
+    fn clone(&self) -> Self {
+        if ENABLED.load(Ordering::Relaxed) {
+            let backtrace = Backtrace::force_capture();
+            CLONES.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push(backtrace);
+        }
+        Tracked(self.0.clone())
+    }
+}
+
+impl<T> Deref for Tracked<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+// Removes `--trace-clones` from the arguments and starts recording clones
+pub fn take_flag(args: &mut Vec<String>) {
+    if let Some(position) = args.iter().position(|arg| arg == "--trace-clones") {
+        args.remove(position);
+        ENABLED.store(true, Ordering::Relaxed);
+    }
+}
+
+// A frame of a backtrace: the function, and where in it if debug info says
+struct Frame {
+    function: String,
+    location: Option<String>,
+}
+
+impl Frame {
+    fn describe(&self) -> String {
+        match &self.location {
+            Some(location) => format!("{} ({})", self.function, location),
+            None => self.function.clone(),
+        }
+    }
+}
+
+// Prints the top clone sites to stderr, if --trace-clones was given
+pub fn print_report() {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let clones = std::mem::take(&mut *CLONES.lock().unwrap_or_else(|poisoned| poisoned.into_inner()));
+    if clones.is_empty() {
+        eprintln!("\n--trace-clones: nothing cloned a Tracked value");
+        return;
+    }
+
+    // A site is the first frame in the demo's own code that isn't a Clone
+    // impl (a derived Clone clones each field, so it's never the culprit),
+    // together with the demo function that called it, for context
+    let mut sites: HashMap<(String, Option<String>), usize> = HashMap::new();
+    for backtrace in &clones {
+        let frames = own_frames(&backtrace.to_string());
+        let mut callers = frames.iter().skip_while(|frame| frame.function.ends_with("::clone"));
+        let site = callers.next().map_or_else(|| "(outside the demo's code)".to_string(), Frame::describe);
+        *sites.entry((site, callers.next().map(Frame::describe))).or_default() += 1;
+    }
+    let mut sites: Vec<_> = sites.into_iter().collect();
+    sites.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+
+    eprintln!("\n--trace-clones: {} clones of Tracked values, from {} places", clones.len(), sites.len());
+    for ((site, caller), count) in sites.iter().take(TOP_SITES) {
+        eprintln!("  {:>7}  {}", count, site);
+        if let Some(caller) = caller {
+            eprintln!("           called from {}", caller);
+        }
+    }
+    if sites.len() > TOP_SITES {
+        eprintln!("  ({} more places cloned less)", sites.len() - TOP_SITES);
+    }
+}
+
+// The frames of a printed backtrace that are in the demo's own code, outside
+// this file. A frame is printed as "  12: crate::module::function", followed
+// by "at ./path/file.rs:40:9" when the location is known
+fn own_frames(backtrace: &str) -> Vec<Frame> {
+    let own_crate = module_path!().split("::").next().unwrap_or_default();
+    let mut frames: Vec<Frame> = Vec::new();
+    // Whether the frame being read is one of ours, so its "at" line is kept
+    let mut own = false;
+    for line in backtrace.lines().map(str::trim) {
+        if let Some(location) = line.strip_prefix("at ") {
+            if let Some(frame) = frames.last_mut().filter(|_| own) {
+                frame.location.get_or_insert_with(|| location.trim_start_matches("./").to_string());
+            }
+            continue;
+        }
+        let Some((number, function)) = line.split_once(": ") else { continue };
+        if number.parse::<usize>().is_err() {
+            continue;
+        }
+        // Trait impls print as <crate::Type as core::clone::Clone>::clone
+        let function = function.trim_start_matches('<');
+        own = function.starts_with(own_crate) && !function.starts_with(module_path!());
+        if own {
+            let function = function[own_crate.len()..].trim_start_matches("::");
+            frames.push(Frame { function: function.to_string(), location: None });
+        }
+    }
+    frames
+}
@@ -0,0 +1,390 @@
+// Whether a lesson pack may run on this machine. A pack's tests and solutions
+// are Rust code: `pack install` compiles and runs them to check the pack, and
+// `exercise check` runs its tests against the learner's code. confine.rs keeps
+// them from hanging, but they still run as the learner, so someone has to
+// decide to trust a pack before any of it runs.
+//
+// A pack can carry a SHA256SUMS file in `sha256sum` format covering pack.toml
+// and every file it names. When it does, installing refuses a pack whose files
+// don't match. The author can also sign that file with an SSH key:
+//
+//   ssh-keygen -Y sign -f ~/.ssh/id_ed25519 -n rust_ownership_demo-pack SHA256SUMS
+//
+// which writes SHA256SUMS.sig. It's checked with ssh-keygen against the keys
+// in an allowed signers file (RUST_OWNERSHIP_DEMO_SIGNERS, or
+// ~/.config/rust_ownership_demo/allowed_signers), one `<who> <key>` per line.
+//
+// What happens next is the trust policy, from `pack install --trust <policy>`
+// or RUST_OWNERSHIP_DEMO_TRUST:
+//
+//   ask     (the default) packs signed by an allowed signer are trusted, and
+//           for any other one you're asked, on a terminal
+//   signed  only packs signed by an allowed signer; for classrooms, where the
+//           instructor signs the packs and the machines carry their key
+//   all     every pack that passes its checks, without asking (scripts, CI)
+//   none    no packs at all
+//
+// A trusted pack is recorded by its fingerprint, a SHA-256 over its files, and
+// an installed pack whose files no longer match is skipped. That catches a
+// pack copied in by hand or edited after it was checked; it doesn't stop
+// someone who can write to the packs directory, since they can edit the
+// record too.
+
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::sha256;
+
+pub const CHECKSUMS: &str = "SHA256SUMS";
+pub const SIGNATURE: &str = "SHA256SUMS.sig";
+
+// What a signature says it's for, so one made for something else can't be reused
+pub const NAMESPACE: &str = "rust_ownership_demo-pack";
+const POLICY_VAR: &str = "RUST_OWNERSHIP_DEMO_TRUST";
+const SIGNERS_VAR: &str = "RUST_OWNERSHIP_DEMO_SIGNERS";
+
+pub enum Policy {
+    Ask,
+    Signed,
+    All,
+    Nothing,
+}
+
+// How much a pack vouches for its own files
+pub enum Seal {
+    // No SHA256SUMS
+    Unsealed,
+    // SHA256SUMS matches, and there's no signature
+    Checksums,
+    // SHA256SUMS matches and is signed, by a key that isn't an allowed signer
+    UnknownSigner,
+    // SHA256SUMS matches and is signed by this allowed signer
+    Signed(String),
+}
+
+impl Seal {
+    pub fn describe(&self) -> String {
+        match self {
+            Seal::Unsealed => "no checksums, unsigned".to_string(),
+            Seal::Checksums => "checksums match, unsigned".to_string(),
+            Seal::UnknownSigner => format!("signed by a key that isn't in {}", signers_file().display()),
+            Seal::Signed(signer) => format!("signed by {}", signer),
+        }
+    }
+}
+
+// The policy `--trust` in `args` names, or else RUST_OWNERSHIP_DEMO_TRUST's
+pub fn policy(args: &[String]) -> Result<Policy, String> {
+    let value = match args.iter().position(|arg| arg == "--trust") {
+        Some(i) => args.get(i + 1).cloned().ok_or("--trust needs a policy: ask, signed, all or none")?,
+        None => env::var(POLICY_VAR).unwrap_or_default(),
+    };
+    match value.as_str() {
+        "" | "ask" => Ok(Policy::Ask),
+        "signed" => Ok(Policy::Signed),
+        "all" => Ok(Policy::All),
+        "none" => Ok(Policy::Nothing),
+        other => Err(format!("unknown trust policy '{}'; it's ask, signed, all or none", other)),
+    }
+}
+
+// $RUST_OWNERSHIP_DEMO_SIGNERS, or allowed_signers in the config directory
+fn signers_file() -> PathBuf {
+    if let Some(path) = env::var_os(SIGNERS_VAR) {
+        return PathBuf::from(path);
+    }
+    config_dir().join("allowed_signers")
+}
+
+// rust_ownership_demo under $XDG_CONFIG_HOME or ~/.config
+pub fn config_dir() -> PathBuf {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .unwrap_or_else(env::temp_dir);
+    base.join("rust_ownership_demo")
+}
+
+// Checks the pack in `dir` against its SHA256SUMS and signature, if it has
+// them. `files` are the ones the pack is made of, pack.toml included; each has
+// to be in SHA256SUMS, so none can be swapped without breaking the signature
+pub fn verify(dir: &Path, files: &[PathBuf]) -> Result<Seal, String> {
+    let sums_path = dir.join(CHECKSUMS);
+    let signed = dir.join(SIGNATURE).exists();
+    let Ok(sums) = fs::read_to_string(&sums_path) else {
+        if signed {
+            return Err(format!("it has a {} but no {} for it to sign", SIGNATURE, CHECKSUMS));
+        }
+        return Ok(Seal::Unsealed);
+    };
+
+    let mut listed = Vec::new();
+    for (number, line) in sums.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+        let bad = || format!("{} line {}: expected `<sha256>  <file>`", CHECKSUMS, number + 1);
+        let (expected, file) = line.split_once(' ').ok_or_else(bad)?;
+        // sha256sum marks files read in binary mode with a *
+        let file = file.trim_start().trim_start_matches('*');
+        let relative = PathBuf::from(file);
+        if expected.len() != 64 || file.is_empty() || !relative.components().all(|c| matches!(c, Component::Normal(_))) {
+            return Err(bad());
+        }
+        let bytes = fs::read(dir.join(&relative)).map_err(|e| format!("could not read {}: {}", file, e))?;
+        if sha256::hex(&bytes) != expected.to_lowercase() {
+            return Err(format!("{} doesn't match its checksum in {}; it has been changed", file, CHECKSUMS));
+        }
+        listed.push(relative);
+    }
+    if let Some(missing) = files.iter().find(|file| !listed.contains(file)) {
+        return Err(format!("{} isn't listed in {}", missing.display(), CHECKSUMS));
+    }
+
+    if signed {
+        check_signature(dir)
+    } else {
+        Ok(Seal::Checksums)
+    }
+}
+
+// Asks ssh-keygen which allowed signer made SHA256SUMS.sig, then whether the
+// signature really is theirs over this SHA256SUMS
+fn check_signature(dir: &Path) -> Result<Seal, String> {
+    let signers = signers_file();
+    if !signers.exists() {
+        return Ok(Seal::UnknownSigner);
+    }
+    let signature = dir.join(SIGNATURE);
+    let found = Command::new("ssh-keygen")
+        .args(["-Y", "find-principals", "-s"])
+        .arg(&signature)
+        .arg("-f")
+        .arg(&signers)
+        .output()
+        .map_err(|e| format!("could not run ssh-keygen to check the signature (is OpenSSH installed?): {}", e))?;
+    let principals = String::from_utf8_lossy(&found.stdout);
+    let Some(signer) = principals.lines().next().filter(|_| found.status.success()) else {
+        return Ok(Seal::UnknownSigner);
+    };
+
+    let sums = fs::File::open(dir.join(CHECKSUMS)).map_err(|e| format!("could not read {}: {}", CHECKSUMS, e))?;
+    let verified = Command::new("ssh-keygen")
+        .args(["-Y", "verify", "-n", NAMESPACE, "-I", signer, "-s"])
+        .arg(&signature)
+        .arg("-f")
+        .arg(&signers)
+        .stdin(sums)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| format!("could not run ssh-keygen to check the signature: {}", e))?;
+    if !verified.success() {
+        return Err(format!("{} isn't a valid signature of {} by {}", SIGNATURE, CHECKSUMS, signer));
+    }
+    Ok(Seal::Signed(signer.to_string()))
+}
+
+// Decides, by the policy, whether the pack described by `about` may run here.
+// `known` is whether this exact pack was trusted before, so asking again about
+// a reinstall would tell nobody anything
+pub fn decide(policy: &Policy, about: &str, seal: &Seal, known: bool) -> Result<(), String> {
+    match (policy, seal) {
+        (Policy::Nothing, _) => Err("the trust policy is none, so no lesson packs can be installed here".to_string()),
+        (Policy::All, _) | (Policy::Ask | Policy::Signed, Seal::Signed(_)) => Ok(()),
+        (Policy::Ask, _) if known => Ok(()),
+        (Policy::Signed, _) => Err(format!(
+            "the trust policy is signed, so only packs signed by a key in {} can be installed, and this one is {}",
+            signers_file().display(),
+            seal.describe()
+        )),
+        (Policy::Ask, _) => ask(about, seal),
+    }
+}
+
+fn ask(about: &str, seal: &Seal) -> Result<(), String> {
+    if !io::stdin().is_terminal() {
+        return Err(format!(
+            "{} ({}) has to be trusted before it runs, and there's no terminal to ask on. \
+             Pass --trust all to trust it, or sign it with a key in {}",
+            about,
+            seal.describe(),
+            signers_file().display()
+        ));
+    }
+    println!("{} ({})", about, seal.describe());
+    println!("Its tests and solutions are Rust code. Installing compiles and runs them, and");
+    println!("`exercise check` runs its tests with your code. They can't hang the demo, but they");
+    println!("run as you, with your files and network. Install only packs you trust.");
+    print!("Trust this pack? [y/N] ");
+    io::stdout().flush().map_err(|e| format!("could not write to the terminal: {}", e))?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer).map_err(|e| format!("could not read the keyboard: {}", e))?;
+    match answer.trim() {
+        "y" | "Y" | "yes" => Ok(()),
+        _ => Err("not trusted, so nothing was installed".to_string()),
+    }
+}
+
+// The SHA256SUMS text for `files`, as `pack seal` writes it
+pub fn checksums(dir: &Path, files: &[PathBuf]) -> Result<String, String> {
+    let mut files = files.to_vec();
+    files.sort();
+    files.dedup();
+    let mut sums = String::new();
+    for file in &files {
+        let bytes = fs::read(dir.join(file)).map_err(|e| format!("could not read {}: {}", file.display(), e))?;
+        sums.push_str(&format!("{}  {}\n", sha256::hex(&bytes), file.display()));
+    }
+    Ok(sums)
+}
+
+// A SHA-256 over the pack's files: the digest of their SHA256SUMS text
+pub fn fingerprint(dir: &Path, files: &[PathBuf]) -> Result<String, String> {
+    checksums(dir, files).map(|sums| sha256::hex(sums.as_bytes()))
+}
+
+// The record of trusted packs: `<fingerprint> <name>` lines in `store`
+pub fn is_trusted(store: &Path, fingerprint: &str) -> bool {
+    let text = fs::read_to_string(store).unwrap_or_default();
+    text.lines().any(|line| line.split_whitespace().next() == Some(fingerprint))
+}
+
+// Records `name` as trusted at `fingerprint`, in place of any earlier version
+pub fn record(store: &Path, name: &str, fingerprint: &str) -> Result<(), String> {
+    let mut lines = others(store, name);
+    lines.push(format!("{} {}", fingerprint, name));
+    write(store, &lines)
+}
+
+pub fn forget(store: &Path, name: &str) -> Result<(), String> {
+    write(store, &others(store, name))
+}
+
+fn others(store: &Path, name: &str) -> Vec<String> {
+    let text = fs::read_to_string(store).unwrap_or_default();
+    text.lines().filter(|line| line.split_whitespace().nth(1) != Some(name)).map(str::to_string).collect()
+}
+
+fn write(store: &Path, lines: &[String]) -> Result<(), String> {
+    let mut text = lines.join("\n");
+    if !text.is_empty() {
+        text.push('\n');
+    }
+    fs::write(store, text).map_err(|e| format!("could not write {}: {}", store.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scratch;
+
+    // A pack directory holding `files`, plus a SHA256SUMS of `sums` if given
+    fn pack(files: &[(&str, &str)], sums: Option<&str>) -> PathBuf {
+        let dir = scratch::private_dir("test").unwrap();
+        for (name, text) in files {
+            fs::write(dir.join(name), text).unwrap();
+        }
+        if let Some(sums) = sums {
+            fs::write(dir.join(CHECKSUMS), sums).unwrap();
+        }
+        dir
+    }
+
+    fn names(files: &[&str]) -> Vec<PathBuf> {
+        files.iter().map(PathBuf::from).collect()
+    }
+
+    fn verify_error(files: &[(&str, &str)], sums: &str) -> String {
+        let dir = pack(files, Some(sums));
+        let error = verify(&dir, &names(&["pack.toml"])).err().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        error
+    }
+
+    #[test]
+    fn a_pack_without_checksums_is_unsealed() {
+        let dir = pack(&[("pack.toml", "")], None);
+        assert!(matches!(verify(&dir, &names(&["pack.toml"])), Ok(Seal::Unsealed)));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn matching_checksums_seal_the_pack() {
+        let files = [("pack.toml", "name = \"p\"\n"), ("lesson.rs", "fn main() {}\n")];
+        let dir = pack(&files, None);
+        fs::write(dir.join(CHECKSUMS), checksums(&dir, &names(&["pack.toml", "lesson.rs"])).unwrap()).unwrap();
+        assert!(matches!(verify(&dir, &names(&["pack.toml", "lesson.rs"])), Ok(Seal::Checksums)));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn binary_mode_names_are_accepted() {
+        // sha256sum -b writes `<sha256> *<file>`
+        let sums = format!("{} *pack.toml\n", sha256::hex(b"name = \"p\"\n").to_uppercase());
+        let dir = pack(&[("pack.toml", "name = \"p\"\n")], Some(&sums));
+        assert!(matches!(verify(&dir, &names(&["pack.toml"])), Ok(Seal::Checksums)));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn malformed_lines_are_rejected() {
+        let expected = format!("{} line 2: expected `<sha256>  <file>`", CHECKSUMS);
+        let good = format!("{}  pack.toml\n", sha256::hex(b""));
+        for bad in ["pack.toml", "abc123  pack.toml", &format!("{}  ", sha256::hex(b""))] {
+            assert_eq!(verify_error(&[("pack.toml", "")], &format!("{}{}\n", good, bad)), expected, "{}", bad);
+        }
+    }
+
+    #[test]
+    fn paths_outside_the_pack_are_rejected() {
+        for file in ["../pack.toml", "/etc/passwd", "./pack.toml"] {
+            let sums = format!("{}  {}\n", sha256::hex(b""), file);
+            let error = verify_error(&[("pack.toml", "")], &sums);
+            assert_eq!(error, format!("{} line 1: expected `<sha256>  <file>`", CHECKSUMS), "{}", file);
+        }
+    }
+
+    #[test]
+    fn a_changed_file_is_rejected() {
+        let sums = format!("{}  pack.toml\n", sha256::hex(b"name = \"p\"\n"));
+        let error = verify_error(&[("pack.toml", "name = \"q\"\n")], &sums);
+        assert_eq!(error, format!("pack.toml doesn't match its checksum in {}; it has been changed", CHECKSUMS));
+    }
+
+    #[test]
+    fn a_file_missing_from_the_checksums_is_rejected() {
+        let dir = pack(&[("pack.toml", ""), ("lesson.rs", "")], None);
+        fs::write(dir.join(CHECKSUMS), checksums(&dir, &names(&["pack.toml"])).unwrap()).unwrap();
+        let error = verify(&dir, &names(&["pack.toml", "lesson.rs"])).err().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(error, format!("lesson.rs isn't listed in {}", CHECKSUMS));
+    }
+
+    #[test]
+    fn a_signature_without_checksums_is_rejected() {
+        let dir = pack(&[("pack.toml", ""), (SIGNATURE, "")], None);
+        let error = verify(&dir, &names(&["pack.toml"])).err().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(error, format!("it has a {} but no {} for it to sign", SIGNATURE, CHECKSUMS));
+    }
+
+    #[test]
+    fn recording_again_replaces_the_earlier_fingerprint() {
+        let dir = scratch::private_dir("test").unwrap();
+        let store = dir.join("trusted");
+        record(&store, "borrowing", "aaaa").unwrap();
+        record(&store, "closures", "bbbb").unwrap();
+        assert!(is_trusted(&store, "aaaa") && is_trusted(&store, "bbbb"));
+
+        record(&store, "borrowing", "cccc").unwrap();
+        assert!(!is_trusted(&store, "aaaa") && is_trusted(&store, "cccc"));
+        assert_eq!(fs::read_to_string(&store).unwrap(), "bbbb closures\ncccc borrowing\n");
+
+        forget(&store, "borrowing").unwrap();
+        assert!(!is_trusted(&store, "cccc") && is_trusted(&store, "bbbb"));
+        forget(&store, "closures").unwrap();
+        assert_eq!(fs::read_to_string(&store).unwrap(), "");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,325 @@
+// `self-update`: replaces the running binary with the newest release, so an
+// instructor who fixes a lesson the morning of a class can have everyone on
+// the fix with one command.
+//
+// Releases are described by a manifest at the update URL (given with --from,
+// or set once in $RUST_OWNERSHIP_DEMO_UPDATE_URL for a workshop's machines):
+//
+//   version 0.2.0
+//   x86_64-linux   <sha256 of the binary>  rust_ownership_demo-x86_64-linux
+//   aarch64-macos  <sha256 of the binary>  https://example.org/rust_ownership_demo-aarch64-macos
+//
+// Binary locations are relative to the manifest unless they're full URLs;
+// either way they, like the manifest, have to be https. The manifest is signed
+// with an SSH key next to it, as <manifest url>.sig:
+//
+//   ssh-keygen -Y sign -f ~/.ssh/id_ed25519 -n rust_ownership_demo-update manifest.txt
+//
+// and the signature is checked with ssh-keygen against update_signers in the
+// config directory (~/.config/rust_ownership_demo/update_signers), one
+// `<who> <key>` per line. That file is separate from the pack signers, so a
+// key trusted to sign lesson packs can't also sign releases, and there's no
+// variable to point it elsewhere. Without it, or without a valid signature,
+// nothing is downloaded past the manifest. Downloads go through curl (or
+// wget), and a binary whose SHA-256 doesn't match the signed manifest is
+// thrown away before anything is replaced.
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::scratch;
+use crate::sha256;
+use crate::trust;
+
+const URL_VARIABLE: &str = "RUST_OWNERSHIP_DEMO_UPDATE_URL";
+// ssh-keygen's namespace for release manifests, so a signature made for
+// anything else (a pack's SHA256SUMS, say) doesn't verify as one
+const NAMESPACE: &str = "rust_ownership_demo-update";
+
+// What the manifest says about the release for this platform
+struct Release {
+    version: String,
+    sha256: String,
+    url: String,
+}
+
+pub fn command(args: &[String]) -> Result<(), String> {
+    let check_only = args.iter().any(|arg| arg == "--check");
+    let force = args.iter().any(|arg| arg == "--force");
+    let from = match args.iter().position(|arg| arg == "--from") {
+        Some(i) => Some(args.get(i + 1).filter(|url| !url.starts_with("--")).ok_or("--from needs a manifest URL")?),
+        None => None,
+    };
+    let manifest_url = match from {
+        Some(url) => url.clone(),
+        None => env::var(URL_VARIABLE)
+            .map_err(|_| format!("no update URL; pass --from <manifest url> or set {}", URL_VARIABLE))?,
+    };
+    require_https(&manifest_url)?;
+
+    let scratch = scratch::private_dir("update")?;
+    let result = update(&manifest_url, &scratch, check_only, force);
+    let _ = fs::remove_dir_all(&scratch);
+    result
+}
+
+fn update(manifest_url: &str, scratch: &Path, check_only: bool, force: bool) -> Result<(), String> {
+    let manifest_path = scratch.join("manifest.txt");
+    let signature_path = scratch.join("manifest.txt.sig");
+    download(manifest_url, &manifest_path)?;
+    download(&format!("{}.sig", manifest_url), &signature_path)?;
+    // Read once, so the bytes that are parsed are the ones that were verified
+    let manifest = fs::read(&manifest_path).map_err(|e| format!("could not read the manifest: {}", e))?;
+    let signer = check_signature(&manifest, &signature_path)?;
+    let manifest = String::from_utf8(manifest).map_err(|_| "the manifest isn't UTF-8 text".to_string())?;
+    let release = parse_manifest(&manifest, manifest_url)?;
+    require_https(&release.url)?;
+
+    let current = env!("CARGO_PKG_VERSION");
+    if !force && !is_newer(&release.version, current) {
+        println!("Already up to date ({}; the newest release is {})", current, release.version);
+        return Ok(());
+    }
+    if check_only {
+        println!("Version {} is available (this is {}); run `self-update` to install it", release.version, current);
+        return Ok(());
+    }
+
+    println!("Downloading {} for {} (signed by {})...", release.version, platform(), signer);
+    let binary_path = scratch.join("binary");
+    download(&release.url, &binary_path)?;
+    let binary = fs::read(&binary_path).map_err(|e| format!("could not read the download: {}", e))?;
+    let actual = sha256::hex(&binary);
+    if actual != release.sha256 {
+        return Err(format!(
+            "the download's SHA-256 is {}, but the manifest says {}; nothing was changed",
+            actual, release.sha256
+        ));
+    }
+
+    let exe = env::current_exe().map_err(|e| format!("could not find the running program: {}", e))?;
+    replace(&exe, &binary)?;
+    println!("Updated {} from {} to {}", exe.display(), current, release.version);
+    Ok(())
+}
+
+// Reads the version and this platform's line out of the manifest
+fn parse_manifest(manifest: &str, manifest_url: &str) -> Result<Release, String> {
+    let mut version = None;
+    let mut binary = None;
+    for line in manifest.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields.as_slice() {
+            ["version", number] => version = Some(number.to_string()),
+            [target, sha256, location] if *target == platform() => binary = Some((sha256.to_lowercase(), *location)),
+            _ => {}
+        }
+    }
+    let version = version.ok_or("the manifest has no `version` line")?;
+    let (sha256, location) = binary.ok_or_else(|| format!("release {} has no binary for {}", version, platform()))?;
+    if sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("the manifest's checksum for {} isn't a SHA-256", platform()));
+    }
+    Ok(Release { version, sha256, url: resolve(manifest_url, location) })
+}
+
+// Plain http (or file://, ftp://...) would let anyone on the network hand out
+// a binary or a manifest
+fn require_https(url: &str) -> Result<(), String> {
+    if url.starts_with("https://") {
+        Ok(())
+    } else {
+        Err(format!("updates are only downloaded over https, not from {}", url))
+    }
+}
+
+// The update_signers file; pinned to the config directory on purpose
+fn signers_file() -> PathBuf {
+    trust::config_dir().join("update_signers")
+}
+
+// Asks ssh-keygen which update signer made the manifest's signature, then
+// whether it really is theirs over this manifest. Returns who signed it
+fn check_signature(manifest: &[u8], signature: &Path) -> Result<String, String> {
+    let signers = signers_file();
+    if !signers.exists() {
+        return Err(format!(
+            "there's no {} to check the release's signature against; nothing was changed",
+            signers.display()
+        ));
+    }
+    let found = Command::new("ssh-keygen")
+        .args(["-Y", "find-principals", "-s"])
+        .arg(signature)
+        .arg("-f")
+        .arg(&signers)
+        .output()
+        .map_err(|e| format!("could not run ssh-keygen to check the signature (is OpenSSH installed?): {}", e))?;
+    let principals = String::from_utf8_lossy(&found.stdout);
+    let Some(signer) = principals.lines().next().filter(|_| found.status.success()) else {
+        return Err(format!("the manifest isn't signed by anyone in {}; nothing was changed", signers.display()));
+    };
+
+    let mut verify = Command::new("ssh-keygen")
+        .args(["-Y", "verify", "-n", NAMESPACE, "-I", signer, "-s"])
+        .arg(signature)
+        .arg("-f")
+        .arg(&signers)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("could not run ssh-keygen to check the signature: {}", e))?;
+    // Dropped after writing, so ssh-keygen sees the end of the manifest
+    if let Some(mut stdin) = verify.stdin.take() {
+        stdin.write_all(manifest).map_err(|e| format!("could not pass ssh-keygen the manifest: {}", e))?;
+    }
+    let verified = verify.wait().map_err(|e| format!("ssh-keygen didn't finish: {}", e))?;
+    if !verified.success() {
+        return Err(format!("the manifest's signature by {} isn't valid; nothing was changed", signer));
+    }
+    Ok(signer.to_string())
+}
+
+// The manifest's name for this machine, e.g. "x86_64-linux"
+fn platform() -> String {
+    format!("{}-{}", env::consts::ARCH, env::consts::OS)
+}
+
+// A binary's location relative to the manifest's URL, unless it's a full URL
+fn resolve(manifest_url: &str, location: &str) -> String {
+    if location.contains("://") {
+        return location.to_string();
+    }
+    match manifest_url.rsplit_once('/') {
+        Some((base, _)) => format!("{}/{}", base, location),
+        None => location.to_string(),
+    }
+}
+
+// Compares dotted versions number by number, so 0.10.0 is newer than 0.9.3
+fn is_newer(candidate: &str, current: &str) -> bool {
+    let numbers = |version: &str| -> Vec<u64> { version.split('.').map(|part| part.parse().unwrap_or(0)).collect() };
+    numbers(candidate) > numbers(current)
+}
+
+// Fetches `url` into `dest` with whichever of curl or wget is installed
+fn download(url: &str, dest: &Path) -> Result<(), String> {
+    let dest_arg = dest.as_os_str();
+    // Redirects have to stay on https too
+    let mut curl = Command::new("curl");
+    curl.args(["--fail", "--silent", "--show-error", "--location", "--proto", "=https", "--proto-redir", "=https"])
+        .args(["--max-time", "300", "--output"]).arg(dest_arg).arg(url);
+    let mut wget = Command::new("wget");
+    wget.args(["--quiet", "--https-only", "--timeout=300", "--output-document"]).arg(dest_arg).arg(url);
+
+    for command in [&mut curl, &mut wget] {
+        let program = command.get_program().to_string_lossy().into_owned();
+        match command.stdin(Stdio::null()).output() {
+            Ok(output) if output.status.success() => return Ok(()),
+            Ok(output) => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(format!("{} could not download {}: {}", program, url, stderr.trim()));
+            }
+            // Not installed; try the next one
+            Err(_) => continue,
+        }
+    }
+    Err("downloading updates needs curl or wget on the PATH".to_string())
+}
+
+// Swaps the new binary in for `exe`. It's written next to it first, so the
+// final rename can't leave a half-written program behind
+fn replace(exe: &Path, binary: &[u8]) -> Result<(), String> {
+    let sibling = |suffix: &str| -> PathBuf {
+        let mut name = exe.file_name().unwrap_or_default().to_os_string();
+        name.push(suffix);
+        exe.with_file_name(name)
+    };
+    let (new, old) = (sibling(".new"), sibling(".old"));
+
+    fs::write(&new, binary).map_err(|e| format!("could not write {}: {}", new.display(), e))?;
+    if let Ok(metadata) = fs::metadata(exe) {
+        // Keeps the executable bit (and whatever else) the old binary had
+        let _ = fs::set_permissions(&new, metadata.permissions());
+    }
+    // A running program can be renamed but, on Windows, not overwritten
+    let _ = fs::remove_file(&old);
+    fs::rename(exe, &old).map_err(|e| format!("could not move {} aside: {}", exe.display(), e))?;
+    if let Err(e) = fs::rename(&new, exe) {
+        let _ = fs::rename(&old, exe);
+        return Err(format!("could not install the update at {}: {}", exe.display(), e));
+    }
+    // Windows keeps the old file locked until we exit; it's removed next time
+    let _ = fs::remove_file(&old);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUM: &str = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+    const MANIFEST_URL: &str = "https://example.org/releases/manifest.txt";
+
+    fn manifest(lines: &[String]) -> Result<Release, String> {
+        parse_manifest(&lines.join("\n"), MANIFEST_URL)
+    }
+
+    #[test]
+    fn reads_this_platforms_release() {
+        let release = manifest(&[
+            "version 0.2.0".to_string(),
+            format!("other-os {} elsewhere", SUM),
+            format!("{} {} rust_ownership_demo", platform(), SUM.to_uppercase()),
+        ])
+        .unwrap();
+        assert_eq!(release.version, "0.2.0");
+        assert_eq!(release.sha256, SUM);
+        assert_eq!(release.url, "https://example.org/releases/rust_ownership_demo");
+    }
+
+    #[test]
+    fn needs_a_version_line() {
+        let error = manifest(&[format!("{} {} binary", platform(), SUM)]).err().unwrap();
+        assert!(error.contains("no `version` line"), "{}", error);
+    }
+
+    #[test]
+    fn needs_a_line_for_this_platform() {
+        let error = manifest(&["version 0.2.0".to_string(), format!("other-os {} binary", SUM)]).err().unwrap();
+        assert!(error.contains(&format!("no binary for {}", platform())), "{}", error);
+    }
+
+    #[test]
+    fn needs_a_whole_checksum() {
+        // Too short, too long, and the right length but not hex
+        for sum in [SUM[1..].to_string(), format!("{}0", SUM), format!("{}g", &SUM[1..])] {
+            let error = manifest(&["version 0.2.0".to_string(), format!("{} {} binary", platform(), sum)]).err().unwrap();
+            assert!(error.contains("isn't a SHA-256"), "{}", error);
+        }
+    }
+
+    #[test]
+    fn compares_versions_by_number() {
+        assert!(is_newer("0.10.0", "0.9.3"));
+        assert!(!is_newer("0.9.3", "0.10.0"));
+        assert!(is_newer("1.0.0", "0.99.99"));
+        assert!(is_newer("0.2.1", "0.2"));
+        assert!(!is_newer("0.2.0", "0.2.0"));
+    }
+
+    #[test]
+    fn resolves_binaries_next_to_the_manifest() {
+        assert_eq!(resolve(MANIFEST_URL, "binary"), "https://example.org/releases/binary");
+        assert_eq!(resolve(MANIFEST_URL, "linux/binary"), "https://example.org/releases/linux/binary");
+        assert_eq!(resolve(MANIFEST_URL, "https://cdn.example.org/binary"), "https://cdn.example.org/binary");
+        // A full URL is kept as it is, so require_https still sees the scheme
+        assert_eq!(resolve(MANIFEST_URL, "http://example.org/binary"), "http://example.org/binary");
+        assert!(require_https(&resolve(MANIFEST_URL, "http://example.org/binary")).is_err());
+        assert!(require_https(&resolve(MANIFEST_URL, "binary")).is_ok());
+    }
+}
@@ -0,0 +1,103 @@
+// The string helpers the lessons narrate, written the way a real API would
+// be: they borrow a str, so Strings, literals and slices of either all work
+// without conversions, and whatever they return borrows from their input
+// instead of copying it.
+//
+// The lessons' standalone code keeps the Rust Book's versions (a byte loop,
+// a &String parameter) because that's what they teach from; these are the
+// ones worth copying.
+
+// The first space-separated word of `s`, or all of `s` if it has no space.
+// The result is a slice of `s`, so it can be used as long as `s` can:
+//
+//   get_first_word("hello world") == "hello"
+//   get_first_word("") == ""
+//
+// It slices at a byte index, which can't split a character: b' ' is never
+// part of a multi-byte UTF-8 sequence (practical_first_word_properties
+// checks this on random strings).
+pub fn get_first_word(s: &str) -> &str {
+    match s.bytes().position(|byte| byte == b' ') {
+        Some(space) => &s[..space],
+        None => s,
+    }
+}
+
+// The length of `s` in bytes, which is what String::len and str::len count;
+// "héllo" is 6. It takes anything that can lend a &str: a literal, a
+// &String, a String, a Box<str> or a Cow<str>. Borrowing means the caller
+// keeps its String:
+//
+//   let s = String::from("hello");
+//   calculate_length(&s) == 5  // and s is still usable
+//
+// It never took &String: that only accepts a String that already exists
+// (api_design_string_params shows why).
+pub fn calculate_length(s: impl AsRef<str>) -> usize {
+    s.as_ref().len()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::*;
+
+    #[test]
+    fn first_word_of_an_empty_string_is_empty() {
+        assert_eq!(get_first_word(""), "");
+    }
+
+    #[test]
+    fn first_word_without_a_space_is_the_whole_string() {
+        assert_eq!(get_first_word("hello"), "hello");
+    }
+
+    #[test]
+    fn first_word_after_a_leading_space_is_empty() {
+        assert_eq!(get_first_word(" hello world"), "");
+    }
+
+    #[test]
+    fn first_word_keeps_multi_byte_characters_whole() {
+        assert_eq!(get_first_word("héllo wörld"), "héllo");
+        assert_eq!(get_first_word("🦀🦀 crab"), "🦀🦀");
+    }
+
+    #[test]
+    fn first_word_borrows_a_prefix_of_its_input() {
+        let text = String::from("hello world");
+        let word = get_first_word(&text);
+        assert_eq!(word.as_ptr(), text.as_ptr());
+        assert!(text.starts_with(word));
+    }
+
+    #[test]
+    fn length_of_a_literal() {
+        assert_eq!(calculate_length("héllo"), 6);
+    }
+
+    #[test]
+    fn length_of_a_borrowed_string_leaves_it_usable() {
+        let s = String::from("hello");
+        assert_eq!(calculate_length(&s), 5);
+        assert_eq!(s, "hello");
+    }
+
+    #[test]
+    fn length_of_an_owned_string() {
+        assert_eq!(calculate_length(String::from("hello")), 5);
+    }
+
+    #[test]
+    fn length_of_a_boxed_str() {
+        let boxed: Box<str> = Box::from("hello");
+        assert_eq!(calculate_length(boxed), 5);
+    }
+
+    #[test]
+    fn length_of_a_cow() {
+        assert_eq!(calculate_length(Cow::Borrowed("hello")), 5);
+        assert_eq!(calculate_length(Cow::<str>::Owned(String::from("hello"))), 5);
+    }
+}
@@ -0,0 +1,178 @@
+// xAPI (Tin Can) statements, so a learning platform can record what was done
+// with the demo. It's off unless RUST_OWNERSHIP_DEMO_XAPI is set; then
+// finishing an example records "completed", solving an exercise records
+// "passed", and a classroom question records "answered" once it's revealed.
+//
+// RUST_OWNERSHIP_DEMO_XAPI is either a file, which gets one JSON statement
+// per line for the platform to import, or the http(s) URL of an LRS
+// statements endpoint, which gets each statement POSTed with curl
+// (RUST_OWNERSHIP_DEMO_XAPI_AUTH holds its "key:secret"). The learner is
+// RUST_OWNERSHIP_DEMO_LEARNER, an email address, or else the login name.
+//
+// Recording is a side effect: if it fails, the learner gets a note and the
+// command carries on.
+
+use std::env;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::lessons::{Example, Section};
+use crate::links;
+use crate::progress;
+
+pub const TARGET_VAR: &str = "RUST_OWNERSHIP_DEMO_XAPI";
+const AUTH_VAR: &str = "RUST_OWNERSHIP_DEMO_XAPI_AUTH";
+
+const COMPLETED: &str = "http://adlnet.gov/expapi/verbs/completed";
+const PASSED: &str = "http://adlnet.gov/expapi/verbs/passed";
+const ANSWERED: &str = "http://adlnet.gov/expapi/verbs/answered";
+const LESSON: &str = "http://adlnet.gov/expapi/activities/lesson";
+const ASSESSMENT: &str = "http://adlnet.gov/expapi/activities/assessment";
+const INTERACTION: &str = "http://adlnet.gov/expapi/activities/cmi.interaction";
+
+// Records that an example was played to the end
+pub fn completed_example(section: &Section, example: &Example) {
+    let activity = links::link(section, example, None);
+    record(COMPLETED, "completed", &activity, example.title, LESSON, "");
+}
+
+// Records that an exercise's hidden tests passed
+pub fn passed_exercise(id: &str, title: &str) {
+    let activity = format!("{}exercises/{}", links::SCHEME, id);
+    let result = r#","result":{"success":true,"completion":true}"#;
+    record(PASSED, "passed", &activity, title, ASSESSMENT, result);
+}
+
+// Records a learner's answer to a quiz question, and whether it was right
+pub fn answered_question(name: &str, prompt: &str, choice: &str, success: bool) {
+    let activity = format!("{}questions/{}", links::SCHEME, name);
+    let result = format!(r#","result":{{"success":{},"response":"{}"}}"#, success, escape(choice));
+    record(ANSWERED, "answered", &activity, prompt, INTERACTION, &result);
+}
+
+fn record(verb: &str, verb_name: &str, activity: &str, name: &str, activity_type: &str, result: &str) {
+    let Some(target) = env::var_os(TARGET_VAR) else { return };
+    let target = target.to_string_lossy().into_owned();
+    let statement = format!(
+        r#"{{"actor":{},"verb":{{"id":"{}","display":{{"en-US":"{}"}}}},"object":{{"objectType":"Activity","id":"{}","definition":{{"name":{{"en-US":"{}"}},"type":"{}"}}}}{},"context":{{"platform":"rust_ownership_demo {}"}},"timestamp":"{}"}}"#,
+        actor(),
+        verb,
+        verb_name,
+        escape(activity),
+        escape(name),
+        activity_type,
+        result,
+        env!("CARGO_PKG_VERSION"),
+        timestamp(SystemTime::now())
+    );
+    let sent = if target.starts_with("http://") || target.starts_with("https://") {
+        post(&target, &statement)
+    } else {
+        append(&target, &statement)
+    };
+    if let Err(reason) = sent {
+        eprintln!("note: could not record the xAPI statement ({}): {}", TARGET_VAR, reason);
+    }
+}
+
+// An email address becomes an mbox; anything else an account on this tool
+fn actor() -> String {
+    let learner = progress::learner();
+    if learner.contains('@') {
+        format!(r#"{{"objectType":"Agent","mbox":"mailto:{}"}}"#, escape(&learner))
+    } else {
+        format!(
+            r#"{{"objectType":"Agent","account":{{"homePage":"{}","name":"{}"}}}}"#,
+            links::SCHEME,
+            escape(&learner)
+        )
+    }
+}
+
+fn append(path: &str, statement: &str) -> Result<(), String> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("could not open {}: {}", path, e))?;
+    writeln!(file, "{}", statement).map_err(|e| format!("could not write {}: {}", path, e))
+}
+
+fn post(url: &str, statement: &str) -> Result<(), String> {
+    // The credentials go to curl on its stdin, as a config file (--config -),
+    // since any local user can read a command line with ps
+    let config = match env::var(AUTH_VAR) {
+        Ok(auth) => format!("user = \"{}\"\n", curl_quote(&auth)),
+        Err(_) => String::new(),
+    };
+    let mut curl = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--max-time", "30", "--request", "POST"])
+        .args(["--header", "Content-Type: application/json"])
+        .args(["--header", "X-Experience-API-Version: 1.0.3"])
+        .args(["--data-binary", statement])
+        .args(["--config", "-"])
+        .arg(url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|_| "sending statements to an LRS needs curl on the PATH".to_string())?;
+    if let Some(mut stdin) = curl.stdin.take() {
+        stdin.write_all(config.as_bytes()).map_err(|e| format!("could not pass curl its settings: {}", e))?;
+    }
+    let output = curl.wait_with_output().map_err(|e| format!("curl didn't finish: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+// A value for a double-quoted string in a curl config file
+fn curl_quote(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n").replace('\r', "\\r")
+}
+
+// A JSON string's contents: quotes, backslashes and control characters escaped
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+// ISO 8601 in UTC, e.g. 2026-10-15T09:30:00Z, as xAPI wants
+fn timestamp(now: SystemTime) -> String {
+    let seconds = now.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (days, rest) = (seconds / 86_400, seconds % 86_400);
+
+    // Days since 1970-01-01 to a civil date (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rest / 3_600,
+        rest % 3_600 / 60,
+        rest % 60
+    )
+}